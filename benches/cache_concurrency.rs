@@ -0,0 +1,65 @@
+//! Keyed cache concurrency benchmarks
+//!
+//! Looks up loaded keys of a `PreloaderCache` from several threads at once, comparing a single shard, which
+//! serializes every lookup on one lock, with the default sharding. Run with `cargo bench --bench cache_concurrency`.
+
+use std::{
+    hint::black_box,
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use preloader::PreloaderCache;
+
+const KEYS: u64 = 1024;
+
+fn loaded_cache(shards: Option<usize>) -> PreloaderCache<u64, u64> {
+    let cache = PreloaderCache::new(|key: &u64| {
+        let key = *key;
+        async move { key }
+    });
+    let cache = match shards {
+        Some(shards) => cache.with_shards(shards),
+        None => cache,
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    runtime.block_on(cache.get_many(0..KEYS));
+    cache
+}
+
+/// Runs `iters` lookups spread over `threads` threads and returns the time they took.
+fn contended(cache: &PreloaderCache<u64, u64>, threads: u64, iters: u64) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for thread in 0..threads {
+            scope.spawn(move || {
+                for i in 0..iters / threads {
+                    let key = (i * 31 + thread * 7) % KEYS;
+                    black_box(cache.try_get(&key).unwrap());
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn cache_concurrency(c: &mut Criterion) {
+    let threads = thread::available_parallelism().map_or(4, |n| n.get().max(4)) as u64;
+    let single = loaded_cache(Some(1));
+    let sharded = loaded_cache(None);
+
+    let mut group = c.benchmark_group(format!("cache_try_get_{}_threads", threads));
+    group.bench_function("1 shard", |b| {
+        b.iter_custom(|iters| contended(&single, threads, iters))
+    });
+    group.bench_function("default shards", |b| {
+        b.iter_custom(|iters| contended(&sharded, threads, iters))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cache_concurrency);
+criterion_main!(benches);
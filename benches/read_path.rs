@@ -0,0 +1,44 @@
+//! Read path benchmarks
+//!
+//! Compares reading an already loaded value through `Preloader` with `std::sync::OnceLock` and
+//! `tokio::sync::OnceCell`. Run with `cargo bench --bench read_path`.
+
+use std::{hint::black_box, sync::OnceLock, time::Duration};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use futures::FutureExt;
+use preloader::{ArcPreloader, Preloader};
+
+fn read_path(c: &mut Criterion) {
+    let preloader = Preloader::ready(42u64);
+    let with_ttl = Preloader::builder().ttl(Duration::from_secs(3600)).build();
+    with_ttl.set(42u64).unwrap();
+    let shared = ArcPreloader::ready(42u64);
+    let once_lock = OnceLock::new();
+    once_lock.set(42u64).unwrap();
+    let once_cell = tokio::sync::OnceCell::new_with(Some(42u64));
+
+    let mut group = c.benchmark_group("read_loaded");
+    group.bench_function("Preloader::try_get", |b| {
+        b.iter(|| *black_box(&preloader).try_get().unwrap())
+    });
+    group.bench_function("Preloader::get", |b| {
+        b.iter(|| *black_box(&preloader).get().now_or_never().unwrap().unwrap())
+    });
+    group.bench_function("Preloader::try_get (ttl)", |b| {
+        b.iter(|| *black_box(&with_ttl).try_get().unwrap())
+    });
+    group.bench_function("ArcPreloader::try_get", |b| {
+        b.iter(|| *black_box(&shared).try_get().unwrap())
+    });
+    group.bench_function("OnceLock::get", |b| {
+        b.iter(|| *black_box(&once_lock).get().unwrap())
+    });
+    group.bench_function("tokio OnceCell::get", |b| {
+        b.iter(|| *black_box(&once_cell).get().unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, read_path);
+criterion_main!(benches);
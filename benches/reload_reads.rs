@@ -0,0 +1,66 @@
+//! Reads during refresh benchmarks
+//!
+//! Reads a reloaded `ArcPreloader` from several threads at once, with and without a writer swapping in new values
+//! as fast as it can. Run with `cargo bench --bench reload_reads`.
+
+use std::{
+    hint::black_box,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use preloader::ArcPreloader;
+
+/// Runs `iters` reads spread over `threads` threads, optionally while another thread keeps replacing the value,
+/// and returns the time the reads took.
+fn reads(preloader: &ArcPreloader<u64>, threads: u64, iters: u64, refreshing: bool) -> Duration {
+    let done = AtomicBool::new(false);
+    thread::scope(|scope| {
+        if refreshing {
+            scope.spawn(|| {
+                let mut value = 0;
+                while !done.load(Ordering::Relaxed) {
+                    value += 1;
+                    black_box(preloader.swap(value));
+                }
+            });
+        }
+        let start = Instant::now();
+        let readers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(move || {
+                    for _ in 0..iters / threads {
+                        black_box(*preloader.try_get().unwrap());
+                    }
+                })
+            })
+            .collect();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        let elapsed = start.elapsed();
+        done.store(true, Ordering::Relaxed);
+        elapsed
+    })
+}
+
+fn reload_reads(c: &mut Criterion) {
+    let threads = thread::available_parallelism().map_or(4, |n| n.get().max(4)) as u64;
+    let preloader = ArcPreloader::ready(0u64);
+    // Readers go through the reloaded value from the start
+    preloader.swap(0);
+
+    let mut group = c.benchmark_group(format!("arc_try_get_{}_threads", threads));
+    group.bench_function("idle", |b| {
+        b.iter_custom(|iters| reads(&preloader, threads, iters, false))
+    });
+    group.bench_function("during refresh", |b| {
+        b.iter_custom(|iters| reads(&preloader, threads, iters, true))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, reload_reads);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+/*
+ * C API of the preloader crate, built with the `ffi` feature.
+ *
+ * Preloaders hold a byte buffer produced by a loader callback. Loads run on a
+ * process-wide runtime started on first use.
+ */
+
+#ifndef PRELOADER_H
+#define PRELOADER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The value is loaded and returned, or the call succeeded */
+#define PRELOADER_LOADED 0
+/* The value is still loading, or loading has not been started */
+#define PRELOADER_PENDING 1
+/* Loading failed */
+#define PRELOADER_FAILED (-1)
+/* A required pointer was null */
+#define PRELOADER_INVALID (-2)
+
+/* Preloader of a byte buffer */
+typedef struct Preloader Preloader;
+
+/* Buffer receiving the bytes produced by a loader callback */
+typedef struct PreloaderSink PreloaderSink;
+
+/*
+ * Loader callback, run once on a runtime thread. Writes the value into `sink`
+ * with `preloader_sink_write` and returns 0, or any other value on failure.
+ */
+typedef int (*PreloaderLoadCallback)(void *user_data, PreloaderSink *sink);
+
+/* Creates a preloader in the idle state; release it with `preloader_free`. */
+Preloader *preloader_new(void);
+
+/*
+ * Starts loading with `callback`, which must be safe to call with `user_data`
+ * from another thread. Does nothing if loading has already been started.
+ */
+int preloader_load_callback(const Preloader *preloader,
+                            PreloaderLoadCallback callback, void *user_data);
+
+/* Appends `len` bytes from `data` to the value being loaded. */
+int preloader_sink_write(PreloaderSink *sink, const uint8_t *data, size_t len);
+
+/*
+ * Retrieves the loaded bytes without waiting. On PRELOADER_LOADED, `*data` and
+ * `*len` describe bytes that stay valid until `preloader_free`.
+ */
+int preloader_try_get(const Preloader *preloader, const uint8_t **data,
+                      size_t *len);
+
+/* Frees a preloader, aborting its load if it is still running. */
+void preloader_free(Preloader *preloader);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PRELOADER_H */
@@ -0,0 +1,209 @@
+//! Derive macros for the `preloader` crate
+//!
+//! This crate is re-exported by `preloader` when its `derive` feature is enabled.
+//! Use it through `preloader::Preload` instead of depending on it directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Expr, Fields, GenericArgument,
+    PathArguments, Type,
+};
+
+/// Derives `load_all()` and `wait_all()` for a struct whose fields are preloaders.
+///
+/// Every field must be a `Preloader<T>` or an `ArcPreloader<T>`, unless it is marked with `#[preload(skip)]`.
+/// A field marked with `#[preload(with = expr)]` is loaded by `load_all()` with the future returned by calling `expr()`.
+///
+/// The derive also generates a `<Name>View<'a>` struct with the same field names, holding `&'a T`
+/// for `Preloader<T>` fields and `Arc<T>` for `ArcPreloader<T>` fields.
+/// `wait_all()` waits until every preloader is loaded and returns this view.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use preloader::{Preload, Preloader};
+///
+/// #[derive(Preload)]
+/// struct AppState {
+///     #[preload(with = load_users)]
+///     users: Preloader<Vec<String>>,
+///     #[preload(with = || async { 10 })]
+///     limit: Preloader<u32>,
+/// }
+///
+/// state.load_all().await;
+/// let view = state.wait_all().await?;
+/// println!("{} users, limit {}", view.users.len(), view.limit);
+/// ```
+#[proc_macro_derive(Preload, attributes(preload))]
+pub fn derive_preload(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Kind of preloader stored in a field
+enum Kind {
+    /// `Preloader<T>`, resolved to `&T`
+    Plain,
+    /// `ArcPreloader<T>`, resolved to `Arc<T>`
+    Arc,
+}
+
+/// A preloader field of the derived struct
+struct Field {
+    ident: Ident,
+    kind: Kind,
+    value: Type,
+    loader: Option<Expr>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "Preload cannot be derived for generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "Preload can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "Preload can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut preloaders = Vec::new();
+    for field in fields {
+        let mut skip = false;
+        let mut loader = None;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("preload"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("with") {
+                    loader = Some(meta.value()?.parse::<Expr>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `skip` or `with = ...`"))
+                }
+            })?;
+        }
+        if skip {
+            continue;
+        }
+
+        let (kind, value) = preloader_type(&field.ty).ok_or_else(|| {
+            Error::new(
+                field.ty.span(),
+                "expected `Preloader<T>` or `ArcPreloader<T>`; mark other fields with `#[preload(skip)]`",
+            )
+        })?;
+        preloaders.push(Field {
+            ident: field.ident.clone().unwrap(),
+            kind,
+            value,
+            loader,
+        });
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let view = format_ident!("{}View", name);
+    let view_doc = format!("Loaded values of every preloader in [`{}`]", name);
+
+    let idents: Vec<_> = preloaders.iter().map(|field| &field.ident).collect();
+    let view_fields = preloaders.iter().map(|field| {
+        let ident = &field.ident;
+        let value = &field.value;
+        match field.kind {
+            Kind::Plain => quote! { pub #ident: &'a #value },
+            Kind::Arc => quote! { pub #ident: ::std::sync::Arc<#value> },
+        }
+    });
+    let loads: Vec<_> = preloaders
+        .iter()
+        .filter_map(|field| {
+            let ident = &field.ident;
+            let loader = field.loader.as_ref()?;
+            Some(quote! { self.#ident.load((#loader)()) })
+        })
+        .collect();
+    let load_all = if loads.is_empty() {
+        quote! {}
+    } else {
+        quote! { ::preloader::__private::tokio::join!(#(#loads),*); }
+    };
+    let wait_all = if idents.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            let (#(#idents,)*) = ::preloader::__private::tokio::try_join!(#(self.#idents.get()),*)?;
+        }
+    };
+
+    Ok(quote! {
+        #[doc = #view_doc]
+        #vis struct #view<'a> {
+            #(#view_fields,)*
+            #[doc(hidden)]
+            pub _marker: ::std::marker::PhantomData<&'a ()>,
+        }
+
+        impl #name {
+            /// Starts loading every field that has a `#[preload(with = ...)]` loader.
+            #vis async fn load_all(&self) {
+                #load_all
+            }
+
+            /// Waits until every preloader is loaded.
+            ///
+            /// Returns the first error if any preloader is not loaded or failed to load.
+            #vis async fn wait_all(&self) -> ::std::result::Result<#view<'_>, ::preloader::PreloaderError> {
+                #wait_all
+                ::std::result::Result::Ok(#view {
+                    #(#idents,)*
+                    _marker: ::std::marker::PhantomData,
+                })
+            }
+        }
+    })
+}
+
+/// Extracts the kind and value type of a `Preloader<T>` or `ArcPreloader<T>` type.
+fn preloader_type(ty: &Type) -> Option<(Kind, Type)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let kind = match segment.ident.to_string().as_str() {
+        "Preloader" => Kind::Plain,
+        "ArcPreloader" => Kind::Arc,
+        _ => return None,
+    };
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(value) => Some((kind, value.clone())),
+        _ => None,
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use preloader::{ArcPreloader, Preloader, PreloaderError};
+use preloader_derive::Preload;
+use tokio::time::sleep;
+
+async fn load_users() -> Vec<String> {
+    sleep(Duration::from_millis(10)).await;
+    vec!["alice".to_string(), "bob".to_string()]
+}
+
+#[derive(Default, Preload)]
+struct AppState {
+    #[preload(with = load_users)]
+    users: Preloader<Vec<String>>,
+    #[preload(with = || async { 10 })]
+    limit: ArcPreloader<u32>,
+    manual: Preloader<&'static str>,
+    #[preload(skip)]
+    label: String,
+}
+
+#[tokio::test]
+async fn test_load_all_and_wait_all() {
+    let state = AppState::default();
+    state.load_all().await;
+    state.manual.set("manual").unwrap();
+
+    let view = state.wait_all().await.unwrap();
+    assert_eq!(view.users.len(), 2);
+    assert_eq!(*view.limit, 10);
+    assert_eq!(*view.manual, "manual");
+    assert!(state.label.is_empty());
+}
+
+#[tokio::test]
+async fn test_wait_all_not_loaded() {
+    let state = AppState::default();
+    state.load_all().await;
+
+    assert!(matches!(
+        state.wait_all().await.err().unwrap(),
+        PreloaderError::NotLoaded
+    ));
+}
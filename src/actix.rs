@@ -0,0 +1,194 @@
+//! actix-web integration module
+//!
+//! This module lets actix-web handlers read preloaded values from the application data.
+//! The [`Preloaded`] extractor rejects requests with `503 Service Unavailable` until the value is loaded,
+//! or waits for it when configured with [`PreloadedConfig`], and [`readiness_route`] reports whether
+//! every preloader of a group is ready.
+
+use std::{fmt, ops::Deref, sync::Arc, time::Duration};
+
+use actix_web::{
+    dev::Payload, http::StatusCode, web, FromRequest, HttpRequest, HttpResponse, ResponseError,
+    Route,
+};
+use futures::future::LocalBoxFuture;
+
+use crate::{Preloader, PreloaderError, PreloaderGroup};
+
+/// Wraps `preloader` as application data, so [`Preloaded`] can extract its value.
+///
+/// # Example
+///
+/// ```rust
+/// use actix_web::App;
+/// use preloader::{actix::app_data, Preloader};
+/// use std::sync::Arc;
+///
+/// let prices: Arc<Preloader<Vec<f64>>> = Arc::new(Preloader::new());
+/// let app = App::new().app_data(app_data(prices));
+/// ```
+pub fn app_data<T: Send + 'static>(preloader: Arc<Preloader<T>>) -> web::Data<Preloader<T>> {
+    web::Data::from(preloader)
+}
+
+/// Configuration of the [`Preloaded`] extractor, registered as application data
+///
+/// Without a configuration, the extractor rejects requests immediately while loading.
+#[derive(Debug, Clone, Default)]
+pub struct PreloadedConfig {
+    wait: Option<Duration>,
+}
+
+impl PreloadedConfig {
+    /// Waits up to `timeout` for loading to finish before rejecting a request.
+    pub fn wait(mut self, timeout: Duration) -> Self {
+        self.wait = Some(timeout);
+        self
+    }
+}
+
+/// Extractor giving a handler access to a loaded value
+///
+/// The preloader is taken from the application data, registered with [`app_data`] or as `web::Data<Preloader<T>>`.
+/// The extractor derefs to `&T`, so no value is cloned.
+/// If the preloader is not loaded yet, the request is rejected with a [`PreloadRejection`].
+///
+/// # Example
+///
+/// ```rust
+/// use actix_web::{web, App};
+/// use preloader::{actix::{app_data, Preloaded, PreloadedConfig}, Preloader};
+/// use std::{collections::HashMap, sync::Arc, time::Duration};
+///
+/// async fn price(prices: Preloaded<HashMap<String, f64>>) -> String {
+///     format!("{:?}", prices.get("apple"))
+/// }
+///
+/// let prices: Arc<Preloader<HashMap<String, f64>>> = Arc::new(Preloader::new());
+/// let app = App::new()
+///     .app_data(app_data(prices))
+///     // Wait up to a second for loading instead of rejecting immediately
+///     .app_data(PreloadedConfig::default().wait(Duration::from_secs(1)))
+///     .route("/price", web::get().to(price));
+/// ```
+pub struct Preloaded<T: Send + 'static>(web::Data<Preloader<T>>);
+
+impl<T: Send + 'static> Preloaded<T> {
+    /// Returns the preloader the value was extracted from.
+    pub fn preloader(&self) -> &web::Data<Preloader<T>> {
+        &self.0
+    }
+}
+
+impl<T: Send + 'static> Deref for Preloaded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // A shared preloader cannot be unloaded, so once extracted the value stays loaded, even past its TTL
+        let (value, _) = self
+            .0
+            .try_get_stale()
+            .expect("extracted preloaders stay loaded");
+        value
+    }
+}
+
+impl<T: fmt::Debug + Send + 'static> fmt::Debug for Preloaded<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Preloaded").field(&**self).finish()
+    }
+}
+
+impl<T: Send + 'static> FromRequest for Preloaded<T> {
+    type Error = PreloadRejection;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let preloader = request.app_data::<web::Data<Preloader<T>>>().cloned();
+        let wait = request
+            .app_data::<PreloadedConfig>()
+            .and_then(|config| config.wait);
+
+        Box::pin(async move {
+            let Some(preloader) = preloader else {
+                return Err(PreloadRejection(PreloaderError::Failed(format!(
+                    "Preloader<{}> is not registered as application data",
+                    std::any::type_name::<T>()
+                ))));
+            };
+            match wait {
+                Some(timeout) => {
+                    tokio::time::timeout(timeout, preloader.resolve(true))
+                        .await
+                        .map_err(|_| PreloaderError::Loading)
+                        .and_then(|result| result.map(drop))
+                        .map_err(PreloadRejection)?;
+                }
+                None => {
+                    preloader.try_get().map_err(PreloadRejection)?;
+                }
+            }
+            Ok(Self(preloader))
+        })
+    }
+}
+
+/// Rejection returned by [`Preloaded`] when the value is not available
+///
+/// Responds with `503 Service Unavailable` while the preloader is idle or loading or the caller's deadline passed,
+/// and with `500 Internal Server Error` if loading failed or the preloader is not registered.
+#[derive(Debug)]
+pub struct PreloadRejection(pub PreloaderError);
+
+impl fmt::Display for PreloadRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for PreloadRejection {
+    fn status_code(&self) -> StatusCode {
+        match self.0 {
+            PreloaderError::NotLoaded
+            | PreloaderError::Loading
+            | PreloaderError::CircuitOpen
+            | PreloaderError::QueueFull
+            | PreloaderError::DeadlineExceeded => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Creates a `GET` route reporting whether every preloader of `group` has been loaded.
+///
+/// Responds with `200 OK` once the group is ready and `503 Service Unavailable` before,
+/// with a plain text body such as `3/5 preloaders loaded`. Suitable as a Kubernetes readiness probe.
+///
+/// # Parameters
+///
+/// - `group`: The group to report on
+///
+/// # Example
+///
+/// ```rust
+/// use actix_web::App;
+/// use preloader::{actix::readiness_route, PreloaderGroup};
+/// use std::sync::Arc;
+///
+/// let group = Arc::new(PreloaderGroup::new());
+/// let app = App::new().route("/ready", readiness_route(group));
+/// ```
+pub fn readiness_route(group: Arc<PreloaderGroup>) -> Route {
+    web::get().to(move || {
+        let group = Arc::clone(&group);
+        async move {
+            let report = group.readiness();
+            let status = if report.is_ready() {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            HttpResponse::build(status).body(report.to_string())
+        }
+    })
+}
@@ -0,0 +1,127 @@
+//! Adaptive timeout module
+//!
+//! This module provides the `AdaptiveTimeout` struct, which derives the timeout of a load attempt from the
+//! durations recorded in the load history instead of a fixed constant. A loader that normally takes 80 ms then
+//! times out after a few hundred milliseconds when it suddenly stalls, rather than after a generous fixed limit.
+
+use std::time::Duration;
+
+/// Policy computing the timeout of a load attempt from past load durations
+///
+/// The timeout is a percentile of the durations of past loads that succeeded on their first attempt, multiplied
+/// by a factor and clamped between a floor and a ceiling. Until enough loads have been recorded, the fixed
+/// [`timeout`](crate::PreloaderBuilder::timeout) applies if one is set, and the ceiling otherwise.
+///
+/// Samples come from [`Preloader::history`](crate::Preloader::history), so raise
+/// [`history`](crate::PreloaderBuilder::history) to base the percentile on more loads.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::AdaptiveTimeout;
+/// use std::time::Duration;
+///
+/// let policy = AdaptiveTimeout::new(Duration::from_millis(100), Duration::from_secs(30))
+///     .with_percentile(0.99)
+///     .with_factor(3.0)
+///     .with_min_samples(3);
+///
+/// let durations = [80, 85, 90, 95].map(Duration::from_millis);
+/// assert_eq!(policy.compute(&durations), Some(Duration::from_millis(285)));
+/// assert_eq!(policy.compute(&durations[..2]), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveTimeout {
+    /// Percentile of the recorded durations, between 0.0 and 1.0
+    percentile: f64,
+    /// Factor applied to the percentile
+    factor: f64,
+    /// Lower bound of the computed timeout
+    floor: Duration,
+    /// Upper bound of the computed timeout
+    ceiling: Duration,
+    /// Number of recorded durations needed before the timeout is computed
+    min_samples: usize,
+}
+
+impl AdaptiveTimeout {
+    /// Creates a policy using three times the 99th percentile, once five loads have been recorded.
+    ///
+    /// # Parameters
+    ///
+    /// - `floor`: Lower bound of the computed timeout
+    /// - `ceiling`: Upper bound of the computed timeout, also used until enough loads have been recorded
+    ///
+    /// # Panics
+    ///
+    /// Panics if `floor` is greater than `ceiling`.
+    pub fn new(floor: Duration, ceiling: Duration) -> Self {
+        assert!(
+            floor <= ceiling,
+            "adaptive timeout floor exceeds its ceiling"
+        );
+        Self {
+            percentile: 0.99,
+            factor: 3.0,
+            floor,
+            ceiling,
+            min_samples: 5,
+        }
+    }
+
+    /// Sets the percentile of the recorded durations the timeout is based on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percentile` is not between 0.0 and 1.0.
+    pub fn with_percentile(mut self, percentile: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&percentile),
+            "percentile must be between 0.0 and 1.0"
+        );
+        self.percentile = percentile;
+        self
+    }
+
+    /// Sets the factor applied to the percentile.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive.
+    pub fn with_factor(mut self, factor: f64) -> Self {
+        assert!(factor > 0.0, "adaptive timeout factor must be positive");
+        self.factor = factor;
+        self
+    }
+
+    /// Sets the number of recorded durations needed before the timeout is computed.
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        self.min_samples = min_samples.max(1);
+        self
+    }
+
+    /// Returns the upper bound of the computed timeout.
+    pub fn ceiling(&self) -> Duration {
+        self.ceiling
+    }
+
+    /// Computes the timeout from `durations`.
+    ///
+    /// # Returns
+    ///
+    /// The timeout, or `None` if fewer than the minimum number of samples are given
+    pub fn compute(&self, durations: &[Duration]) -> Option<Duration> {
+        if durations.len() < self.min_samples {
+            return None;
+        }
+        let mut sorted = durations.to_vec();
+        sorted.sort_unstable();
+        let rank = (self.percentile * sorted.len() as f64).ceil() as usize;
+        let percentile = sorted[rank.clamp(1, sorted.len()) - 1];
+        Some(
+            percentile
+                .mul_f64(self.factor)
+                .clamp(self.floor, self.ceiling),
+        )
+    }
+}
@@ -0,0 +1,191 @@
+//! Type-erased preloader module
+//!
+//! This module provides the `AnyPreloader` trait, an object-safe view of a preloader whatever its value type, so
+//! groups, registries, and dashboards can hold `Vec<Arc<dyn AnyPreloader>>` mixing preloaders of different types.
+
+use std::{
+    any::Any,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{ArcPreloader, Preloader, PreloaderError, PreloaderState, Readiness};
+
+/// Preloader of any value type, usable as a trait object
+///
+/// Exposes the state, name, and timing of a preloader, and through [`Readiness`] waiting for it, without naming
+/// its value type. When the value itself is needed, `downcast::<T>()` and `downcast_arc::<T>()` on
+/// `dyn AnyPreloader` recover the typed preloader.
+///
+/// Implemented for [`Preloader`] and [`ArcPreloader`].
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{AnyPreloader, ArcPreloader, Preloader, PreloaderState};
+/// use std::sync::Arc;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let preloaders: Vec<Arc<dyn AnyPreloader>> = vec![
+///         Arc::new(Preloader::spawn(async { vec!["alice", "bob"] })),
+///         Arc::new(ArcPreloader::spawn(async { 42u64 })),
+///     ];
+///
+///     for preloader in &preloaders {
+///         assert!(preloader.wait_ready().await);
+///         assert_eq!(preloader.state(), PreloaderState::Loaded);
+///     }
+///
+///     let users = preloaders[0].downcast::<Vec<&str>>().unwrap();
+///     assert_eq!(users.try_get().unwrap().len(), 2);
+///     assert!(preloaders[1].downcast::<u64>().is_none());
+///     assert_eq!(*preloaders[1].downcast_arc::<u64>().unwrap().try_get().unwrap(), 42);
+/// }
+/// ```
+pub trait AnyPreloader: Readiness {
+    /// Returns the current state.
+    fn state(&self) -> PreloaderState;
+
+    /// Returns the name set through the builder.
+    fn name(&self) -> Option<&str>;
+
+    /// Returns the name of the value type, for display.
+    fn type_name(&self) -> &'static str;
+
+    /// Returns the time at which the latest load was started.
+    fn started_at(&self) -> Option<Instant>;
+
+    /// Returns the time at which the value finished loading.
+    fn loaded_at(&self) -> Option<Instant>;
+
+    /// Returns how long loading took.
+    fn load_duration(&self) -> Option<Duration>;
+
+    /// Returns the error of the latest failed load, if it is still recorded.
+    fn last_error(&self) -> Option<PreloaderError>;
+
+    /// Cancels the running load, returning whether one was running.
+    fn cancel(&self) -> bool;
+
+    /// Returns the preloader as [`Any`], to downcast it back to its concrete type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Converts the shared preloader into [`Any`], to downcast it back to a shared concrete preloader.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{AnyPreloader, Preloader};
+    /// use std::sync::Arc;
+    ///
+    /// let erased: Arc<dyn AnyPreloader> = Arc::new(Preloader::ready(7u8));
+    /// let typed: Arc<Preloader<u8>> = erased.into_any().downcast().unwrap();
+    /// assert_eq!(*typed.try_get().unwrap(), 7);
+    /// ```
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>;
+}
+
+impl dyn AnyPreloader {
+    /// Returns the preloader as a `Preloader<T>`, or `None` if it is another type of preloader or holds another
+    /// value type.
+    pub fn downcast<T: Send + Sync + 'static>(&self) -> Option<&Preloader<T>> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Returns the preloader as an `ArcPreloader<T>`, or `None` if it is another type of preloader or holds
+    /// another value type.
+    pub fn downcast_arc<T: Send + Sync + 'static>(&self) -> Option<&ArcPreloader<T>> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Checks if the preloader is a `Preloader<T>` or an `ArcPreloader<T>`.
+    pub fn holds<T: Send + Sync + 'static>(&self) -> bool {
+        self.downcast::<T>().is_some() || self.downcast_arc::<T>().is_some()
+    }
+}
+
+impl<T: Send + Sync + 'static> AnyPreloader for Preloader<T> {
+    fn state(&self) -> PreloaderState {
+        Preloader::state(self)
+    }
+
+    fn name(&self) -> Option<&str> {
+        Preloader::name(self)
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn started_at(&self) -> Option<Instant> {
+        Preloader::started_at(self)
+    }
+
+    fn loaded_at(&self) -> Option<Instant> {
+        Preloader::loaded_at(self)
+    }
+
+    fn load_duration(&self) -> Option<Duration> {
+        Preloader::load_duration(self)
+    }
+
+    fn last_error(&self) -> Option<PreloaderError> {
+        Preloader::last_error(self)
+    }
+
+    fn cancel(&self) -> bool {
+        Preloader::cancel(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+}
+
+impl<T: Send + Sync + 'static> AnyPreloader for ArcPreloader<T> {
+    fn state(&self) -> PreloaderState {
+        ArcPreloader::state(self)
+    }
+
+    fn name(&self) -> Option<&str> {
+        ArcPreloader::name(self)
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn started_at(&self) -> Option<Instant> {
+        ArcPreloader::started_at(self)
+    }
+
+    fn loaded_at(&self) -> Option<Instant> {
+        ArcPreloader::loaded_at(self)
+    }
+
+    fn load_duration(&self) -> Option<Duration> {
+        ArcPreloader::load_duration(self)
+    }
+
+    fn last_error(&self) -> Option<PreloaderError> {
+        ArcPreloader::last_error(self)
+    }
+
+    fn cancel(&self) -> bool {
+        ArcPreloader::cancel(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+}
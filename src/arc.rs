@@ -0,0 +1,1944 @@
+//! Arc-based preloader module
+//!
+//! This module provides the `ArcPreloader` struct, a preloader that hands out `Arc<T>` instead of `&T`.
+//! The returned handles can be moved into spawned tasks without borrowing the preloader and without cloning the data.
+
+use std::{
+    collections::hash_map::RandomState,
+    fmt::{self, Display},
+    future::{Future, IntoFuture},
+    hash::{BuildHasher, Hasher},
+    ops::Deref,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use arc_swap::{ArcSwapOption, Guard};
+use futures::{future::BoxFuture, FutureExt};
+use tokio::{
+    sync::{oneshot, watch},
+    task::AbortHandle,
+};
+
+use crate::{
+    events::Events, preloader::panicked, source, CancellationToken, ErrorContext, LoadAttempt,
+    LoadContext, LoadHandle, LoadPolicy, LoadSource, LoadTrigger, Preloader, PreloaderError,
+    PreloaderEvent, PreloaderState, PreloaderStats, RetainedValue, ValueHistory, WaitStats,
+};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Function checking a reloaded value before it replaces the current one
+type Validator<T> = Arc<dyn Fn(&T) -> std::result::Result<(), String> + Send + Sync>;
+
+/// Function checking if a reloaded value equals the current one
+type Comparator<T> = Arc<dyn Fn(&T, &T) -> bool + Send + Sync>;
+
+/// Reload requested while another one is running, shared by every request coalesced into it
+struct PendingReload<T> {
+    /// Loader of the latest request
+    loader: BoxFuture<'static, Result<Arc<T>>>,
+    /// Outcome reported to every coalesced request, `None` until the reload has finished
+    outcome: watch::Sender<Option<Result<()>>>,
+}
+
+/// Asynchronous data preloader returning shared `Arc<T>` handles
+///
+/// `ArcPreloader` behaves like [`Preloader`], but stores the loaded data in an `Arc`
+/// and returns cheap clones of it from `get()` and `try_get()`.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::ArcPreloader;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let preloader = ArcPreloader::new();
+///     preloader.load(async { vec![1, 2, 3] }).await;
+///
+///     let data = preloader.get().await.unwrap();
+///     tokio::spawn(async move {
+///         assert_eq!(data.len(), 3);
+///     })
+///     .await
+///     .unwrap();
+/// }
+/// ```
+///
+/// # Generic Type
+///
+/// - `T`: The type of data to load. Must satisfy `Send + Sync + 'static`.
+pub struct ArcPreloader<T: Send + Sync + 'static> {
+    /// Preloader storing the shared value
+    inner: Preloader<Arc<T>>,
+    /// Value stored by the latest reload, replacing the one loaded by `inner`
+    ///
+    /// Readers load a snapshot without locking, so a reload swapping in a new value never blocks them.
+    latest: ArcSwapOption<Latest<T>>,
+    /// Whether `latest` has ever been set or a replacement is pending, so reads of a value that was never reloaded
+    /// skip the snapshot
+    reloaded: AtomicBool,
+    /// Generation of the value held by `inner`, above 1 once `get_mut` has moved a reloaded value into it
+    inner_generation: u64,
+    /// Value of the latest replacing `load()` under `LoadPolicy::ReplaceWhenReady`, stored by the next read
+    replacement: Mutex<Option<oneshot::Receiver<Arc<T>>>>,
+    /// Whether `replacement` holds a receiver
+    replacing: AtomicBool,
+    /// Reload waiting for the running one to finish, replaced by every later request
+    pending_reload: Mutex<Option<PendingReload<T>>>,
+    /// Held while a reload runs, so at most one runs at a time
+    reloading: tokio::sync::Mutex<()>,
+    /// Outcome of the running `refresh()`, awaited by every request made before it finishes
+    refreshing: Mutex<Option<Outcome>>,
+    /// Background tasks reloading the value
+    tasks: Tasks,
+    /// Task started by `refresh_every`, stopped by `stop_refresh`
+    refresher: Tasks,
+    /// Time at which the scheduled refresh task starts its next refresh
+    next_refresh: Mutex<Option<tokio::time::Instant>>,
+    /// Callbacks that must accept a reloaded value before it is stored
+    validators: Vec<Validator<T>>,
+    /// Comparison detecting reloaded values equal to the current one, which keep the current generation
+    comparator: Option<Comparator<T>>,
+    /// Value returned by `get()` and `try_get()` until the first value is loaded
+    placeholder: Option<Arc<T>>,
+    /// Read guards alive, and the reloaded value waiting for the last of them to be dropped
+    readers: Mutex<Readers<T>>,
+    /// Values replaced by reloads and swaps, kept for `history_values()`
+    values: Option<ValueHistory<T>>,
+}
+
+/// Read guards of a preloader, deferring reloaded values while any is alive
+struct Readers<T> {
+    /// Number of live guards
+    count: usize,
+    /// Latest value reloaded while guards were alive, stored when the last one is dropped
+    deferred: Option<Arc<T>>,
+}
+
+/// Returns a random duration between zero and `max`, used to spread scheduled refreshes.
+fn random_delay(max: Duration) -> Duration {
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+    // Every `RandomState` is seeded differently, which is random enough to spread refreshes
+    let random = RandomState::new().build_hasher().finish();
+    max.mul_f64(random as f64 / u64::MAX as f64)
+}
+
+/// Value stored by a reload
+struct Latest<T> {
+    value: Arc<T>,
+    /// Time at which the value was stored
+    loaded_at: tokio::time::Instant,
+    /// Generation of the value, counting the first load as generation 1
+    generation: u64,
+}
+
+/// Receiver of the result of a reload, `None` until the reload has finished
+type Outcome = watch::Receiver<Option<Result<()>>>;
+
+/// Clears the running refresh of a preloader when it finishes or its caller is dropped
+struct Refreshing<'a>(&'a Mutex<Option<Outcome>>);
+
+impl Drop for Refreshing<'_> {
+    fn drop(&mut self) {
+        *self.0.lock().unwrap() = None;
+    }
+}
+
+/// Background tasks of a preloader, aborted when it is dropped
+#[derive(Default)]
+struct Tasks(Mutex<Vec<AbortHandle>>);
+
+impl Tasks {
+    /// Aborts every task.
+    fn abort(&self) {
+        for task in self.0.lock().unwrap().drain(..) {
+            task.abort();
+        }
+    }
+}
+
+impl Drop for Tasks {
+    fn drop(&mut self) {
+        for task in self.0.get_mut().unwrap().drain(..) {
+            task.abort();
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> ArcPreloader<T> {
+    /// Creates a new `ArcPreloader` instance in the initial `Idle` state.
+    pub fn new() -> Self {
+        Self::from_inner(Preloader::new())
+    }
+
+    /// Creates an `ArcPreloader` that already holds the given value.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: The already available data
+    pub fn ready(value: T) -> Self {
+        Self::from_inner(Preloader::ready(Arc::new(value)))
+    }
+
+    /// Creates a new `ArcPreloader` and immediately starts loading data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn spawn(future: impl Future<Output = T> + Send + 'static) -> Self {
+        Self::from_inner(Preloader::spawn(async move { Arc::new(future.await) }))
+    }
+
+    /// Creates a new `ArcPreloader` in the `Idle` state that stores a loader factory.
+    ///
+    /// See [`Preloader::with_loader`].
+    pub fn with_loader<F, Fut>(mut factory: F) -> Self
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self::from_inner(Preloader::with_loader(move || {
+            let future = factory();
+            async move { Arc::new(future.await) }
+        }))
+    }
+
+    /// Wraps a preloader that stores its data in an `Arc`.
+    pub(crate) fn from_inner(inner: Preloader<Arc<T>>) -> Self {
+        Self {
+            inner,
+            latest: ArcSwapOption::empty(),
+            reloaded: AtomicBool::new(false),
+            inner_generation: 1,
+            replacement: Mutex::new(None),
+            replacing: AtomicBool::new(false),
+            pending_reload: Mutex::new(None),
+            reloading: tokio::sync::Mutex::new(()),
+            refreshing: Mutex::new(None),
+            tasks: Tasks::default(),
+            refresher: Tasks::default(),
+            next_refresh: Mutex::new(None),
+            validators: Vec::new(),
+            comparator: None,
+            placeholder: None,
+            readers: Mutex::new(Readers {
+                count: 0,
+                deferred: None,
+            }),
+            values: None,
+        }
+    }
+
+    /// Adds a validation callback, run on every reloaded value before it is stored.
+    ///
+    /// A reloaded value only replaces the current one if every callback accepts it; otherwise the reload fails
+    /// with `PreloaderError::Failed` and readers keep getting the current value. Values stored by the first load,
+    /// such as through `load()` or `start()`, are not validated.
+    ///
+    /// # Parameters
+    ///
+    /// - `validate`: Function returning an error describing why the value is invalid
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let catalog = ArcPreloader::ready(vec!["apple"])
+    ///         .validate(|items| if items.is_empty() { Err("empty catalog") } else { Ok(()) });
+    ///
+    ///     assert!(catalog.reload(async { vec![] }).await.is_err());
+    ///     assert_eq!(*catalog.try_get().unwrap(), vec!["apple"]);
+    /// }
+    /// ```
+    pub fn validate<F, E>(mut self, validate: F) -> Self
+    where
+        F: Fn(&T) -> std::result::Result<(), E> + Send + Sync + 'static,
+        E: Display,
+    {
+        self.validators.push(Arc::new(move |value| {
+            validate(value).map_err(|error| format!("invalid value: {}", error))
+        }));
+        self
+    }
+
+    /// Keeps the current value when a reload produces an equal one.
+    ///
+    /// The generation is not incremented and subscribers of `events()` receive no `Refreshed` event, so
+    /// consumers reacting to changes are not woken by reloads that changed nothing. The age of the value is
+    /// reset, as after any successful reload. Values stored with [`swap`](Self::swap) are always stored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let config = ArcPreloader::ready("level = info".to_string()).dedupe();
+    ///     let seen = config.generation();
+    ///
+    ///     config.reload(async { "level = info".to_string() }).await.unwrap();
+    ///     assert!(!config.changed_since(seen));
+    ///
+    ///     config.reload(async { "level = debug".to_string() }).await.unwrap();
+    ///     assert!(config.changed_since(seen));
+    /// }
+    /// ```
+    pub fn dedupe(self) -> Self
+    where
+        T: PartialEq,
+    {
+        self.dedupe_by(T::eq)
+    }
+
+    /// Keeps the current value when a reload produces one that `eq` considers equal to it.
+    ///
+    /// See [`dedupe`](Self::dedupe).
+    ///
+    /// # Parameters
+    ///
+    /// - `eq`: Function returning `true` if the reloaded value (second argument) equals the current one (first)
+    pub fn dedupe_by(mut self, eq: impl Fn(&T, &T) -> bool + Send + Sync + 'static) -> Self {
+        self.comparator = Some(Arc::new(eq));
+        self
+    }
+
+    /// Sets a placeholder that `get()` and `try_get()` return right away until the first value is loaded.
+    ///
+    /// The placeholder is reported as generation 0 by [`try_get_versioned`](Self::try_get_versioned) and
+    /// [`generation`](Self::generation), and `is_loaded()` stays `false` while it is served. [`wait`](Self::wait)
+    /// still waits for the real value, so it can be used to learn when the placeholder was replaced. If the load
+    /// fails, readers get the error instead of the placeholder.
+    ///
+    /// # Parameters
+    ///
+    /// - `placeholder`: The value to serve while loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let avatar = ArcPreloader::spawn(async {
+    ///         tokio::time::sleep(Duration::from_millis(10)).await;
+    ///         "avatar.png"
+    ///     })
+    ///     .with_placeholder("spinner.gif");
+    ///
+    ///     // Render something immediately
+    ///     assert_eq!(*avatar.get().await.unwrap(), "spinner.gif");
+    ///     assert_eq!(avatar.generation(), 0);
+    ///
+    ///     // Re-render once the real value is in
+    ///     assert_eq!(*avatar.wait().await.unwrap(), "avatar.png");
+    ///     assert_eq!(*avatar.try_get().unwrap(), "avatar.png");
+    /// }
+    /// ```
+    pub fn with_placeholder(mut self, placeholder: T) -> Self {
+        self.placeholder = Some(Arc::new(placeholder));
+        self
+    }
+
+    /// Keeps the last `len` values of the preloader, including the current one, for
+    /// [`history_values`](Self::history_values).
+    ///
+    /// A value is kept when a reload or [`swap`](Self::swap) replaces it. Reloads that a
+    /// [comparator](Self::dedupe) finds equal to the current value do not replace it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero.
+    pub fn with_value_history(mut self, len: usize) -> Self {
+        assert!(len > 0, "the value history must keep at least one value");
+        self.values = Some(ValueHistory::new(len));
+        self
+    }
+
+    /// Returns the values kept by [`with_value_history`](Self::with_value_history), oldest first, ending with the
+    /// current value.
+    ///
+    /// Returns an empty list if no history is kept or no value has been loaded. Suited to computing what changed
+    /// between two refreshes without storing copies of the values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let flags = ArcPreloader::ready(vec!["search"]).with_value_history(2);
+    ///     flags.reload(async { vec!["search", "checkout"] }).await.unwrap();
+    ///
+    ///     let [previous, current] = &flags.history_values()[..] else {
+    ///         panic!("two values are kept");
+    ///     };
+    ///     let added: Vec<_> = current
+    ///         .value
+    ///         .iter()
+    ///         .filter(|flag| !previous.value.contains(flag))
+    ///         .collect();
+    ///     assert_eq!(added, [&"checkout"]);
+    ///     assert_eq!((previous.generation, current.generation), (1, 2));
+    /// }
+    /// ```
+    pub fn history_values(&self) -> Vec<RetainedValue<T>> {
+        let Some(values) = &self.values else {
+            return Vec::new();
+        };
+        let current = self
+            .try_get_versioned()
+            .ok()
+            .and_then(|(generation, value)| {
+                Some(RetainedValue {
+                    value,
+                    generation: (generation > 0).then_some(generation)?,
+                    loaded_at: self.loaded_at()?,
+                })
+            });
+        values.values(current)
+    }
+
+    /// Loads a new value with `future` and swaps it in once it is loaded and validated.
+    ///
+    /// Until then, `get()` and `try_get()` keep returning the current value without waiting, so readers never
+    /// see a loading gap. Handles obtained before the swap keep pointing to the previous value.
+    /// If the preloader is `Idle` or failed, this loads it instead, like `load()` would; if the first load
+    /// is still in progress, this waits for it and `future` is not run.
+    ///
+    /// Bursts of reloads are coalesced: while a reload is running, later requests wait for it and then share a
+    /// single reload run with the `future` of the latest request, whose result they all receive.
+    ///
+    /// # Parameters
+    ///
+    /// - `future`: The asynchronous task loading the new value
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the new value was stored
+    /// - `Err(PreloaderError)`: If loading panicked or the value was rejected by a validation callback;
+    ///   the current value is kept
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let prices = ArcPreloader::ready(vec![1.0, 2.0]);
+    ///     let before = prices.try_get().unwrap();
+    ///
+    ///     prices.reload(async { vec![1.5, 2.5] }).await.unwrap();
+    ///     assert_eq!(*prices.try_get().unwrap(), vec![1.5, 2.5]);
+    ///     assert_eq!(*before, vec![1.0, 2.0]);
+    /// }
+    /// ```
+    pub async fn reload(&self, future: impl Future<Output = T> + Send + 'static) -> Result<()> {
+        self.reload_result(async move { Ok(Arc::new(future.await)) })
+            .await
+    }
+
+    /// Loads a new value with the stored loader factory and swaps it in once it is loaded and validated.
+    ///
+    /// The configured timeout, retries, and hedging apply. See [`reload`](Self::reload).
+    ///
+    /// Concurrent calls are coalesced: a call made while another refresh is running does not start a load, but
+    /// waits for the running one and returns its result, so a stampede of callers runs the loader once.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the new value was stored
+    /// - `Err(PreloaderError)`: If loading failed, the value was rejected, or no loader factory is stored, or
+    ///   `Cancelled` if the caller running the shared refresh was dropped
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let loads = Arc::new(AtomicU32::new(0));
+    ///     let counter = Arc::clone(&loads);
+    ///     let rates = ArcPreloader::with_loader(move || {
+    ///         let counter = Arc::clone(&counter);
+    ///         async move {
+    ///             tokio::time::sleep(Duration::from_millis(20)).await;
+    ///             counter.fetch_add(1, Ordering::SeqCst)
+    ///         }
+    ///     });
+    ///
+    ///     let (a, b, c) = tokio::join!(rates.refresh(), rates.refresh(), rates.refresh());
+    ///     assert!(a.is_ok() && b.is_ok() && c.is_ok());
+    ///     assert_eq!(loads.load(Ordering::SeqCst), 1);
+    /// }
+    /// ```
+    pub async fn refresh(&self) -> Result<()> {
+        match self.inner.stored_load() {
+            Some(load) => self.reload_shared(load).await,
+            None => Err(PreloaderError::Failed(
+                "no loader factory is stored".to_string(),
+            )),
+        }
+    }
+
+    /// Loads a new value with `loader`, or waits for the result of the reload started by this method that is
+    /// already running.
+    ///
+    /// `loader` is dropped without being run when a reload is joined.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If a value is loaded after the reload
+    /// - `Err(PreloaderError)`: The error of the shared reload, or `Cancelled` if the caller running it was dropped
+    async fn reload_shared(
+        &self,
+        loader: impl Future<Output = Result<Arc<T>>> + Send + 'static,
+    ) -> Result<()> {
+        let running = {
+            let mut refreshing = self.refreshing.lock().unwrap();
+            match refreshing.as_ref() {
+                Some(outcome) if outcome.borrow().is_none() => Err(outcome.clone()),
+                _ => {
+                    let (tx, rx) = watch::channel(None);
+                    *refreshing = Some(rx);
+                    Ok(tx)
+                }
+            }
+        };
+
+        match running {
+            Ok(outcome) => {
+                let _refreshing = Refreshing(&self.refreshing);
+                let result = self.reload_result(loader).await;
+                outcome.send_replace(Some(result.clone()));
+                result
+            }
+            Err(mut outcome) => match outcome.wait_for(Option::is_some).await {
+                Ok(result) => result.clone().expect("outcome is set"),
+                Err(_) => Err(PreloaderError::Cancelled),
+            },
+        }
+    }
+
+    /// Loads a new value from `source` and swaps it in once it is loaded and validated.
+    ///
+    /// The value is loaded through the [`refresh`](LoadSource::refresh) hook of the source with the current
+    /// value, or through [`load`](LoadSource::load) if none is loaded. The configured timeout, retries, and
+    /// hedging apply. See [`reload`](Self::reload).
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: The source of the value, usually a clone of the `Arc` passed to
+    ///   [`from_source`](Self::from_source)
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the new value was stored
+    /// - `Err(PreloaderError)`: If loading failed or the value was rejected
+    pub async fn refresh_source<S: LoadSource<T>>(&self, source: S) -> Result<()> {
+        let source = Arc::new(source);
+        let current = self.try_get().ok();
+        let load = self
+            .inner
+            .context_load(CancellationToken::new(), move |ctx| {
+                let source = Arc::clone(&source);
+                let current = current.clone();
+                async move {
+                    match current {
+                        Some(current) => source::refetch(source, ctx, current).await,
+                        None => source::fetch(source, ctx).await,
+                    }
+                    .map(Arc::new)
+                }
+            });
+        self.reload_result(load).await
+    }
+
+    /// Loads a new value with `loader`, replacing the loaded value or loading again after a failure.
+    ///
+    /// Reloads run one at a time. A request made while another reload is running is queued, replacing any
+    /// request queued before it, so a burst of requests runs at most one more reload, with the latest loader.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If a value is loaded after the reload
+    /// - `Err(PreloaderError)`: The error of the reload the request ran in, or `Cancelled` if the caller running
+    ///   it was dropped
+    pub(crate) async fn reload_result(
+        &self,
+        loader: impl Future<Output = Result<Arc<T>>> + Send + 'static,
+    ) -> Result<()> {
+        let mut outcome = {
+            let mut pending = self.pending_reload.lock().unwrap();
+            match pending.as_mut() {
+                Some(pending) => {
+                    // Latest wins: the queued reload runs this loader instead
+                    pending.loader = Box::pin(loader);
+                    pending.outcome.subscribe()
+                }
+                None => {
+                    let (tx, rx) = watch::channel(None);
+                    *pending = Some(PendingReload {
+                        loader: Box::pin(loader),
+                        outcome: tx,
+                    });
+                    rx
+                }
+            }
+        };
+
+        let _running = self.reloading.lock().await;
+        // A caller holding the lock before this one may have run the reload already
+        if let Some(result) = outcome.borrow_and_update().clone() {
+            return result;
+        }
+        let pending = self.pending_reload.lock().unwrap().take();
+        let Some(pending) = pending else {
+            return Err(PreloaderError::Cancelled);
+        };
+        let result = self.reload_now(pending.loader).await;
+        pending.outcome.send_replace(Some(result.clone()));
+        result
+    }
+
+    /// Loads a new value with `loader` right away, replacing the loaded value or loading again after a failure.
+    ///
+    /// The value must pass the validation callbacks. If the preloader is loaded and `loader` fails,
+    /// the loaded value is kept.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If a value is loaded after the reload
+    /// - `Err(PreloaderError)`: The error of `loader`, or of the load that was already in progress
+    async fn reload_now(
+        &self,
+        loader: impl Future<Output = Result<Arc<T>>> + Send + 'static,
+    ) -> Result<()> {
+        let validators = self.validators.clone();
+        let loader = self.inner.supervised(loader);
+        let loader = async move {
+            let value = loader.await?;
+            for validate in &validators {
+                validate(&value).map_err(PreloaderError::Failed)?;
+            }
+            Ok(value)
+        };
+
+        // An expired value is replaced like any other
+        if self.inner.try_get_stale().is_some() {
+            let started_at = SystemTime::now();
+            let start = Instant::now();
+            self.inner.reset_attempts();
+            let value = AssertUnwindSafe(loader)
+                .catch_unwind()
+                .await
+                .unwrap_or_else(|payload| Err(panicked(payload)));
+            self.inner.history_log().record(LoadAttempt {
+                started_at,
+                duration: start.elapsed(),
+                attempts: self.inner.attempt_count(),
+                trigger: LoadTrigger::Refresh,
+                error: value.as_ref().err().cloned(),
+            });
+            self.store_reloaded(value?);
+            return Ok(());
+        }
+
+        self.inner.reload_result(loader).await;
+        self.inner.get().await.map(drop)
+    }
+
+    /// Re-runs the stored loader factory every `interval`, replacing the value with each successful load.
+    ///
+    /// The first refresh happens one `interval` after this call. Readers keep getting the previous value
+    /// while a refresh is running, and a failed refresh keeps it as well. An `Idle` or failed preloader is loaded
+    /// by the next refresh. Calling this again replaces the running schedule. Refreshing stops when
+    /// [`stop_refresh`](Self::stop_refresh) is called or the preloader is dropped.
+    ///
+    /// # Parameters
+    ///
+    /// - `interval`: Time between the start of two refreshes
+    ///
+    /// # Returns
+    ///
+    /// `true` if refreshing was scheduled, `false` if no loader factory is stored
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    /// use std::{sync::Arc, time::Duration};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let flags = Arc::new(ArcPreloader::with_loader(|| async { vec!["dark-mode"] }));
+    ///     flags.start().await;
+    ///     flags.refresh_every(Duration::from_secs(30));
+    ///     assert!(flags.is_refreshing());
+    ///
+    ///     flags.stop_refresh();
+    ///     assert!(!flags.is_refreshing());
+    /// }
+    /// ```
+    pub fn refresh_every(self: &Arc<Self>, interval: Duration) -> bool {
+        let mut deadline = tokio::time::Instant::now();
+        self.schedule_refresh(move || {
+            deadline = (deadline + interval).max(tokio::time::Instant::now());
+            Some(deadline)
+        })
+    }
+
+    /// Reloads the value in the background once it reaches `fraction` of its TTL, before it expires.
+    ///
+    /// With a TTL of 10 minutes and a `fraction` of `0.8`, the stored loader factory runs 8 minutes after the
+    /// current value was loaded, so readers keep getting a fresh value instead of `Expired` or waiting for a load.
+    /// A failed refresh keeps the current value and is retried four times within the remaining fifth of the TTL.
+    /// An `Idle` or failed preloader is loaded right away. Calling this replaces a running
+    /// [`refresh_every`](Self::refresh_every) schedule, and [`stop_refresh`](Self::stop_refresh) stops it.
+    ///
+    /// # Parameters
+    ///
+    /// - `fraction`: Share of the TTL after which the value is refreshed, at least `0.0` and less than `1.0`
+    ///
+    /// # Returns
+    ///
+    /// `true` if refreshing was scheduled, `false` if no loader factory or no TTL is configured
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fraction` is not in `0.0..1.0`, or if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{ArcPreloader, Preloader};
+    /// use std::{sync::Arc, time::Duration};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let rates = Arc::new(
+    ///         Preloader::builder()
+    ///             .ttl(Duration::from_secs(600))
+    ///             .build_arc_with_loader(|| async { vec![("EUR", 1.08)] }),
+    ///     );
+    ///     rates.start().await;
+    ///     assert!(rates.refresh_ahead(0.8));
+    ///     println!("refreshing at {:?}", rates.next_refresh());
+    /// }
+    /// ```
+    pub fn refresh_ahead(self: &Arc<Self>, fraction: f64) -> bool {
+        assert!(
+            (0.0..1.0).contains(&fraction),
+            "refresh-ahead fraction must be at least 0.0 and less than 1.0"
+        );
+        let Some(ttl) = self.inner.ttl() else {
+            return false;
+        };
+        let ahead = ttl.mul_f64(fraction);
+        let retry = (ttl - ahead) / 4;
+
+        let weak = Arc::downgrade(self);
+        let mut first = true;
+        self.schedule_refresh(move || {
+            let age = weak.upgrade()?.age();
+            let now = tokio::time::Instant::now();
+            let next = match age {
+                Some(age) if age < ahead => now + (ahead - age),
+                _ if first => now,
+                // A refresh is due but no fresh value is stored, so the last refresh failed
+                _ => now + retry,
+            };
+            first = false;
+            Some(next)
+        })
+    }
+
+    /// Re-runs the stored loader factory at the times returned by `next`, until it returns `None`.
+    ///
+    /// Replaces the running schedule. See [`refresh_every`](Self::refresh_every).
+    ///
+    /// # Returns
+    ///
+    /// `true` if refreshing was scheduled, `false` if no loader factory is stored
+    pub(crate) fn schedule_refresh(
+        self: &Arc<Self>,
+        mut next: impl FnMut() -> Option<tokio::time::Instant> + Send + 'static,
+    ) -> bool {
+        if !self.inner.has_loader() {
+            return false;
+        }
+        self.stop_refresh();
+
+        let jitter = self.inner.refresh_jitter();
+        let weak = Arc::downgrade(self);
+        let task = tokio::spawn(async move {
+            while let Some(deadline) = next() {
+                let deadline = deadline + random_delay(jitter);
+                match weak.upgrade() {
+                    Some(preloader) => *preloader.next_refresh.lock().unwrap() = Some(deadline),
+                    None => return,
+                }
+                tokio::time::sleep_until(deadline).await;
+
+                let Some(preloader) = weak.upgrade() else {
+                    return;
+                };
+                if let Some(load) = preloader.inner.stored_load() {
+                    _ = preloader.reload_result(load).await;
+                }
+            }
+            if let Some(preloader) = weak.upgrade() {
+                *preloader.next_refresh.lock().unwrap() = None;
+            }
+        });
+        self.refresher.0.lock().unwrap().push(task.abort_handle());
+        true
+    }
+
+    /// Stops refreshing started by [`refresh_every`](Self::refresh_every).
+    ///
+    /// The current value is kept.
+    pub fn stop_refresh(&self) {
+        self.refresher.abort();
+        *self.next_refresh.lock().unwrap() = None;
+    }
+
+    /// Checks if the value is refreshed periodically.
+    pub fn is_refreshing(&self) -> bool {
+        !self.refresher.0.lock().unwrap().is_empty()
+    }
+
+    /// Returns the time at which the next scheduled refresh starts, or `None` if no refresh is scheduled.
+    pub fn next_refresh(&self) -> Option<Instant> {
+        self.next_refresh
+            .lock()
+            .unwrap()
+            .map(tokio::time::Instant::into_std)
+    }
+
+    /// Keeps `task` running until the preloader is dropped.
+    pub(crate) fn attach(&self, task: AbortHandle) {
+        let mut tasks = self.tasks.0.lock().unwrap();
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(task);
+    }
+
+    /// Stores a reloaded value, or defers it until the last [`ReadGuard`] is dropped.
+    fn store_reloaded(&self, value: Arc<T>) {
+        // A finished replacement is stored first, so this later reload wins
+        self.take_replacement();
+        let mut readers = self.readers.lock().unwrap();
+        if readers.count > 0 {
+            readers.deferred = Some(value);
+            return;
+        }
+        self.swap_in(value);
+    }
+
+    /// Stores a reloaded value, or only resets the age of the current one if the comparator considers them equal.
+    ///
+    /// Called with `readers` locked, so the current value is read without storing a finished replacement.
+    fn swap_in(&self, value: Arc<T>) {
+        let latest = self.latest.load();
+        let (current, generation) = match latest.as_ref() {
+            Some(latest) => (Some(Arc::clone(&latest.value)), latest.generation),
+            None => (
+                self.inner
+                    .try_get_stale()
+                    .map(|(value, _)| Arc::clone(value)),
+                self.inner_generation,
+            ),
+        };
+        match (&self.comparator, current) {
+            // A reload returning the stored `Arc` itself, such as an unmodified HTTP response, changed nothing
+            (comparator, Some(current))
+                if Arc::ptr_eq(&current, &value)
+                    || comparator.as_ref().is_some_and(|eq| eq(&current, &value)) =>
+            {
+                self.latest.store(Some(Arc::new(Latest {
+                    value: current,
+                    loaded_at: tokio::time::Instant::now(),
+                    generation,
+                })));
+                self.reloaded.store(true, Ordering::Release);
+            }
+            _ => {
+                self.store_latest(value);
+            }
+        }
+    }
+
+    /// Stores a reloaded value as the next generation and notifies event subscribers.
+    ///
+    /// # Returns
+    ///
+    /// The value stored by the previous reload, if any
+    fn store_latest(&self, value: Arc<T>) -> Option<Arc<T>> {
+        let loaded_at = tokio::time::Instant::now();
+        let next_generation = |latest: &Option<Arc<Latest<T>>>| {
+            latest
+                .as_ref()
+                .map_or(self.inner_generation, |latest| latest.generation)
+                + 1
+        };
+        // Concurrent reloads retry until each one has stored its value as its own generation
+        let previous = self.latest.rcu(|latest| {
+            Some(Arc::new(Latest {
+                value: Arc::clone(&value),
+                loaded_at,
+                generation: next_generation(latest),
+            }))
+        });
+        self.reloaded.store(true, Ordering::Release);
+        self.event_feed().emit(PreloaderEvent::Refreshed {
+            generation: next_generation(&previous),
+        });
+        if let Some(values) = &self.values {
+            let replaced = match &previous {
+                Some(previous) => Some(RetainedValue {
+                    value: Arc::clone(&previous.value),
+                    generation: previous.generation,
+                    loaded_at: previous.loaded_at.into_std(),
+                }),
+                None => self.inner.try_get_stale().zip(self.inner.loaded_at()).map(
+                    |((value, _), loaded_at)| RetainedValue {
+                        value: Arc::clone(value),
+                        generation: self.inner_generation,
+                        loaded_at,
+                    },
+                ),
+            };
+            if let Some(replaced) = replaced {
+                values.record(replaced);
+            }
+        }
+        previous.map(|previous| Arc::clone(&previous.value))
+    }
+
+    /// Stores the value of a finished replacing `load()`, if there is one.
+    fn take_replacement(&self) {
+        if !self.replacing.load(Ordering::Acquire) {
+            return;
+        }
+        let value = {
+            let mut replacement = self.replacement.lock().unwrap();
+            let Some(receiver) = replacement.as_mut() else {
+                return;
+            };
+            let value = match receiver.try_recv() {
+                Ok(value) => Some(value),
+                Err(oneshot::error::TryRecvError::Empty) => return,
+                // The replacement failed and the current value stays
+                Err(oneshot::error::TryRecvError::Closed) => None,
+            };
+            *replacement = None;
+            self.replacing.store(false, Ordering::Release);
+            value
+        };
+        if let Some(value) = value {
+            self.store_reloaded(value);
+        }
+    }
+
+    /// Returns the value stored by the latest reload, storing a finished replacement first.
+    fn read_latest(&self) -> Guard<Option<Arc<Latest<T>>>> {
+        self.take_replacement();
+        self.latest.load()
+    }
+
+    /// Takes the value stored by the latest reload, if any.
+    fn take_latest(&mut self) -> Option<Arc<T>> {
+        let latest = self.latest.swap(None)?;
+        Some(Arc::clone(&latest.value))
+    }
+
+    /// Claims an `Idle` preloader for a load whose result is delivered with [`finish`](Self::finish).
+    ///
+    /// See [`Preloader::claim_external`].
+    pub(crate) fn claim_external(&self) -> bool {
+        self.inner.claim_external()
+    }
+
+    /// Stores the result of a load claimed with [`claim_external`](Self::claim_external).
+    pub(crate) fn finish(&self, result: Result<Arc<T>>) {
+        self.inner.finish(result);
+    }
+
+    /// Starts a loading task producing a result, following the load policy if loading was already started.
+    #[cfg_attr(not(feature = "replay"), allow(dead_code))]
+    pub(crate) async fn load_result(
+        &self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> LoadHandle {
+        self.inner
+            .load_result(async move { loader.await.map(Arc::new) })
+            .await
+    }
+
+    /// Returns the value stored by the latest reload with its generation, or `None` if the value has not been replaced.
+    #[inline]
+    fn latest(&self) -> Option<Result<(u64, Arc<T>)>> {
+        if !self.reloaded.load(Ordering::Acquire) {
+            return None;
+        }
+        let latest = self.read_latest();
+        let latest = latest.as_ref()?;
+        Some(match self.inner.ttl() {
+            Some(ttl) if latest.loaded_at.elapsed() >= ttl => Err(PreloaderError::Expired),
+            _ => Ok((latest.generation, Arc::clone(&latest.value))),
+        })
+    }
+
+    /// Starts an asynchronous task to load data.
+    ///
+    /// See [`Preloader::load`]. Under `LoadPolicy::ReplaceWhenReady`, a call made while a value is loaded or loading
+    /// starts a replacing load: readers keep getting the current value, and the new one is swapped in once it is
+    /// loaded and validated. Only the latest replacing load is swapped in, and a failed one keeps the current value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{ArcPreloader, LoadPolicy, Preloader};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::builder()
+    ///         .load_policy(LoadPolicy::ReplaceWhenReady)
+    ///         .build_arc();
+    ///     preloader.load(async { "v1" }).await.await.unwrap();
+    ///
+    ///     let replacing = preloader.load(async { "v2" }).await;
+    ///     replacing.await.unwrap();
+    ///     assert_eq!(*preloader.try_get().unwrap(), "v2");
+    /// }
+    /// ```
+    pub async fn load(&self, future: impl Future<Output = T> + Send + 'static) -> LoadHandle {
+        let future = async move { Arc::new(future.await) };
+        match self.inner.claim_load(future).await {
+            Ok(handle) => handle,
+            Err(future) if self.inner.load_policy() == LoadPolicy::ReplaceWhenReady => {
+                self.replace_when_ready(future.map(Ok))
+            }
+            Err(_) => self.inner.overlapping_load(),
+        }
+    }
+
+    /// Spawns a load whose value replaces the current one once it is loaded and validated.
+    ///
+    /// # Parameters
+    ///
+    /// - `future`: The asynchronous task producing the new value or an error
+    fn replace_when_ready(
+        &self,
+        future: impl Future<Output = Result<Arc<T>>> + Send + 'static,
+    ) -> LoadHandle {
+        let future = self.inner.supervised(future);
+        let (tx, rx) = oneshot::channel();
+        let (outcome_tx, outcome_rx) = watch::channel(None);
+        let validators = self.validators.clone();
+        let history = self.inner.history_log().clone();
+        let task = async move {
+            let started_at = SystemTime::now();
+            let start = Instant::now();
+            let result = AssertUnwindSafe(future)
+                .catch_unwind()
+                .await
+                .map_err(panicked)
+                .and_then(|result| {
+                    let value = result?;
+                    for validate in &validators {
+                        validate(&value).map_err(PreloaderError::Failed)?;
+                    }
+                    Ok(value)
+                });
+            history.record(LoadAttempt {
+                started_at,
+                duration: start.elapsed(),
+                attempts: 1,
+                trigger: LoadTrigger::Refresh,
+                error: result.as_ref().err().cloned(),
+            });
+            let outcome = result.as_ref().map(drop).map_err(Clone::clone);
+            if let Ok(value) = result {
+                _ = tx.send(value);
+            }
+            outcome_tx.send_replace(Some(outcome));
+        };
+        let task = self.inner.spawn_named(task).abort_handle();
+        self.attach(task.clone());
+        // A replacement started earlier is dropped, so the latest call wins
+        *self.replacement.lock().unwrap() = Some(rx);
+        self.replacing.store(true, Ordering::Release);
+        self.reloaded.store(true, Ordering::Release);
+        LoadHandle::new(Some(task), outcome_rx)
+    }
+
+    /// Starts an asynchronous task to load data, handing the loader a [`CancellationToken`].
+    ///
+    /// See [`Preloader::load_cancellable`].
+    pub async fn load_cancellable<F, Fut>(&self, loader: F) -> LoadHandle
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = Option<T>> + Send + 'static,
+    {
+        let future = loader;
+        self.inner
+            .load_cancellable(move |token| {
+                let future = future(token);
+                async move { future.await.map(Arc::new) }
+            })
+            .await
+    }
+
+    /// Starts an asynchronous task to load data, passing a [`LoadContext`] to the loader for every attempt.
+    ///
+    /// See [`Preloader::load_with_context`].
+    pub async fn load_with_context<F, Fut>(&self, mut factory: F) -> LoadHandle
+    where
+        F: FnMut(LoadContext) -> Fut + Send + 'static,
+        Fut: Future<Output = Option<T>> + Send + 'static,
+    {
+        self.inner
+            .load_with_context(move |ctx| {
+                let future = factory(ctx);
+                async move { future.await.map(Arc::new) }
+            })
+            .await
+    }
+
+    /// Cancels the running load.
+    ///
+    /// See [`Preloader::cancel`]. A running reload is not affected.
+    pub fn cancel(&self) -> bool {
+        self.inner.cancel()
+    }
+
+    /// Starts an asynchronous task to load data, re-creating the future when an attempt fails.
+    ///
+    /// See [`Preloader::load_with`].
+    pub async fn load_with<F, Fut>(&self, mut factory: F) -> LoadHandle
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        self.inner
+            .load_with(move || {
+                let future = factory();
+                async move { Arc::new(future.await) }
+            })
+            .await
+    }
+
+    /// Starts loading with the stored loader factory.
+    ///
+    /// See [`Preloader::start`].
+    pub async fn start(&self) {
+        self.inner.start().await;
+    }
+
+    /// Starts loading again with the stored loader factory after a failed load.
+    ///
+    /// See [`Preloader::restart`].
+    pub async fn restart(&self) -> bool {
+        self.inner.restart().await
+    }
+
+    /// Starts loading with several futures concurrently; the first one to succeed provides the value.
+    ///
+    /// See [`Preloader::load_race`].
+    pub async fn load_race<F>(&self, futures: impl IntoIterator<Item = F>) -> LoadHandle
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        self.inner
+            .load_race(
+                futures
+                    .into_iter()
+                    .map(|future| async move { Arc::new(future.await) }),
+            )
+            .await
+    }
+
+    /// Stores a value directly, bypassing any loader.
+    ///
+    /// See [`Preloader::set`].
+    pub fn set(&self, value: T) -> std::result::Result<(), T> {
+        self.inner
+            .set(Arc::new(value))
+            .map_err(|value| Arc::into_inner(value).unwrap())
+    }
+
+    /// Replaces the value with `value` right away and returns the previous one.
+    ///
+    /// Readers are never blocked: they get either the previous or the new value, and handles obtained before
+    /// the swap keep pointing to the previous one. An `Idle` or failed preloader moves to `Loaded`. If the first
+    /// load is still in progress, `value` takes precedence over its result. Subscribers of `events()` receive
+    /// `Refreshed` when a value is replaced.
+    ///
+    /// # Returns
+    ///
+    /// The previous value, or `None` if no value was loaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    ///
+    /// let session = ArcPreloader::ready("token-1".to_string());
+    /// let previous = session.swap("token-2".to_string());
+    /// assert_eq!(previous.as_deref().map(String::as_str), Some("token-1"));
+    /// assert_eq!(*session.try_get().unwrap(), "token-2");
+    /// ```
+    pub fn swap(&self, value: T) -> Option<Arc<T>> {
+        let value = match self.inner.set_or_recover(Arc::new(value)) {
+            Ok(()) => return None,
+            Err(value) => value,
+        };
+        self.take_replacement();
+        let stale = self
+            .inner
+            .try_get_stale()
+            .map(|(value, _)| Arc::clone(value));
+        self.store_latest(value).or(stale)
+    }
+
+    /// Retrieves a shared handle to the loaded data, waiting until loading is complete.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Arc<T>)`: If the data was successfully loaded
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    pub async fn get(&self) -> Result<Arc<T>> {
+        self.resolve(false).await
+    }
+
+    /// Retrieves a shared handle to the loaded data, waiting until loading is complete, or to `fallback` if
+    /// loading failed.
+    ///
+    /// See [`Preloader::get_or`].
+    pub async fn get_or(&self, fallback: T) -> Arc<T> {
+        self.get_or_else(|_| fallback).await
+    }
+
+    /// Retrieves a shared handle to the loaded data, waiting until loading is complete, or to the value computed by
+    /// `fallback` from the error if loading failed.
+    ///
+    /// See [`Preloader::get_or_else`].
+    pub async fn get_or_else(&self, fallback: impl FnOnce(PreloaderError) -> T) -> Arc<T> {
+        match self.get().await {
+            Ok(value) => value,
+            Err(error) => {
+                self.inner.log_fallback(&error);
+                Arc::new(fallback(error))
+            }
+        }
+    }
+
+    /// Starts loading with `future` if the preloader is `Idle`, then waits for the result.
+    ///
+    /// See [`Preloader::get_or_load`]. An expired value is reloaded with `future`, coalesced with concurrent
+    /// calls and [`refresh`](Self::refresh) calls, so callers racing at the expiry moment share a single load.
+    ///
+    /// With a [soft timeout](crate::PreloaderBuilder::soft_timeout), the expired value is returned if the reload
+    /// has not finished within it, and the reload keeps running in the background until the hard timeout.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main(flavor = "current_thread", start_paused = true)]
+    /// async fn main() {
+    ///     let rates = Preloader::builder()
+    ///         .ttl(Duration::from_secs(60))
+    ///         .soft_timeout(Duration::from_millis(50))
+    ///         .timeout(Duration::from_secs(2))
+    ///         .build_arc();
+    ///     rates.load(async { 1.08 }).await;
+    ///     tokio::time::sleep(Duration::from_secs(61)).await;
+    ///
+    ///     // The slow reload exceeds the soft timeout, so the previous value is served meanwhile
+    ///     let slow = async {
+    ///         tokio::time::sleep(Duration::from_millis(500)).await;
+    ///         1.09
+    ///     };
+    ///     assert_eq!(*rates.get_or_load(slow).await.unwrap(), 1.08);
+    ///
+    ///     tokio::time::sleep(Duration::from_secs(1)).await;
+    ///     assert_eq!(*rates.get().await.unwrap(), 1.09);
+    /// }
+    /// ```
+    pub async fn get_or_load(
+        &self,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> Result<Arc<T>> {
+        if matches!(self.try_get(), Err(PreloaderError::Expired)) {
+            match self.inner.timeouts() {
+                (hard, Some(soft)) => {
+                    let future = async move {
+                        match hard {
+                            Some(hard) => tokio::time::timeout(hard, future)
+                                .await
+                                .map_err(|_| PreloaderError::Timeout),
+                            None => Ok(future.await),
+                        }
+                        .map(Arc::new)
+                    };
+                    if let Some(stale) = self.reload_detached(future, soft).await? {
+                        return Ok(stale);
+                    }
+                }
+                (_, None) => {
+                    self.reload_shared(async move { Ok(Arc::new(future.await)) })
+                        .await?;
+                }
+            }
+            return self.get().await;
+        }
+        self.load(future).await;
+        self.get().await
+    }
+
+    /// Reloads with `future` in a background task, or joins the shared reload that is already running, and waits
+    /// for it for at most `soft`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(None)`: If the reload finished and stored its value
+    /// - `Ok(Some(Arc<T>))`: The current, expired value, if the reload is still running after `soft`
+    /// - `Err(PreloaderError)`: The error of the reload
+    async fn reload_detached(
+        &self,
+        future: impl Future<Output = Result<Arc<T>>> + Send + 'static,
+        soft: Duration,
+    ) -> Result<Option<Arc<T>>> {
+        let mut outcome = {
+            let mut refreshing = self.refreshing.lock().unwrap();
+            match refreshing.as_ref() {
+                Some(outcome) if outcome.borrow().is_none() => outcome.clone(),
+                _ => {
+                    let outcome = self.replace_when_ready(future).outcome();
+                    *refreshing = Some(outcome.clone());
+                    outcome
+                }
+            }
+        };
+
+        let finished = match tokio::time::timeout(soft, outcome.wait_for(Option::is_some)).await {
+            Ok(Ok(result)) => Some(result.clone().expect("outcome is set")),
+            Ok(Err(_)) => Some(Err(PreloaderError::Cancelled)),
+            Err(_) => None,
+        };
+        match finished {
+            Some(result) => result.map(|()| None),
+            // The reload keeps running and its value is swapped in once it is loaded
+            None => Ok(self.try_get_stale().map(|(value, _)| value)),
+        }
+    }
+
+    /// Initializes the preloader with a fallible `init` if it is `Idle`, then returns the value.
+    ///
+    /// See [`Preloader::get_or_try_load`].
+    pub async fn get_or_try_load<F, Fut, E>(&self, init: F) -> std::result::Result<Arc<T>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = std::result::Result<T, E>>,
+        E: From<PreloaderError>,
+    {
+        if let Some(latest) = self.latest() {
+            return latest.map(|(_, value)| value).map_err(E::from);
+        }
+        self.inner
+            .get_or_try_load(move || {
+                let init = init();
+                async move { init.await.map(Arc::new) }
+            })
+            .await
+            .cloned()
+    }
+
+    /// Initializes the preloader with `init` if it is `Idle`, then returns the value.
+    ///
+    /// See [`Preloader::get_or_init`].
+    pub async fn get_or_init<F, Fut>(&self, init: F) -> Result<Arc<T>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        self.get_or_try_load(|| {
+            let init = init();
+            async move { Ok::<_, PreloaderError>(init.await) }
+        })
+        .await
+    }
+
+    /// Checks if the value has been loaded, like `tokio::sync::OnceCell::initialized`.
+    pub fn initialized(&self) -> bool {
+        self.is_loaded()
+    }
+
+    /// Waits until the value has been loaded, even if loading has not been started yet.
+    ///
+    /// See [`Preloader::wait`].
+    pub async fn wait(&self) -> Result<Arc<T>> {
+        self.resolve(true).await
+    }
+
+    /// Waits for the result of loading.
+    ///
+    /// See [`Preloader::resolve`].
+    pub(crate) async fn resolve(&self, wait_idle: bool) -> Result<Arc<T>> {
+        self.resolve_versioned(wait_idle)
+            .await
+            .map(|(_, value)| value)
+    }
+
+    /// Waits for the result of loading, together with the generation of the value.
+    async fn resolve_versioned(&self, wait_idle: bool) -> Result<(u64, Arc<T>)> {
+        if let Some(latest) = self.latest() {
+            return latest;
+        }
+        if !wait_idle && self.placeholder.is_some() {
+            // Answers with the placeholder while the first value is pending
+            if let Ok(value) = self.try_get_versioned() {
+                return Ok(value);
+            }
+        }
+        let value = Arc::clone(self.inner.resolve(wait_idle).await?);
+        // A reload may have replaced the value while waiting
+        Ok(self
+            .latest()
+            .transpose()?
+            .unwrap_or((self.inner_generation, value)))
+    }
+
+    /// Attempts to retrieve a shared handle to the loaded data immediately.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Arc<T>)`: If the data was successfully loaded
+    /// - `Err(PreloaderError)`: If the data is not loaded or is still loading
+    pub fn try_get(&self) -> Result<Arc<T>> {
+        self.try_get_versioned().map(|(_, value)| value)
+    }
+
+    /// Waits until loading is complete and returns a guard holding the current value in place.
+    ///
+    /// While any guard is alive, values stored by reloads and refreshes are held back, and the latest of them
+    /// replaces the current value when the last guard is dropped. Every read made while holding a guard, through
+    /// it or through `get()`, then sees the same value. Values set explicitly with [`swap`](Self::swap) are not
+    /// deferred. The placeholder is never returned; the guard waits for the first loaded value instead.
+    ///
+    /// Holding guards for long delays every reload, so keep them for the duration of one unit of work, such as
+    /// a request.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ReadGuard)`: The guard giving access to the value
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let prices = ArcPreloader::ready(vec![10, 20]);
+    ///
+    ///     let guard = prices.get_guard().await.unwrap();
+    ///     prices.reload(async { vec![11, 21] }).await.unwrap();
+    ///     // The reload waits for the guard
+    ///     assert_eq!(*prices.get().await.unwrap(), [10, 20]);
+    ///     assert_eq!(guard[0] + guard[1], 30);
+    ///
+    ///     drop(guard);
+    ///     assert_eq!(*prices.get().await.unwrap(), [11, 21]);
+    /// }
+    /// ```
+    pub async fn get_guard(&self) -> Result<ReadGuard<'_, T>> {
+        loop {
+            self.take_replacement();
+            {
+                let mut readers = self.readers.lock().unwrap();
+                if let Some(current) = self.current_value() {
+                    let (generation, value) = current?;
+                    readers.count += 1;
+                    return Ok(ReadGuard {
+                        preloader: self,
+                        value,
+                        generation,
+                    });
+                }
+            }
+            self.inner.resolve(false).await?;
+        }
+    }
+
+    /// Returns the current value and its generation, or `None` if the first value is still pending.
+    ///
+    /// Called with `readers` locked, so a finished replacement is not stored.
+    fn current_value(&self) -> Option<Result<(u64, Arc<T>)>> {
+        if let Some(latest) = self.latest.load().as_ref() {
+            return Some(match self.inner.ttl() {
+                Some(ttl) if latest.loaded_at.elapsed() >= ttl => Err(PreloaderError::Expired),
+                _ => Ok((latest.generation, Arc::clone(&latest.value))),
+            });
+        }
+        match self.inner.try_get() {
+            Ok(value) => Some(Ok((self.inner_generation, Arc::clone(value)))),
+            Err(PreloaderError::NotLoaded | PreloaderError::Loading) => None,
+            Err(error) => Some(Err(error)),
+        }
+    }
+
+    /// Releases a [`ReadGuard`], storing the deferred reloaded value once the last one is released.
+    fn release_reader(&self) {
+        let mut readers = self.readers.lock().unwrap();
+        readers.count -= 1;
+        if readers.count == 0 {
+            if let Some(value) = readers.deferred.take() {
+                self.swap_in(value);
+            }
+        }
+    }
+
+    /// Retrieves a shared handle to the loaded data together with its generation, waiting until loading is complete.
+    ///
+    /// The generation starts at 1 for the first loaded value and is incremented every time a reload replaces it,
+    /// so comparing generations tells whether the value has changed since it was last read.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((u64, Arc<T>))`: The generation and the data, read together
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::ArcPreloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let catalog = ArcPreloader::ready(vec!["apple"]);
+    ///     let (seen, _) = catalog.get_versioned().await.unwrap();
+    ///
+    ///     catalog.reload(async { vec!["apple", "pear"] }).await.unwrap();
+    ///     let (generation, items) = catalog.get_versioned().await.unwrap();
+    ///     assert!(generation > seen);
+    ///     assert_eq!(items.len(), 2);
+    /// }
+    /// ```
+    pub async fn get_versioned(&self) -> Result<(u64, Arc<T>)> {
+        self.resolve_versioned(false).await
+    }
+
+    /// Attempts to retrieve a shared handle to the loaded data together with its generation immediately.
+    ///
+    /// See [`get_versioned`](Self::get_versioned).
+    pub fn try_get_versioned(&self) -> Result<(u64, Arc<T>)> {
+        if let Some(latest) = self.latest() {
+            return latest;
+        }
+        let value = match self.inner.try_get() {
+            Ok(value) => Arc::clone(value),
+            Err(error) => return self.placeholder().map(|value| (0, value)).ok_or(error),
+        };
+        Ok(self
+            .latest()
+            .transpose()?
+            .unwrap_or((self.inner_generation, value)))
+    }
+
+    /// Returns the placeholder if one is set and the first value is still pending.
+    fn placeholder(&self) -> Option<Arc<T>> {
+        let pending = matches!(
+            self.inner.state(),
+            PreloaderState::Idle | PreloaderState::Start | PreloaderState::Loading
+        );
+        self.placeholder.as_ref().filter(|_| pending).cloned()
+    }
+
+    /// Returns the generation of the current value, or 0 if no value has been loaded.
+    ///
+    /// The first loaded value is generation 1, and every reload that replaces the value increments it.
+    pub fn generation(&self) -> u64 {
+        match self.read_latest().as_ref() {
+            Some(latest) => latest.generation,
+            None if self.inner.generation() == 0 => 0,
+            None => self.inner_generation,
+        }
+    }
+
+    /// Checks if the value has been replaced since `generation` was read.
+    ///
+    /// See [`generation`](Self::generation) and [`dedupe`](Self::dedupe).
+    pub fn changed_since(&self, generation: u64) -> bool {
+        self.generation() != generation
+    }
+
+    /// Returns a mutable reference to the loaded data if no other handle to it exists.
+    ///
+    /// See [`Preloader::get_mut`] and [`Arc::get_mut`].
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.take_replacement();
+        if let Some(latest) = self.latest.swap(None) {
+            // Readers only hold a snapshot during a read, so under `&mut self` no other handle to it exists
+            let latest = match Arc::try_unwrap(latest) {
+                Ok(latest) => latest,
+                Err(latest) => {
+                    self.latest.store(Some(latest));
+                    return None;
+                }
+            };
+            // A reloaded value is moved into `inner`, the only place a mutable reference can point into
+            let generation = latest.generation;
+            return match self.inner.replace_loaded(latest.value, latest.loaded_at) {
+                Ok(value) => {
+                    self.inner_generation = generation;
+                    Arc::get_mut(value)
+                }
+                Err(value) => {
+                    self.latest.store(Some(Arc::new(Latest {
+                        value,
+                        loaded_at: latest.loaded_at,
+                        generation,
+                    })));
+                    None
+                }
+            };
+        }
+        self.inner.get_mut().and_then(Arc::get_mut)
+    }
+
+    /// Drops the loaded value to reclaim its memory, moving the preloader back to the `Idle` state.
+    ///
+    /// The value is freed once the handles returned before are dropped. See [`Preloader::unload`]; the next
+    /// loaded value starts again at generation 1.
+    ///
+    /// # Returns
+    ///
+    /// Whether a value was dropped
+    pub fn unload(&mut self) -> bool {
+        *self.replacement.get_mut().unwrap() = None;
+        *self.replacing.get_mut() = false;
+        let reloaded = self.take_latest().is_some();
+        *self.reloaded.get_mut() = false;
+        self.inner_generation = 1;
+        self.inner.unload() || reloaded
+    }
+
+    /// Takes the shared handle to the loaded data, consuming the `ArcPreloader`.
+    pub async fn take(self) -> Result<Arc<T>> {
+        self.take_replacement();
+        match self.latest.swap(None) {
+            Some(latest) => Ok(Arc::clone(&latest.value)),
+            None => self.inner.take().await,
+        }
+    }
+
+    /// Takes the shared handle if the data is available right now, handing the `ArcPreloader` back otherwise.
+    ///
+    /// See [`Preloader::try_take`].
+    #[allow(clippy::result_large_err)]
+    pub fn try_take(mut self) -> std::result::Result<Arc<T>, Self> {
+        self.take_replacement();
+        if let Some(value) = self.take_latest() {
+            return Ok(value);
+        }
+        // Checked on the borrowed preloader, so `self` is handed back with its configuration intact
+        if self.inner.try_get().is_err() {
+            return Err(self);
+        }
+        self.inner.try_take().map_err(|_| unreachable!())
+    }
+
+    /// Takes the shared handle, waiting at most `timeout` for loading to complete.
+    ///
+    /// See [`Preloader::take_timeout`].
+    #[allow(clippy::result_large_err)]
+    pub async fn take_timeout(mut self, timeout: Duration) -> std::result::Result<Arc<T>, Self> {
+        self.take_replacement();
+        if let Some(value) = self.take_latest() {
+            return Ok(value);
+        }
+        let loaded = matches!(
+            tokio::time::timeout(timeout, self.inner.get()).await,
+            Ok(Ok(_))
+        );
+        if !loaded {
+            return Err(self);
+        }
+        self.inner.try_take().map_err(|_| unreachable!())
+    }
+
+    /// Extracts the shared handle if the preloader is in the `Loaded` state, consuming it.
+    ///
+    /// See [`Preloader::into_inner`].
+    pub fn into_inner(self) -> Option<Arc<T>> {
+        self.take_replacement();
+        match self.latest.swap(None) {
+            Some(latest) => Some(Arc::clone(&latest.value)),
+            None => self.inner.into_inner(),
+        }
+    }
+
+    /// Checks if the preloader has completed loading and data is available.
+    pub fn is_loaded(&self) -> bool {
+        self.inner.is_loaded()
+    }
+
+    /// Returns the current state of the preloader.
+    ///
+    /// See [`Preloader::state`].
+    pub fn state(&self) -> PreloaderState {
+        self.inner.state()
+    }
+
+    /// Checks if loading has not been started yet.
+    pub fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    /// Checks if loading has been started and has not finished yet.
+    pub fn is_loading(&self) -> bool {
+        self.inner.is_loading()
+    }
+
+    /// Checks if loading finished with an error, including a poisoned preloader.
+    pub fn is_failed(&self) -> bool {
+        self.inner.is_failed()
+    }
+
+    /// Returns the time at which the latest load was started.
+    pub fn started_at(&self) -> Option<Instant> {
+        self.inner.started_at()
+    }
+
+    /// Returns the channel of lifecycle events.
+    pub(crate) fn event_feed(&self) -> &Events {
+        self.inner.event_feed()
+    }
+
+    /// Returns the error of the latest failed load together with the preloader name and the number of attempts.
+    ///
+    /// See [`Preloader::error_context`].
+    pub fn error_context(&self) -> Option<ErrorContext> {
+        self.inner.error_context()
+    }
+
+    /// Returns the latest finished loads and reloads, oldest first.
+    ///
+    /// See [`Preloader::history`]; reloads replacing a loaded value are recorded as `LoadTrigger::Refresh`.
+    pub fn history(&self) -> Vec<LoadAttempt> {
+        self.inner.history()
+    }
+
+    /// Returns how many callers are blocked in `get()` right now and how long past waits took.
+    ///
+    /// See [`Preloader::wait_stats`].
+    pub fn wait_stats(&self) -> WaitStats {
+        self.inner.wait_stats()
+    }
+
+    /// Returns the p50, p95, p99, and maximum durations of the latest loads, reloads, and waits.
+    ///
+    /// See [`Preloader::stats`].
+    pub fn stats(&self) -> PreloaderStats {
+        self.inner.stats()
+    }
+
+    /// Returns the error of the latest failed load, if it is still recorded.
+    ///
+    /// See [`Preloader::last_error`].
+    pub fn last_error(&self) -> Option<PreloaderError> {
+        self.inner.last_error()
+    }
+
+    /// Returns the time at which the value finished loading, or was last replaced by a reload.
+    pub fn loaded_at(&self) -> Option<Instant> {
+        match self.read_latest().as_ref() {
+            Some(latest) => Some(latest.loaded_at.into_std()),
+            None => self.inner.loaded_at(),
+        }
+    }
+
+    /// Returns how long loading took.
+    ///
+    /// See [`Preloader::load_duration`].
+    pub fn load_duration(&self) -> Option<Duration> {
+        self.inner.load_duration()
+    }
+
+    /// Returns how long ago the value finished loading, or was last replaced by a reload.
+    pub fn age(&self) -> Option<Duration> {
+        match self.read_latest().as_ref() {
+            Some(latest) => Some(latest.loaded_at.elapsed()),
+            None => self.inner.age(),
+        }
+    }
+
+    /// Returns the current value together with its age, even while a refresh is running, after a refresh failed,
+    /// or once the value has expired.
+    ///
+    /// # Returns
+    ///
+    /// The latest value and the time since it was loaded or replaced, or `None` if no value has been loaded
+    pub fn try_get_stale(&self) -> Option<(Arc<T>, Duration)> {
+        if let Some(latest) = self.read_latest().as_ref() {
+            return Some((Arc::clone(&latest.value), latest.loaded_at.elapsed()));
+        }
+        self.inner
+            .try_get_stale()
+            .map(|(value, age)| (Arc::clone(value), age))
+    }
+
+    /// Retrieves the current value together with its age, waiting for the first load if no value exists yet.
+    ///
+    /// Serving slightly old data is often better than blocking or failing: once any value has been loaded,
+    /// this returns it immediately, regardless of running or failed refreshes and the TTL.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((Arc<T>, Duration))`: The latest value and the time since it was loaded or replaced
+    /// - `Err(PreloaderError)`: If no value could be loaded
+    pub async fn get_stale(&self) -> Result<(Arc<T>, Duration)> {
+        if let Some(stale) = self.try_get_stale() {
+            return Ok(stale);
+        }
+        let result = self.get().await.map(drop);
+        self.try_get_stale().ok_or_else(|| result.unwrap_err())
+    }
+
+    /// Checks if the loaded value is older than the configured TTL.
+    pub fn is_expired(&self) -> bool {
+        match self.latest() {
+            Some(latest) => latest.is_err(),
+            None => self.inner.is_expired(),
+        }
+    }
+
+    /// Returns the name set through the builder, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    /// Returns the timeout of a load attempt starting now.
+    ///
+    /// See [`Preloader::attempt_timeout`].
+    pub fn attempt_timeout(&self) -> Option<Duration> {
+        self.inner.attempt_timeout()
+    }
+}
+
+/// Awaiting a reference to an `ArcPreloader` is equivalent to calling [`ArcPreloader::get`].
+impl<'a, T: Send + Sync + 'static> IntoFuture for &'a ArcPreloader<T> {
+    type Output = Result<Arc<T>>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Result<Arc<T>>> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.get())
+    }
+}
+
+/// Shows the state and timing without requiring `T: Debug`; see [`Preloader`]'s `Debug` implementation.
+impl<T: Send + Sync + 'static> fmt::Debug for ArcPreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArcPreloader").field(&self.inner).finish()
+    }
+}
+
+impl<T: Send + Sync + 'static> From<T> for ArcPreloader<T> {
+    /// Equivalent to [`ArcPreloader::ready`].
+    fn from(value: T) -> Self {
+        Self::ready(value)
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for ArcPreloader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Access to the value of an [`ArcPreloader`] that defers reloads until dropped
+///
+/// Created by [`ArcPreloader::get_guard`].
+pub struct ReadGuard<'a, T: Send + Sync + 'static> {
+    preloader: &'a ArcPreloader<T>,
+    value: Arc<T>,
+    generation: u64,
+}
+
+impl<T: Send + Sync + 'static> ReadGuard<'_, T> {
+    /// Returns the generation of the guarded value.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns a shared handle to the guarded value, which outlives the guard.
+    pub fn to_arc(&self) -> Arc<T> {
+        Arc::clone(&self.value)
+    }
+}
+
+impl<T: Send + Sync + 'static> Deref for ReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Send + Sync + 'static> Drop for ReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.preloader.release_reader();
+    }
+}
+
+impl<T: Send + Sync + fmt::Debug + 'static> fmt::Debug for ReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadGuard")
+            .field("value", &self.value)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
@@ -0,0 +1,176 @@
+//! Preloader array module
+//!
+//! This module provides the `PreloaderArray` struct, a fixed number of preloaders of the same type whose count is
+//! known at compile time. Loading takes exactly one future per slot, and waiting returns an array of values that
+//! can be destructured without length checks.
+
+use std::{fmt, future::Future, ops::Index, slice};
+
+use futures::future;
+
+use crate::{LoadHandle, Preloader, PreloaderError};
+
+/// Fixed-size array of `N` preloaders
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::PreloaderArray;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let shards: PreloaderArray<Vec<u32>, 3> = PreloaderArray::new();
+///     shards
+///         .load_all([0, 1, 2].map(|shard| async move { vec![shard; 4] }))
+///         .await;
+///
+///     // The second shard can be read on its own
+///     assert_eq!(shards[1].get().await.unwrap().len(), 4);
+///
+///     let [first, second, third] = shards.wait_all().await.unwrap();
+///     assert_eq!((first[0], second[0], third[0]), (0, 1, 2));
+/// }
+/// ```
+pub struct PreloaderArray<T: Send + 'static, const N: usize> {
+    items: [Preloader<T>; N],
+}
+
+impl<T: Send + 'static, const N: usize> PreloaderArray<T, N> {
+    /// Creates an array of `N` preloaders in the `Idle` state.
+    pub fn new() -> Self {
+        Self::from(std::array::from_fn(|_| Preloader::new()))
+    }
+
+    /// Starts loading every slot, each with the future at the same index.
+    ///
+    /// Like [`Preloader::load`], a slot that is not in the `Idle` state ignores its future.
+    ///
+    /// # Parameters
+    ///
+    /// - `futures`: The asynchronous tasks loading each slot, in order
+    ///
+    /// # Returns
+    ///
+    /// The handles of the loading tasks, in order
+    pub async fn load_all<F>(&self, futures: [F; N]) -> [LoadHandle; N]
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        let handles = future::join_all(
+            self.items
+                .iter()
+                .zip(futures)
+                .map(|(item, future)| item.load(future)),
+        )
+        .await;
+        match handles.try_into() {
+            Ok(handles) => handles,
+            Err(_) => unreachable!("one handle is returned per slot"),
+        }
+    }
+
+    /// Waits until every slot has finished loading and returns references to their values, in order.
+    ///
+    /// Slots are awaited concurrently. A slot that is still `Idle` is waited on until its loading is started.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok([&T; N])`: The values of all slots
+    /// - `Err(Vec<(usize, PreloaderError)>)`: The index and the error of each slot that failed
+    pub async fn wait_all(&self) -> Result<[&T; N], Vec<(usize, PreloaderError)>> {
+        let results = future::join_all(self.items.iter().map(Preloader::get)).await;
+        let mut values = Vec::with_capacity(N);
+        let mut failed = Vec::new();
+        for (index, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => failed.push((index, error)),
+            }
+        }
+        match values.try_into() {
+            Ok(values) if failed.is_empty() => Ok(values),
+            _ => Err(failed),
+        }
+    }
+
+    /// Returns the preloader of the slot at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<&Preloader<T>> {
+        self.items.get(index)
+    }
+
+    /// Returns the preloaders of all slots.
+    pub fn items(&self) -> &[Preloader<T>; N] {
+        &self.items
+    }
+
+    /// Returns an iterator over the preloaders of all slots, in order.
+    pub fn iter(&self) -> slice::Iter<'_, Preloader<T>> {
+        self.items.iter()
+    }
+
+    /// Returns the number of slots loaded successfully.
+    pub fn loaded_count(&self) -> usize {
+        self.items.iter().filter(|item| item.is_loaded()).count()
+    }
+
+    /// Checks if every slot has been loaded successfully.
+    pub fn is_ready(&self) -> bool {
+        self.items.iter().all(Preloader::is_loaded)
+    }
+
+    /// Returns the number of slots, `N`.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Checks if the array has no slots.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the preloaders of all slots, consuming the array.
+    pub fn into_inner(self) -> [Preloader<T>; N] {
+        self.items
+    }
+}
+
+impl<T: Send + 'static, const N: usize> Default for PreloaderArray<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Groups preloaders configured through [`Preloader::builder`].
+impl<T: Send + 'static, const N: usize> From<[Preloader<T>; N]> for PreloaderArray<T, N> {
+    fn from(items: [Preloader<T>; N]) -> Self {
+        Self { items }
+    }
+}
+
+impl<T: Send + 'static, const N: usize> Index<usize> for PreloaderArray<T, N> {
+    type Output = Preloader<T>;
+
+    fn index(&self, index: usize) -> &Preloader<T> {
+        &self.items[index]
+    }
+}
+
+impl<'a, T: Send + 'static, const N: usize> IntoIterator for &'a PreloaderArray<T, N> {
+    type Item = &'a Preloader<T>;
+    type IntoIter = slice::Iter<'a, Preloader<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+/// Shows the progress without requiring `T: Debug`.
+impl<T: Send + 'static, const N: usize> fmt::Debug for PreloaderArray<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreloaderArray")
+            .field("len", &N)
+            .field("loaded", &self.loaded_count())
+            .finish()
+    }
+}
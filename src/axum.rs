@@ -0,0 +1,153 @@
+//! axum integration module
+//!
+//! This module lets axum handlers read preloaded values from the application state.
+//! The [`Preloaded`] extractor rejects requests with `503 Service Unavailable` until the value is loaded,
+//! and [`readiness_route`] reports whether every preloader of a group is ready.
+
+use std::{fmt, ops::Deref, sync::Arc};
+
+use ::axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, MethodRouter},
+};
+
+use crate::{Preloader, PreloaderError, PreloaderGroup};
+
+/// Extractor giving a handler access to a loaded value
+///
+/// The preloader is taken from the application state, which must provide an `Arc<Preloader<T>>`
+/// through [`FromRef`]. The extractor derefs to `&T`, so no value is cloned.
+/// If the preloader is not loaded yet, the request is rejected with a [`PreloadRejection`].
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use preloader::{axum::Preloaded, Preloader};
+/// use std::{collections::HashMap, sync::Arc};
+///
+/// async fn price(prices: Preloaded<HashMap<String, f64>>) -> String {
+///     format!("{:?}", prices.get("apple"))
+/// }
+///
+/// // Any state works as long as it implements `FromRef` for the preloader
+/// let prices: Arc<Preloader<HashMap<String, f64>>> = Arc::new(Preloader::new());
+/// let app: Router = Router::new().route("/price", get(price)).with_state(prices);
+/// ```
+pub struct Preloaded<T: Send + 'static>(Arc<Preloader<T>>);
+
+impl<T: Send + 'static> Preloaded<T> {
+    /// Returns the preloader the value was extracted from.
+    pub fn preloader(&self) -> &Arc<Preloader<T>> {
+        &self.0
+    }
+}
+
+impl<T: Send + 'static> Deref for Preloaded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // A shared preloader cannot be unloaded, so once extracted the value stays loaded, even past its TTL
+        let (value, _) = self
+            .0
+            .try_get_stale()
+            .expect("extracted preloaders stay loaded");
+        value
+    }
+}
+
+impl<T: fmt::Debug + Send + 'static> fmt::Debug for Preloaded<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Preloaded").field(&**self).finish()
+    }
+}
+
+impl<S, T> FromRequestParts<S> for Preloaded<T>
+where
+    S: Send + Sync,
+    T: Send + Sync + 'static,
+    Arc<Preloader<T>>: FromRef<S>,
+{
+    type Rejection = PreloadRejection;
+
+    async fn from_request_parts(_parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let preloader = Arc::<Preloader<T>>::from_ref(state);
+        preloader.try_get().map_err(PreloadRejection)?;
+        Ok(Self(preloader))
+    }
+}
+
+/// Rejection returned by [`Preloaded`] when the value is not available
+///
+/// Responds with `503 Service Unavailable` while the preloader is idle or loading or the caller's deadline passed,
+/// and with `500 Internal Server Error` if loading failed.
+#[derive(Debug)]
+pub struct PreloadRejection(pub PreloaderError);
+
+impl PreloadRejection {
+    /// Returns the status code of the response.
+    pub fn status(&self) -> StatusCode {
+        match self.0 {
+            PreloaderError::NotLoaded
+            | PreloaderError::Loading
+            | PreloaderError::CircuitOpen
+            | PreloaderError::QueueFull
+            | PreloaderError::DeadlineExceeded => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl fmt::Display for PreloadRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for PreloadRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl IntoResponse for PreloadRejection {
+    fn into_response(self) -> Response {
+        (self.status(), self.0.to_string()).into_response()
+    }
+}
+
+/// Creates a `GET` route reporting whether every preloader of `group` has been loaded.
+///
+/// Responds with `200 OK` once the group is ready and `503 Service Unavailable` before,
+/// with a plain text body such as `3/5 preloaders loaded`. Suitable as a Kubernetes readiness probe.
+///
+/// # Parameters
+///
+/// - `group`: The group to report on
+///
+/// # Example
+///
+/// ```rust
+/// use axum::Router;
+/// use preloader::{axum::readiness_route, PreloaderGroup};
+/// use std::sync::Arc;
+///
+/// let group = Arc::new(PreloaderGroup::new());
+/// let app: Router = Router::new().route("/ready", readiness_route(group));
+/// ```
+pub fn readiness_route<S>(group: Arc<PreloaderGroup>) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    get(move || async move {
+        let report = group.readiness();
+        let status = if report.is_ready() {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+        (status, report.to_string())
+    })
+}
@@ -0,0 +1,135 @@
+//! Background runtime module
+//!
+//! This module provides `BackgroundRuntime`, a small Tokio runtime owned by the crate for low-priority preloads.
+//! Bulk warmup spawned there runs on its own threads, so it cannot take worker threads from the runtime handling
+//! latency-sensitive requests.
+
+use std::{
+    fmt, io,
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+};
+
+use tokio::{
+    runtime::{self, Handle},
+    sync::oneshot,
+};
+
+/// Tokio runtime driven by a dedicated background thread, for low-priority loads
+///
+/// Cloning the runtime is cheap and shares it. The runtime shuts down once every clone is dropped, including the
+/// clones held by preloaders built with [`PreloaderBuilder::background_runtime`](crate::PreloaderBuilder::background_runtime).
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{BackgroundRuntime, Preloader};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let background = BackgroundRuntime::new(1).unwrap();
+///     let warmup = Preloader::builder().background_runtime(&background).build();
+///     warmup
+///         .load(async { std::thread::current().name().map(String::from) })
+///         .await;
+///     assert_eq!(
+///         warmup.get().await.unwrap().as_deref(),
+///         Some("preloader-background")
+///     );
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BackgroundRuntime {
+    handle: Handle,
+    worker_threads: usize,
+    /// Stops the driving thread when the last clone is dropped
+    _shutdown: Arc<Shutdown>,
+}
+
+struct Shutdown(Mutex<Option<oneshot::Sender<()>>>);
+
+impl Drop for Shutdown {
+    fn drop(&mut self) {
+        if let Some(sender) = self.0.get_mut().unwrap().take() {
+            _ = sender.send(());
+        }
+    }
+}
+
+impl BackgroundRuntime {
+    /// Starts a multi-threaded runtime with `worker_threads` worker threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `worker_threads` is zero.
+    pub fn new(worker_threads: usize) -> io::Result<Self> {
+        assert!(
+            worker_threads > 0,
+            "a background runtime needs at least one worker thread"
+        );
+        let runtime = runtime::Builder::new_multi_thread()
+            .worker_threads(worker_threads)
+            .thread_name("preloader-background")
+            .enable_all()
+            .build()?;
+        Self::drive(runtime, worker_threads)
+    }
+
+    /// Starts a single-threaded runtime, run by one background thread.
+    ///
+    /// Every task spawned on it shares that thread, so loaders should not block.
+    pub fn current_thread() -> io::Result<Self> {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Self::drive(runtime, 1)
+    }
+
+    /// Returns the process-wide background runtime, a single-threaded runtime started on first use.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime could not be started.
+    pub fn global() -> &'static BackgroundRuntime {
+        static GLOBAL: OnceLock<BackgroundRuntime> = OnceLock::new();
+        GLOBAL.get_or_init(|| {
+            Self::current_thread().expect("failed to start the preloader background runtime")
+        })
+    }
+
+    /// Runs `runtime` on a background thread until the last clone is dropped.
+    fn drive(runtime: runtime::Runtime, worker_threads: usize) -> io::Result<Self> {
+        let handle = runtime.handle().clone();
+        let (sender, receiver) = oneshot::channel();
+        // The runtime is dropped on its own thread, where blocking on its shutdown is allowed
+        thread::Builder::new()
+            .name("preloader-background".to_string())
+            .spawn(move || {
+                _ = runtime.block_on(receiver);
+            })?;
+        Ok(Self {
+            handle,
+            worker_threads,
+            _shutdown: Arc::new(Shutdown(Mutex::new(Some(sender)))),
+        })
+    }
+
+    /// Returns the handle of the runtime, for spawning other tasks on it.
+    pub fn handle(&self) -> &Handle {
+        &self.handle
+    }
+
+    /// Returns the number of worker threads of the runtime.
+    pub fn worker_threads(&self) -> usize {
+        self.worker_threads
+    }
+}
+
+impl fmt::Debug for BackgroundRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BackgroundRuntime")
+            .field("worker_threads", &self.worker_threads)
+            .finish_non_exhaustive()
+    }
+}
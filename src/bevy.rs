@@ -0,0 +1,212 @@
+//! Bevy integration module
+//!
+//! This module provides `PreloadPlugin`, which drives preloaders on Bevy's async compute task pool
+//! instead of a Tokio runtime. Loads are registered on the [`Preloads`] resource, their progress can be shown
+//! on a loading screen, and a [`PreloadFinished`] message is written whenever one of them completes.
+
+use std::{
+    future::Future,
+    panic::AssertUnwindSafe,
+    sync::{Arc, Mutex},
+};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    message::{Message, MessageWriter},
+    resource::Resource,
+    system::ResMut,
+};
+use bevy_tasks::{futures::check_ready, AsyncComputeTaskPool, Task, TaskPool};
+use futures::FutureExt;
+
+use crate::{preloader::panicked, EntryState, EntryStatus, Preloader, PreloaderError};
+
+/// Plugin driving the loads registered on [`Preloads`]
+///
+/// Adds the [`Preloads`] resource and the [`PreloadFinished`] message. Completed loads are collected
+/// once per frame, before `Update`. Loads run on the task pools set up by Bevy's `TaskPoolPlugin`,
+/// which is part of `DefaultPlugins` and `MinimalPlugins`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_app::{App, Startup, TaskPoolPlugin, Update};
+/// use bevy_ecs::system::{Res, ResMut};
+/// use preloader::{PreloadPlugin, Preloads};
+///
+/// fn start_loading(mut preloads: ResMut<Preloads>) {
+///     // Keep the returned preloader, for example in a resource, to read the value later
+///     let _level = preloads.add("level", async { vec![0u8; 1024] });
+/// }
+///
+/// fn loading_screen(preloads: Res<Preloads>) {
+///     if !preloads.is_ready() {
+///         println!("Loading... {:.0}%", preloads.progress() * 100.0);
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins((TaskPoolPlugin::default(), PreloadPlugin))
+///     .add_systems(Startup, start_loading)
+///     .add_systems(Update, loading_screen)
+///     .update();
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PreloadPlugin;
+
+impl Plugin for PreloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Preloads>()
+            .add_message::<PreloadFinished>()
+            .add_systems(PreUpdate, poll_preloads);
+    }
+}
+
+/// Message written when a load registered on [`Preloads`] completes
+#[derive(Debug, Clone, Message)]
+pub struct PreloadFinished {
+    /// Name the load was registered under
+    pub name: String,
+    /// Outcome of the load
+    pub result: Result<(), PreloaderError>,
+}
+
+/// Load running on the task pool
+struct Pending {
+    index: usize,
+    /// Polls the task and stores its result in the preloader once it is complete
+    poll: Box<dyn FnMut() -> Option<Result<(), PreloaderError>> + Send>,
+}
+
+/// Registered load and its current state
+struct Entry {
+    name: String,
+    state: EntryState,
+}
+
+/// Resource registering loads driven by [`PreloadPlugin`] and reporting their progress
+#[derive(Resource, Default)]
+pub struct Preloads {
+    entries: Vec<Entry>,
+    pending: Mutex<Vec<Pending>>,
+}
+
+impl Preloads {
+    /// Starts loading `future` on Bevy's async compute task pool.
+    ///
+    /// The returned preloader is in the `Start` state until the plugin collects the result,
+    /// so `try_get()` can be checked from any system. A panicking future fails the load with
+    /// `PreloaderError::Panicked`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name identifying the load in status reports and messages
+    /// - `future`: The asynchronous task loading the value
+    ///
+    /// # Returns
+    ///
+    /// The preloader receiving the value
+    pub fn add<T, F>(&mut self, name: impl Into<String>, future: F) -> Arc<Preloader<T>>
+    where
+        T: Send + Sync + 'static,
+        F: Future<Output = T> + Send + 'static,
+    {
+        let preloader = Arc::new(Preloader::new());
+        preloader.claim_external();
+
+        let mut task: Task<Result<T, PreloaderError>> =
+            AsyncComputeTaskPool::get_or_init(TaskPool::new).spawn(
+                AssertUnwindSafe(future)
+                    .catch_unwind()
+                    .map(|result| result.map_err(panicked)),
+            );
+        let target = Arc::clone(&preloader);
+        let poll = Box::new(move || {
+            let result = check_ready(&mut task)?;
+            let outcome = result.as_ref().map(drop).map_err(Clone::clone);
+            target.finish(result);
+            Some(outcome)
+        });
+
+        self.pending.get_mut().unwrap().push(Pending {
+            index: self.entries.len(),
+            poll,
+        });
+        self.entries.push(Entry {
+            name: name.into(),
+            state: EntryState::Loading,
+        });
+        preloader
+    }
+
+    /// Checks if every registered load has completed successfully.
+    pub fn is_ready(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|entry| matches!(entry.state, EntryState::Loaded))
+    }
+
+    /// Returns the fraction of registered loads that have completed, from `0.0` to `1.0`.
+    ///
+    /// Failed loads count as completed. Returns `1.0` if nothing is registered.
+    pub fn progress(&self) -> f32 {
+        if self.entries.is_empty() {
+            return 1.0;
+        }
+        let finished = self
+            .entries
+            .iter()
+            .filter(|entry| !matches!(entry.state, EntryState::Loading))
+            .count();
+        finished as f32 / self.entries.len() as f32
+    }
+
+    /// Returns the number of loads that have completed successfully.
+    pub fn loaded_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.state, EntryState::Loaded))
+            .count()
+    }
+
+    /// Returns the number of registered loads.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if no loads are registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the status of every registered load, in registration order.
+    pub fn status(&self) -> Vec<EntryStatus> {
+        self.entries
+            .iter()
+            .map(|entry| EntryStatus {
+                name: entry.name.clone(),
+                state: entry.state.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Collects the results of completed loads and writes a message for each of them.
+fn poll_preloads(mut preloads: ResMut<Preloads>, mut finished: MessageWriter<PreloadFinished>) {
+    let preloads = &mut *preloads;
+    preloads.pending.get_mut().unwrap().retain_mut(|pending| {
+        let Some(result) = (pending.poll)() else {
+            return true;
+        };
+        let entry = &mut preloads.entries[pending.index];
+        entry.state = match &result {
+            Ok(()) => EntryState::Loaded,
+            Err(error) => EntryState::Failed(error.clone()),
+        };
+        finished.write(PreloadFinished {
+            name: entry.name.clone(),
+            result,
+        });
+        false
+    });
+}
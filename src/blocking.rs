@@ -0,0 +1,261 @@
+//! Blocking preloader module
+//!
+//! This module provides the `BlockingPreloader` struct for synchronous applications without an async runtime.
+//! Loaders run on a plain `std::thread`, hand their result back through a channel, and callers block on a
+//! condition variable, with the same preload-and-cache pattern and method names as [`Preloader`](crate::Preloader).
+
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Condvar, Mutex, MutexGuard, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{preloader::panicked, PreloaderError, PreloaderState};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Loading state shared between callers
+struct Slot<T> {
+    state: PreloaderState,
+    /// Result channel of the loading thread, held by no one while a caller is receiving from it
+    receiver: Option<Receiver<Result<T>>>,
+    /// Error recorded when loading failed
+    error: Option<PreloaderError>,
+}
+
+/// Preloader for synchronous code, loading on a `std::thread`
+///
+/// `BlockingPreloader` needs no async runtime: `load()` spawns a thread running the loader,
+/// and `get()` blocks the calling thread until the value is available.
+/// Loader panics are caught and reported as `PreloaderError::Panicked`.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::BlockingPreloader;
+///
+/// fn main() {
+///     let index = BlockingPreloader::spawn(|| (0..1000).sum::<u32>());
+///     // ... other startup work ...
+///     assert_eq!(index.get().unwrap(), &499500);
+/// }
+/// ```
+///
+/// # Generic Type
+///
+/// - `T`: The type of data to load. Must satisfy `Send + 'static`.
+pub struct BlockingPreloader<T: Send + 'static> {
+    slot: Mutex<Slot<T>>,
+    value: OnceLock<T>,
+    /// Notified whenever the state changes or the result channel is put back
+    changed: Condvar,
+}
+
+impl<T: Send + 'static> BlockingPreloader<T> {
+    /// Creates a new `BlockingPreloader` in the `Idle` state.
+    pub fn new() -> Self {
+        Self {
+            slot: Mutex::new(Slot {
+                state: PreloaderState::Idle,
+                receiver: None,
+                error: None,
+            }),
+            value: OnceLock::new(),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Creates a `BlockingPreloader` that is already loaded with `value`.
+    pub fn ready(value: T) -> Self {
+        let preloader = Self::new();
+        _ = preloader.value.set(value);
+        preloader.lock().state = PreloaderState::Loaded;
+        preloader
+    }
+
+    /// Creates a `BlockingPreloader` and immediately starts loading with `loader` on a new thread.
+    pub fn spawn(loader: impl FnOnce() -> T + Send + 'static) -> Self {
+        let preloader = Self::new();
+        preloader.load(loader);
+        preloader
+    }
+
+    /// Starts loading with `loader` on a new thread.
+    ///
+    /// This does nothing if the preloader is not in the `Idle` state.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: Function producing the data
+    pub fn load(&self, loader: impl FnOnce() -> T + Send + 'static) {
+        let mut slot = self.lock();
+        if slot.state != PreloaderState::Idle {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(loader)).map_err(panicked);
+            _ = tx.send(result);
+        });
+        slot.receiver = Some(rx);
+        slot.state = PreloaderState::Loading;
+        self.changed.notify_all();
+    }
+
+    /// Retrieves the loaded data, blocking the current thread until loading is complete.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the data was successfully loaded
+    /// - `Err(PreloaderError::NotLoaded)`: If loading has not been started
+    /// - `Err(PreloaderError)`: If the loader panicked
+    pub fn get(&self) -> Result<&T> {
+        self.resolve(None)
+    }
+
+    /// Retrieves the loaded data, blocking the current thread for at most `timeout`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the data was loaded in time
+    /// - `Err(PreloaderError::DeadlineExceeded)`: If `timeout` elapsed while loading
+    /// - `Err(PreloaderError)`: If the data is not loaded or the loader panicked
+    pub fn get_timeout(&self, timeout: Duration) -> Result<&T> {
+        self.resolve(Some(Instant::now() + timeout))
+    }
+
+    /// Attempts to retrieve the loaded data without blocking.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the data is loaded
+    /// - `Err(PreloaderError::Loading)`: If loading has not finished yet
+    /// - `Err(PreloaderError)`: If the data is not loaded or the loader panicked
+    pub fn try_get(&self) -> Result<&T> {
+        let mut slot = self.lock();
+        if slot.state == PreloaderState::Loading {
+            if let Some(result) = slot.receiver.as_ref().and_then(|rx| rx.try_recv().ok()) {
+                self.settle(&mut slot, result);
+            }
+        }
+        self.settled(&slot)
+    }
+
+    /// Returns the current state of the preloader.
+    pub fn state(&self) -> PreloaderState {
+        _ = self.try_get();
+        self.lock().state
+    }
+
+    /// Checks if the data is loaded and ready for immediate access.
+    pub fn is_loaded(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Extracts the loaded data, consuming the preloader.
+    ///
+    /// Returns `None` if loading has not completed or failed.
+    pub fn into_inner(self) -> Option<T> {
+        self.value.into_inner()
+    }
+
+    /// Waits for the result of loading until `deadline`, if one is given.
+    fn resolve(&self, deadline: Option<Instant>) -> Result<&T> {
+        let mut slot = self.lock();
+        loop {
+            if slot.state != PreloaderState::Loading {
+                return self.settled(&slot);
+            }
+
+            match slot.receiver.take() {
+                // Receive without holding the lock, so other callers can check the state meanwhile
+                Some(rx) => {
+                    drop(slot);
+                    let received = match deadline {
+                        Some(deadline) => {
+                            rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                        }
+                        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    };
+                    slot = self.lock();
+                    match received {
+                        Ok(result) => self.settle(&mut slot, result),
+                        Err(RecvTimeoutError::Disconnected) => {
+                            self.settle(&mut slot, Err(PreloaderError::Cancelled))
+                        }
+                        Err(RecvTimeoutError::Timeout) => {
+                            slot.receiver = Some(rx);
+                            self.changed.notify_all();
+                            return Err(PreloaderError::DeadlineExceeded);
+                        }
+                    }
+                }
+                // Another caller is receiving; wait until it stores the result or gives up
+                None => {
+                    slot = match deadline {
+                        Some(deadline) => {
+                            let remaining = deadline.saturating_duration_since(Instant::now());
+                            if remaining.is_zero() {
+                                return Err(PreloaderError::DeadlineExceeded);
+                            }
+                            self.changed.wait_timeout(slot, remaining).unwrap().0
+                        }
+                        None => self.changed.wait(slot).unwrap(),
+                    };
+                }
+            }
+        }
+    }
+
+    /// Stores the result of loading and wakes every waiting caller.
+    fn settle(&self, slot: &mut Slot<T>, result: Result<T>) {
+        slot.receiver = None;
+        match result {
+            Ok(value) => {
+                _ = self.value.set(value);
+                slot.state = PreloaderState::Loaded;
+            }
+            Err(error) => {
+                slot.error = Some(error);
+                slot.state = PreloaderState::Failed;
+            }
+        }
+        self.changed.notify_all();
+    }
+
+    /// Returns the result of a preloader that is not loading.
+    fn settled(&self, slot: &Slot<T>) -> Result<&T> {
+        match slot.state {
+            PreloaderState::Idle => Err(PreloaderError::NotLoaded),
+            PreloaderState::Loaded => Ok(self.value.get().unwrap()),
+            PreloaderState::Failed => Err(slot.error.clone().unwrap()),
+            _ => Err(PreloaderError::Loading),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Slot<T>> {
+        self.slot.lock().unwrap()
+    }
+}
+
+impl<T: Send + 'static> Default for BlockingPreloader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Send + 'static> fmt::Debug for BlockingPreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let slot = self.lock();
+        f.debug_struct("BlockingPreloader")
+            .field("state", &slot.state)
+            .field("error", &slot.error)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,260 @@
+//! Blocking thread pool module
+//!
+//! This module provides `BlockingPool`, a dedicated pool of threads for blocking loaders such as file parsing or
+//! synchronous client calls. Running heavy preloads there instead of on Tokio's shared blocking pool keeps them
+//! from starving the other blocking work of the application.
+
+use std::{
+    fmt, io,
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+
+use tokio::{sync::oneshot, task};
+
+use crate::{preloader::panicked, LoadHandle, Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Function run on every thread of a [`BlockingPool`] when it starts
+type ThreadStart = Arc<dyn Fn() + Send + Sync>;
+
+/// Dedicated pool of threads running blocking loaders
+///
+/// Cloning the pool is cheap and shares its threads. The threads exit once every clone is dropped and the queued
+/// jobs are finished.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{BlockingPool, Preloader};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let pool = BlockingPool::builder()
+///         .threads(2)
+///         .thread_name("preload")
+///         .build()
+///         .unwrap();
+///
+///     let index = Preloader::new();
+///     index
+///         .load_blocking_on(&pool, || std::thread::current().name().map(String::from))
+///         .await;
+///     assert!(index.get().await.unwrap().as_deref().unwrap().starts_with("preload-"));
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BlockingPool {
+    sender: Arc<mpsc::Sender<Job>>,
+    threads: usize,
+}
+
+impl BlockingPool {
+    /// Creates a builder for configuring a pool.
+    pub fn builder() -> BlockingPoolBuilder {
+        BlockingPoolBuilder::default()
+    }
+
+    /// Creates a pool of `threads` threads with the default settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    pub fn new(threads: usize) -> io::Result<Self> {
+        Self::builder().threads(threads).build()
+    }
+
+    /// Returns the number of threads in the pool.
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Runs `f` on a thread of the pool and waits for its result.
+    ///
+    /// Jobs are queued while every thread is busy.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(R)`: The result of `f`
+    /// - `Err(PreloaderError::Panicked)`: If `f` panicked
+    pub async fn run<R: Send + 'static>(
+        &self,
+        f: impl FnOnce() -> R + Send + 'static,
+    ) -> Result<R> {
+        let (tx, rx) = oneshot::channel();
+        let job = Box::new(move || {
+            _ = tx.send(panic::catch_unwind(AssertUnwindSafe(f)));
+        });
+        self.sender
+            .send(job)
+            .map_err(|_| PreloaderError::Cancelled)?;
+        rx.await
+            .map_err(|_| PreloaderError::Cancelled)?
+            .map_err(panicked)
+    }
+}
+
+impl fmt::Debug for BlockingPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingPool")
+            .field("threads", &self.threads)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Builder for [`BlockingPool`]
+pub struct BlockingPoolBuilder {
+    threads: usize,
+    name: String,
+    stack_size: Option<usize>,
+    on_thread_start: Option<ThreadStart>,
+}
+
+impl Default for BlockingPoolBuilder {
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().map_or(1, usize::from),
+            name: "preloader-blocking".to_string(),
+            stack_size: None,
+            on_thread_start: None,
+        }
+    }
+}
+
+impl BlockingPoolBuilder {
+    /// Sets the number of threads, the available parallelism by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    pub fn threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "a blocking pool needs at least one thread");
+        self.threads = threads;
+        self
+    }
+
+    /// Sets the prefix of the thread names, followed by the index of each thread.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The prefix, `preloader-blocking` by default
+    pub fn thread_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the stack size of the threads, in bytes.
+    pub fn stack_size(mut self, size: usize) -> Self {
+        self.stack_size = Some(size);
+        self
+    }
+
+    /// Sets a function run on every thread when it starts.
+    ///
+    /// Use it to lower the priority of the threads or pin them to cores with the platform's APIs.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: The function, run on the new thread before it takes any job
+    pub fn on_thread_start(mut self, f: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_thread_start = Some(Arc::new(f));
+        self
+    }
+
+    /// Starts the threads and creates the pool.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(BlockingPool)`: The pool
+    /// - `Err(io::Error)`: If a thread could not be started
+    pub fn build(self) -> io::Result<BlockingPool> {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for index in 0..self.threads {
+            let mut builder = thread::Builder::new().name(format!("{}-{}", self.name, index));
+            if let Some(size) = self.stack_size {
+                builder = builder.stack_size(size);
+            }
+            let receiver = Arc::clone(&receiver);
+            let on_thread_start = self.on_thread_start.clone();
+            builder.spawn(move || {
+                if let Some(on_thread_start) = on_thread_start {
+                    on_thread_start();
+                }
+                loop {
+                    // The lock is released before the job runs, so other threads can take the next one
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => return,
+                    }
+                }
+            })?;
+        }
+        Ok(BlockingPool {
+            sender: Arc::new(sender),
+            threads: self.threads,
+        })
+    }
+}
+
+impl fmt::Debug for BlockingPoolBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingPoolBuilder")
+            .field("threads", &self.threads)
+            .field("name", &self.name)
+            .field("stack_size", &self.stack_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Starts loading by running `f` on Tokio's blocking thread pool.
+    ///
+    /// Like [`load`](Self::load), this returns a [`LoadHandle`] and follows the configured
+    /// [`LoadPolicy`](crate::LoadPolicy) if loading was already started.
+    /// A panic in `f` is caught and reported as `PreloaderError::Panicked`.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: The blocking function producing the data
+    pub async fn load_blocking(&self, f: impl FnOnce() -> T + Send + 'static) -> LoadHandle {
+        self.load_result(run_blocking(None, f)).await
+    }
+
+    /// Starts loading by running `f` on `pool` instead of Tokio's blocking thread pool.
+    ///
+    /// See [`load_blocking`](Self::load_blocking).
+    ///
+    /// # Parameters
+    ///
+    /// - `pool`: The dedicated pool to run `f` on
+    /// - `f`: The blocking function producing the data
+    pub async fn load_blocking_on(
+        &self,
+        pool: &BlockingPool,
+        f: impl FnOnce() -> T + Send + 'static,
+    ) -> LoadHandle {
+        self.load_result(run_blocking(Some(pool.clone()), f)).await
+    }
+}
+
+/// Runs `f` on `pool`, or on Tokio's blocking thread pool if `None`.
+pub(crate) async fn run_blocking<R: Send + 'static>(
+    pool: Option<BlockingPool>,
+    f: impl FnOnce() -> R + Send + 'static,
+) -> Result<R> {
+    match pool {
+        Some(pool) => pool.run(f).await,
+        None => match task::spawn_blocking(f).await {
+            Ok(output) => Ok(output),
+            Err(error) if error.is_panic() => Err(panicked(error.into_panic())),
+            Err(_) => Err(PreloaderError::Cancelled),
+        },
+    }
+}
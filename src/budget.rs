@@ -0,0 +1,213 @@
+//! Time-slicing module
+//!
+//! This module provides `Budget`, which long CPU-bound loaders call inside their loops to give the runtime worker
+//! back once they have run for a time slice, and `TimeSlice`, which configures the slice and reports loads that
+//! run too long without yielding. A loader parsing a large file then no longer delays every other task scheduled
+//! on its worker.
+
+use std::{
+    fmt,
+    future::{self, Future},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Function called when a loader runs longer than the warning limit of a [`TimeSlice`] without yielding
+type OnOverrun = Arc<dyn Fn(&SliceOverrun) + Send + Sync>;
+
+/// Time slice used when none is configured
+pub const DEFAULT_TIME_SLICE: Duration = Duration::from_millis(1);
+
+/// Report of a loader that ran longer than the warning limit of its [`TimeSlice`] without yielding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SliceOverrun {
+    /// Name of the preloader, if it has one
+    pub name: Option<String>,
+    /// Time the loader ran without yielding
+    pub elapsed: Duration,
+    /// The exceeded warning limit
+    pub limit: Duration,
+}
+
+/// Time a loader may run before yielding, and the limit after which not yielding is reported
+///
+/// The slice is used by the [`Budget`] handed to loaders through [`LoadContext::budget`](crate::LoadContext::budget).
+/// The warning limit applies to every poll of the load, so loaders that never call
+/// [`maybe_yield`](Budget::maybe_yield) are reported too.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{LoadContext, Preloader, TimeSlice};
+/// use std::time::Duration;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let preloader = Preloader::builder()
+///         .name("index")
+///         .time_slice(
+///             TimeSlice::new(Duration::from_millis(2))
+///                 .warn_after(Duration::from_millis(50))
+///                 .on_overrun(|overrun| eprintln!("{:?} blocked for {:?}", overrun.name, overrun.elapsed)),
+///         )
+///         .build();
+///     preloader
+///         .load_with_context(|ctx: LoadContext| async move {
+///             let mut budget = ctx.budget();
+///             let mut sum = 0u64;
+///             for n in 0..100_000u64 {
+///                 sum += n;
+///                 budget.maybe_yield().await;
+///             }
+///             Some(sum)
+///         })
+///         .await;
+///     assert_eq!(*preloader.get().await.unwrap(), 4_999_950_000);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TimeSlice {
+    slice: Duration,
+    warn_after: Option<Duration>,
+    on_overrun: Option<OnOverrun>,
+}
+
+impl TimeSlice {
+    /// Creates a time slice letting loaders run for `slice` before [`Budget::maybe_yield`] yields.
+    pub fn new(slice: Duration) -> Self {
+        Self {
+            slice,
+            warn_after: None,
+            on_overrun: None,
+        }
+    }
+
+    /// Reports every time a loader runs longer than `limit` without yielding.
+    ///
+    /// Overruns are logged with a tracing warning when the `tracing` feature is enabled, and passed to the
+    /// function set with [`on_overrun`](Self::on_overrun).
+    pub fn warn_after(mut self, limit: Duration) -> Self {
+        self.warn_after = Some(limit);
+        self
+    }
+
+    /// Sets a function called every time a loader runs longer than the warning limit without yielding.
+    pub fn on_overrun(mut self, f: impl Fn(&SliceOverrun) + Send + Sync + 'static) -> Self {
+        self.on_overrun = Some(Arc::new(f));
+        self
+    }
+
+    /// Returns the time a loader may run before yielding.
+    pub fn slice(&self) -> Duration {
+        self.slice
+    }
+
+    /// Returns the limit after which not yielding is reported, if one is set.
+    pub fn warn_limit(&self) -> Option<Duration> {
+        self.warn_after
+    }
+
+    /// Reports an overrun if `elapsed` exceeds the warning limit.
+    fn check(&self, name: Option<&str>, elapsed: Duration) {
+        let Some(limit) = self.warn_after.filter(|limit| elapsed > *limit) else {
+            return;
+        };
+        let overrun = SliceOverrun {
+            name: name.map(str::to_string),
+            elapsed,
+            limit,
+        };
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            name = overrun.name.as_deref(),
+            elapsed = ?overrun.elapsed,
+            limit = ?overrun.limit,
+            "preloader loader ran too long without yielding"
+        );
+        if let Some(on_overrun) = &self.on_overrun {
+            on_overrun(&overrun);
+        }
+    }
+
+    /// Runs `load`, reporting every poll that takes longer than the warning limit.
+    pub(crate) fn watch_polls<O>(
+        self,
+        name: Option<String>,
+        load: impl Future<Output = O> + Send + 'static,
+    ) -> impl Future<Output = O> + Send + 'static {
+        let mut load = Box::pin(load);
+        future::poll_fn(move |cx| {
+            let started = Instant::now();
+            let poll = load.as_mut().poll(cx);
+            self.check(name.as_deref(), started.elapsed());
+            poll
+        })
+    }
+}
+
+impl Default for TimeSlice {
+    fn default() -> Self {
+        Self::new(DEFAULT_TIME_SLICE)
+    }
+}
+
+impl fmt::Debug for TimeSlice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeSlice")
+            .field("slice", &self.slice)
+            .field("warn_after", &self.warn_after)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Cooperative time budget of a loader, yielding to the runtime once a time slice is used up
+///
+/// Call [`maybe_yield`](Self::maybe_yield) inside tight loops; it returns immediately until the slice is used up,
+/// then yields once and starts a new slice. Loaders started with `load_with_context()` get one configured from
+/// the preloader through [`LoadContext::budget`](crate::LoadContext::budget).
+#[derive(Debug, Clone)]
+pub struct Budget {
+    time_slice: TimeSlice,
+    slice_start: Instant,
+}
+
+impl Budget {
+    /// Creates a budget starting its first slice now.
+    ///
+    /// Overruns of the warning limit of `time_slice` are reported when [`maybe_yield`](Self::maybe_yield) is
+    /// finally called.
+    pub fn new(time_slice: TimeSlice) -> Self {
+        Self {
+            time_slice,
+            slice_start: Instant::now(),
+        }
+    }
+
+    /// Returns the time since the current slice started.
+    pub fn elapsed(&self) -> Duration {
+        self.slice_start.elapsed()
+    }
+
+    /// Checks if the current slice is used up.
+    pub fn should_yield(&self) -> bool {
+        self.elapsed() >= self.time_slice.slice
+    }
+
+    /// Yields to the runtime if the current slice is used up, reporting it if it exceeded the warning limit.
+    pub async fn maybe_yield(&mut self) {
+        let elapsed = self.elapsed();
+        if elapsed < self.time_slice.slice {
+            return;
+        }
+        self.time_slice.check(None, elapsed);
+        tokio::task::yield_now().await;
+        self.slice_start = Instant::now();
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self::new(TimeSlice::default())
+    }
+}
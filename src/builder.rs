@@ -0,0 +1,404 @@
+//! Preloader builder module
+//!
+//! This module provides the `PreloaderBuilder` struct for configuring a `Preloader` at construction time.
+
+use std::{future::Future, marker::PhantomData, sync::Arc, time::Duration};
+
+use tokio::runtime::Handle;
+use tokio_util::task::TaskTracker;
+
+use crate::{
+    retry::ErrorClassifier, AdaptiveTimeout, ArcPreloader, BackgroundRuntime, CircuitBreaker,
+    ErrorPolicy, FailureMode, LazyPreloader, LoadPolicy, Preloader, PreloaderConfig,
+    PreloaderError, RetryPolicy, TimeSlice, Watchdog,
+};
+
+/// Configuration shared by every load of a preloader
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Options {
+    /// Maximum duration of a single load attempt
+    pub(crate) timeout: Option<Duration>,
+    /// Policy computing the attempt timeout from the load history, overriding `timeout` once enough loads are recorded
+    pub(crate) adaptive_timeout: Option<AdaptiveTimeout>,
+    /// Duration after which callers waiting for a replacing load get the previous value instead
+    pub(crate) soft_timeout: Option<Duration>,
+    /// Policy for retrying failed load attempts
+    pub(crate) retry: RetryPolicy,
+    /// Delay after which a second, hedging attempt is started
+    pub(crate) hedge: Option<Duration>,
+    /// Circuit breaker stopping attempts after repeated failures
+    pub(crate) circuit: Option<CircuitBreaker>,
+    /// Policy for remembering failed loads
+    pub(crate) error_policy: ErrorPolicy,
+    /// Function choosing the error policy per error, overriding `error_policy`
+    pub(crate) error_classifier: Option<ErrorClassifier>,
+    /// State entered after a failed load
+    pub(crate) failure_mode: FailureMode,
+    /// Behavior of `load()` when the preloader is not `Idle`
+    pub(crate) load_policy: LoadPolicy,
+    /// Whether the loading task is aborted when the preloader is dropped
+    pub(crate) abort_on_drop: bool,
+    /// Duration for which a loaded value stays valid
+    pub(crate) ttl: Option<Duration>,
+    /// Name used for instrumentation
+    pub(crate) name: Option<String>,
+    /// Runtime on which loading tasks are spawned
+    pub(crate) runtime: Option<Handle>,
+    /// Background runtime owning `runtime`, kept running while the preloader exists
+    pub(crate) background: Option<BackgroundRuntime>,
+    /// Tracker of the loading tasks, so they can be waited for before the runtime shuts down
+    pub(crate) tracker: Option<TaskTracker>,
+    /// Whether loads are instrumented with tracing spans and named tokio-console tasks
+    pub(crate) instrument: bool,
+    /// Number of finished loads kept for `history()`
+    pub(crate) history: usize,
+    /// Upper bound of the random delay added to every scheduled refresh
+    pub(crate) refresh_jitter: Duration,
+    /// Watchdog reporting and restarting the loads that exceed their expected duration
+    pub(crate) watchdog: Option<Watchdog>,
+    /// Time slice of the budgets handed to loaders, and limit on how long a load may run without yielding
+    pub(crate) time_slice: Option<TimeSlice>,
+}
+
+impl Options {
+    /// Creates the options of a new preloader from the installed [`PreloaderConfig`].
+    pub(crate) fn new() -> Self {
+        PreloaderConfig::current().options()
+    }
+}
+
+/// Builder for configuring a [`Preloader`]
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{Preloader, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let preloader: Preloader<String> = Preloader::builder()
+///     .name("config")
+///     .timeout(Duration::from_secs(5))
+///     .retry(RetryPolicy::exponential(3, Duration::from_millis(100)))
+///     .abort_on_drop(true)
+///     .build();
+/// assert_eq!(preloader.name(), Some("config"));
+/// ```
+#[derive(Debug)]
+pub struct PreloaderBuilder<T> {
+    options: Options,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + 'static> PreloaderBuilder<T> {
+    /// Creates a new builder starting from the installed [`PreloaderConfig`].
+    pub fn new() -> Self {
+        Self {
+            options: Options::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the maximum duration of a single load attempt.
+    ///
+    /// An attempt that exceeds the timeout is aborted and fails with `PreloaderError::Timeout`. This is the hard
+    /// tier of the timeouts; see [`soft_timeout`](Self::soft_timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Computes the timeout of every load attempt from the durations of past loads.
+    ///
+    /// Once enough loads are recorded in the history, the computed timeout replaces the fixed
+    /// [`timeout`](Self::timeout), which applies until then. See [`AdaptiveTimeout`].
+    pub fn adaptive_timeout(mut self, policy: AdaptiveTimeout) -> Self {
+        self.options.adaptive_timeout = Some(policy);
+        self
+    }
+
+    /// Sets the duration after which callers waiting for a load that replaces an existing value get that value
+    /// instead, while the load keeps running.
+    ///
+    /// Applies to [`ArcPreloader::get_or_load`] on an expired value: if the reload has not finished within the
+    /// soft timeout, the expired value is returned and the new one is swapped in once it is loaded. The reload is
+    /// still bounded by the hard [`timeout`](Self::timeout), after which it is aborted and its error reported.
+    pub fn soft_timeout(mut self, timeout: Duration) -> Self {
+        self.options.soft_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the policy for retrying failed load attempts.
+    ///
+    /// Retries only apply to loads started with [`Preloader::load_with`] or a stored loader factory,
+    /// since a plain future cannot be run again.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.options.retry = policy;
+        self
+    }
+
+    /// Sets the delay after which a second, hedging attempt is started.
+    ///
+    /// If an attempt has not finished within the delay, another attempt is started concurrently
+    /// and whichever succeeds first provides the value. This reduces tail latency for loaders that occasionally stall.
+    /// Like retries, hedging only applies to loads started with [`Preloader::load_with`] or a stored loader factory.
+    pub fn hedge(mut self, delay: Duration) -> Self {
+        self.options.hedge = Some(delay);
+        self
+    }
+
+    /// Sets the watchdog reporting loads that run longer than expected.
+    ///
+    /// Unlike the [`timeout`](Self::timeout), the watchdog covers the whole load, including retries, and only
+    /// aborts it if [`Watchdog::restart`] is set. See [`Watchdog`].
+    pub fn watchdog(mut self, watchdog: Watchdog) -> Self {
+        self.options.watchdog = Some(watchdog);
+        self
+    }
+
+    /// Sets the time slice of the [`Budget`](crate::Budget) handed to loaders through
+    /// [`LoadContext::budget`](crate::LoadContext::budget).
+    ///
+    /// If [`TimeSlice::warn_after`] is set, every load is checked for polls running longer than the limit,
+    /// whether or not its loader uses the budget. See [`TimeSlice`].
+    pub fn time_slice(mut self, time_slice: TimeSlice) -> Self {
+        self.options.time_slice = Some(time_slice);
+        self
+    }
+
+    /// Sets the circuit breaker guarding load attempts.
+    ///
+    /// While the circuit is open, attempts fail with `PreloaderError::CircuitOpen` without running the loader,
+    /// which also ends any remaining retries. See [`CircuitBreaker`].
+    pub fn circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.options.circuit = Some(breaker);
+        self
+    }
+
+    /// Sets whether a failed load is remembered or retried on the next `get()`.
+    ///
+    /// See [`ErrorPolicy`]. Retrying requires a stored loader factory.
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.options.error_policy = policy;
+        self
+    }
+
+    /// Chooses the error policy separately for every failed load, from its error.
+    ///
+    /// Real upstreams need different treatment per error class: a missing resource can be remembered for minutes,
+    /// while a timeout should be retried right away. `classify` overrides [`error_policy`](Self::error_policy).
+    /// Retrying requires a stored loader factory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{ErrorPolicy, Preloader, PreloaderError};
+    /// use std::time::Duration;
+    ///
+    /// let preloader: Preloader<String> = Preloader::builder()
+    ///     .error_policy_with(|error| match error {
+    ///         PreloaderError::Failed(message) if message.contains("404") => {
+    ///             ErrorPolicy::CacheFor(Duration::from_secs(600))
+    ///         }
+    ///         PreloaderError::Timeout => ErrorPolicy::Retry,
+    ///         _ => ErrorPolicy::Cache,
+    ///     })
+    ///     .build_with_loader(|| async { "data".to_string() });
+    /// ```
+    pub fn error_policy_with(
+        mut self,
+        classify: impl Fn(&PreloaderError) -> ErrorPolicy + Send + Sync + 'static,
+    ) -> Self {
+        self.options.error_classifier = Some(ErrorClassifier::new(classify));
+        self
+    }
+
+    /// Sets the state the preloader ends up in after a load fails, panics, or is cancelled.
+    ///
+    /// See [`FailureMode`].
+    pub fn on_failure(mut self, mode: FailureMode) -> Self {
+        self.options.failure_mode = mode;
+        self
+    }
+
+    /// Sets what `load()` does when the preloader is not `Idle`.
+    ///
+    /// See [`LoadPolicy`]. By default the call is ignored.
+    pub fn load_policy(mut self, policy: LoadPolicy) -> Self {
+        self.options.load_policy = policy;
+        self
+    }
+
+    /// Sets how many finished loads are kept for [`Preloader::history`].
+    ///
+    /// Defaults to [`DEFAULT_HISTORY_LEN`](crate::DEFAULT_HISTORY_LEN); zero disables the history.
+    pub fn history(mut self, len: usize) -> Self {
+        self.options.history = len;
+        self
+    }
+
+    /// Sets whether the loading task is aborted when the preloader is dropped.
+    ///
+    /// By default the loading task keeps running in the background after the preloader is dropped.
+    pub fn abort_on_drop(mut self, abort_on_drop: bool) -> Self {
+        self.options.abort_on_drop = abort_on_drop;
+        self
+    }
+
+    /// Sets the duration for which a loaded value stays valid.
+    ///
+    /// Once the value is older than the TTL, `get()` and `try_get()` return `PreloaderError::Expired`.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.options.ttl = Some(ttl);
+        self
+    }
+
+    /// Sets the name used to identify the preloader in instrumentation.
+    ///
+    /// The name is shown in `Debug` output and [`ErrorContext`](crate::ErrorContext), and is used by
+    /// [`registry::register_named`](crate::registry::register_named). With the `tracing` feature, loads run in a
+    /// `preload` span carrying the name.
+    ///
+    /// With the `tokio-console` feature and `RUSTFLAGS="--cfg tokio_unstable"`, loading tasks are spawned
+    /// under this name, so they can be told apart in tokio-console instead of appearing as anonymous tasks.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.options.name = Some(name.into());
+        self
+    }
+
+    /// Sets the upper bound of a random delay added to every scheduled refresh.
+    ///
+    /// Applies to [`ArcPreloader::refresh_every`], [`ArcPreloader::refresh_ahead`], and cron schedules, so a fleet
+    /// of instances, or many preloaders with the same interval, do not all hit the upstream at the same instant.
+    /// The jittered time is reported by [`ArcPreloader::next_refresh`]. With `refresh_ahead`, keep the jitter
+    /// below the remaining share of the TTL so refreshes still happen before the value expires.
+    pub fn refresh_jitter(mut self, jitter: Duration) -> Self {
+        self.options.refresh_jitter = jitter;
+        self
+    }
+
+    /// Sets whether loads are instrumented.
+    ///
+    /// Instrumented loads run in a `preload` tracing span with the `tracing` feature, and are spawned as named
+    /// tasks with the `tokio-console` feature. Enabled by default.
+    pub fn instrument(mut self, instrument: bool) -> Self {
+        self.options.instrument = instrument;
+        self
+    }
+
+    /// Sets the runtime on which loading tasks are spawned.
+    ///
+    /// By default tasks are spawned on the runtime that calls `load()`.
+    pub fn runtime(mut self, handle: Handle) -> Self {
+        self.options.runtime = Some(handle);
+        self.options.background = None;
+        self
+    }
+
+    /// Spawns loading tasks on the process-wide [`BackgroundRuntime::global`], for low-priority preloads.
+    ///
+    /// Bulk warmup then runs on a thread of its own instead of the workers of the calling runtime.
+    pub fn background(self) -> Self {
+        self.background_runtime(BackgroundRuntime::global())
+    }
+
+    /// Spawns loading tasks on `background`, which keeps running as long as the preloader exists.
+    pub fn background_runtime(mut self, background: &BackgroundRuntime) -> Self {
+        self.options.runtime = Some(background.handle().clone());
+        self.options.background = Some(background.clone());
+        self
+    }
+
+    /// Tracks the loading tasks with `tracker`.
+    ///
+    /// Before shutting the runtime down, close the tracker and wait for it, so no load is killed between finishing
+    /// and storing its value. Tasks that should not be waited for can be stopped first through their
+    /// [`LoadHandle`](crate::LoadHandle).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Preloader, TaskTracker};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let tracker = TaskTracker::new();
+    ///     let config = Preloader::builder().task_tracker(tracker.clone()).build();
+    ///     config.load(async { "config" }).await;
+    ///
+    ///     // On shutdown
+    ///     tracker.close();
+    ///     tracker.wait().await;
+    ///     assert_eq!(*config.try_get().unwrap(), "config");
+    /// }
+    /// ```
+    pub fn task_tracker(mut self, tracker: TaskTracker) -> Self {
+        self.options.tracker = Some(tracker);
+        self
+    }
+
+    /// Creates the configured `Preloader` in the `Idle` state.
+    pub fn build(self) -> Preloader<T> {
+        Preloader::with_options(self.options)
+    }
+
+    /// Creates the configured `Preloader` and immediately starts loading data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no runtime was configured and this is called outside of a Tokio runtime.
+    pub fn spawn(self, future: impl Future<Output = T> + Send + 'static) -> Preloader<T> {
+        let mut preloader = self.build();
+        preloader.begin(future);
+        preloader
+    }
+
+    /// Creates the configured `Preloader` in the `Idle` state, storing a loader factory.
+    ///
+    /// See [`Preloader::with_loader`].
+    pub fn build_with_loader<F, Fut>(self, factory: F) -> Preloader<T>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        self.build().stored(factory)
+    }
+
+    /// Creates the configured [`LazyPreloader`], which loads with `factory` on first access.
+    pub fn build_lazy<F, Fut>(self, factory: F) -> LazyPreloader<T>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        LazyPreloader::with_options(self.options, factory)
+    }
+}
+
+impl<T: Send + Sync + 'static> PreloaderBuilder<T> {
+    /// Creates the configured [`ArcPreloader`] in the `Idle` state.
+    pub fn build_arc(self) -> ArcPreloader<T> {
+        ArcPreloader::from_inner(Preloader::<Arc<T>>::with_options(self.options))
+    }
+
+    /// Creates the configured [`ArcPreloader`] in the `Idle` state, storing a loader factory.
+    ///
+    /// See [`ArcPreloader::with_loader`].
+    pub fn build_arc_with_loader<F, Fut>(self, mut factory: F) -> ArcPreloader<T>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        ArcPreloader::from_inner(Preloader::<Arc<T>>::with_options(self.options).stored(
+            move || {
+                let future = factory();
+                async move { Arc::new(future.await) }
+            },
+        ))
+    }
+}
+
+impl<T: Send + 'static> Default for PreloaderBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,1240 @@
+//! Keyed cache module
+//!
+//! This module provides the `PreloaderCache` struct, which keeps one preloader per key
+//! and loads each key's value with a shared loader function on first access.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    hash::{BuildHasher, Hash, RandomState},
+    num::NonZeroUsize,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread,
+    time::Duration,
+};
+
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt,
+};
+use tokio::time::Instant;
+
+use crate::{preloader::panicked, ArcPreloader, MemoryFootprint, PreloaderError, PreloaderEvent};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Number of shards of a new cache per available CPU
+const SHARDS_PER_CPU: usize = 4;
+
+/// Shared function creating the loading task of a key
+pub(crate) type KeyLoader<K, V> = Arc<dyn Fn(&K) -> BoxFuture<'static, V> + Send + Sync>;
+
+/// Shared function creating the loading task of several keys at once
+type BatchLoader<K, V> = Arc<dyn Fn(Vec<K>) -> BoxFuture<'static, HashMap<K, V>> + Send + Sync>;
+
+/// Function returning the weight of a value, such as its size in bytes
+type Weigher<V> = Box<dyn Fn(&V) -> usize + Send + Sync>;
+
+/// Function called with the key, value, and cause of every removed entry
+type RemovalListener<K, V> = Box<dyn Fn(&K, Arc<V>, RemovalCause) + Send + Sync>;
+
+/// Function recognizing values that record the absence of a key
+type IsAbsent<V> = fn(&V) -> bool;
+
+/// How often every entry is checked for expiry when the cache has an expiry policy but no time-to-idle
+const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Computes how long each loaded value of a [`PreloaderCache`] stays fresh
+///
+/// Set with [`PreloaderCache::with_expiry_policy`]. Closures taking the key and value implement it, and so does
+/// `Duration`, which gives every key the same TTL.
+pub trait ExpiryPolicy<K, V>: Send + Sync {
+    /// Returns how long `value`, loaded for `key`, stays fresh after being loaded, or `None` to keep it until it
+    /// is removed otherwise.
+    fn expire_after(&self, key: &K, value: &V) -> Option<Duration>;
+}
+
+impl<K, V, F> ExpiryPolicy<K, V> for F
+where
+    F: Fn(&K, &V) -> Option<Duration> + Send + Sync,
+{
+    fn expire_after(&self, key: &K, value: &V) -> Option<Duration> {
+        self(key, value)
+    }
+}
+
+impl<K, V> ExpiryPolicy<K, V> for Duration {
+    fn expire_after(&self, _key: &K, _value: &V) -> Option<Duration> {
+        Some(*self)
+    }
+}
+
+/// Why an entry left a [`PreloaderCache`], as reported to its [removal listener](PreloaderCache::with_removal_listener)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RemovalCause {
+    /// Removed with `remove()` or `clear()`
+    Explicit,
+    /// Evicted to keep the cache within its memory budget
+    Size,
+    /// Expired, such as a cached absence older than the negative TTL or a value older than its expiry policy allows
+    Expired,
+}
+
+/// Counters of a cache's lookups and loads, as reported by [`PreloaderCache::stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Lookups answered by a value or error that was already loaded
+    pub hits: u64,
+    /// Lookups that started a load
+    pub misses: u64,
+    /// Lookups that joined a load already in progress instead of starting another
+    pub coalesced: u64,
+    /// Hits answered by a cached absence, see [`PreloaderCache::with_negative_ttl`]
+    pub negative_hits: u64,
+    /// Keys evicted to stay within the memory budget
+    pub evictions: u64,
+    /// Number of loads whose duration was measured
+    pub loads: u64,
+    /// Total duration of the measured loads
+    pub total_load_time: Duration,
+}
+
+impl CacheStats {
+    /// Returns the fraction of lookups that were hits, or `0.0` if there were none.
+    ///
+    /// Coalesced lookups count as hits, since they did not start a load of their own.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses + self.coalesced;
+        if lookups == 0 {
+            return 0.0;
+        }
+        (self.hits + self.coalesced) as f64 / lookups as f64
+    }
+
+    /// Returns the average duration of the measured loads.
+    pub fn average_load_time(&self) -> Option<Duration> {
+        let loads = u32::try_from(self.loads).ok().filter(|&loads| loads > 0)?;
+        Some(self.total_load_time / loads)
+    }
+}
+
+/// Atomic counters behind [`CacheStats`]
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    coalesced: AtomicU64,
+    negative_hits: AtomicU64,
+    evictions: AtomicU64,
+    loads: AtomicU64,
+    /// Total load time in nanoseconds
+    load_nanos: AtomicU64,
+}
+
+/// Preloader of a key together with its bookkeeping
+struct Entry<V: Send + Sync + 'static> {
+    preloader: Arc<ArcPreloader<V>>,
+    /// Weight of the loaded value, once it has been measured
+    weight: Option<usize>,
+    /// Tick of the latest access, for least-recently-used eviction
+    accessed: u64,
+    /// Time of the latest access, for time-to-idle expiration
+    used: Instant,
+    /// Whether a `get()` has already started or joined the load
+    requested: bool,
+}
+
+/// Entries of one shard of a cache and their total weight
+struct Entries<K, V: Send + Sync + 'static> {
+    map: HashMap<K, Entry<V>>,
+    /// Sum of the measured weights
+    weight: usize,
+}
+
+impl<K: Eq + Hash, V: Send + Sync + 'static> Entries<K, V> {
+    fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+            weight: 0,
+        }
+    }
+
+    /// Returns the entry of `key` and marks it as used at `tick`, and at `now` if the time of use is tracked.
+    fn touch(&mut self, key: &K, (tick, now): (u64, Option<Instant>)) -> Option<&mut Entry<V>> {
+        let entry = self.map.get_mut(key)?;
+        entry.accessed = tick;
+        if let Some(now) = now {
+            entry.used = now;
+        }
+        Some(entry)
+    }
+
+    /// Removes `key`, returning its preloader.
+    fn remove(&mut self, key: &K) -> Option<Arc<ArcPreloader<V>>> {
+        let entry = self.map.remove(key)?;
+        self.weight -= entry.weight.unwrap_or(0);
+        Some(entry.preloader)
+    }
+}
+
+/// Loaded entry of a cache, as written by [`PreloaderCache::persist`]
+#[cfg_attr(not(feature = "serde"), allow(dead_code))]
+pub(crate) struct LoadedEntry<K, V> {
+    pub(crate) key: K,
+    pub(crate) value: Arc<V>,
+    /// Time since the value was loaded
+    pub(crate) age: Duration,
+    /// Time since the entry was last accessed, or zero if the cache has no time-to-idle
+    pub(crate) idle: Duration,
+}
+
+/// Preloader of a key looked up by a `get()`, with how the lookup was counted
+struct Lookup<V: Send + Sync + 'static> {
+    preloader: Arc<ArcPreloader<V>>,
+    /// Whether this lookup is the first request for the key
+    missed: bool,
+    /// Whether the key was already loaded or failed
+    hit: bool,
+}
+
+/// Cache of preloaders keyed by `K`
+///
+/// Each key gets its own [`ArcPreloader`], created and started on the first [`get`](Self::get) for that key.
+/// Concurrent callers asking for the same key share one load. Keys are spread over independently locked
+/// shards, so lookups of different keys from many threads rarely wait on each other.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::PreloaderCache;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let users = PreloaderCache::new(|id: &u32| {
+///         let id = *id;
+///         async move { format!("user-{}", id) }
+///     });
+///
+///     assert_eq!(*users.get(&7).await.unwrap(), "user-7");
+///     assert!(users.contains_key(&7));
+///     assert_eq!(users.len(), 1);
+/// }
+/// ```
+///
+/// # Generic Types
+///
+/// - `K`: The key type. Must satisfy `Eq + Hash + Clone + Send + Sync + 'static`.
+/// - `V`: The type of the cached values. Must satisfy `Send + Sync + 'static`.
+pub struct PreloaderCache<K, V: Send + Sync + 'static> {
+    /// Preloader of every key that has been requested, split into shards by key hash
+    shards: Box<[Mutex<Entries<K, V>>]>,
+    /// Hasher selecting the shard of a key
+    hasher: RandomState,
+    /// Counter ordering accesses across shards, for least-recently-used eviction
+    tick: AtomicU64,
+    /// When the cache was created, the origin of `swept`
+    created: Instant,
+    /// Nanoseconds after `created` at which every entry was last checked for idleness
+    swept: AtomicU64,
+    /// Function creating the loading task of a key
+    pub(crate) loader: KeyLoader<K, V>,
+    /// Function loading the missing keys of a `get_many()` together
+    batch: Option<BatchLoader<K, V>>,
+    /// Maximum total weight of the loaded values, and the function weighing them
+    budget: Option<(usize, Weigher<V>)>,
+    /// Function called when an entry is removed
+    listener: Option<RemovalListener<K, V>>,
+    /// How long an entry is kept without being accessed
+    idle: Option<Duration>,
+    /// How long a cached absence is kept, and the function recognizing absent values
+    negative: Option<(Duration, IsAbsent<V>)>,
+    /// Policy computing how long each loaded value is kept
+    expiry: Option<Box<dyn ExpiryPolicy<K, V>>>,
+    /// Lookup and load counters
+    counters: Counters,
+}
+
+impl<K, V> PreloaderCache<K, V>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    /// Creates an empty cache loading values with `loader`.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: Function creating the asynchronous task that loads the value of a key
+    pub fn new<F, Fut>(loader: F) -> Self
+    where
+        F: Fn(&K) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = V> + Send + 'static,
+    {
+        Self {
+            shards: new_shards(
+                thread::available_parallelism().map_or(1, NonZeroUsize::get) * SHARDS_PER_CPU,
+            ),
+            hasher: RandomState::new(),
+            tick: AtomicU64::new(0),
+            created: Instant::now(),
+            swept: AtomicU64::new(0),
+            loader: Arc::new(move |key| Box::pin(loader(key))),
+            batch: None,
+            budget: None,
+            listener: None,
+            idle: None,
+            negative: None,
+            expiry: None,
+            counters: Counters::default(),
+        }
+    }
+
+    /// Sets the number of independently locked shards the keys are spread over.
+    ///
+    /// By default a cache has four shards per available CPU. More shards let more threads look up different
+    /// keys at the same time; a single shard serializes every lookup on one lock.
+    ///
+    /// # Parameters
+    ///
+    /// - `shards`: Number of shards
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    pub fn with_shards(mut self, shards: usize) -> Self {
+        assert!(shards > 0, "a cache needs at least one shard");
+        self.shards = new_shards(shards);
+        self
+    }
+
+    /// Limits the total weight of the loaded values, evicting the least recently used keys when it is exceeded.
+    ///
+    /// Each value is weighed once, when a `get()` or `try_get()` first sees it loaded. Values still loading
+    /// do not count towards the budget. A value heavier than the whole budget is returned to the caller
+    /// but not kept, and does not evict other keys.
+    ///
+    /// # Parameters
+    ///
+    /// - `budget`: Maximum total weight, for example in bytes
+    /// - `weigher`: Function returning the weight of a value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderCache;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let assets = PreloaderCache::new(|size: &usize| {
+    ///         let size = *size;
+    ///         async move { vec![0u8; size] }
+    ///     })
+    ///     .with_memory_budget(1024, |bytes: &Vec<u8>| bytes.len());
+    ///
+    ///     assets.get(&600).await.unwrap();
+    ///     assets.get(&500).await.unwrap();
+    ///     assert!(!assets.contains_key(&600));
+    ///     assert_eq!(assets.weight(), 500);
+    /// }
+    /// ```
+    pub fn with_memory_budget(
+        mut self,
+        budget: usize,
+        weigher: impl Fn(&V) -> usize + Send + Sync + 'static,
+    ) -> Self {
+        self.budget = Some((budget, Box::new(weigher)));
+        self
+    }
+
+    /// Calls `listener` whenever an entry holding a value leaves the cache.
+    ///
+    /// The listener receives the key, its value, and the [`RemovalCause`], and can release resources tied to the
+    /// value, such as open files. It runs on the thread removing the entry, after the cache's lock is released.
+    /// Entries removed before their value loaded are not reported.
+    ///
+    /// # Parameters
+    ///
+    /// - `listener`: Function called with the key, value, and cause of every removed entry
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{PreloaderCache, RemovalCause};
+    /// use std::sync::{Arc, Mutex};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let closed = Arc::new(Mutex::new(Vec::new()));
+    ///     let log = Arc::clone(&closed);
+    ///     let files = PreloaderCache::new(|path: &String| {
+    ///         let path = path.clone();
+    ///         async move { format!("handle for {}", path) }
+    ///     })
+    ///     .with_removal_listener(move |path, _handle, cause| {
+    ///         log.lock().unwrap().push((path.clone(), cause));
+    ///     });
+    ///
+    ///     files.get(&"a.txt".to_string()).await.unwrap();
+    ///     files.remove(&"a.txt".to_string());
+    ///     assert_eq!(*closed.lock().unwrap(), [("a.txt".to_string(), RemovalCause::Explicit)]);
+    /// }
+    /// ```
+    pub fn with_removal_listener(
+        mut self,
+        listener: impl Fn(&K, Arc<V>, RemovalCause) + Send + Sync + 'static,
+    ) -> Self {
+        self.listener = Some(Box::new(listener));
+        self
+    }
+
+    /// Removes entries that have not been accessed for `idle`, so rarely read keys age out while hot ones stay cached.
+    ///
+    /// Only loaded or failed entries expire; a load in progress is kept. Any lookup removes the expired entry it
+    /// finds, and every entry is checked at most once per `idle` during lookups. Expired entries are reported to
+    /// the removal listener with [`RemovalCause::Expired`].
+    ///
+    /// # Parameters
+    ///
+    /// - `idle`: How long an entry is kept without being accessed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderCache;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main(flavor = "current_thread", start_paused = true)]
+    /// async fn main() {
+    ///     let sessions = PreloaderCache::new(|id: &u32| {
+    ///         let id = *id;
+    ///         async move { format!("session-{}", id) }
+    ///     })
+    ///     .with_time_to_idle(Duration::from_secs(60));
+    ///
+    ///     sessions.get(&1).await.unwrap();
+    ///     tokio::time::advance(Duration::from_secs(61)).await;
+    ///     assert!(sessions.try_get(&1).is_err());
+    /// }
+    /// ```
+    pub fn with_time_to_idle(mut self, idle: Duration) -> Self {
+        self.idle = Some(idle);
+        self
+    }
+
+    /// Expires every loaded value once the TTL computed by `policy` from its key and value has passed since it was
+    /// loaded.
+    ///
+    /// The next lookup of an expired key loads it again. Entries nobody reads are removed by a periodic check of
+    /// the whole cache, reported to the removal listener as [`RemovalCause::Expired`].
+    ///
+    /// # Parameters
+    ///
+    /// - `policy`: Policy computing the TTL of a loaded value, such as a closure or a `Duration` shared by all keys
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderCache;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// struct Token {
+    ///     secret: String,
+    ///     expires_in: u64,
+    /// }
+    ///
+    /// #[tokio::main(flavor = "current_thread", start_paused = true)]
+    /// async fn main() {
+    ///     let tokens = PreloaderCache::new(|service: &&str| {
+    ///         let expires_in = if *service == "billing" { 60 } else { 3600 };
+    ///         async move {
+    ///             Token {
+    ///                 secret: "s3cret".to_string(),
+    ///                 expires_in,
+    ///             }
+    ///         }
+    ///     })
+    ///     .with_expiry_policy(|_: &&str, token: &Token| Some(Duration::from_secs(token.expires_in)));
+    ///
+    ///     tokens.get(&"billing").await.unwrap();
+    ///     tokens.get(&"search").await.unwrap();
+    ///     tokio::time::advance(Duration::from_secs(61)).await;
+    ///     assert!(tokens.try_get(&"billing").is_err());
+    ///     assert_eq!(tokens.try_get(&"search").unwrap().secret, "s3cret");
+    /// }
+    /// ```
+    pub fn with_expiry_policy(mut self, policy: impl ExpiryPolicy<K, V> + 'static) -> Self {
+        self.expiry = Some(Box::new(policy));
+        self
+    }
+
+    /// Retrieves the value of `key`, starting its load if needed and waiting until it is complete.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to look up
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Arc<V>)`: If the value was successfully loaded
+    /// - `Err(PreloaderError)`: If an error occurred during loading
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key has not been loaded yet and this is called outside of a Tokio runtime.
+    pub async fn get(&self, key: &K) -> Result<Arc<V>> {
+        let (expired, lookup) = {
+            let mut entries = self.shard(key);
+            (
+                self.expire(&mut entries, key),
+                self.lookup(&mut entries, key),
+            )
+        };
+        self.removed(
+            expired.into_iter().chain(self.sweep()),
+            RemovalCause::Expired,
+        );
+        lookup.preloader.start().await;
+        self.complete(key, lookup).await
+    }
+
+    /// Retrieves the values of several keys, loading the missing ones concurrently.
+    ///
+    /// Keys that are already loaded resolve immediately, and keys already loading join the running load.
+    /// With a [batch loader](Self::with_batch_loader), the keys nobody has requested yet are loaded together
+    /// in a single call; otherwise each of them is loaded with the cache's loader. Duplicate keys are looked up once.
+    ///
+    /// # Parameters
+    ///
+    /// - `keys`: The keys to look up
+    ///
+    /// # Returns
+    ///
+    /// The result of every key, as [`get`](Self::get) would return it
+    ///
+    /// # Panics
+    ///
+    /// Panics if a key has not been loaded yet and this is called outside of a Tokio runtime.
+    pub async fn get_many(&self, keys: impl IntoIterator<Item = K>) -> HashMap<K, Result<Arc<V>>> {
+        let mut lookups = HashMap::new();
+        let mut claimed = Vec::new();
+        let mut expired = Vec::new();
+        for key in keys {
+            if lookups.contains_key(&key) {
+                continue;
+            }
+            let mut entries = self.shard(&key);
+            expired.extend(self.expire(&mut entries, &key));
+            let lookup = self.lookup(&mut entries, &key);
+            if self.batch.is_some() && lookup.missed && lookup.preloader.claim_external() {
+                claimed.push((key.clone(), Arc::clone(&lookup.preloader)));
+            }
+            drop(entries);
+            lookups.insert(key, lookup);
+        }
+        expired.extend(self.sweep());
+        self.removed(expired, RemovalCause::Expired);
+
+        if let Some(batch) = self.batch.as_ref().filter(|_| !claimed.is_empty()) {
+            // Spawned, so the claimed keys are delivered even if this call is dropped
+            tokio::spawn(load_batch(Arc::clone(batch), claimed));
+        }
+
+        future::join_all(lookups.into_iter().map(|(key, lookup)| async move {
+            lookup.preloader.start().await;
+            let result = self.complete(&key, lookup).await;
+            (key, result)
+        }))
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    /// Loads the keys of a [`get_many`](Self::get_many) that nobody has requested yet with one call of `loader`.
+    ///
+    /// The loader receives the missing keys and returns the values it found. A key missing from the returned
+    /// map fails with `PreloaderError::Failed`, and a panic fails every key of the batch. Batched loads use
+    /// neither the timeout nor the retry policy of the per-key preloaders.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: Function creating the asynchronous task that loads the values of several keys
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderCache;
+    /// use std::collections::HashMap;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let users = PreloaderCache::new(|id: &u32| {
+    ///         let id = *id;
+    ///         async move { format!("user-{}", id) }
+    ///     })
+    ///     .with_batch_loader(|ids: Vec<u32>| async move {
+    ///         // One query for every missing key
+    ///         ids.into_iter()
+    ///             .map(|id| (id, format!("user-{}", id)))
+    ///             .collect::<HashMap<_, _>>()
+    ///     });
+    ///
+    ///     let found = users.get_many([1, 2, 3]).await;
+    ///     assert_eq!(**found[&2].as_ref().unwrap(), "user-2");
+    ///     assert_eq!(users.len(), 3);
+    /// }
+    /// ```
+    pub fn with_batch_loader<F, Fut>(mut self, loader: F) -> Self
+    where
+        F: Fn(Vec<K>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = HashMap<K, V>> + Send + 'static,
+    {
+        self.batch = Some(Arc::new(move |keys| Box::pin(loader(keys))));
+        self
+    }
+
+    /// Attempts to retrieve the value of `key` immediately, without starting a load.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Arc<V>)`: If the value is loaded
+    /// - `Err(PreloaderError::NotLoaded)`: If the key has not been requested yet
+    /// - `Err(PreloaderError::Loading)`: If the value is still loading
+    pub fn try_get(&self, key: &K) -> Result<Arc<V>> {
+        let (expired, preloader) = {
+            let mut entries = self.shard(key);
+            let expired = self.expire(&mut entries, key);
+            let access = self.access();
+            (
+                expired,
+                entries
+                    .touch(key, access)
+                    .map(|entry| Arc::clone(&entry.preloader)),
+            )
+        };
+        self.removed(
+            expired.into_iter().chain(self.sweep()),
+            RemovalCause::Expired,
+        );
+        match preloader {
+            Some(preloader) => {
+                let value = preloader.try_get()?;
+                self.weigh(key, &preloader, &value);
+                Ok(value)
+            }
+            None => Err(PreloaderError::NotLoaded),
+        }
+    }
+
+    /// Returns every loaded entry.
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    pub(crate) fn loaded_entries(&self) -> Vec<LoadedEntry<K, V>> {
+        let mut loaded = Vec::new();
+        for shard in self.shards.iter() {
+            let entries = shard.lock().unwrap();
+            for (key, entry) in &entries.map {
+                let Ok(value) = entry.preloader.try_get() else {
+                    continue;
+                };
+                loaded.push(LoadedEntry {
+                    key: key.clone(),
+                    value,
+                    age: entry.preloader.age().unwrap_or_default(),
+                    idle: match self.idle {
+                        Some(_) => entry.used.elapsed(),
+                        None => Duration::ZERO,
+                    },
+                });
+            }
+        }
+        loaded
+    }
+
+    /// Stores `value` for `key` as if it was last accessed `idle` ago, unless the entry would already have expired
+    /// after `age`, or the key is already loading or loaded.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the value was stored
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    pub(crate) fn insert_loaded(&self, key: &K, value: V, age: Duration, idle: Duration) -> bool {
+        let expired = self.idle.is_some_and(|tti| idle >= tti)
+            || self
+                .negative
+                .is_some_and(|(ttl, is_absent)| is_absent(&value) && age >= ttl)
+            || self.expires(key, &value, age);
+        if expired {
+            return false;
+        }
+
+        let mut entries = self.shard(key);
+        let entry = self.entry(&mut entries, key);
+        if entry.preloader.set(value).is_err() {
+            return false;
+        }
+        if self.idle.is_some() {
+            let now = Instant::now();
+            entry.used = now.checked_sub(idle).unwrap_or(now);
+        }
+        true
+    }
+
+    /// Returns the preloader of `key`, creating it in the `Idle` state if needed.
+    ///
+    /// The returned preloader stores the cache's loader, so [`ArcPreloader::start`] loads the key.
+    pub fn preloader(&self, key: &K) -> Arc<ArcPreloader<V>> {
+        let mut entries = self.shard(key);
+        Arc::clone(&self.entry(&mut entries, key).preloader)
+    }
+
+    /// Locks the shard holding `key`.
+    fn shard(&self, key: &K) -> MutexGuard<'_, Entries<K, V>> {
+        let index = self.hasher.hash_one(key) as usize % self.shards.len();
+        self.shards[index].lock().unwrap()
+    }
+
+    /// Returns the tick and time recording an access.
+    ///
+    /// The tick is only counted when the cache has a budget to evict for, and the time only when it has a
+    /// time-to-idle, so lookups in caches without them skip the shared counter and the clock.
+    fn access(&self) -> (u64, Option<Instant>) {
+        let tick = match self.budget {
+            Some(_) => self.tick.fetch_add(1, Ordering::Relaxed) + 1,
+            None => 0,
+        };
+        (tick, self.idle.map(|_| Instant::now()))
+    }
+
+    /// Returns the preloader of `key` for a `get()`, counting the lookup as a hit, miss, or coalesced request.
+    fn lookup(&self, entries: &mut Entries<K, V>, key: &K) -> Lookup<V> {
+        let entry = self.entry(entries, key);
+        let hit = entry.preloader.is_loaded() || entry.preloader.is_failed();
+        let counter = if hit {
+            &self.counters.hits
+        } else if entry.requested || !entry.preloader.is_idle() {
+            &self.counters.coalesced
+        } else {
+            &self.counters.misses
+        };
+        let missed = !entry.requested && entry.preloader.is_idle();
+        entry.requested = true;
+        counter.fetch_add(1, Ordering::Relaxed);
+        Lookup {
+            preloader: Arc::clone(&entry.preloader),
+            missed,
+            hit,
+        }
+    }
+
+    /// Waits for the value of a started lookup, recording its load time and weight.
+    async fn complete(&self, key: &K, lookup: Lookup<V>) -> Result<Arc<V>> {
+        let Lookup {
+            preloader,
+            missed,
+            hit,
+        } = lookup;
+        let value = preloader.get().await?;
+        if hit && self.is_absent(&value) {
+            self.counters.negative_hits.fetch_add(1, Ordering::Relaxed);
+        }
+        if missed {
+            if let Some(duration) = preloader.load_duration() {
+                self.counters.loads.fetch_add(1, Ordering::Relaxed);
+                self.counters
+                    .load_nanos
+                    .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+            }
+        }
+        self.weigh(key, &preloader, &value);
+        Ok(value)
+    }
+
+    /// Checks if `value`, loaded for `key` `age` ago, is older than the expiry policy allows.
+    fn expires(&self, key: &K, value: &V, age: Duration) -> bool {
+        self.expiry.as_ref().is_some_and(|expiry| {
+            expiry
+                .expire_after(key, value)
+                .is_some_and(|ttl| age >= ttl)
+        })
+    }
+
+    /// Checks if `value` is a cached absence.
+    fn is_absent(&self, value: &V) -> bool {
+        self.negative.is_some_and(|(_, is_absent)| is_absent(value))
+    }
+
+    /// Removes `key` from the shard `entries` if it expired, returning the removed entry.
+    ///
+    /// The caller reports the removal with [`removed`](Self::removed) once the shard's lock is released.
+    fn expire(&self, entries: &mut Entries<K, V>, key: &K) -> Option<(K, Arc<ArcPreloader<V>>)> {
+        if self.idle.is_none() && self.negative.is_none() && self.expiry.is_none() {
+            return None;
+        }
+        let expired = entries
+            .map
+            .get(key)
+            .is_some_and(|entry| self.is_expired(key, entry, Instant::now()));
+        let preloader = expired.then(|| entries.remove(key)).flatten()?;
+        Some((key.clone(), preloader))
+    }
+
+    /// Removes the expired entries of every shard, at most once per time-to-idle, or periodically with an expiry
+    /// policy, so entries nobody reads still age out.
+    ///
+    /// Must be called without holding a shard's lock; the caller reports the removals with
+    /// [`removed`](Self::removed).
+    fn sweep(&self) -> Vec<(K, Arc<ArcPreloader<V>>)> {
+        let Some(interval) = self
+            .idle
+            .or(self.expiry.as_ref().map(|_| EXPIRY_SWEEP_INTERVAL))
+        else {
+            return Vec::new();
+        };
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.created).as_nanos() as u64;
+        let swept = self.swept.load(Ordering::Relaxed);
+        // Only the caller that moves `swept` forward checks the entries
+        if elapsed.saturating_sub(swept) < interval.as_nanos() as u64
+            || self
+                .swept
+                .compare_exchange(swept, elapsed, Ordering::Relaxed, Ordering::Relaxed)
+                .is_err()
+        {
+            return Vec::new();
+        }
+
+        let mut expired = Vec::new();
+        for shard in self.shards.iter() {
+            let mut entries = shard.lock().unwrap();
+            let keys: Vec<K> = entries
+                .map
+                .iter()
+                .filter(|(key, entry)| self.is_expired(key, entry, now))
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in keys {
+                expired.extend(entries.remove(&key).map(|preloader| (key, preloader)));
+            }
+        }
+        expired
+    }
+
+    /// Checks if the `entry` of `key` has been idle longer than the time-to-idle, holds a cached absence older than
+    /// the negative TTL, or holds a value older than the expiry policy allows.
+    fn is_expired(&self, key: &K, entry: &Entry<V>, now: Instant) -> bool {
+        let preloader = &entry.preloader;
+        let idle = self.idle.is_some_and(|idle| {
+            (preloader.is_loaded() || preloader.is_failed())
+                && now.duration_since(entry.used) >= idle
+        });
+        idle || self.negative.is_some_and(|(ttl, is_absent)| {
+            preloader.try_get().is_ok_and(|value| is_absent(&value))
+                && preloader.age().is_some_and(|age| age >= ttl)
+        }) || self.expiry.is_some()
+            && preloader.try_get().is_ok_and(|value| {
+                preloader
+                    .age()
+                    .is_some_and(|age| self.expires(key, &value, age))
+            })
+    }
+
+    /// Returns the entry of `key`, marked as recently used, creating it with an `Idle` preloader if needed.
+    fn entry<'a>(&self, entries: &'a mut Entries<K, V>, key: &K) -> &'a mut Entry<V> {
+        let access = self.access();
+        if entries.touch(key, access).is_none() {
+            let loader = Arc::clone(&self.loader);
+            let owned = key.clone();
+            entries.map.insert(
+                key.clone(),
+                Entry {
+                    preloader: Arc::new(ArcPreloader::with_loader(move || loader(&owned))),
+                    weight: None,
+                    accessed: access.0,
+                    used: access.1.unwrap_or_else(Instant::now),
+                    requested: false,
+                },
+            );
+        }
+        entries.map.get_mut(key).unwrap()
+    }
+
+    /// Records the weight of a value the first time it is seen loaded and evicts keys until the cache is
+    /// within its budget.
+    fn weigh(&self, key: &K, preloader: &Arc<ArcPreloader<V>>, value: &V) {
+        let Some((budget, weigher)) = &self.budget else {
+            return;
+        };
+
+        let evicted = {
+            let mut entries = self.shard(key);
+            let weight = match entries.map.get_mut(key) {
+                Some(entry)
+                    if entry.weight.is_none() && Arc::ptr_eq(&entry.preloader, preloader) =>
+                {
+                    let weight = weigher(value);
+                    entry.weight = Some(weight);
+                    weight
+                }
+                _ => return,
+            };
+            entries.weight += weight;
+
+            if weight > *budget {
+                // Evicting other keys could not make room
+                let removed = entries.remove(key);
+                removed
+                    .map(|preloader| (key.clone(), preloader))
+                    .into_iter()
+                    .collect()
+            } else {
+                drop(entries);
+                self.evict(*budget)
+            }
+        };
+
+        self.counters
+            .evictions
+            .fetch_add(evicted.len() as u64, Ordering::Relaxed);
+        self.removed(evicted, RemovalCause::Size);
+    }
+
+    /// Removes the least recently used weighed entries until the total weight is within `budget`, returning them.
+    fn evict(&self, budget: usize) -> Vec<(K, Arc<ArcPreloader<V>>)> {
+        // Every shard is locked, always in the same order, so the least recently used entries are found across
+        // the whole cache
+        let mut shards: Vec<_> = self
+            .shards
+            .iter()
+            .map(|shard| shard.lock().unwrap())
+            .collect();
+        let mut weight: usize = shards.iter().map(|entries| entries.weight).sum();
+        if weight <= budget {
+            return Vec::new();
+        }
+
+        let mut weighed: Vec<_> = shards
+            .iter()
+            .enumerate()
+            .flat_map(|(index, entries)| {
+                entries
+                    .map
+                    .iter()
+                    .filter(|(_, entry)| entry.weight.is_some())
+                    .map(move |(key, entry)| (entry.accessed, index, key.clone()))
+            })
+            .collect();
+        weighed.sort_unstable_by_key(|(accessed, _, _)| *accessed);
+
+        let mut evicted = Vec::new();
+        for (_, index, key) in weighed {
+            if weight <= budget {
+                break;
+            }
+            let entries = &mut shards[index];
+            let before = entries.weight;
+            if let Some(preloader) = entries.remove(&key) {
+                weight -= before - entries.weight;
+                evicted.push((key, preloader));
+            }
+        }
+        evicted
+    }
+
+    /// Emits `Evicted` for removed entries and passes those holding a value to the removal listener.
+    ///
+    /// Must be called without holding a shard's lock, so the listener may use the cache.
+    fn removed(
+        &self,
+        removed: impl IntoIterator<Item = (K, Arc<ArcPreloader<V>>)>,
+        cause: RemovalCause,
+    ) {
+        for (key, preloader) in removed {
+            preloader.event_feed().emit(PreloaderEvent::Evicted);
+            if let Some(listener) = &self.listener {
+                if let Ok(value) = preloader.try_get() {
+                    listener(&key, value, cause);
+                }
+            }
+        }
+    }
+
+    /// Returns the lookup and load counters collected since the cache was created or the stats were reset.
+    ///
+    /// Only [`get`](Self::get) is counted; `try_get()` and `preloader()` are not.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderCache;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let squares = PreloaderCache::new(|n: &u64| {
+    ///         let n = *n;
+    ///         async move { n * n }
+    ///     });
+    ///     squares.get(&3).await.unwrap();
+    ///     squares.get(&3).await.unwrap();
+    ///
+    ///     let stats = squares.stats();
+    ///     assert_eq!((stats.hits, stats.misses), (1, 1));
+    ///     assert_eq!(stats.hit_rate(), 0.5);
+    /// }
+    /// ```
+    pub fn stats(&self) -> CacheStats {
+        let counters = &self.counters;
+        CacheStats {
+            hits: counters.hits.load(Ordering::Relaxed),
+            misses: counters.misses.load(Ordering::Relaxed),
+            coalesced: counters.coalesced.load(Ordering::Relaxed),
+            negative_hits: counters.negative_hits.load(Ordering::Relaxed),
+            evictions: counters.evictions.load(Ordering::Relaxed),
+            loads: counters.loads.load(Ordering::Relaxed),
+            total_load_time: Duration::from_nanos(counters.load_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Resets every counter reported by [`stats`](Self::stats) to zero.
+    pub fn reset_stats(&self) {
+        let counters = &self.counters;
+        for counter in [
+            &counters.hits,
+            &counters.misses,
+            &counters.coalesced,
+            &counters.negative_hits,
+            &counters.evictions,
+            &counters.loads,
+            &counters.load_nanos,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the total weight of the loaded values that have been weighed.
+    ///
+    /// Always `0` without a [memory budget](Self::with_memory_budget).
+    pub fn weight(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().weight)
+            .sum()
+    }
+
+    /// Returns the configured memory budget, if any.
+    pub fn memory_budget(&self) -> Option<usize> {
+        self.budget.as_ref().map(|(budget, _)| *budget)
+    }
+
+    /// Checks if `key` has been requested and not removed since.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.shard(key).map.contains_key(key)
+    }
+
+    /// Removes `key` from the cache, so the next `get()` loads it again.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was present
+    pub fn remove(&self, key: &K) -> bool {
+        let removed = self.shard(key).remove(key);
+        match removed {
+            Some(preloader) => {
+                self.removed([(key.clone(), preloader)], RemovalCause::Explicit);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes every key from the cache.
+    pub fn clear(&self) {
+        let removed: Vec<_> = self
+            .shards
+            .iter()
+            .flat_map(|shard| {
+                let mut entries = shard.lock().unwrap();
+                entries.weight = 0;
+                entries.map.drain().collect::<Vec<_>>()
+            })
+            .collect();
+        self.removed(
+            removed
+                .into_iter()
+                .map(|(key, entry)| (key, entry.preloader)),
+            RemovalCause::Explicit,
+        );
+    }
+
+    /// Returns the number of keys in the cache, including those still loading.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().map.len())
+            .sum()
+    }
+
+    /// Checks if the cache holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.shards
+            .iter()
+            .all(|shard| shard.lock().unwrap().map.is_empty())
+    }
+}
+
+impl<K, V> PreloaderCache<K, V>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: MemoryFootprint + Send + Sync + 'static,
+{
+    /// Limits the approximate memory kept by the loaded values to `budget` bytes, weighing each value with
+    /// [`MemoryFootprint::memory_footprint`].
+    ///
+    /// See [`with_memory_budget`](Self::with_memory_budget).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderCache;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let pages = PreloaderCache::new(|id: &u32| {
+    ///         let id = *id;
+    ///         async move { "x".repeat(id as usize * 100) }
+    ///     })
+    ///     .with_footprint_budget(1024);
+    ///
+    ///     pages.get(&6).await.unwrap();
+    ///     pages.get(&5).await.unwrap();
+    ///     assert!(!pages.contains_key(&6));
+    /// }
+    /// ```
+    pub fn with_footprint_budget(self, budget: usize) -> Self {
+        self.with_memory_budget(budget, V::memory_footprint)
+    }
+
+    /// Returns the approximate number of bytes kept by the loaded values.
+    ///
+    /// Unlike [`weight`](Self::weight), every loaded value is measured now, whether or not a budget is set.
+    pub fn memory_footprint(&self) -> usize {
+        self.shards
+            .iter()
+            .flat_map(|shard| {
+                let preloaders: Vec<_> = shard
+                    .lock()
+                    .unwrap()
+                    .map
+                    .values()
+                    .map(|entry| Arc::clone(&entry.preloader))
+                    .collect();
+                preloaders
+            })
+            .map(|preloader| preloader.memory_footprint().unwrap_or(0))
+            .sum()
+    }
+}
+
+impl<K, V> PreloaderCache<K, Option<V>>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    /// Keeps absent values, loaded as `None`, for `ttl` before loading the key again.
+    ///
+    /// A loader returning `Option<V>` distinguishes a key known not to exist, `Ok(None)` from
+    /// [`get`](Self::get), from a failed load, `Err(PreloaderError)`. Caching the absence keeps hot missing keys
+    /// from hammering the loader, while the TTL lets keys created later be found. Present values are not affected.
+    /// Lookups answered by a cached absence are counted as [`CacheStats::negative_hits`].
+    ///
+    /// # Parameters
+    ///
+    /// - `ttl`: How long a key is remembered as absent
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderCache;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let users = PreloaderCache::new(|id: &u32| {
+    ///         let id = *id;
+    ///         async move { (id < 100).then(|| format!("user-{}", id)) }
+    ///     })
+    ///     .with_negative_ttl(Duration::from_secs(30));
+    ///
+    ///     assert_eq!(users.get(&7).await.unwrap().as_deref(), Some("user-7"));
+    ///     assert_eq!(*users.get(&404).await.unwrap(), None);
+    ///     assert_eq!(*users.get(&404).await.unwrap(), None);
+    ///     assert_eq!(users.stats().negative_hits, 1);
+    /// }
+    /// ```
+    pub fn with_negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative = Some((ttl, Option::is_none));
+        self
+    }
+}
+
+/// Runs one batched load of `claimed` and delivers each key's value to its preloader.
+async fn load_batch<K, V>(batch: BatchLoader<K, V>, claimed: Vec<(K, Arc<ArcPreloader<V>>)>)
+where
+    K: Eq + Hash + Clone,
+    V: Send + Sync + 'static,
+{
+    let keys = claimed.iter().map(|(key, _)| key.clone()).collect();
+    let loaded = AssertUnwindSafe(async move { batch(keys).await })
+        .catch_unwind()
+        .await
+        .map_err(panicked);
+    match loaded {
+        Ok(mut values) => {
+            for (key, preloader) in claimed {
+                preloader.finish(values.remove(&key).map(Arc::new).ok_or_else(|| {
+                    PreloaderError::Failed("batch load returned no value for the key".to_string())
+                }));
+            }
+        }
+        Err(error) => {
+            for (_, preloader) in claimed {
+                preloader.finish(Err(error.clone()));
+            }
+        }
+    }
+}
+
+/// Creates `count` empty shards.
+fn new_shards<K: Eq + Hash, V: Send + Sync + 'static>(count: usize) -> Box<[Mutex<Entries<K, V>>]> {
+    (0..count).map(|_| Mutex::new(Entries::new())).collect()
+}
+
+/// Shows the number of keys without requiring `K: Debug` or `V: Debug`.
+impl<K, V: Send + Sync + 'static> fmt::Debug for PreloaderCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (len, weight) = self.shards.iter().fold((0, 0), |(len, weight), shard| {
+            let entries = shard.lock().unwrap();
+            (len + entries.map.len(), weight + entries.weight)
+        });
+        f.debug_struct("PreloaderCache")
+            .field("len", &len)
+            .field("weight", &weight)
+            .field("shards", &self.shards.len())
+            .field(
+                "memory_budget",
+                &self.budget.as_ref().map(|(budget, _)| budget),
+            )
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,137 @@
+//! Value cell module
+//!
+//! This module provides `ValueCell`, the lock-free core of `Preloader`: the atomic state machine and the cell
+//! publishing the loaded value to readers. Built with `RUSTFLAGS="--cfg preloader_loom"`, it uses loom's atomics and
+//! `UnsafeCell` instead of the standard ones, so the races between loading and reading can be model-checked.
+//! The cfg is crate-specific because dependencies such as `event-listener` react to a plain `--cfg loom`.
+
+use std::sync::atomic::Ordering;
+
+#[cfg(preloader_loom)]
+use loom::{cell::UnsafeCell, sync::atomic::AtomicU8};
+#[cfg(not(preloader_loom))]
+use std::sync::atomic::AtomicU8;
+
+use crate::PreloaderState;
+
+/// `std::cell::UnsafeCell` with the closure-based access API of loom's `UnsafeCell`
+#[cfg(not(preloader_loom))]
+#[derive(Debug)]
+struct UnsafeCell<T>(std::cell::UnsafeCell<T>);
+
+#[cfg(not(preloader_loom))]
+impl<T> UnsafeCell<T> {
+    fn new(value: T) -> Self {
+        Self(std::cell::UnsafeCell::new(value))
+    }
+
+    #[inline]
+    fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+        f(self.0.get())
+    }
+
+    #[inline]
+    fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+}
+
+/// Atomic state machine together with the value it guards
+///
+/// The value is written once, by the caller that claimed the load, before the state is set to `Loaded`
+/// with `Release` ordering. Readers that observe `Loaded` with `Acquire` ordering may then read it.
+#[derive(Debug)]
+pub(crate) struct ValueCell<T> {
+    state: AtomicU8,
+    value: UnsafeCell<Option<T>>,
+}
+
+impl<T> ValueCell<T> {
+    /// Creates an empty cell in the given state.
+    pub(crate) fn new(state: PreloaderState) -> Self {
+        Self {
+            state: AtomicU8::new(state as u8),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    /// Loads the current state.
+    #[inline]
+    pub(crate) fn load(&self, order: Ordering) -> PreloaderState {
+        decode(self.state.load(order))
+    }
+
+    /// Stores a new state.
+    #[inline]
+    pub(crate) fn store(&self, state: PreloaderState, order: Ordering) {
+        self.state.store(state as u8, order);
+    }
+
+    /// Changes the state to `new` if it is `current`.
+    ///
+    /// # Returns
+    ///
+    /// The previous state, as `Ok` if it was `current`
+    #[inline]
+    pub(crate) fn compare_exchange(
+        &self,
+        current: PreloaderState,
+        new: PreloaderState,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<PreloaderState, PreloaderState> {
+        self.state
+            .compare_exchange(current as u8, new as u8, success, failure)
+            .map(decode)
+            .map_err(decode)
+    }
+
+    /// Stores the value and changes the state to `Loaded`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must be the only one completing the load, and the state must not have been `Loaded` before,
+    /// so that no reader can access the value while it is written.
+    #[inline]
+    pub(crate) unsafe fn publish(&self, value: T) {
+        self.value.with_mut(|slot| *slot = Some(value));
+        self.store(PreloaderState::Loaded, Ordering::Release);
+    }
+
+    /// Returns a reference to the published value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must have observed the `Loaded` state with `Acquire` ordering.
+    #[inline]
+    pub(crate) unsafe fn get(&self) -> &T {
+        self.value.with(|slot| (*slot).as_ref().unwrap())
+    }
+
+    /// Returns a mutable reference to the value, if any.
+    #[inline]
+    pub(crate) fn get_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the exclusive borrow rules out concurrent readers
+        self.value.with_mut(|slot| unsafe { (*slot).as_mut() })
+    }
+
+    /// Takes the value out of the cell.
+    #[inline]
+    pub(crate) fn take(&mut self) -> Option<T> {
+        // SAFETY: the exclusive borrow rules out concurrent readers
+        self.value.with_mut(|slot| unsafe { (*slot).take() })
+    }
+}
+
+/// Converts a stored state back to a `PreloaderState`.
+#[inline]
+fn decode(state: u8) -> PreloaderState {
+    match state {
+        0 => PreloaderState::Idle,
+        1 => PreloaderState::Start,
+        2 => PreloaderState::Loading,
+        3 => PreloaderState::Loaded,
+        4 => PreloaderState::Failed,
+        _ => PreloaderState::Poisoned,
+    }
+}
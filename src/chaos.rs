@@ -0,0 +1,174 @@
+//! Fault injection module
+//!
+//! This module injects faults into the loads of named preloaders, so tests can check that an application degrades
+//! gracefully when specific preloads are slow, fail, or never finish. Faults are registered process-wide under a
+//! preloader name with [`inject`] and apply to every load started afterwards by a preloader with that name,
+//! including reloads and refreshes. Faults registered under `"*"` apply to the preloaders without faults of their
+//! own.
+//!
+//! # Example
+//!
+//! ```rust
+//! use preloader::{chaos, Preloader, PreloaderError};
+//! use tokio;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     chaos::inject("chaos-doc/pricing", chaos::Fault::new().failure(1.0));
+//!
+//!     let pricing = Preloader::builder().name("chaos-doc/pricing").build();
+//!     pricing.load(async { 42 }).await;
+//!     assert!(matches!(pricing.get().await, Err(PreloaderError::Failed(_))));
+//!
+//!     chaos::clear("chaos-doc/pricing");
+//! }
+//! ```
+
+use std::{
+    collections::{hash_map::RandomState, BTreeMap},
+    future::{self, Future},
+    hash::{BuildHasher, Hasher},
+    sync::Mutex,
+    time::Duration,
+};
+
+use crate::PreloaderError;
+
+/// Name matching every preloader without faults of its own
+pub const ANY: &str = "*";
+
+static FAULTS: Mutex<BTreeMap<String, Fault>> = Mutex::new(BTreeMap::new());
+
+/// Faults injected into the loads of a preloader, each with the probability of hitting a load
+///
+/// Added latency and hangs happen before the loader runs and count against the attempt timeout, so a hang
+/// fails with `PreloaderError::Timeout` when a timeout is configured and never finishes otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Fault {
+    /// Probability and duration of the added latency
+    latency: Option<(f64, Duration)>,
+    /// Probability of failing the load
+    failure: f64,
+    /// Probability of the load never finishing
+    hang: f64,
+}
+
+impl Fault {
+    /// Creates a fault that injects nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delays loads by `delay` with the given probability.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `probability` is not between 0.0 and 1.0.
+    pub fn latency(mut self, probability: f64, delay: Duration) -> Self {
+        self.latency = Some((check(probability), delay));
+        self
+    }
+
+    /// Fails loads with `PreloaderError::Failed` with the given probability.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `probability` is not between 0.0 and 1.0.
+    pub fn failure(mut self, probability: f64) -> Self {
+        self.failure = check(probability);
+        self
+    }
+
+    /// Keeps loads from finishing with the given probability.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `probability` is not between 0.0 and 1.0.
+    pub fn hang(mut self, probability: f64) -> Self {
+        self.hang = check(probability);
+        self
+    }
+
+    /// Runs `load` with the faults applied.
+    ///
+    /// # Parameters
+    ///
+    /// - `timeout`: The attempt timeout bounding the added latency and hangs
+    /// - `load`: The load to run unless a fault fails it first
+    pub(crate) async fn apply<T>(
+        self,
+        timeout: Option<Duration>,
+        load: impl Future<Output = Result<T, PreloaderError>>,
+    ) -> Result<T, PreloaderError> {
+        let delay = async {
+            if let Some((probability, delay)) = self.latency {
+                if chance(probability) {
+                    tokio::time::sleep(delay).await;
+                }
+            }
+            if chance(self.hang) {
+                future::pending::<()>().await;
+            }
+        };
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, delay)
+                .await
+                .map_err(|_| PreloaderError::Timeout)?,
+            None => delay.await,
+        }
+        if chance(self.failure) {
+            return Err(PreloaderError::Failed("injected failure".to_string()));
+        }
+        load.await
+    }
+}
+
+/// Injects `fault` into the loads of the preloaders named `name`, replacing any fault injected under that name.
+///
+/// # Parameters
+///
+/// - `name`: Name of the preloaders, or [`ANY`] for every preloader without faults of its own
+/// - `fault`: The faults to inject
+pub fn inject(name: impl Into<String>, fault: Fault) {
+    FAULTS.lock().unwrap().insert(name.into(), fault);
+}
+
+/// Stops injecting faults into the loads of the preloaders named `name`.
+///
+/// # Returns
+///
+/// `true` if faults were injected under `name`
+pub fn clear(name: &str) -> bool {
+    FAULTS.lock().unwrap().remove(name).is_some()
+}
+
+/// Stops injecting faults into every preloader.
+pub fn clear_all() {
+    FAULTS.lock().unwrap().clear();
+}
+
+/// Returns the faults injected into the preloaders named `name`.
+pub(crate) fn fault_for(name: Option<&str>) -> Option<Fault> {
+    let faults = FAULTS.lock().unwrap();
+    name.and_then(|name| faults.get(name))
+        .or_else(|| faults.get(ANY))
+        .copied()
+}
+
+fn check(probability: f64) -> f64 {
+    assert!(
+        (0.0..=1.0).contains(&probability),
+        "probability must be between 0.0 and 1.0"
+    );
+    probability
+}
+
+/// Returns `true` with the given probability.
+fn chance(probability: f64) -> bool {
+    if probability <= 0.0 {
+        return false;
+    }
+    // Every `RandomState` is seeded differently, which is random enough to pick the faulty loads
+    let random = RandomState::new().build_hasher().finish();
+    (random as f64 / u64::MAX as f64) < probability
+}
@@ -0,0 +1,183 @@
+//! Circuit breaker module
+//!
+//! This module provides the `CircuitBreaker` struct, which stops load attempts against a failing upstream
+//! for a cool-down period after several consecutive failures, and then lets a single probe through.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+use crate::PreloaderError;
+
+/// State of a [`CircuitBreaker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Load attempts are allowed
+    Closed,
+    /// Load attempts fail immediately with `PreloaderError::CircuitOpen`
+    Open,
+    /// The cool-down has elapsed; the next attempt is a probe deciding whether the circuit closes again
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+struct Breaker {
+    /// Number of consecutive failed attempts while closed
+    failures: u32,
+    /// Time the circuit opened, if it is open or half-open
+    opened_at: Option<Instant>,
+    /// Whether a half-open probe is running
+    probing: bool,
+}
+
+/// Circuit breaker stopping load attempts after repeated failures
+///
+/// After `failure_threshold` consecutive failed attempts the circuit opens, and attempts fail immediately
+/// with `PreloaderError::CircuitOpen` for the cool-down period. Once it has elapsed, the circuit is half-open:
+/// one attempt is let through as a probe, and it closes the circuit on success or opens it again on failure.
+///
+/// Clones share their state, so one breaker can protect an upstream used by several preloaders.
+/// Like retries, the breaker only applies to loads started with
+/// [`Preloader::load_with`](crate::Preloader::load_with) or a stored loader factory.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{CircuitBreaker, CircuitState, Preloader, PreloaderError, RetryPolicy};
+/// use std::time::Duration;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+///     let preloader: Preloader<u32> = Preloader::builder()
+///         .retry(RetryPolicy::fixed(5, Duration::ZERO))
+///         .circuit_breaker(breaker.clone())
+///         .build();
+///
+///     preloader.load_with(|| async { panic!("upstream unavailable") }).await;
+///     assert!(matches!(preloader.get().await, Err(PreloaderError::CircuitOpen)));
+///     assert_eq!(breaker.state(), CircuitState::Open);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cool_down: Duration,
+    state: Arc<Mutex<Breaker>>,
+}
+
+impl CircuitBreaker {
+    /// Creates a closed circuit breaker.
+    ///
+    /// # Parameters
+    ///
+    /// - `failure_threshold`: Number of consecutive failures opening the circuit; values below 1 are treated as 1
+    /// - `cool_down`: Duration for which the circuit stays open before a probe is allowed
+    pub fn new(failure_threshold: u32, cool_down: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cool_down,
+            state: Arc::default(),
+        }
+    }
+
+    /// Returns the current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(_) if state.probing => CircuitState::HalfOpen,
+            Some(opened_at) if opened_at.elapsed() < self.cool_down => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Returns the number of consecutive failed attempts since the circuit last closed.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.lock().unwrap().failures
+    }
+
+    /// Closes the circuit and clears the failure count.
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = Breaker::default();
+    }
+
+    /// Asks for permission to start a load attempt.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(CircuitPermit)`: The attempt may start; its outcome must be reported through the permit
+    /// - `Err(PreloaderError::CircuitOpen)`: The circuit is open, or another probe is already running
+    pub(crate) fn acquire(&self) -> Result<CircuitPermit, PreloaderError> {
+        let mut state = self.state.lock().unwrap();
+        let probe = match state.opened_at {
+            None => false,
+            Some(opened_at) if state.probing || opened_at.elapsed() < self.cool_down => {
+                return Err(PreloaderError::CircuitOpen)
+            }
+            Some(_) => {
+                state.probing = true;
+                true
+            }
+        };
+        Ok(CircuitPermit {
+            breaker: self.clone(),
+            probe,
+        })
+    }
+
+    fn record(&self, success: bool) {
+        let mut state = self.state.lock().unwrap();
+        if success {
+            *state = Breaker::default();
+            return;
+        }
+
+        state.failures = state.failures.saturating_add(1);
+        if state.probing || state.failures >= self.failure_threshold {
+            state.opened_at = Some(Instant::now());
+        }
+        state.probing = false;
+    }
+}
+
+impl fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("state", &self.state())
+            .field("consecutive_failures", &self.consecutive_failures())
+            .field("failure_threshold", &self.failure_threshold)
+            .field("cool_down", &self.cool_down)
+            .finish()
+    }
+}
+
+/// Permission to run one load attempt, reporting its outcome to the [`CircuitBreaker`]
+///
+/// Dropping the permit without reporting, for example because the load was cancelled, releases a
+/// half-open probe without changing the circuit.
+pub(crate) struct CircuitPermit {
+    breaker: CircuitBreaker,
+    probe: bool,
+}
+
+impl CircuitPermit {
+    /// Reports the outcome of the attempt.
+    pub(crate) fn finish(mut self, success: bool) {
+        self.probe = false;
+        self.breaker.record(success);
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        if self.probe {
+            self.breaker.state.lock().unwrap().probing = false;
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Preloader combinator module
+//!
+//! This module provides combinators that derive new preloaders from existing ones.
+
+use std::{future::Future, sync::Arc};
+
+use crate::{Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+impl<T: Send + Sync + 'static> Preloader<T> {
+    /// Creates a derived preloader whose value is computed from this preloader's value.
+    ///
+    /// The derived preloader starts in the `Loading` state. Its task waits until this preloader has been
+    /// loaded (including waiting for `load()` to be called) and then applies `f` to the value.
+    /// If loading this preloader fails, the derived preloader fails with the same error.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: Function computing the derived value
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::sync::Arc;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let raw = Arc::new(Preloader::new());
+    ///     let parsed = Arc::clone(&raw).map(|text: &String| text.parse::<u32>().unwrap());
+    ///
+    ///     raw.load(async { "42".to_string() }).await;
+    ///     assert_eq!(*parsed.get().await.unwrap(), 42);
+    /// }
+    /// ```
+    pub fn map<U, F>(self: Arc<Self>, f: F) -> Preloader<U>
+    where
+        U: Send + 'static,
+        F: FnOnce(&T) -> U + Send + 'static,
+    {
+        let mut derived = Preloader::new();
+        derived.start_loader(async move { self.resolve(true).await.map(f) });
+        derived
+    }
+
+    /// Creates a derived preloader whose value is loaded asynchronously from this preloader's value.
+    ///
+    /// Behaves like [`map`](Self::map), but `f` returns a future.
+    /// Since the future must be `'static`, `f` should copy whatever it needs out of the reference.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: Function creating the asynchronous task that loads the derived value
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::sync::Arc;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let url = Arc::new(Preloader::ready("https://example.com".to_string()));
+    ///     let page = Arc::clone(&url).and_then(|url: &String| {
+    ///         let url = url.clone();
+    ///         async move { format!("contents of {}", url) }
+    ///     });
+    ///
+    ///     assert_eq!(*page.get().await.unwrap(), "contents of https://example.com");
+    /// }
+    /// ```
+    pub fn and_then<U, F, Fut>(self: Arc<Self>, f: F) -> Preloader<U>
+    where
+        U: Send + 'static,
+        F: FnOnce(&T) -> Fut + Send + 'static,
+        Fut: Future<Output = U> + Send + 'static,
+    {
+        let mut derived = Preloader::new();
+        derived.start_loader(async move {
+            let future = f(self.resolve(true).await?);
+            Ok(future.await)
+        });
+        derived
+    }
+
+    /// Waits until both this preloader and `other` are loaded.
+    ///
+    /// Both preloaders are awaited concurrently. The first error returned by either of them is returned.
+    /// Use the [`join!`](crate::join) macro to wait on more than two preloaders.
+    ///
+    /// # Parameters
+    ///
+    /// - `other`: The preloader to wait on together with this one
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((&T, &U))`: References to both loaded values
+    /// - `Err(PreloaderError)`: If either preloader is not loaded or failed to load
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let name = Preloader::spawn(async { "config".to_string() });
+    ///     let size = Preloader::spawn(async { 42 });
+    ///
+    ///     let (name, size) = Preloader::zip(&name, &size).await.unwrap();
+    ///     assert_eq!(name, "config");
+    ///     assert_eq!(*size, 42);
+    /// }
+    /// ```
+    pub async fn zip<'a, U: Send + 'static>(
+        &'a self,
+        other: &'a Preloader<U>,
+    ) -> Result<(&'a T, &'a U)> {
+        tokio::try_join!(self.get(), other.get())
+    }
+}
@@ -0,0 +1,309 @@
+//! Configuration loader module
+//!
+//! This module provides `ConfigPreloader`, a hot-reloadable holder for typed configuration files.
+//! The file is deserialized from JSON, TOML, or YAML into a user type and checked by validation callbacks;
+//! it can be refreshed on demand or, with the `watch` feature, whenever the file changes.
+
+use std::{
+    fmt::{self, Display},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{file::read_parsed, ArcPreloader, Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Function deserializing and validating the contents of a configuration file
+type Parser<T> = Arc<dyn Fn(&[u8]) -> std::result::Result<T, String> + Send + Sync>;
+
+/// Validation callback of a configuration
+type Validator<T> = Box<dyn Fn(&T) -> std::result::Result<(), String> + Send + Sync>;
+
+/// File format of a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// JSON, detected from the `.json` extension
+    Json,
+    /// TOML, detected from the `.toml` extension
+    ///
+    /// Requires the `toml` feature.
+    #[cfg(feature = "toml")]
+    Toml,
+    /// YAML, detected from the `.yaml` and `.yml` extensions
+    ///
+    /// Requires the `yaml` feature.
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Detects the format from the extension of `path`.
+    ///
+    /// Returns `None` if the extension is missing, unknown, or its format is not enabled.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Self::Json),
+            #[cfg(feature = "toml")]
+            "toml" => Some(Self::Toml),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Deserializes `contents` in this format.
+    fn parse<T: DeserializeOwned>(self, contents: &[u8]) -> std::result::Result<T, String> {
+        match self {
+            Self::Json => serde_json::from_slice(contents).map_err(|error| error.to_string()),
+            #[cfg(feature = "toml")]
+            Self::Toml => std::str::from_utf8(contents)
+                .map_err(|error| error.to_string())
+                .and_then(|contents| toml::from_str(contents).map_err(|error| error.to_string())),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => serde_yaml::from_slice(contents).map_err(|error| error.to_string()),
+        }
+    }
+}
+
+/// Builder for a [`ConfigPreloader`]
+pub struct ConfigBuilder<T> {
+    path: PathBuf,
+    format: Option<ConfigFormat>,
+    validators: Vec<Validator<T>>,
+    #[cfg(feature = "watch")]
+    watch: bool,
+}
+
+impl<T: DeserializeOwned + Send + Sync + 'static> ConfigBuilder<T> {
+    /// Sets the file format instead of detecting it from the file extension.
+    pub fn format(mut self, format: ConfigFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Adds a validation callback, run after every successful deserialization.
+    ///
+    /// A configuration is only stored if every callback accepts it.
+    ///
+    /// # Parameters
+    ///
+    /// - `validate`: Function returning an error describing why the configuration is invalid
+    pub fn validate<F, E>(mut self, validate: F) -> Self
+    where
+        F: Fn(&T) -> std::result::Result<(), E> + Send + Sync + 'static,
+        E: Display,
+    {
+        self.validators.push(Box::new(move |config| {
+            validate(config).map_err(|error| format!("invalid configuration: {}", error))
+        }));
+        self
+    }
+
+    /// Sets whether to reload the configuration whenever the file changes.
+    ///
+    /// Requires the `watch` feature. See [`ArcPreloader::watch_file_de`].
+    #[cfg(feature = "watch")]
+    pub fn watch(mut self, watch: bool) -> Self {
+        self.watch = watch;
+        self
+    }
+
+    /// Creates the `ConfigPreloader` and starts loading the file.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ConfigPreloader<T>)`: The configuration holder, already loading
+    /// - `Err(io::Error)`: If the format cannot be detected from the file extension, or the file cannot be watched
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn build(self) -> io::Result<ConfigPreloader<T>> {
+        let format = match self.format {
+            Some(format) => format,
+            None => ConfigFormat::from_path(&self.path).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unknown configuration format: {}", self.path.display()),
+                )
+            })?,
+        };
+
+        let validators = self.validators;
+        let parser: Parser<T> = Arc::new(move |contents| {
+            let config = format.parse(contents)?;
+            for validate in &validators {
+                validate(&config)?;
+            }
+            Ok(config)
+        });
+
+        #[cfg(feature = "watch")]
+        if self.watch {
+            let parse = Arc::clone(&parser);
+            let preloader =
+                ArcPreloader::watch_file_de(&self.path, move |contents| parse(contents))?;
+            return Ok(ConfigPreloader {
+                path: Arc::from(self.path),
+                parser,
+                preloader,
+            });
+        }
+
+        let parse = Arc::clone(&parser);
+        let path = self.path.clone();
+        let mut inner = Preloader::new();
+        inner.start_loader(async move {
+            read_parsed(path, |contents| parse(contents))
+                .await
+                .map(Arc::new)
+        });
+        Ok(ConfigPreloader {
+            path: Arc::from(self.path),
+            parser,
+            preloader: Arc::new(ArcPreloader::from_inner(inner)),
+        })
+    }
+}
+
+/// Hot-reloadable holder of a typed configuration file
+///
+/// The file is deserialized into `T` and checked by the validation callbacks of the [`ConfigBuilder`].
+/// [`refresh`](Self::refresh) reads the file again; an invalid new version is rejected and the previous
+/// configuration stays in place.
+///
+/// Cloning a `ConfigPreloader` is cheap; clones share the same configuration.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::ConfigPreloader;
+/// use serde::Deserialize;
+/// use tokio;
+///
+/// #[derive(Deserialize)]
+/// struct Settings {
+///     port: u16,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let path = std::env::temp_dir().join("preloader-config-example.json");
+///     std::fs::write(&path, r#"{"port": 8080}"#).unwrap();
+///
+///     let settings = ConfigPreloader::<Settings>::builder(&path)
+///         .validate(|settings| if settings.port == 0 { Err("port must not be 0") } else { Ok(()) })
+///         .build()
+///         .unwrap();
+///     assert_eq!(settings.get().await.unwrap().port, 8080);
+///
+///     std::fs::write(&path, r#"{"port": 0}"#).unwrap();
+///     assert!(settings.refresh().await.is_err());
+///     assert_eq!(settings.get().await.unwrap().port, 8080);
+///     # std::fs::remove_file(&path).unwrap();
+/// }
+/// ```
+pub struct ConfigPreloader<T: Send + Sync + 'static> {
+    path: Arc<Path>,
+    parser: Parser<T>,
+    preloader: Arc<ArcPreloader<T>>,
+}
+
+impl<T: DeserializeOwned + Send + Sync + 'static> ConfigPreloader<T> {
+    /// Returns a builder for a configuration read from the file at `path`.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The configuration file
+    pub fn builder(path: impl Into<PathBuf>) -> ConfigBuilder<T> {
+        ConfigBuilder {
+            path: path.into(),
+            format: None,
+            validators: Vec::new(),
+            #[cfg(feature = "watch")]
+            watch: false,
+        }
+    }
+
+    /// Creates a `ConfigPreloader` for the file at `path`, detecting the format from its extension.
+    ///
+    /// See [`ConfigBuilder::build`].
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        Self::builder(path).build()
+    }
+
+    /// Retrieves the current configuration, waiting until the first load is complete.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Arc<T>)`: The latest valid configuration
+    /// - `Err(PreloaderError::Failed)`: If no valid configuration could be loaded yet
+    pub async fn get(&self) -> Result<Arc<T>> {
+        self.preloader.get().await
+    }
+
+    /// Attempts to retrieve the current configuration immediately.
+    pub fn try_get(&self) -> Result<Arc<T>> {
+        self.preloader.try_get()
+    }
+
+    /// Returns the generation of the current configuration, incremented every time it is replaced.
+    ///
+    /// See [`ArcPreloader::generation`].
+    pub fn generation(&self) -> u64 {
+        self.preloader.generation()
+    }
+
+    /// Reads, deserializes, and validates the file again, replacing the configuration if it is valid.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the new configuration was stored
+    /// - `Err(PreloaderError::Failed)`: If the file cannot be read, deserialized, or validated;
+    ///   the previous configuration is kept
+    pub async fn refresh(&self) -> Result<()> {
+        let parser = Arc::clone(&self.parser);
+        let path = self.path.to_path_buf();
+        self.preloader
+            .reload_result(async move {
+                read_parsed(path, |contents| parser(contents))
+                    .await
+                    .map(Arc::new)
+            })
+            .await
+    }
+
+    /// Returns the path of the configuration file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the preloader holding the configuration.
+    pub fn preloader(&self) -> &ArcPreloader<T> {
+        &self.preloader
+    }
+}
+
+impl<T: Send + Sync + 'static> Clone for ConfigPreloader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            path: Arc::clone(&self.path),
+            parser: Arc::clone(&self.parser),
+            preloader: Arc::clone(&self.preloader),
+        }
+    }
+}
+
+/// Shows the path and the state of the configuration without requiring `T: Debug`.
+impl<T: Send + Sync + 'static> fmt::Debug for ConfigPreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigPreloader")
+            .field("path", &self.path)
+            .field("preloader", &self.preloader)
+            .finish()
+    }
+}
@@ -0,0 +1,114 @@
+//! Load context module
+//!
+//! This module provides [`LoadContext`], the handle passed to loaders started with
+//! [`Preloader::load_with_context`](crate::Preloader::load_with_context). It bundles the per-load facilities —
+//! progress reporting, cancellation, the attempt number, the attempt deadline, and the time-slicing budget — so new ones can be added
+//! without changing the loader signature.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use crate::{events::Events, Budget, PreloaderEvent, TimeSlice};
+
+/// Per-attempt handle passed to context-aware loaders
+///
+/// A new context is created for every attempt, including retries and hedged attempts.
+/// All attempts of one load share the same cancellation token.
+#[derive(Debug, Clone)]
+pub struct LoadContext {
+    name: Option<String>,
+    attempt: u32,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    token: CancellationToken,
+    events: Events,
+    time_slice: Duration,
+}
+
+impl LoadContext {
+    /// Creates the context shared by the attempts of a load.
+    pub(crate) fn new(
+        name: Option<String>,
+        timeout: Option<Duration>,
+        token: CancellationToken,
+        events: Events,
+        time_slice: Option<&TimeSlice>,
+    ) -> Self {
+        Self {
+            name,
+            attempt: 0,
+            timeout,
+            deadline: None,
+            token,
+            events,
+            time_slice: time_slice.map_or(crate::DEFAULT_TIME_SLICE, TimeSlice::slice),
+        }
+    }
+
+    /// Creates the context of attempt number `attempt`, starting now.
+    pub(crate) fn for_attempt(&self, attempt: u32) -> Self {
+        Self {
+            attempt,
+            deadline: self.timeout.map(|timeout| Instant::now() + timeout),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the name of the preloader, if one was set through the builder.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the number of this attempt, starting at 1 and increasing with every retry.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Returns the instant at which the configured timeout stops this attempt, if a timeout is configured.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Returns the time left until [`deadline`](Self::deadline), if a timeout is configured.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Reports the progress of the load to subscribers of [`Preloader::events`](crate::Preloader::events).
+    ///
+    /// # Parameters
+    ///
+    /// - `progress`: Fraction of the load that is done, clamped to `0.0..=1.0`
+    pub fn report_progress(&self, progress: f32) {
+        self.events.emit(PreloaderEvent::ProgressUpdated {
+            progress: progress.clamp(0.0, 1.0),
+        });
+    }
+
+    /// Returns a budget yielding to the runtime once the time slice of the preloader is used up.
+    ///
+    /// Call [`Budget::maybe_yield`] inside long CPU-bound loops. The slice is set with
+    /// [`PreloaderBuilder::time_slice`](crate::PreloaderBuilder::time_slice), whose warning limit is checked on
+    /// every poll of the load, so the budget itself does not report overruns.
+    pub fn budget(&self) -> Budget {
+        Budget::new(TimeSlice::new(self.time_slice))
+    }
+
+    /// Returns the token that is cancelled when [`Preloader::cancel`](crate::Preloader::cancel) is called.
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Checks if the load has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Waits until the load is cancelled, for use in `tokio::select!`.
+    pub async fn cancelled(&self) {
+        self.token.cancelled().await
+    }
+}
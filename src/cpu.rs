@@ -0,0 +1,83 @@
+//! CPU-bound loading module
+//!
+//! This module provides `load_cpu()`, which runs CPU-heavy loaders such as parsing or index building on a rayon
+//! thread pool and bridges the result back to the preloader, so the work does not occupy tokio's blocking threads.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+};
+
+use rayon::ThreadPool;
+use tokio::sync::oneshot;
+
+use crate::{preloader::panicked, LoadHandle, Preloader, PreloaderError};
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Starts loading by running `f` on the rayon global thread pool.
+    ///
+    /// Like [`load`](Self::load), a call made while loading was already started is handled by the configured
+    /// [`LoadPolicy`](crate::LoadPolicy).
+    /// A panic in `f` is caught and reported as `PreloaderError::Panicked`.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: The CPU-bound function producing the data
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let primes = Preloader::new();
+    ///     primes
+    ///         .load_cpu(|| (2..10_000u32).filter(|n| (2..*n).all(|d| n % d != 0)).count())
+    ///         .await;
+    ///     assert_eq!(primes.get().await.unwrap(), &1229);
+    /// }
+    /// ```
+    pub async fn load_cpu(&self, f: impl FnOnce() -> T + Send + 'static) -> LoadHandle {
+        self.load_rayon(None, f).await
+    }
+
+    /// Starts loading by running `f` on `pool` instead of the rayon global thread pool.
+    ///
+    /// See [`load_cpu`](Self::load_cpu).
+    ///
+    /// # Parameters
+    ///
+    /// - `pool`: The rayon thread pool to run `f` on
+    /// - `f`: The CPU-bound function producing the data
+    pub async fn load_cpu_on(
+        &self,
+        pool: &Arc<ThreadPool>,
+        f: impl FnOnce() -> T + Send + 'static,
+    ) -> LoadHandle {
+        self.load_rayon(Some(Arc::clone(pool)), f).await
+    }
+
+    /// Starts a loading task waiting for `f` to run on `pool`, or the global pool if `None`.
+    async fn load_rayon(
+        &self,
+        pool: Option<Arc<ThreadPool>>,
+        f: impl FnOnce() -> T + Send + 'static,
+    ) -> LoadHandle {
+        self.load_result(async move {
+            let (tx, rx) = oneshot::channel();
+            let job = move || {
+                _ = tx.send(panic::catch_unwind(AssertUnwindSafe(f)));
+            };
+            match pool {
+                Some(pool) => pool.spawn(job),
+                None => rayon::spawn(job),
+            }
+            rx.await
+                .map_err(|_| PreloaderError::Cancelled)?
+                .map_err(panicked)
+        })
+        .await
+    }
+}
@@ -0,0 +1,149 @@
+//! Database warmup module
+//!
+//! This module provides ready-made loaders built on sqlx, so the database layer can be warmed up during boot
+//! alongside other preloaders: [`Preloader::connect`] opens a connection pool and
+//! [`Preloader::fetch_all`] / [`Preloader::fetch_one`] load the rows of a query into typed values.
+
+use std::future::Future;
+
+use sqlx::{pool::PoolOptions, Database, Executor, FromRow, IntoArguments, Pool};
+
+use crate::{Preloader, PreloaderError};
+
+/// Converts a sqlx error into a load failure.
+fn failed(error: sqlx::Error) -> PreloaderError {
+    PreloaderError::Failed(error.to_string())
+}
+
+impl<DB: Database> Preloader<Pool<DB>> {
+    /// Creates a new `Preloader` and immediately starts opening a connection pool to `url`.
+    ///
+    /// The pool is opened with default options and at least one connection is established,
+    /// so a loaded preloader means the database is reachable.
+    /// Connection errors make the load fail with `PreloaderError::Failed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `url`: The database URL, such as `postgres://user@localhost/app`
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use sqlx::SqlitePool;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let pool: Preloader<SqlitePool> = Preloader::connect("sqlite::memory:");
+    ///     let users: Preloader<Vec<(i64, String)>> = Preloader::fetch_all(
+    ///         pool.get_cloned().await.unwrap(),
+    ///         "SELECT 1, 'admin'",
+    ///     );
+    ///     assert_eq!(users.get().await.unwrap()[0].1, "admin");
+    /// }
+    /// ```
+    pub fn connect(url: impl Into<String>) -> Self {
+        Self::connect_with(PoolOptions::new(), url)
+    }
+
+    /// Creates a new `Preloader` and immediately starts opening a connection pool to `url` with `options`.
+    ///
+    /// See [`connect`](Self::connect).
+    ///
+    /// # Parameters
+    ///
+    /// - `options`: Pool settings such as the maximum number of connections
+    /// - `url`: The database URL
+    pub fn connect_with(options: PoolOptions<DB>, url: impl Into<String>) -> Self {
+        let url = url.into();
+        let mut preloader = Self::new();
+        preloader.start_loader(async move { options.connect(&url).await.map_err(failed) });
+        preloader
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Creates a new `Preloader` and immediately starts running `query` against `pool`.
+    ///
+    /// Use this for queries that need bind parameters or custom decoding;
+    /// plain queries can use [`fetch_all`](Self::fetch_all) and [`fetch_one`](Self::fetch_one).
+    /// Errors returned by `query` make the load fail with `PreloaderError::Failed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `pool`: The connection pool to query
+    /// - `query`: Function creating the asynchronous query from the pool
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn from_query<DB, F, Fut>(pool: Pool<DB>, query: F) -> Self
+    where
+        DB: Database,
+        F: FnOnce(Pool<DB>) -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>> + Send + 'static,
+    {
+        let future = query(pool);
+        let mut preloader = Self::new();
+        preloader.start_loader(async move { future.await.map_err(failed) });
+        preloader
+    }
+
+    /// Creates a new `Preloader` and immediately starts loading the first row returned by `sql`.
+    ///
+    /// Fails with `PreloaderError::Failed` if the query returns no rows.
+    ///
+    /// # Parameters
+    ///
+    /// - `pool`: The connection pool to query
+    /// - `sql`: The query, without bind parameters
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn fetch_one<DB>(pool: Pool<DB>, sql: impl Into<String>) -> Self
+    where
+        DB: Database,
+        T: for<'r> FromRow<'r, DB::Row> + Unpin,
+        for<'q> DB::Arguments<'q>: IntoArguments<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let sql = sql.into();
+        Self::from_query(pool, move |pool| async move {
+            sqlx::query_as::<DB, T>(&sql).fetch_one(&pool).await
+        })
+    }
+}
+
+impl<T: Send + 'static> Preloader<Vec<T>> {
+    /// Creates a new `Preloader` and immediately starts loading all rows returned by `sql`.
+    ///
+    /// Each row is decoded into `T` through [`FromRow`], which tuples implement
+    /// and structs can derive with sqlx's `macros` feature.
+    ///
+    /// # Parameters
+    ///
+    /// - `pool`: The connection pool to query
+    /// - `sql`: The query, without bind parameters
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn fetch_all<DB>(pool: Pool<DB>, sql: impl Into<String>) -> Self
+    where
+        DB: Database,
+        T: for<'r> FromRow<'r, DB::Row> + Unpin,
+        for<'q> DB::Arguments<'q>: IntoArguments<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let sql = sql.into();
+        Self::from_query(pool, move |pool| async move {
+            sqlx::query_as::<DB, T>(&sql).fetch_all(&pool).await
+        })
+    }
+}
@@ -0,0 +1,83 @@
+//! Deadline module
+//!
+//! This module provides caller deadlines: a request handler with a fixed budget runs inside [`with_deadline`],
+//! and every `get()` awaited within it fails with `PreloaderError::DeadlineExceeded` once the budget is spent,
+//! instead of waiting out a slow load.
+
+use std::future::Future;
+
+use tokio::time::Instant;
+
+use crate::{Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+tokio::task_local! {
+    static DEADLINE: Instant;
+}
+
+/// Runs `future` with `deadline` as the deadline of every `get()` awaited within it.
+///
+/// A nested call can only shorten the deadline, never extend it.
+/// Values that are already loaded are returned even after the deadline has passed.
+///
+/// # Parameters
+///
+/// - `deadline`: The instant after which waiting preloaders give up
+/// - `future`: The work to run, usually a request handler
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{with_deadline, Preloader, PreloaderError};
+/// use std::time::Duration;
+/// use tokio::time::{sleep, Instant};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let slow = Preloader::spawn(async {
+///         sleep(Duration::from_secs(10)).await;
+///         "data"
+///     });
+///
+///     let deadline = Instant::now() + Duration::from_millis(200);
+///     let result = with_deadline(deadline, async { slow.get().await.copied() }).await;
+///     assert!(matches!(result, Err(PreloaderError::DeadlineExceeded)));
+/// }
+/// ```
+pub async fn with_deadline<F: Future>(deadline: Instant, future: F) -> F::Output {
+    let deadline = current_deadline().map_or(deadline, |outer| outer.min(deadline));
+    DEADLINE.scope(deadline, future).await
+}
+
+/// Returns the deadline set by the innermost enclosing [`with_deadline`], if any.
+pub fn current_deadline() -> Option<Instant> {
+    DEADLINE.try_with(|deadline| *deadline).ok()
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Retrieves the loaded data, giving up at `deadline`.
+    ///
+    /// Behaves like `get()` inside [`with_deadline`]; an enclosing deadline that is earlier still applies.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the data was loaded before the deadline
+    /// - `Err(PreloaderError::DeadlineExceeded)`: If the deadline passed while waiting
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    pub async fn get_until(&self, deadline: Instant) -> Result<&T> {
+        with_deadline(deadline, self.get()).await
+    }
+}
+
+/// Waits for `future`, failing with `DeadlineExceeded` once the current deadline passes.
+///
+/// `future` is polled before the deadline is checked, so results that are already available are returned.
+pub(crate) async fn bounded<T>(future: impl Future<Output = Result<T>>) -> Result<T> {
+    match current_deadline() {
+        Some(deadline) => tokio::time::timeout_at(deadline, future)
+            .await
+            .unwrap_or(Err(PreloaderError::DeadlineExceeded)),
+        None => future.await,
+    }
+}
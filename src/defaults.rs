@@ -0,0 +1,128 @@
+//! Default configuration module
+//!
+//! This module provides [`PreloaderConfig`], a configuration profile that can be installed as the process-wide
+//! default. Every preloader created afterwards starts from it, through `new()`, `with_loader()`, or `builder()`,
+//! so construction sites do not need to repeat the same builder calls. Builder calls still override it.
+
+use std::{sync::RwLock, time::Duration};
+
+use crate::{builder::Options, RetryPolicy, DEFAULT_HISTORY_LEN};
+
+/// Installed process-wide default, `None` for the built-in defaults
+static DEFAULT: RwLock<Option<PreloaderConfig>> = RwLock::new(None);
+
+/// Configuration profile applied to every new preloader once installed
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{Preloader, PreloaderConfig, PreloaderError, RetryPolicy};
+/// use std::time::Duration;
+/// use tokio::time::sleep;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     PreloaderConfig::new()
+///         .timeout(Duration::from_millis(50))
+///         .retry(RetryPolicy::exponential(3, Duration::from_millis(100)))
+///         .abort_on_drop(true)
+///         .install();
+///
+///     // Starts from the installed profile
+///     let users = Preloader::new();
+///     users.load(async { sleep(Duration::from_secs(1)).await; vec!["alice"] }).await;
+///     assert!(matches!(users.get().await, Err(PreloaderError::Timeout)));
+///
+///     // Builder calls override the profile
+///     let orders = Preloader::builder().timeout(Duration::from_secs(5)).build();
+///     orders.load(async { sleep(Duration::from_millis(100)).await; vec![1001] }).await;
+///     assert_eq!(*orders.get().await.unwrap(), [1001]);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreloaderConfig {
+    timeout: Option<Duration>,
+    retry: RetryPolicy,
+    abort_on_drop: bool,
+    instrument: bool,
+}
+
+impl PreloaderConfig {
+    /// Creates a profile with the built-in defaults: no timeout, no retries, no abort on drop,
+    /// and instrumentation enabled.
+    pub fn new() -> Self {
+        Self {
+            timeout: None,
+            retry: RetryPolicy::default(),
+            abort_on_drop: false,
+            instrument: true,
+        }
+    }
+
+    /// Sets the maximum duration of a single load attempt.
+    ///
+    /// See [`PreloaderBuilder::timeout`](crate::PreloaderBuilder::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the policy for retrying failed load attempts.
+    ///
+    /// See [`PreloaderBuilder::retry`](crate::PreloaderBuilder::retry).
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Sets whether loading tasks are aborted when their preloader is dropped.
+    ///
+    /// See [`PreloaderBuilder::abort_on_drop`](crate::PreloaderBuilder::abort_on_drop).
+    pub fn abort_on_drop(mut self, abort_on_drop: bool) -> Self {
+        self.abort_on_drop = abort_on_drop;
+        self
+    }
+
+    /// Sets whether loads are instrumented.
+    ///
+    /// See [`PreloaderBuilder::instrument`](crate::PreloaderBuilder::instrument).
+    pub fn instrument(mut self, instrument: bool) -> Self {
+        self.instrument = instrument;
+        self
+    }
+
+    /// Installs this profile as the process-wide default, replacing any previously installed one.
+    ///
+    /// Preloaders that already exist keep their configuration.
+    pub fn install(self) {
+        *DEFAULT.write().unwrap() = Some(self);
+    }
+
+    /// Restores the built-in defaults for preloaders created from now on.
+    pub fn uninstall() {
+        *DEFAULT.write().unwrap() = None;
+    }
+
+    /// Returns the installed process-wide default, or the built-in defaults if none is installed.
+    pub fn current() -> Self {
+        DEFAULT.read().unwrap().clone().unwrap_or_default()
+    }
+
+    /// Creates the options of a new preloader from this profile.
+    pub(crate) fn options(&self) -> Options {
+        Options {
+            timeout: self.timeout,
+            retry: self.retry,
+            abort_on_drop: self.abort_on_drop,
+            instrument: self.instrument,
+            history: DEFAULT_HISTORY_LEN,
+            ..Options::default()
+        }
+    }
+}
+
+impl Default for PreloaderConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,68 @@
+//! Deref module
+//!
+//! This module provides accessors for preloaders holding smart pointers, such as `Box<dyn Trait>` produced by
+//! a plugin loader. They return the pointee, so a `Preloader<Box<dyn DataSource + Send + Sync>>` hands out
+//! `&(dyn DataSource + Send + Sync)` instead of a reference to the box.
+
+use std::ops::Deref;
+
+use crate::{Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+impl<T> Preloader<T>
+where
+    T: Deref + Send + 'static,
+{
+    /// Retrieves the value the loaded pointer points to, waiting until loading is complete.
+    ///
+    /// Equivalent to `get().await.map(|value| &**value)`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T::Target)`: If data was successfully loaded
+    /// - `Err(PreloaderError)`: If an error occurred during loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// trait DataSource: Send + Sync {
+    ///     fn name(&self) -> &str;
+    /// }
+    ///
+    /// struct Postgres;
+    ///
+    /// impl DataSource for Postgres {
+    ///     fn name(&self) -> &str {
+    ///         "postgres"
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let source: Preloader<Box<dyn DataSource>> = Preloader::new();
+    ///     source.load(async { Box::new(Postgres) as Box<dyn DataSource> }).await;
+    ///
+    ///     let source: &dyn DataSource = source.get_deref().await.unwrap();
+    ///     assert_eq!(source.name(), "postgres");
+    /// }
+    /// ```
+    pub async fn get_deref(&self) -> Result<&T::Target> {
+        self.get().await.map(|value| &**value)
+    }
+
+    /// Attempts to retrieve the value the loaded pointer points to immediately.
+    ///
+    /// Equivalent to `try_get().map(|value| &**value)`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T::Target)`: If data is loaded
+    /// - `Err(PreloaderError)`: If data is not loaded yet or loading failed
+    pub fn try_get_deref(&self) -> Result<&T::Target> {
+        self.try_get().map(|value| &**value)
+    }
+}
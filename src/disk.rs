@@ -0,0 +1,463 @@
+//! Disk cache module
+//!
+//! This module provides the `DiskCache` struct, which persists loaded values as bincode files in a cache directory.
+//! Loads through a disk cache check for a stored file first, fall back to the loader, and write the value back
+//! once the loader succeeds.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    future::Future,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    panic,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use bincode::Options;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::task;
+
+#[cfg(feature = "encryption")]
+use crate::{encryption::Cipher, EncryptionKey};
+use crate::{LoadHandle, Preloader, PreloaderCache};
+
+/// Persistent cache storing loaded values as files in a directory
+///
+/// Every value is stored in its own file named after its key, together with the cache's [version](Self::with_version).
+/// Unreadable, corrupt, or outdated files are treated as missing and replaced by the next successful load. Writing back is best effort: if the file cannot be written,
+/// the loaded value is still used.
+///
+/// Cloning a disk cache is cheap; clones use the same directory.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{DiskCache, Preloader};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let dir = std::env::temp_dir().join("preloader-disk-cache-example");
+///     let cache = DiskCache::new(&dir);
+///
+///     let first = Preloader::new();
+///     cache.load(&first, "numbers", async { vec![1, 2, 3] }).await;
+///     assert_eq!(*first.get().await.unwrap(), [1, 2, 3]);
+///
+///     // The second load reads the file instead of running the loader
+///     let second: Preloader<Vec<i32>> = Preloader::new();
+///     cache.load(&second, "numbers", async { unreachable!() }).await;
+///     assert_eq!(*second.get().await.unwrap(), [1, 2, 3]);
+///     # std::fs::remove_dir_all(&dir).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: Arc<PathBuf>,
+    compression: Compression,
+    version: Arc<str>,
+    max_value_size: u64,
+    #[cfg(feature = "encryption")]
+    cipher: Option<Cipher>,
+}
+
+/// Default maximum size in bytes of a stored value, see [`DiskCache::with_max_value_size`]
+pub const DEFAULT_MAX_VALUE_SIZE: u64 = 1 << 30;
+
+/// Compression applied to the files of a [`DiskCache`]
+///
+/// Each compression uses its own file extension, so changing it treats previously stored values as missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Files are stored uncompressed, with the `.bin` extension
+    #[default]
+    None,
+    /// Files are gzip-compressed at the given level from 0 to 9, with the `.bin.gz` extension
+    ///
+    /// Requires the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    Gzip(u32),
+    /// Files are zstd-compressed at the given level from 1 to 22, with the `.bin.zst` extension
+    ///
+    /// Requires the `zstd` feature.
+    #[cfg(feature = "zstd")]
+    Zstd(i32),
+}
+
+impl Compression {
+    /// Returns the file extension of this compression.
+    fn extension(self) -> &'static str {
+        match self {
+            Compression::None => "bin",
+            #[cfg(feature = "gzip")]
+            Compression::Gzip(_) => "bin.gz",
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(_) => "bin.zst",
+        }
+    }
+}
+
+impl DiskCache {
+    /// Creates a cache storing its files in `dir`.
+    ///
+    /// The directory is created on the first write if it does not exist.
+    ///
+    /// # Parameters
+    ///
+    /// - `dir`: The cache directory
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Arc::new(dir.into()),
+            compression: Compression::None,
+            version: Arc::from(""),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+            #[cfg(feature = "encryption")]
+            cipher: None,
+        }
+    }
+
+    /// Sets the compression of the stored files.
+    ///
+    /// Clones share the directory but not the compression, so each preloader can use its own:
+    /// `disk.clone().with_compression(Compression::Zstd(3))`.
+    ///
+    /// # Parameters
+    ///
+    /// - `compression`: The compression applied when writing and expected when reading
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the maximum size in bytes of a stored value once decompressed, [`DEFAULT_MAX_VALUE_SIZE`] by default.
+    ///
+    /// Reading stops at this size, so a corrupt length prefix cannot allocate a huge buffer; larger values are
+    /// treated as missing. Uncompressed files are also bounded by their own size.
+    ///
+    /// # Parameters
+    ///
+    /// - `bytes`: The maximum size of a decoded value
+    pub fn with_max_value_size(mut self, bytes: u64) -> Self {
+        self.max_value_size = bytes;
+        self
+    }
+
+    /// Returns the maximum size in bytes of a stored value.
+    pub fn max_value_size(&self) -> u64 {
+        self.max_value_size
+    }
+
+    /// Encrypts the stored files with `key`.
+    ///
+    /// Encrypted files use the extension of the [`Compression`] followed by `.enc`, so plaintext files written
+    /// before are treated as missing. Files that cannot be decrypted with the key are treated as missing as well,
+    /// so rotating the key only costs one load per value. Requires the `encryption` feature.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key encrypting and authenticating every file
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{DiskCache, EncryptionKey, Preloader};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let dir = std::env::temp_dir().join("preloader-encrypted-example");
+    ///     let key = EncryptionKey::from_bytes([7; 32]);
+    ///     let cache = DiskCache::new(&dir).with_encryption(key);
+    ///
+    ///     let token = Preloader::new();
+    ///     cache.load(&token, "token", async { "secret".to_string() }).await;
+    ///     assert_eq!(token.get().await.unwrap(), "secret");
+    ///
+    ///     let stored = std::fs::read(cache.path("token")).unwrap();
+    ///     assert!(!stored.windows(6).any(|bytes| bytes == b"secret"));
+    ///     # std::fs::remove_dir_all(&dir).unwrap();
+    /// }
+    /// ```
+    #[cfg(feature = "encryption")]
+    pub fn with_encryption(mut self, key: EncryptionKey) -> Self {
+        self.cipher = Some(key.cipher());
+        self
+    }
+
+    /// Checks if the stored files are encrypted.
+    #[cfg(feature = "encryption")]
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// Returns the compression of the stored files.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// Tags stored values with a version, such as the application version or a schema hash.
+    ///
+    /// Stored values written with a different version are treated as missing, so the loader runs and replaces them.
+    /// Values are untagged (the empty version) by default.
+    ///
+    /// # Parameters
+    ///
+    /// - `version`: The version written with every value and required when reading
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Arc::from(version.into());
+        self
+    }
+
+    /// Returns the version stored values are tagged with.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path of the file storing `key`.
+    ///
+    /// Characters other than ASCII letters, digits, `-`, `_`, and `.` are percent-encoded,
+    /// and the extension depends on the [`Compression`] and on whether the files are encrypted.
+    pub fn path(&self, key: &str) -> PathBuf {
+        let extension = self.compression.extension();
+        let mut name = String::with_capacity(key.len() + extension.len() + 1);
+        for byte in key.bytes() {
+            match byte {
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' => {
+                    name.push(byte as char)
+                }
+                _ => name.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        name.push('.');
+        name.push_str(extension);
+        #[cfg(feature = "encryption")]
+        if self.cipher.is_some() {
+            name.push_str(".enc");
+        }
+        self.dir.join(name)
+    }
+
+    /// Starts loading `preloader`, reading the value of `key` from disk if it is stored.
+    ///
+    /// If no file is stored for `key`, `future` is awaited and its value is written back.
+    /// Returns the handle of [`Preloader::load`], which follows the preloader's [`LoadPolicy`](crate::LoadPolicy) if
+    /// loading was already started.
+    ///
+    /// # Parameters
+    ///
+    /// - `preloader`: The preloader to load
+    /// - `key`: The key the value is stored under
+    /// - `future`: The asynchronous task to execute if the value is not stored
+    pub async fn load<T>(
+        &self,
+        preloader: &Preloader<T>,
+        key: impl Into<String>,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> LoadHandle
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+    {
+        preloader
+            .load(self.clone().fetch(key.into(), move || future))
+            .await
+    }
+
+    /// Removes the stored value of `key`, if any.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)`: If a file was removed
+    /// - `Ok(false)`: If no value was stored
+    /// - `Err(io::Error)`: If the file could not be removed
+    pub async fn remove(&self, key: &str) -> io::Result<bool> {
+        match tokio::fs::remove_file(self.path(key)).await {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Reads the value of `key` from disk, or awaits the future created by `loader` and writes its value back.
+    pub(crate) async fn fetch<T, F, Fut>(self, key: String, loader: F) -> T
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let path = self.path(&key);
+        let cache = self.clone();
+        let read = path.clone();
+        if let Ok(Some(value)) = task::spawn_blocking(move || cache.read_file(&read)).await {
+            return value;
+        }
+
+        let value = loader().await;
+        // The value is moved to the blocking thread for writing and handed back afterwards
+        match task::spawn_blocking(move || {
+            _ = self.write_file(&path, &value);
+            value
+        })
+        .await
+        {
+            Ok(value) => value,
+            Err(error) => panic::resume_unwind(error.into_panic()),
+        }
+    }
+
+    /// Reads and deserializes a stored value, returning `None` if it is missing, corrupt, or of another version.
+    fn read_file<T: DeserializeOwned>(&self, path: &Path) -> Option<T> {
+        #[cfg(feature = "encryption")]
+        if let Some(cipher) = &self.cipher {
+            let plaintext = cipher.open(file_name(path), &fs::read(path).ok()?)?;
+            return self.decompress(plaintext.as_slice(), plaintext.len() as u64);
+        }
+        let file = File::open(path).ok()?;
+        let size = file.metadata().ok()?.len();
+        self.decompress(BufReader::new(file), size)
+    }
+
+    /// Decompresses and deserializes a stored value.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: The stored contents
+    /// - `size`: Size of the stored contents, bounding the size of an uncompressed value
+    fn decompress<T: DeserializeOwned>(&self, reader: impl BufRead, size: u64) -> Option<T> {
+        let limit = self.max_value_size;
+        match self.compression {
+            Compression::None => self.decode(reader, size.min(limit)),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip(_) => self.decode(flate2::bufread::GzDecoder::new(reader), limit),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(_) => self.decode(zstd::Decoder::with_buffer(reader).ok()?, limit),
+        }
+    }
+
+    /// Deserializes the version tag and, if it matches, the value, reading at most `limit` bytes for the value.
+    fn decode<T: DeserializeOwned>(&self, mut reader: impl Read, limit: u64) -> Option<T> {
+        // The limit keeps a corrupt length prefix from allocating a huge tag
+        let version: String = bincode::options()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .with_limit(self.version.len() as u64 + 8)
+            .deserialize_from(&mut reader)
+            .ok()?;
+        if version != *self.version {
+            return None;
+        }
+        // Same encoding as `bincode::serialize_into`, but bounded like the tag
+        bincode::options()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .with_limit(limit)
+            .deserialize_from(reader)
+            .ok()
+    }
+
+    /// Serializes a value into a temporary file and moves it into place, so readers never see a partial file.
+    fn write_file<T: Serialize>(&self, path: &Path, value: &T) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // Unique per write, so concurrent writes of the same key never share a temporary file
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(format!(
+            ".{}-{}.tmp",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let temp = PathBuf::from(temp);
+
+        let result = (|| {
+            let file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&temp)?;
+            let mut writer = BufWriter::new(file);
+            #[cfg(feature = "encryption")]
+            if let Some(cipher) = &self.cipher {
+                // The plaintext only ever exists in memory
+                let mut plaintext = Vec::new();
+                self.compress(&mut plaintext, value)?;
+                let sealed = cipher
+                    .seal(file_name(path), &plaintext)
+                    .ok_or_else(|| io::Error::other("failed to encrypt the value"))?;
+                writer.write_all(&sealed)?;
+                writer.flush()?;
+                return fs::rename(&temp, path);
+            }
+            self.compress(&mut writer, value)?;
+            writer.flush()?;
+            fs::rename(&temp, path)
+        })();
+        if result.is_err() {
+            _ = fs::remove_file(&temp);
+        }
+        result
+    }
+
+    /// Serializes and compresses a value.
+    fn compress<T: Serialize>(&self, writer: &mut impl Write, value: &T) -> io::Result<()> {
+        match self.compression {
+            Compression::None => self.encode(writer, value),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip(level) => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(writer, flate2::Compression::new(level));
+                self.encode(&mut encoder, value)?;
+                encoder.finish().map(drop)
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(level) => {
+                let mut encoder = zstd::Encoder::new(writer, level)?;
+                self.encode(&mut encoder, value)?;
+                encoder.finish().map(drop)
+            }
+        }
+    }
+
+    /// Serializes the version tag followed by the value.
+    fn encode<T: Serialize>(&self, mut writer: impl Write, value: &T) -> io::Result<()> {
+        bincode::serialize_into(&mut writer, &*self.version).map_err(io::Error::other)?;
+        bincode::serialize_into(writer, value).map_err(io::Error::other)
+    }
+}
+
+/// Returns the file name of `path`, authenticated together with encrypted contents.
+#[cfg(feature = "encryption")]
+fn file_name(path: &Path) -> &[u8] {
+    path.file_name().map_or(&[], |name| name.as_encoded_bytes())
+}
+
+impl<K, V> PreloaderCache<K, V>
+where
+    K: Eq + std::hash::Hash + Clone + ToString + Send + Sync + 'static,
+    V: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    /// Persists the values of this cache in `disk`, using `key.to_string()` as the file key.
+    ///
+    /// Keys loaded after this call check `disk` first and write their values back after loading.
+    ///
+    /// # Parameters
+    ///
+    /// - `disk`: The disk cache storing the values
+    pub fn with_disk_cache(mut self, disk: DiskCache) -> Self {
+        let loader = self.loader;
+        self.loader = Arc::new(move |key: &K| {
+            let loader = Arc::clone(&loader);
+            let owned = key.clone();
+            Box::pin(disk.clone().fetch(key.to_string(), move || loader(&owned)))
+        });
+        self
+    }
+}
@@ -0,0 +1,234 @@
+//! Download module
+//!
+//! This module provides the `Download` preset for the classic "preload a model or asset bundle" case:
+//! a large HTTP body or file is streamed to a target path in chunks, progress is reported through
+//! [`Preloader::events`], an optional SHA-256 checksum is verified, and the preloader resolves to the final path.
+
+use std::{fmt, path::PathBuf, sync::Arc};
+
+use sha2::{Digest, Sha256};
+use tokio::{
+    fs::{self, File},
+    io::{AsyncReadExt, AsyncWriteExt},
+};
+
+use crate::{events::Events, Fetch, LoadHandle, Preloader, PreloaderError, PreloaderEvent};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Size of the chunks read from a file source
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Callback receiving the number of bytes written so far and the total size, if known
+type ProgressCallback = Arc<dyn Fn(u64, Option<u64>) + Send + Sync>;
+
+/// Where a download reads from
+#[derive(Debug, Clone)]
+enum Source {
+    Http(Fetch),
+    File(PathBuf),
+}
+
+/// Large download streamed to a file, loaded with [`Preloader::load_download`]
+///
+/// The data is first written to a temporary file next to the target, named `<target>.part`, and only
+/// renamed to the target once it is complete and its checksum matches, so the target never holds a partial
+/// or corrupt file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use preloader::{Download, Preloader};
+/// use std::path::PathBuf;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let model: Preloader<PathBuf> = Preloader::new();
+///     model
+///         .load_download(
+///             Download::http("https://example.com/model.bin", "/var/cache/model.bin")
+///                 .sha256("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")
+///                 .on_progress(|written, total| println!("{written} of {total:?} bytes")),
+///         )
+///         .await;
+///     println!("model stored at {:?}", model.get().await);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Download {
+    source: Source,
+    target: PathBuf,
+    sha256: Option<String>,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl Download {
+    /// Creates a download of the response body of `request`.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: A URL or a configured [`Fetch`]
+    /// - `target`: Path the body is stored at
+    pub fn http(request: impl Into<Fetch>, target: impl Into<PathBuf>) -> Self {
+        Self::with_source(Source::Http(request.into()), target.into())
+    }
+
+    /// Creates a copy of the file at `source`, for example from a network mount.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: The file to copy
+    /// - `target`: Path the copy is stored at
+    pub fn file(source: impl Into<PathBuf>, target: impl Into<PathBuf>) -> Self {
+        Self::with_source(Source::File(source.into()), target.into())
+    }
+
+    fn with_source(source: Source, target: PathBuf) -> Self {
+        Self {
+            source,
+            target,
+            sha256: None,
+            on_progress: None,
+        }
+    }
+
+    /// Sets the expected SHA-256 checksum, as a hexadecimal string.
+    ///
+    /// A download whose checksum differs fails with `PreloaderError::Failed`, and the target is left untouched.
+    pub fn sha256(mut self, checksum: impl Into<String>) -> Self {
+        self.sha256 = Some(checksum.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Sets a callback receiving the number of bytes written so far and the total size, if known,
+    /// after every chunk.
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(u64, Option<u64>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Runs the download.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(PathBuf)`: The target path, holding the complete and verified data
+    /// - `Err(PreloaderError::Failed)`: If reading, writing, or the checksum failed
+    async fn run(self, events: Events) -> Result<PathBuf> {
+        let partial = partial_path(&self.target);
+        let result = self.stream_to(&partial, &events).await;
+        if result.is_err() {
+            _ = fs::remove_file(&partial).await;
+            return result.map(|_| self.target);
+        }
+
+        fs::rename(&partial, &self.target).await.map_err(failed)?;
+        Ok(self.target)
+    }
+
+    /// Streams the source into `partial` and verifies the checksum.
+    async fn stream_to(&self, partial: &PathBuf, events: &Events) -> Result<()> {
+        let mut file = File::create(partial).await.map_err(failed)?;
+        let mut hasher = Sha256::new();
+        let mut written = 0u64;
+        let mut write = async |chunk: &[u8], total: Option<u64>| -> Result<()> {
+            file.write_all(chunk).await.map_err(failed)?;
+            hasher.update(chunk);
+            written += chunk.len() as u64;
+            if let Some(callback) = &self.on_progress {
+                callback(written, total);
+            }
+            if let Some(total) = total.filter(|&total| total > 0) {
+                events.emit(PreloaderEvent::ProgressUpdated {
+                    progress: (written as f64 / total as f64).min(1.0) as f32,
+                });
+            }
+            Ok(())
+        };
+
+        match &self.source {
+            Source::Http(request) => {
+                let mut response = request.clone().send().await?;
+                let total = response.content_length();
+                while let Some(chunk) = response
+                    .chunk()
+                    .await
+                    .map_err(|error| PreloaderError::Failed(error.to_string()))?
+                {
+                    write(&chunk, total).await?;
+                }
+            }
+            Source::File(source) => {
+                let mut source = File::open(source).await.map_err(failed)?;
+                let total = source.metadata().await.map_err(failed)?.len();
+                let mut buffer = vec![0; CHUNK_SIZE];
+                loop {
+                    let read = source.read(&mut buffer).await.map_err(failed)?;
+                    if read == 0 {
+                        break;
+                    }
+                    write(&buffer[..read], Some(total)).await?;
+                }
+            }
+        }
+
+        file.flush().await.map_err(failed)?;
+        file.sync_all().await.map_err(failed)?;
+
+        if let Some(expected) = &self.sha256 {
+            let actual: String = hasher
+                .finalize()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect();
+            if &actual != expected {
+                return Err(PreloaderError::Failed(format!(
+                    "checksum mismatch: expected {expected}, got {actual}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Download {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Download")
+            .field("source", &self.source)
+            .field("target", &self.target)
+            .field("sha256", &self.sha256)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Preloader<PathBuf> {
+    /// Starts loading by running `download`, resolving to the path of the stored file.
+    ///
+    /// While the total size is known, [`PreloaderEvent::ProgressUpdated`] events report the fraction written.
+    /// Like [`load`](Self::load), an overlapping call is handled by the configured [`LoadPolicy`](crate::LoadPolicy).
+    /// Read, write, and checksum errors make loading fail with `PreloaderError::Failed`.
+    /// To memory-map the result, pass the path to `Preloader::<Bytes>::load_mmap`.
+    ///
+    /// # Parameters
+    ///
+    /// - `download`: The download to run
+    pub async fn load_download(&self, download: Download) -> LoadHandle {
+        let events = self.event_feed().clone();
+        self.load_result(download.run(events)).await
+    }
+}
+
+/// Returns the path of the temporary file a download writes to.
+fn partial_path(target: &std::path::Path) -> PathBuf {
+    let mut partial = target.as_os_str().to_owned();
+    partial.push(".part");
+    PathBuf::from(partial)
+}
+
+/// Converts an I/O error into a load failure.
+fn failed(error: std::io::Error) -> PreloaderError {
+    PreloaderError::Failed(error.to_string())
+}
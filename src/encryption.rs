@@ -0,0 +1,101 @@
+//! Disk cache encryption module
+//!
+//! This module provides `EncryptionKey`, with which a `DiskCache` encrypts every stored file with AES-256-GCM, so
+//! cached secrets, tokens, or personal data are never written to disk in plaintext.
+
+use std::fmt;
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+
+/// Length of the random nonce written in front of every encrypted file
+const NONCE_LEN: usize = 12;
+
+/// 256-bit key encrypting the files of a [`DiskCache`](crate::DiskCache)
+///
+/// Files are sealed with AES-256-GCM under a fresh random nonce, and authenticated together with the name of the
+/// file, so a file that was tampered with, moved to another key, or encrypted with another key is treated as
+/// missing. The key itself is never printed by `Debug`.
+///
+/// Requires the `encryption` feature.
+#[derive(Clone)]
+pub struct EncryptionKey(Key<Aes256Gcm>);
+
+impl EncryptionKey {
+    /// Creates a key from 32 bytes, for example loaded from a secret store.
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes.into())
+    }
+
+    /// Creates a random key from the operating system's random number generator.
+    ///
+    /// Files encrypted with it can only be read back by the same process, unless the key is kept elsewhere.
+    pub fn generate() -> Self {
+        Self(Aes256Gcm::generate_key(OsRng))
+    }
+
+    /// Returns the cipher using this key.
+    pub(crate) fn cipher(&self) -> Cipher {
+        Cipher(Aes256Gcm::new(&self.0))
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptionKey(..)")
+    }
+}
+
+/// AES-256-GCM cipher of a disk cache
+#[derive(Clone)]
+pub(crate) struct Cipher(Aes256Gcm);
+
+impl Cipher {
+    /// Encrypts `plaintext`, authenticating it together with `name`.
+    ///
+    /// # Returns
+    ///
+    /// The random nonce followed by the ciphertext
+    pub(crate) fn seal(&self, name: &[u8], plaintext: &[u8]) -> Option<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .0
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: name,
+                },
+            )
+            .ok()?;
+        let mut sealed = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Some(sealed)
+    }
+
+    /// Decrypts the output of [`seal`](Self::seal), returning `None` if it was not sealed under `name` with this key.
+    pub(crate) fn open(&self, name: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+        if sealed.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        self.0
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: name,
+                },
+            )
+            .ok()
+    }
+}
+
+impl fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Cipher(..)")
+    }
+}
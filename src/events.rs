@@ -0,0 +1,161 @@
+//! Events module
+//!
+//! This module provides the `PreloaderEvent` enum and the `events()` stream of `Preloader` and `ArcPreloader`,
+//! a single typed feed of lifecycle changes for supervisors and UIs.
+
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use tokio::sync::broadcast;
+
+use crate::{ArcPreloader, Preloader, PreloaderError};
+
+/// Number of events buffered for a subscriber that falls behind
+const CAPACITY: usize = 64;
+
+/// Lifecycle event of a preloader, delivered by [`Preloader::events`]
+#[derive(Debug, Clone)]
+pub enum PreloaderEvent {
+    /// A load was started
+    Started,
+    /// The loader reported progress, from `0.0` to `1.0`
+    ProgressUpdated {
+        /// Reported fraction of the load that is done
+        progress: f32,
+    },
+    /// A stream loader received another item
+    ItemsLoaded {
+        /// Number of items received so far
+        count: u64,
+    },
+    /// A load finished and the value is available
+    Completed {
+        /// Time from the start of the load until it finished
+        duration: Duration,
+    },
+    /// A load failed
+    Failed {
+        /// The error that ended the load
+        error: PreloaderError,
+    },
+    /// The value was removed, for example from a [`PreloaderCache`](crate::PreloaderCache)
+    Evicted,
+    /// A reload replaced the value of an [`ArcPreloader`]
+    Refreshed {
+        /// Generation of the new value
+        generation: u64,
+    },
+    /// A load exceeded the budget of its [`Watchdog`](crate::Watchdog)
+    Stalled {
+        /// The exceeded budget
+        budget: Duration,
+        /// Whether the load is aborted and restarted
+        restarting: bool,
+    },
+}
+
+/// Broadcast channel of a preloader's events, created when the first subscriber arrives
+///
+/// Clones share the channel, so a loading task can report its outcome without borrowing the preloader.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Events(Arc<OnceLock<broadcast::Sender<PreloaderEvent>>>);
+
+impl Events {
+    /// Sends `event` to the current subscribers, if any.
+    pub(crate) fn emit(&self, event: PreloaderEvent) {
+        if let Some(sender) = self.0.get() {
+            _ = sender.send(event);
+        }
+    }
+
+    /// Sends `Completed` or `Failed` for the outcome of a load.
+    pub(crate) fn emit_outcome<T>(&self, result: &Result<T, PreloaderError>, duration: Duration) {
+        match result {
+            Ok(_) => self.emit(PreloaderEvent::Completed { duration }),
+            Err(error) => self.emit(PreloaderEvent::Failed {
+                error: error.clone(),
+            }),
+        }
+    }
+
+    /// Returns a stream of the events sent from now on.
+    pub(crate) fn subscribe(&self) -> BoxStream<'static, PreloaderEvent> {
+        let receiver = self
+            .0
+            .get_or_init(|| broadcast::channel(CAPACITY).0)
+            .subscribe();
+        stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Returns a stream of the lifecycle events that happen from now on.
+    ///
+    /// Events are delivered even if nobody calls `get()`. A subscriber that falls more than 64 events behind
+    /// skips the oldest ones. The stream ends when the preloader is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures::StreamExt;
+    /// use preloader::{Preloader, PreloaderEvent};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
+    ///     let mut events = preloader.events();
+    ///
+    ///     preloader.load(async { 42 }).await;
+    ///     assert!(matches!(events.next().await, Some(PreloaderEvent::Started)));
+    ///     assert!(matches!(events.next().await, Some(PreloaderEvent::Completed { .. })));
+    /// }
+    /// ```
+    pub fn events(&self) -> BoxStream<'static, PreloaderEvent> {
+        self.event_feed().subscribe()
+    }
+
+    /// Reports the progress of the running load to subscribers of [`events`](Self::events).
+    ///
+    /// # Parameters
+    ///
+    /// - `progress`: Fraction of the load that is done, clamped to `0.0..=1.0`
+    pub fn report_progress(&self, progress: f32) {
+        self.event_feed().emit(PreloaderEvent::ProgressUpdated {
+            progress: progress.clamp(0.0, 1.0),
+        });
+    }
+}
+
+impl<T: Send + Sync + 'static> ArcPreloader<T> {
+    /// Returns a stream of the lifecycle events that happen from now on, including reloads.
+    ///
+    /// See [`Preloader::events`].
+    pub fn events(&self) -> BoxStream<'static, PreloaderEvent> {
+        self.event_feed().subscribe()
+    }
+
+    /// Reports the progress of the running load to subscribers of [`events`](Self::events).
+    ///
+    /// See [`Preloader::report_progress`].
+    pub fn report_progress(&self, progress: f32) {
+        self.event_feed().emit(PreloaderEvent::ProgressUpdated {
+            progress: progress.clamp(0.0, 1.0),
+        });
+    }
+}
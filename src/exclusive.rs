@@ -0,0 +1,189 @@
+//! Exclusive-access preloader module
+//!
+//! This module provides `ExclusivePreloader`, which shares a preloader of a `Send` but not `Sync` value across
+//! threads. `Preloader<T>` hands out `&T` to every caller at once, so it is only `Sync` when `T` is; this wrapper
+//! instead serializes every access through a lock, or hands out owned clones.
+
+use std::{
+    fmt,
+    future::Future,
+    ops::Deref,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::{LoadHandle, Preloader, PreloaderError, PreloaderState};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Preloader of a `Send` value that may not be `Sync`, shareable across threads
+///
+/// The value is only reachable through a [`ValueGuard`] from [`lock`](Self::lock), which holds a lock for as long
+/// as it lives, or as an owned copy from [`get_cloned`](Self::get_cloned). No two threads ever use the value at the
+/// same time, so types like `Cell` or `RefCell` can be preloaded and shared.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::ExclusivePreloader;
+/// use std::{cell::Cell, sync::Arc};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let hits = Arc::new(ExclusivePreloader::new());
+///     hits.load(async { Cell::new(0u32) }).await;
+///
+///     let tasks: Vec<_> = (0..4)
+///         .map(|_| {
+///             let hits = Arc::clone(&hits);
+///             tokio::spawn(async move {
+///                 let hits = hits.lock().await.unwrap();
+///                 hits.set(hits.get() + 1);
+///             })
+///         })
+///         .collect();
+///     for task in tasks {
+///         task.await.unwrap();
+///     }
+///     assert_eq!(hits.get_cloned().await.unwrap().get(), 4);
+/// }
+/// ```
+pub struct ExclusivePreloader<T: Send + 'static> {
+    inner: Preloader<T>,
+    /// Lock held by every access to the value
+    access: Mutex<()>,
+}
+
+// SAFETY: The value is only reached through `ValueGuard`, which holds `access`, or cloned while holding it, so
+// it is never used from two threads at once. Everything else in `Preloader` is `Sync` regardless of `T`.
+unsafe impl<T: Send + 'static> Sync for ExclusivePreloader<T> {}
+
+/// Future borrowing the inner preloader, which is `Send` for the same reason `ExclusivePreloader` is `Sync`
+struct Shared<F>(F);
+
+// SAFETY: The future only shares the inner preloader across threads the way `ExclusivePreloader` does, and never
+// holds a reference to the value across an await point.
+unsafe impl<F> Send for Shared<F> {}
+
+impl<F: Future> Future for Shared<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: The wrapped future is structurally pinned and never moved out
+        unsafe { self.map_unchecked_mut(|shared| &mut shared.0) }.poll(cx)
+    }
+}
+
+impl<T: Send + 'static> ExclusivePreloader<T> {
+    /// Creates a new `ExclusivePreloader` in the `Idle` state.
+    pub fn new() -> Self {
+        Self::from(Preloader::new())
+    }
+
+    /// Starts an asynchronous task to load data.
+    ///
+    /// See [`Preloader::load`].
+    pub async fn load(&self, future: impl Future<Output = T> + Send + 'static) -> LoadHandle {
+        Shared(self.inner.load(future)).await
+    }
+
+    /// Waits until loading is complete, then locks the value.
+    ///
+    /// Other callers of `lock()` and `get_cloned()` wait until the guard is dropped. The guard is not `Send`
+    /// unless `T` is `Sync`, so the value stays on the thread that locked it.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ValueGuard)`: The guard giving access to the value
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    pub async fn lock(&self) -> Result<ValueGuard<'_, T>> {
+        let access = self.access.lock().await;
+        let value = Shared(self.inner.get()).await?;
+        Ok(ValueGuard {
+            value,
+            _access: access,
+        })
+    }
+
+    /// Waits until loading is complete and returns a clone of the value.
+    pub async fn get_cloned(&self) -> Result<T>
+    where
+        T: Clone,
+    {
+        self.lock().await.map(|value| value.clone())
+    }
+
+    /// Returns the current state of the preloader.
+    pub fn state(&self) -> PreloaderState {
+        self.inner.state()
+    }
+
+    /// Checks if the value has been loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.inner.is_loaded()
+    }
+
+    /// Returns the wrapped preloader.
+    pub fn into_inner(self) -> Preloader<T> {
+        self.inner
+    }
+}
+
+impl<T: Send + 'static> Default for ExclusivePreloader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shares a preloader configured through [`Preloader::builder`].
+impl<T: Send + 'static> From<Preloader<T>> for ExclusivePreloader<T> {
+    fn from(inner: Preloader<T>) -> Self {
+        Self {
+            inner,
+            access: Mutex::new(()),
+        }
+    }
+}
+
+impl<T: Send + 'static> fmt::Debug for ExclusivePreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExclusivePreloader")
+            .field("state", &self.state())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Access to the value of an [`ExclusivePreloader`], released when dropped
+///
+/// The guard cannot leave the thread that locked the value unless `T` is `Sync`:
+///
+/// ```compile_fail
+/// use preloader::ExclusivePreloader;
+/// use std::cell::Cell;
+///
+/// async fn leak(hits: &'static ExclusivePreloader<Cell<u32>>) {
+///     let guard = hits.lock().await.unwrap();
+///     std::thread::spawn(move || guard.set(1));
+/// }
+/// ```
+pub struct ValueGuard<'a, T> {
+    value: &'a T,
+    _access: MutexGuard<'a, ()>,
+}
+
+impl<T> Deref for ValueGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ValueGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.value, f)
+    }
+}
@@ -0,0 +1,217 @@
+//! C FFI module
+//!
+//! This module exposes a C API over preloaders of byte buffers, so native applications embedding a Rust core can
+//! drive preloading through FFI. Loads run on a process-wide Tokio runtime started on first use; the C loader
+//! callback writes the loaded bytes into a sink, and `preloader_try_get` hands them back without copying.
+//!
+//! ```c
+//! int load_catalog(void *user_data, PreloaderSink *sink) {
+//!     const char *catalog = fetch_catalog(user_data);
+//!     preloader_sink_write(sink, (const uint8_t *)catalog, strlen(catalog));
+//!     return 0;
+//! }
+//!
+//! Preloader *catalog = preloader_new();
+//! preloader_load_callback(catalog, load_catalog, NULL);
+//!
+//! const uint8_t *data;
+//! size_t len;
+//! if (preloader_try_get(catalog, &data, &len) == PRELOADER_LOADED) {
+//!     render(data, len);
+//! }
+//! preloader_free(catalog);
+//! ```
+//!
+//! The declarations are in `include/preloader.h`. Build the embedding library as a `staticlib` or `cdylib` with
+//! the `ffi` feature enabled.
+
+use std::{
+    ffi::{c_int, c_void},
+    ptr, slice,
+    sync::OnceLock,
+};
+
+use tokio::runtime::Runtime;
+
+use crate::{Preloader, PreloaderError};
+
+/// `preloader_try_get` status: the value is loaded and returned
+pub const PRELOADER_LOADED: c_int = 0;
+/// `preloader_try_get` status: the value is still loading, or loading has not been started
+pub const PRELOADER_PENDING: c_int = 1;
+/// `preloader_try_get` status: loading failed
+pub const PRELOADER_FAILED: c_int = -1;
+/// Status of any function given a null pointer
+pub const PRELOADER_INVALID: c_int = -2;
+
+/// Opaque preloader of a byte buffer, created by [`preloader_new`] and freed by [`preloader_free`]
+pub struct FfiPreloader(Preloader<Vec<u8>>);
+
+/// Opaque buffer receiving the bytes produced by a loader callback
+pub struct PreloaderSink(Vec<u8>);
+
+/// Loader callback: writes the value into `sink` and returns 0 on success, or any other value on failure
+pub type PreloaderLoadCallback =
+    unsafe extern "C" fn(user_data: *mut c_void, sink: *mut PreloaderSink) -> c_int;
+
+/// Callback and user data moved to the thread running the load
+struct Callback {
+    callback: PreloaderLoadCallback,
+    user_data: *mut c_void,
+}
+
+// SAFETY: `preloader_load_callback` requires the callback and its user data to be usable from any thread
+unsafe impl Send for Callback {}
+
+impl Callback {
+    fn call(self) -> Result<Vec<u8>, PreloaderError> {
+        let mut sink = PreloaderSink(Vec::new());
+        // SAFETY: Guaranteed by the caller of `preloader_load_callback`
+        match unsafe { (self.callback)(self.user_data, &mut sink) } {
+            0 => Ok(sink.0),
+            code => Err(PreloaderError::Failed(format!(
+                "loader callback returned {}",
+                code
+            ))),
+        }
+    }
+}
+
+/// Returns the runtime running the loads, starting it on first use.
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("preloader-ffi")
+            .enable_all()
+            .build()
+            .expect("failed to start the preloader runtime")
+    })
+}
+
+/// Creates a preloader in the `Idle` state.
+///
+/// The returned pointer must be released with [`preloader_free`].
+#[no_mangle]
+pub extern "C" fn preloader_new() -> *mut FfiPreloader {
+    let preloader = Preloader::builder()
+        .runtime(runtime().handle().clone())
+        .abort_on_drop(true)
+        .build();
+    Box::into_raw(Box::new(FfiPreloader(preloader)))
+}
+
+/// Starts loading by running `callback` on a blocking thread of the preloader runtime.
+///
+/// Does nothing if loading has already been started. A nonzero return value of `callback` fails the load.
+///
+/// # Returns
+///
+/// `PRELOADER_LOADED` (0) once the load is started or ignored, or `PRELOADER_INVALID` if `preloader` is null
+///
+/// # Safety
+///
+/// `preloader` must be null or returned by [`preloader_new`] and not freed. `callback` must be safe to call with
+/// `user_data` from another thread, once, at any time until the load finishes.
+#[no_mangle]
+pub unsafe extern "C" fn preloader_load_callback(
+    preloader: *const FfiPreloader,
+    callback: PreloaderLoadCallback,
+    user_data: *mut c_void,
+) -> c_int {
+    // SAFETY: Guaranteed by the caller
+    let Some(preloader) = (unsafe { preloader.as_ref() }) else {
+        return PRELOADER_INVALID;
+    };
+    let callback = Callback {
+        callback,
+        user_data,
+    };
+    runtime().block_on(preloader.0.load_result(async move {
+        tokio::task::spawn_blocking(move || callback.call())
+            .await
+            .map_err(|_| PreloaderError::Cancelled)?
+    }));
+    PRELOADER_LOADED
+}
+
+/// Appends `len` bytes from `data` to the value being loaded.
+///
+/// # Safety
+///
+/// `sink` must be the sink passed to the running loader callback, and `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn preloader_sink_write(
+    sink: *mut PreloaderSink,
+    data: *const u8,
+    len: usize,
+) -> c_int {
+    // SAFETY: Guaranteed by the caller
+    let Some(sink) = (unsafe { sink.as_mut() }) else {
+        return PRELOADER_INVALID;
+    };
+    if len > 0 {
+        if data.is_null() {
+            return PRELOADER_INVALID;
+        }
+        // SAFETY: Guaranteed by the caller
+        sink.0
+            .extend_from_slice(unsafe { slice::from_raw_parts(data, len) });
+    }
+    PRELOADER_LOADED
+}
+
+/// Retrieves the loaded bytes without waiting.
+///
+/// On success, `*data` and `*len` describe the loaded bytes, which stay valid and unchanged until
+/// [`preloader_free`]. Otherwise they are set to null and 0.
+///
+/// # Returns
+///
+/// `PRELOADER_LOADED`, `PRELOADER_PENDING`, `PRELOADER_FAILED`, or `PRELOADER_INVALID` if a pointer is null
+///
+/// # Safety
+///
+/// `preloader` must be null or returned by [`preloader_new`] and not freed, and `data` and `len` must be null or
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn preloader_try_get(
+    preloader: *const FfiPreloader,
+    data: *mut *const u8,
+    len: *mut usize,
+) -> c_int {
+    // SAFETY: Guaranteed by the caller
+    let (Some(preloader), false, false) =
+        (unsafe { preloader.as_ref() }, data.is_null(), len.is_null())
+    else {
+        return PRELOADER_INVALID;
+    };
+    let (value, status) = match preloader.0.try_get() {
+        Ok(value) => ((value.as_ptr(), value.len()), PRELOADER_LOADED),
+        Err(PreloaderError::NotLoaded | PreloaderError::Loading) => {
+            ((ptr::null(), 0), PRELOADER_PENDING)
+        }
+        Err(_) => ((ptr::null(), 0), PRELOADER_FAILED),
+    };
+    // SAFETY: Guaranteed by the caller
+    unsafe {
+        *data = value.0;
+        *len = value.1;
+    }
+    status
+}
+
+/// Frees a preloader, aborting its load if it is still running.
+///
+/// # Safety
+///
+/// `preloader` must be null or returned by [`preloader_new`] and not freed yet. Bytes returned by
+/// [`preloader_try_get`] must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn preloader_free(preloader: *mut FfiPreloader) {
+    if !preloader.is_null() {
+        // SAFETY: Guaranteed by the caller
+        drop(unsafe { Box::from_raw(preloader) });
+    }
+}
@@ -0,0 +1,241 @@
+//! File loader module
+//!
+//! This module provides ready-made loaders that read a file, either as raw bytes or parsed by a function
+//! such as `serde_json::from_slice`. With the `watch` feature, an [`ArcPreloader`] can also reload the file
+//! automatically whenever it changes on disk.
+
+use std::{fmt::Display, path::PathBuf, sync::Arc};
+#[cfg(feature = "watch")]
+use std::{io, time::Duration};
+
+#[cfg(feature = "watch")]
+use notify::{event::ModifyKind, Event, EventKind, RecursiveMode, Watcher};
+#[cfg(feature = "watch")]
+use tokio::sync::mpsc;
+
+use crate::{ArcPreloader, Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Reads the file at `path` and parses its contents with `parse`.
+pub(crate) async fn read_parsed<T>(
+    path: PathBuf,
+    parse: impl FnOnce(&[u8]) -> std::result::Result<T, String>,
+) -> Result<T> {
+    let contents = tokio::fs::read(&path)
+        .await
+        .map_err(|error| PreloaderError::Failed(format!("{}: {}", path.display(), error)))?;
+    parse(&contents)
+        .map_err(|error| PreloaderError::Failed(format!("{}: {}", path.display(), error)))
+}
+
+impl Preloader<Vec<u8>> {
+    /// Creates a new `Preloader` and immediately starts reading the contents of the file at `path`.
+    ///
+    /// If the file cannot be read, loading fails with `PreloaderError::Failed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn from_file(path: impl Into<PathBuf>) -> Self {
+        Self::from_file_de(path, |contents| {
+            Ok::<_, std::convert::Infallible>(contents.to_vec())
+        })
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Creates a new `Preloader` and immediately starts reading the file at `path` and parsing it with `parse`.
+    ///
+    /// Any deserializer taking a byte slice works as `parse`, such as `|bytes| serde_json::from_slice(bytes)`
+    /// or `|bytes| toml::from_str(std::str::from_utf8(bytes)?)`.
+    /// If the file cannot be read or parsed, loading fails with `PreloaderError::Failed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The file to read
+    /// - `parse`: Function parsing the file contents
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::collections::HashMap;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let path = std::env::temp_dir().join("preloader-from-file-example.json");
+    ///     std::fs::write(&path, r#"{"port": 8080}"#).unwrap();
+    ///
+    ///     let config: Preloader<HashMap<String, u16>> =
+    ///         Preloader::from_file_de(&path, |bytes| serde_json::from_slice(bytes));
+    ///     assert_eq!(config.get().await.unwrap()["port"], 8080);
+    ///     # std::fs::remove_file(&path).unwrap();
+    /// }
+    /// ```
+    pub fn from_file_de<F, E>(path: impl Into<PathBuf>, parse: F) -> Self
+    where
+        F: FnOnce(&[u8]) -> std::result::Result<T, E> + Send + 'static,
+        E: Display,
+    {
+        let mut preloader = Self::new();
+        preloader.start_loader(read_parsed(path.into(), |contents| {
+            parse(contents).map_err(|error| error.to_string())
+        }));
+        preloader
+    }
+}
+
+impl ArcPreloader<Vec<u8>> {
+    /// Creates a new `ArcPreloader` and immediately starts reading the contents of the file at `path`.
+    ///
+    /// See [`Preloader::from_file`].
+    pub fn from_file(path: impl Into<PathBuf>) -> Self {
+        Self::from_file_de(path, |contents| {
+            Ok::<_, std::convert::Infallible>(contents.to_vec())
+        })
+    }
+}
+
+impl<T: Send + Sync + 'static> ArcPreloader<T> {
+    /// Creates a new `ArcPreloader` and immediately starts reading the file at `path` and parsing it with `parse`.
+    ///
+    /// See [`Preloader::from_file_de`].
+    pub fn from_file_de<F, E>(path: impl Into<PathBuf>, parse: F) -> Self
+    where
+        F: FnOnce(&[u8]) -> std::result::Result<T, E> + Send + 'static,
+        E: Display,
+    {
+        Self::from_inner(Preloader::from_file_de(path, move |contents| {
+            parse(contents).map(Arc::new)
+        }))
+    }
+}
+
+#[cfg(feature = "watch")]
+impl ArcPreloader<Vec<u8>> {
+    /// Creates a new `ArcPreloader` that reads the file at `path` and reloads it whenever it changes.
+    ///
+    /// See [`ArcPreloader::watch_file_de`].
+    pub fn watch_file(path: impl Into<PathBuf>) -> io::Result<Arc<Self>> {
+        Self::watch_file_de(path, |contents| {
+            Ok::<_, std::convert::Infallible>(contents.to_vec())
+        })
+    }
+}
+
+#[cfg(feature = "watch")]
+impl<T: Send + Sync + 'static> ArcPreloader<T> {
+    /// Creates a new `ArcPreloader` that reads and parses the file at `path`, and reloads it whenever it changes.
+    ///
+    /// The directory containing the file is watched, so files replaced by editors or deployment tools
+    /// are picked up as well. Bursts of changes are coalesced into a single reload.
+    ///
+    /// - While the value is loaded, a change replaces it once the new contents have been parsed;
+    ///   if they cannot be read or parsed, the previous value is kept.
+    /// - After a failed load, a change starts loading again.
+    ///
+    /// Watching stops when the returned preloader is dropped.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The file to read and watch
+    /// - `parse`: Function parsing the file contents, called for every reload
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Arc<ArcPreloader<T>>)`: The preloader, already loading
+    /// - `Err(io::Error)`: If the file's directory cannot be watched
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use preloader::ArcPreloader;
+    /// use std::{collections::HashMap, sync::Arc};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let flags: Arc<ArcPreloader<HashMap<String, bool>>> =
+    ///         ArcPreloader::watch_file_de("flags.json", |bytes| serde_json::from_slice(bytes)).unwrap();
+    ///     // Always the latest valid contents of flags.json
+    ///     let current = flags.get().await.unwrap();
+    /// }
+    /// ```
+    pub fn watch_file_de<F, E>(path: impl Into<PathBuf>, parse: F) -> io::Result<Arc<Self>>
+    where
+        F: Fn(&[u8]) -> std::result::Result<T, E> + Send + Sync + 'static,
+        E: Display,
+    {
+        let path: PathBuf = path.into();
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let name = path.file_name().map(ToOwned::to_owned);
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            let changed = match event.kind {
+                EventKind::Create(_) => true,
+                EventKind::Modify(kind) => !matches!(kind, ModifyKind::Metadata(_)),
+                _ => false,
+            };
+            if changed
+                && event
+                    .paths
+                    .iter()
+                    .any(|changed| changed.file_name() == name.as_deref())
+            {
+                _ = tx.send(());
+            }
+        })
+        .map_err(io::Error::other)?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(io::Error::other)?;
+
+        let parse = Arc::new(parse);
+        let load = move || {
+            let parse = Arc::clone(&parse);
+            read_parsed(path.clone(), move |contents| {
+                parse(contents)
+                    .map(Arc::new)
+                    .map_err(|error| error.to_string())
+            })
+        };
+
+        let mut inner = Preloader::new();
+        inner.start_loader(load());
+        let preloader = Arc::new(Self::from_inner(inner));
+
+        let weak = Arc::downgrade(&preloader);
+        let task = tokio::spawn(async move {
+            // The watcher stops when this task is aborted
+            let _watcher = watcher;
+            while rx.recv().await.is_some() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                while rx.try_recv().is_ok() {}
+
+                let Some(preloader) = weak.upgrade() else {
+                    return;
+                };
+                _ = preloader.reload_result(load()).await;
+            }
+        });
+        preloader.attach(task.abort_handle());
+        Ok(preloader)
+    }
+}
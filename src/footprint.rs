@@ -0,0 +1,232 @@
+//! Memory footprint module
+//!
+//! This module provides the `MemoryFootprint` trait, estimating how many bytes a loaded value keeps resident, so
+//! preloaders and caches can report their approximate memory use and caches can bound it with a memory budget.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    mem::{size_of, size_of_val},
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{ArcPreloader, Preloader};
+
+/// Approximate number of bytes a value keeps resident
+///
+/// The estimate is the inline size of the value plus the heap memory it owns. Collections count their capacity
+/// rather than their length, and allocator overhead is ignored. A value behind an `Arc` or `Rc` is counted in full
+/// by every handle, so shared data may be counted more than once.
+///
+/// Types without heap data can rely on the default implementation, which only counts their inline size:
+///
+/// ```rust
+/// use preloader::MemoryFootprint;
+///
+/// struct Limits {
+///     max_users: u32,
+///     max_jobs: u32,
+/// }
+///
+/// impl MemoryFootprint for Limits {}
+///
+/// assert_eq!(Limits { max_users: 10, max_jobs: 2 }.memory_footprint(), 8);
+/// ```
+///
+/// Types owning heap data add the heap size of their fields:
+///
+/// ```rust
+/// use preloader::MemoryFootprint;
+///
+/// struct Catalog {
+///     name: String,
+///     items: Vec<String>,
+/// }
+///
+/// impl MemoryFootprint for Catalog {
+///     fn heap_size(&self) -> usize {
+///         self.name.heap_size() + self.items.heap_size()
+///     }
+/// }
+/// ```
+pub trait MemoryFootprint {
+    /// Returns the number of bytes the value owns on the heap, excluding its inline size.
+    fn heap_size(&self) -> usize {
+        0
+    }
+
+    /// Returns the approximate number of bytes the value keeps resident, inline and on the heap.
+    fn memory_footprint(&self) -> usize {
+        size_of_val(self) + self.heap_size()
+    }
+}
+
+macro_rules! inline_only {
+    ($($ty:ty),* $(,)?) => {
+        $(impl MemoryFootprint for $ty {})*
+    };
+}
+
+inline_only!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    Duration,
+    Instant,
+    SystemTime,
+);
+
+impl MemoryFootprint for str {}
+
+impl MemoryFootprint for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for [T] {
+    fn heap_size(&self) -> usize {
+        self.iter().map(T::heap_size).sum()
+    }
+}
+
+impl<T: MemoryFootprint, const N: usize> MemoryFootprint for [T; N] {
+    fn heap_size(&self) -> usize {
+        self.as_slice().heap_size()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.as_slice().heap_size()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for VecDeque<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, T::heap_size)
+    }
+}
+
+impl<T: MemoryFootprint + ?Sized> MemoryFootprint for Box<T> {
+    fn heap_size(&self) -> usize {
+        (**self).memory_footprint()
+    }
+}
+
+impl<T: MemoryFootprint + ?Sized> MemoryFootprint for Arc<T> {
+    fn heap_size(&self) -> usize {
+        // The strong and weak counts are stored next to the value
+        2 * size_of::<usize>() + (**self).memory_footprint()
+    }
+}
+
+impl<T: MemoryFootprint + ?Sized> MemoryFootprint for Rc<T> {
+    fn heap_size(&self) -> usize {
+        2 * size_of::<usize>() + (**self).memory_footprint()
+    }
+}
+
+impl<K: MemoryFootprint, V: MemoryFootprint, S> MemoryFootprint for HashMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(key, value)| key.heap_size() + value.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl<T: MemoryFootprint, S> MemoryFootprint for HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: MemoryFootprint, V: MemoryFootprint> MemoryFootprint for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        self.iter()
+            .map(|(key, value)| key.memory_footprint() + value.memory_footprint())
+            .sum()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for BTreeSet<T> {
+    fn heap_size(&self) -> usize {
+        self.iter().map(T::memory_footprint).sum()
+    }
+}
+
+macro_rules! tuple {
+    ($($name:ident),+) => {
+        impl<$($name: MemoryFootprint),+> MemoryFootprint for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn heap_size(&self) -> usize {
+                let ($($name,)+) = self;
+                0 $(+ $name.heap_size())+
+            }
+        }
+    };
+}
+
+tuple!(A);
+tuple!(A, B);
+tuple!(A, B, C);
+tuple!(A, B, C, D);
+
+#[cfg(feature = "bytes")]
+impl MemoryFootprint for bytes::Bytes {
+    fn heap_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: MemoryFootprint + Send + 'static> Preloader<T> {
+    /// Returns the approximate number of bytes kept resident by the loaded value, or `None` if no value is loaded.
+    ///
+    /// An expired value still counts, as it stays in memory until it is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    ///
+    /// let names = Preloader::ready(vec![String::from("alice")]);
+    /// assert!(names.memory_footprint().unwrap() >= 5);
+    /// ```
+    pub fn memory_footprint(&self) -> Option<usize> {
+        self.try_get_stale()
+            .map(|(value, _)| value.memory_footprint())
+    }
+}
+
+impl<T: MemoryFootprint + Send + Sync + 'static> ArcPreloader<T> {
+    /// Returns the approximate number of bytes kept resident by the current value, or `None` if no value is loaded.
+    ///
+    /// See [`Preloader::memory_footprint`]. The `Arc` holding the value is not counted.
+    pub fn memory_footprint(&self) -> Option<usize> {
+        self.try_get_stale()
+            .map(|(value, _)| T::memory_footprint(&value))
+    }
+}
@@ -0,0 +1,349 @@
+//! Preloader graph module
+//!
+//! This module provides `PreloaderGraph` for loading preloaders that depend on each other.
+//! Dependencies are declared by name, validated when the graph is built, and loaders receive the loaded
+//! values of their dependencies as inputs.
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use futures::future::{self, BoxFuture};
+
+use crate::{group::Member, GroupError, Preloader};
+
+/// Error returned when a [`GraphBuilder`] describes an invalid graph
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GraphError {
+    /// Two nodes were registered under the same name
+    #[error("duplicate preloader name `{0}`")]
+    DuplicateName(String),
+
+    /// A node depends on a name that was never registered
+    #[error("preloader `{node}` depends on unknown preloader `{dependency}`")]
+    UnknownDependency {
+        /// Name of the dependent node
+        node: String,
+        /// Name of the missing dependency
+        dependency: String,
+    },
+
+    /// The dependencies form a cycle
+    ///
+    /// The nodes are listed with each one depending on the next, and the first node repeated at the end.
+    #[error("dependency cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// Loaded values of a node's dependencies
+pub struct Inputs {
+    values: HashMap<String, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Inputs {
+    /// Returns the loaded value of the dependency registered under `name`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of a declared dependency
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` was not declared as a dependency or its value is not of type `U`.
+    pub fn get<U: Send + 'static>(&self, name: &str) -> &U {
+        let value = self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("`{}` is not a declared dependency", name));
+        let preloader = value
+            .downcast_ref::<Preloader<U>>()
+            .unwrap_or_else(|| panic!("dependency `{}` has a different type", name));
+        preloader
+            .try_get()
+            .expect("dependencies are loaded before their dependents")
+    }
+}
+
+/// Deferred `load()` call of a node, receiving its inputs and dependencies
+type Starter = Box<dyn FnOnce(Inputs, Vec<Arc<dyn Member>>) -> BoxFuture<'static, ()> + Send>;
+
+/// A registered node of the graph
+struct Node {
+    name: String,
+    dependencies: Vec<String>,
+    member: Arc<dyn Member>,
+    value: Arc<dyn Any + Send + Sync>,
+    starter: Mutex<Option<Starter>>,
+}
+
+/// Builder collecting the nodes of a [`PreloaderGraph`]
+///
+/// Nodes may be added in any order; dependencies are resolved by name in [`build`](Self::build).
+#[derive(Default)]
+pub struct GraphBuilder {
+    nodes: Vec<Node>,
+}
+
+impl GraphBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a node whose loader runs once all of its dependencies are loaded.
+    ///
+    /// If a dependency fails to load, the node fails with the same error without running its loader.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Unique name of the node
+    /// - `dependencies`: Names of the nodes whose values this node needs
+    /// - `loader`: Function creating the loading task from the dependencies' values
+    ///
+    /// # Returns
+    ///
+    /// The node's preloader
+    pub fn add<T, F, Fut>(
+        &mut self,
+        name: impl Into<String>,
+        dependencies: &[&str],
+        loader: F,
+    ) -> Arc<Preloader<T>>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(Inputs) -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let preloader = Arc::new(Preloader::new());
+        let node = Arc::clone(&preloader);
+        let starter: Starter = Box::new(move |inputs, dependencies| {
+            Box::pin(async move {
+                node.load_result(async move {
+                    for dependency in &dependencies {
+                        dependency.wait().await?;
+                    }
+                    Ok(loader(inputs).await)
+                })
+                .await;
+            })
+        });
+
+        self.nodes.push(Node {
+            name: name.into(),
+            dependencies: dependencies.iter().map(|name| name.to_string()).collect(),
+            member: Arc::clone(&preloader) as Arc<dyn Member>,
+            value: Arc::clone(&preloader) as Arc<dyn Any + Send + Sync>,
+            starter: Mutex::new(Some(starter)),
+        });
+        preloader
+    }
+
+    /// Validates the dependencies and creates the graph.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(PreloaderGraph)`: The graph, with its nodes in dependency order
+    /// - `Err(GraphError)`: If a name is duplicated, a dependency is unknown, or the dependencies form a cycle
+    pub fn build(self) -> Result<PreloaderGraph, GraphError> {
+        let mut index = HashMap::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if index.insert(node.name.as_str(), i).is_some() {
+                return Err(GraphError::DuplicateName(node.name.clone()));
+            }
+        }
+
+        let mut edges = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let mut targets = Vec::with_capacity(node.dependencies.len());
+            for dependency in &node.dependencies {
+                match index.get(dependency.as_str()) {
+                    Some(&target) => targets.push(target),
+                    None => {
+                        return Err(GraphError::UnknownDependency {
+                            node: node.name.clone(),
+                            dependency: dependency.clone(),
+                        })
+                    }
+                }
+            }
+            edges.push(targets);
+        }
+
+        let order = topological_order(&edges).map_err(|cycle| {
+            GraphError::Cycle(
+                cycle
+                    .into_iter()
+                    .map(|i| self.nodes[i].name.clone())
+                    .collect(),
+            )
+        })?;
+
+        let mut slots: Vec<_> = self.nodes.into_iter().map(Some).collect();
+        let nodes = order
+            .into_iter()
+            .map(|i| slots[i].take().unwrap())
+            .collect();
+        Ok(PreloaderGraph { nodes })
+    }
+}
+
+/// Visit state of a node during the depth-first search
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Unvisited,
+    Visiting,
+    Done,
+}
+
+/// Orders the nodes so that every node comes after its dependencies.
+///
+/// # Returns
+///
+/// - `Ok(Vec<usize>)`: Node indices in dependency order
+/// - `Err(Vec<usize>)`: Node indices forming a cycle, each depending on the next, with the first node repeated at the end
+fn topological_order(edges: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    fn visit(
+        node: usize,
+        edges: &[Vec<usize>],
+        marks: &mut [Mark],
+        path: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) -> Result<(), Vec<usize>> {
+        match marks[node] {
+            Mark::Done => return Ok(()),
+            Mark::Visiting => {
+                let start = path.iter().position(|&i| i == node).unwrap();
+                let mut cycle = path[start..].to_vec();
+                cycle.push(node);
+                return Err(cycle);
+            }
+            Mark::Unvisited => {}
+        }
+
+        marks[node] = Mark::Visiting;
+        path.push(node);
+        for &dependency in &edges[node] {
+            visit(dependency, edges, marks, path, order)?;
+        }
+        path.pop();
+        marks[node] = Mark::Done;
+        order.push(node);
+        Ok(())
+    }
+
+    let mut marks = vec![Mark::Unvisited; edges.len()];
+    let mut path = Vec::new();
+    let mut order = Vec::with_capacity(edges.len());
+    for node in 0..edges.len() {
+        visit(node, edges, &mut marks, &mut path, &mut order)?;
+    }
+    Ok(order)
+}
+
+/// Validated graph of preloaders loaded in dependency order
+///
+/// Every node starts loading as soon as all of its dependencies are loaded,
+/// so independent branches of the graph load concurrently.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::PreloaderGraph;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut builder = PreloaderGraph::builder();
+///     let url = builder.add("db_url", &["config"], |inputs| {
+///         let config: &String = inputs.get("config");
+///         let url = format!("postgres://{}", config);
+///         async move { url }
+///     });
+///     builder.add("config", &[], |_| async { "localhost".to_string() });
+///
+///     let graph = builder.build().unwrap();
+///     assert_eq!(graph.order(), ["config", "db_url"]);
+///
+///     graph.start().await;
+///     graph.wait_all().await.unwrap();
+///     assert_eq!(url.try_get().unwrap(), "postgres://localhost");
+/// }
+/// ```
+pub struct PreloaderGraph {
+    /// Nodes in dependency order
+    nodes: Vec<Node>,
+}
+
+impl PreloaderGraph {
+    /// Creates a builder for registering the nodes of a graph.
+    pub fn builder() -> GraphBuilder {
+        GraphBuilder::new()
+    }
+
+    /// Returns the node names in dependency order.
+    pub fn order(&self) -> Vec<&str> {
+        self.nodes.iter().map(|node| node.name.as_str()).collect()
+    }
+
+    /// Starts loading every node that has not been started yet.
+    ///
+    /// Each node waits for its dependencies before running its loader.
+    /// This returns as soon as all loaders are registered; use [`wait_all`](Self::wait_all) to wait for completion.
+    pub async fn start(&self) {
+        let index: HashMap<_, _> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.name.as_str(), i))
+            .collect();
+
+        for node in &self.nodes {
+            let starter = node.starter.lock().unwrap().take();
+            let Some(starter) = starter else {
+                continue;
+            };
+
+            let dependencies: Vec<_> = node
+                .dependencies
+                .iter()
+                .map(|name| &self.nodes[index[name.as_str()]])
+                .collect();
+            let inputs = Inputs {
+                values: dependencies
+                    .iter()
+                    .map(|dependency| (dependency.name.clone(), Arc::clone(&dependency.value)))
+                    .collect(),
+            };
+            let members = dependencies
+                .iter()
+                .map(|dependency| Arc::clone(&dependency.member))
+                .collect();
+            starter(inputs, members).await;
+        }
+    }
+
+    /// Waits until every node has finished loading.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If every node was loaded
+    /// - `Err(GroupError)`: The nodes that failed to load, including those whose dependencies failed
+    pub async fn wait_all(&self) -> Result<(), GroupError> {
+        let results = future::join_all(self.nodes.iter().map(|node| node.member.wait())).await;
+
+        let failures: Vec<_> = self
+            .nodes
+            .iter()
+            .zip(results)
+            .filter_map(|(node, result)| result.err().map(|error| (node.name.clone(), error)))
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(GroupError::new(failures))
+        }
+    }
+}
@@ -0,0 +1,974 @@
+//! Preloader group module
+//!
+//! This module provides the `PreloaderGroup` struct for orchestrating many preloaders at startup.
+//! Preloaders are registered with their loaders, started together with bounded concurrency, and awaited as a whole.
+//! Entries can be split into phases, each started once every entry of the previous phase has loaded.
+
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures::future::{self, BoxFuture};
+use tokio::sync::{watch, Semaphore};
+
+use crate::{Preloader, PreloaderError, Watchdog};
+
+/// Loading state of a single group entry
+#[derive(Debug, Clone)]
+pub enum EntryState {
+    /// The group has not been started yet
+    Pending,
+    /// The entry is waiting for a concurrency slot or loading
+    Loading,
+    /// The entry has been loaded
+    Loaded,
+    /// The entry failed to load
+    Failed(PreloaderError),
+}
+
+/// Status of a single group entry
+#[derive(Debug, Clone)]
+pub struct EntryStatus {
+    /// Name the entry was registered under
+    pub name: String,
+    /// Current loading state
+    pub state: EntryState,
+}
+
+/// Error returned by [`PreloaderGroup::wait_all`] when some entries failed to load
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{} preloader(s) failed to load", failures.len())]
+pub struct GroupError {
+    failures: Vec<(String, PreloaderError)>,
+}
+
+impl GroupError {
+    /// Creates an error from the names and errors of the failed entries.
+    pub(crate) fn new(failures: Vec<(String, PreloaderError)>) -> Self {
+        Self { failures }
+    }
+
+    /// Returns the names and errors of the entries that failed to load.
+    pub fn failures(&self) -> &[(String, PreloaderError)] {
+        &self.failures
+    }
+}
+
+/// Outcome of [`PreloaderGroup::shutdown`]
+///
+/// Each list holds entry names in registration order.
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownReport {
+    /// Entries that finished loading successfully
+    pub completed: Vec<String>,
+    /// Entries that finished with an error, together with the error
+    pub failed: Vec<(String, PreloaderError)>,
+    /// Entries still loading when the timeout elapsed, whose loading tasks were aborted
+    pub aborted: Vec<String>,
+    /// Entries that were never started
+    pub not_started: Vec<String>,
+}
+
+impl ShutdownReport {
+    /// Checks if every in-flight load finished before the timeout, so nothing was aborted.
+    pub fn is_drained(&self) -> bool {
+        self.aborted.is_empty()
+    }
+}
+
+/// Readiness of a group, as reported by [`PreloaderGroup::readiness`]
+///
+/// Each list holds entry names in registration order. With the `serde` feature the report serializes to an object
+/// such as `{"ready":false,"total":3,"loaded":1,"failed":{"users":"..."},"loading":{"prices":1.5},"not_started":[]}`,
+/// with the elapsed time of loading entries in seconds, for health endpoints that return JSON.
+#[derive(Debug, Clone, Default)]
+pub struct ReadinessReport {
+    /// Number of registered entries
+    pub total: usize,
+    /// Number of entries that have been loaded
+    pub loaded: usize,
+    /// Entries that failed to load, together with the error
+    pub failed: Vec<(String, PreloaderError)>,
+    /// Entries still loading or waiting for a concurrency slot, together with the time since they were started
+    pub loading: Vec<(String, Duration)>,
+    /// Entries that have not been started yet
+    pub not_started: Vec<String>,
+}
+
+impl ReadinessReport {
+    /// Checks if every entry has been loaded.
+    pub fn is_ready(&self) -> bool {
+        self.loaded == self.total
+    }
+}
+
+impl fmt::Display for ReadinessReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} preloaders loaded", self.loaded, self.total)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ReadinessReport {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        /// Failed entries as a map from name to error message
+        struct Failed<'a>(&'a [(String, PreloaderError)]);
+
+        impl serde::Serialize for Failed<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_map(self.0.iter().map(|(name, error)| (name, error.to_string())))
+            }
+        }
+
+        /// Loading entries as a map from name to elapsed seconds
+        struct Loading<'a>(&'a [(String, Duration)]);
+
+        impl serde::Serialize for Loading<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_map(
+                    self.0
+                        .iter()
+                        .map(|(name, elapsed)| (name, elapsed.as_secs_f64())),
+                )
+            }
+        }
+
+        let mut report = serializer.serialize_struct("ReadinessReport", 6)?;
+        report.serialize_field("ready", &self.is_ready())?;
+        report.serialize_field("total", &self.total)?;
+        report.serialize_field("loaded", &self.loaded)?;
+        report.serialize_field("failed", &Failed(&self.failed))?;
+        report.serialize_field("loading", &Loading(&self.loading))?;
+        report.serialize_field("not_started", &self.not_started)?;
+        report.end()
+    }
+}
+
+/// Load timings of a group, as reported by [`PreloaderGroup::timing`]
+///
+/// Offsets are measured from the moment the group was started. With the `serde` feature the report serializes to
+/// an object such as `{"total":1.5,"parallelism":1.8,"entries":[{"name":"users","phase":"default","started":0.0,
+/// "running":0.2,"finished":1.5,"loaded":true}]}`, with offsets in seconds. [`to_chrome_trace`](Self::to_chrome_trace)
+/// renders the same timings for `chrome://tracing` or Perfetto.
+#[derive(Debug, Clone, Default)]
+pub struct TimingReport {
+    /// Offset at which the last entry finished loading
+    pub total: Duration,
+    /// Entries started by the group, in registration order
+    pub entries: Vec<EntryTiming>,
+}
+
+/// Load timing of a single group entry
+#[derive(Debug, Clone)]
+pub struct EntryTiming {
+    /// Name the entry was registered under
+    pub name: String,
+    /// Name of the phase the entry belongs to
+    pub phase: String,
+    /// Offset at which the loading task started, before waiting for a concurrency slot
+    pub started: Duration,
+    /// Offset at which the loader got a concurrency slot and began running
+    pub running: Option<Duration>,
+    /// Offset at which loading finished, successfully or not
+    pub finished: Option<Duration>,
+    /// Whether the entry has been loaded
+    pub loaded: bool,
+}
+
+impl EntryTiming {
+    /// Returns how long the entry waited for a concurrency slot.
+    pub fn queued(&self) -> Option<Duration> {
+        Some(self.running? - self.started)
+    }
+
+    /// Returns how long the loader ran, excluding the wait for a concurrency slot.
+    pub fn duration(&self) -> Option<Duration> {
+        Some(self.finished? - self.running?)
+    }
+}
+
+impl TimingReport {
+    /// Returns the finished entry whose loader ran the longest.
+    pub fn slowest(&self) -> Option<&EntryTiming> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.duration().is_some())
+            .max_by_key(|entry| entry.duration())
+    }
+
+    /// Returns the average number of loaders running at the same time, from the start of the group to `total`.
+    ///
+    /// A value close to 1 means the entries effectively loaded one after another.
+    pub fn parallelism(&self) -> f64 {
+        if self.total.is_zero() {
+            return 0.0;
+        }
+        let busy: Duration = self.entries.iter().filter_map(EntryTiming::duration).sum();
+        busy.as_secs_f64() / self.total.as_secs_f64()
+    }
+
+    /// Renders the finished entries in the Chrome trace event format.
+    ///
+    /// Each entry becomes a complete event on the first lane free at its start, so concurrent loads appear side by
+    /// side. Save the result as a `.json` file and open it in `chrome://tracing` or <https://ui.perfetto.dev>.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderGroup;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut group = PreloaderGroup::new();
+    ///     group.add("users", async { vec!["alice"] });
+    ///     group.start().await;
+    ///     group.wait_all().await.unwrap();
+    ///
+    ///     let trace = group.timing().to_chrome_trace();
+    ///     assert!(trace.starts_with(r#"{"traceEvents":[{"name":"users","#));
+    /// }
+    /// ```
+    pub fn to_chrome_trace(&self) -> String {
+        let mut lanes: Vec<Duration> = Vec::new();
+        let mut finished: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| Some((entry, entry.running?, entry.finished?)))
+            .collect();
+        finished.sort_by_key(|&(_, running, _)| running);
+
+        let events: Vec<_> = finished
+            .into_iter()
+            .map(|(entry, running, finished)| {
+                let lane = match lanes.iter().position(|&end| end <= running) {
+                    Some(lane) => lane,
+                    None => {
+                        lanes.push(Duration::ZERO);
+                        lanes.len() - 1
+                    }
+                };
+                lanes[lane] = finished;
+                format!(
+                    r#"{{"name":"{}","cat":"{}","ph":"X","ts":{},"dur":{},"pid":1,"tid":{},"args":{{"queued_us":{},"loaded":{}}}}}"#,
+                    json_escape(&entry.name),
+                    json_escape(&entry.phase),
+                    running.as_micros(),
+                    (finished - running).as_micros(),
+                    lane + 1,
+                    (running - entry.started).as_micros(),
+                    entry.loaded,
+                )
+            })
+            .collect();
+        format!(r#"{{"traceEvents":[{}]}}"#, events.join(","))
+    }
+}
+
+/// Escapes `text` for use inside a JSON string literal.
+fn json_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TimingReport {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut report = serializer.serialize_struct("TimingReport", 3)?;
+        report.serialize_field("total", &self.total.as_secs_f64())?;
+        report.serialize_field("parallelism", &self.parallelism())?;
+        report.serialize_field("entries", &self.entries)?;
+        report.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for EntryTiming {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let seconds = |offset: Option<Duration>| offset.map(|offset| offset.as_secs_f64());
+        let mut entry = serializer.serialize_struct("EntryTiming", 6)?;
+        entry.serialize_field("name", &self.name)?;
+        entry.serialize_field("phase", &self.phase)?;
+        entry.serialize_field("started", &self.started.as_secs_f64())?;
+        entry.serialize_field("running", &seconds(self.running))?;
+        entry.serialize_field("finished", &seconds(self.finished))?;
+        entry.serialize_field("loaded", &self.loaded)?;
+        entry.end()
+    }
+}
+
+/// Type-erased view of a preloader registered in a group
+pub(crate) trait Member: Send + Sync {
+    /// Returns the current loading state.
+    fn state(&self) -> EntryState;
+
+    /// Waits until loading has finished.
+    fn wait(&self) -> BoxFuture<'_, Result<(), PreloaderError>>;
+
+    /// Waits until loading has been started and has finished, returning whether the value was loaded.
+    fn ready(&self) -> BoxFuture<'_, bool>;
+
+    /// Aborts the loading task, returning whether one was still running.
+    fn abort(&self) -> bool;
+
+    /// Returns the time at which the latest load was started.
+    fn started_at(&self) -> Option<Instant>;
+
+    /// Fails an `Idle` preloader with `error` without loading it.
+    fn fail(&self, error: PreloaderError);
+}
+
+impl<T: Send + Sync + 'static> Member for Preloader<T> {
+    fn state(&self) -> EntryState {
+        match self.try_get() {
+            Ok(_) => EntryState::Loaded,
+            Err(PreloaderError::NotLoaded) => EntryState::Pending,
+            Err(PreloaderError::Loading) => EntryState::Loading,
+            Err(error) => EntryState::Failed(error),
+        }
+    }
+
+    fn wait(&self) -> BoxFuture<'_, Result<(), PreloaderError>> {
+        Box::pin(async move { self.get().await.map(|_| ()) })
+    }
+
+    fn ready(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async move { self.resolve(true).await.is_ok() })
+    }
+
+    fn abort(&self) -> bool {
+        Preloader::abort(self)
+    }
+
+    fn started_at(&self) -> Option<Instant> {
+        Preloader::started_at(self)
+    }
+
+    fn fail(&self, error: PreloaderError) {
+        if self.claim_external() {
+            self.finish(Err(error));
+        }
+    }
+}
+
+/// Deferred `load()` call of an entry
+type Starter =
+    Box<dyn FnOnce(Option<Arc<Semaphore>>, Option<Watchdog>) -> BoxFuture<'static, ()> + Send>;
+
+/// Pending loader of an entry, taken by whoever starts or skips it first
+type SharedStarter = Arc<Mutex<Option<Starter>>>;
+
+/// Instants at which the loader of an entry reached each stage
+#[derive(Default)]
+struct Timing {
+    started: Option<tokio::time::Instant>,
+    running: Option<tokio::time::Instant>,
+    finished: Option<tokio::time::Instant>,
+}
+
+/// Records the end of a load when dropped, so panicking and aborted loaders are timed too
+struct FinishOnDrop(Arc<Mutex<Timing>>);
+
+impl Drop for FinishOnDrop {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().finished = Some(tokio::time::Instant::now());
+    }
+}
+
+/// A registered preloader together with its pending loader
+struct Entry {
+    name: String,
+    member: Arc<dyn Member>,
+    timing: Arc<Mutex<Timing>>,
+    /// Shared with the task starting later phases, so `shutdown()` can take it first
+    starter: SharedStarter,
+    /// Index of the phase the entry belongs to
+    phase: usize,
+}
+
+/// Named stage of a group
+struct Phase {
+    name: String,
+    timeout: Option<Duration>,
+}
+
+/// Entries of a phase, as started by the task running the phases in order
+struct PhaseRun {
+    name: String,
+    timeout: Option<Duration>,
+    entries: Vec<(Arc<dyn Member>, SharedStarter)>,
+}
+
+impl PhaseRun {
+    /// Starts every entry of the phase that has not been started yet.
+    async fn start(&self, semaphore: &Option<Arc<Semaphore>>, watchdog: &Option<Watchdog>) {
+        for (_, starter) in &self.entries {
+            let starter = starter.lock().unwrap().take();
+            if let Some(starter) = starter {
+                starter(semaphore.clone(), watchdog.clone()).await;
+            }
+        }
+    }
+
+    /// Waits until every entry of the phase has finished loading, returning whether all were loaded in time.
+    ///
+    /// Entries still loading when the timeout elapses are aborted.
+    async fn settle(&self) -> bool {
+        let loads = future::join_all(self.entries.iter().map(|(member, _)| member.wait()));
+        let results = match self.timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, loads).await {
+                Ok(results) => results,
+                Err(_) => {
+                    for (member, _) in &self.entries {
+                        member.abort();
+                    }
+                    return false;
+                }
+            },
+            None => loads.await,
+        };
+        results.iter().all(Result::is_ok)
+    }
+
+    /// Fails every entry of the phase that has not been started yet, because phase `failed` did not complete.
+    fn skip(&self, failed: &str) {
+        for (member, starter) in &self.entries {
+            if starter.lock().unwrap().take().is_some() {
+                member.fail(PreloaderError::Failed(format!(
+                    "phase `{failed}` did not complete"
+                )));
+            }
+        }
+    }
+}
+
+/// Group of preloaders started and awaited together
+///
+/// Entries are registered with [`add`](Self::add) and begin loading when [`start`](Self::start) is called.
+/// At most `max_concurrency` loaders run at the same time; the others wait in the `Loading` state for a free slot.
+///
+/// Startup stages are declared with [`phase`](Self::phase): entries added after it are started only once every
+/// entry of the previous phase has loaded.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::PreloaderGroup;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut group = PreloaderGroup::new().max_concurrency(2);
+///     let users = group.add("users", async { vec!["alice", "bob"] });
+///     let limit = group.add("limit", async { 10 });
+///
+///     group.start().await;
+///     group.wait_all().await.unwrap();
+///
+///     assert!(group.is_ready());
+///     assert_eq!(users.try_get().unwrap().len(), 2);
+///     assert_eq!(*limit.try_get().unwrap(), 10);
+/// }
+/// ```
+#[derive(Default)]
+pub struct PreloaderGroup {
+    entries: Vec<Entry>,
+    phases: Vec<Phase>,
+    max_concurrency: Option<usize>,
+    watchdog: Option<Watchdog>,
+    /// Set once the task running later phases has started or skipped all of them
+    phased: Mutex<Option<watch::Receiver<bool>>>,
+    /// Time at which the group was first started
+    started_at: Mutex<Option<tokio::time::Instant>>,
+}
+
+impl PreloaderGroup {
+    /// Creates an empty group without a concurrency limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of loaders running at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrency` is zero.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        assert!(max_concurrency > 0, "max_concurrency must be positive");
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+
+    /// Sets a watchdog reporting the entries whose load runs longer than its budget.
+    ///
+    /// The budget of an entry starts once it gets a concurrency slot. Entries are loaded from one-shot futures,
+    /// so the group's watchdog only reports stalled loads and never restarts them; configure a [`Watchdog`] on the
+    /// preloader itself through [`Preloader::builder`] to restart it with its loader factory.
+    pub fn watchdog(mut self, watchdog: Watchdog) -> Self {
+        self.watchdog = Some(watchdog);
+        self
+    }
+
+    /// Begins a new phase; entries added afterwards are started only once every entry of the previous phase has
+    /// loaded.
+    ///
+    /// Entries added before the first phase form a leading phase named `default`. If an entry of a phase fails,
+    /// the later phases are never started and their entries fail with `PreloaderError::Failed`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderGroup;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut group = PreloaderGroup::new();
+    ///     group.phase_with_timeout("infra", Duration::from_secs(5));
+    ///     let pool = group.add("pool", async { "postgres://db" });
+    ///     group.phase("data");
+    ///     let users = group.add("users", async { vec!["alice", "bob"] });
+    ///
+    ///     // "users" is started in the background once "pool" has loaded
+    ///     group.start().await;
+    ///     group.wait_all().await.unwrap();
+    ///     assert_eq!(*pool.try_get().unwrap(), "postgres://db");
+    ///     assert_eq!(users.try_get().unwrap().len(), 2);
+    /// }
+    /// ```
+    pub fn phase(&mut self, name: impl Into<String>) {
+        self.phases.push(Phase {
+            name: name.into(),
+            timeout: None,
+        });
+    }
+
+    /// Begins a new phase whose entries must all load within `timeout` of the phase being started.
+    ///
+    /// Entries still loading when `timeout` elapses are aborted, so they fail with `PreloaderError::Cancelled`,
+    /// and the later phases are never started. See [`phase`](Self::phase).
+    pub fn phase_with_timeout(&mut self, name: impl Into<String>, timeout: Duration) {
+        self.phases.push(Phase {
+            name: name.into(),
+            timeout: Some(timeout),
+        });
+    }
+
+    /// Registers a new preloader that will load `future` once the group is started.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name identifying the entry in status reports
+    /// - `future`: The asynchronous task loading the entry
+    ///
+    /// # Returns
+    ///
+    /// The registered preloader, shared with the group
+    pub fn add<T, F>(&mut self, name: impl Into<String>, future: F) -> Arc<Preloader<T>>
+    where
+        T: Send + Sync + 'static,
+        F: Future<Output = T> + Send + 'static,
+    {
+        let preloader = Arc::new(Preloader::new());
+        self.add_with(name, Arc::clone(&preloader), future);
+        preloader
+    }
+
+    /// Registers an existing preloader that will load `future` once the group is started.
+    ///
+    /// Use this to register preloaders configured through [`Preloader::builder`].
+    /// If the preloader has already been started elsewhere, the group only tracks it.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name identifying the entry in status reports
+    /// - `preloader`: The preloader to register
+    /// - `future`: The asynchronous task loading the entry
+    pub fn add_with<T, F>(
+        &mut self,
+        name: impl Into<String>,
+        preloader: Arc<Preloader<T>>,
+        future: F,
+    ) where
+        T: Send + Sync + 'static,
+        F: Future<Output = T> + Send + 'static,
+    {
+        let name = name.into();
+        let loader = Arc::clone(&preloader);
+        let entry = name.clone();
+        let timing = Arc::new(Mutex::new(Timing::default()));
+        let times = Arc::clone(&timing);
+        let starter: Starter = Box::new(move |semaphore, watchdog| {
+            Box::pin(async move {
+                let events = loader.event_feed().clone();
+                loader
+                    .load(async move {
+                        times.lock().unwrap().started = Some(tokio::time::Instant::now());
+                        let _finish = FinishOnDrop(Arc::clone(&times));
+                        let _permit = match &semaphore {
+                            Some(semaphore) => Some(semaphore.acquire().await),
+                            None => None,
+                        };
+                        times.lock().unwrap().running = Some(tokio::time::Instant::now());
+                        match watchdog {
+                            Some(watchdog) => {
+                                watchdog
+                                    .watch(Some(entry), events, future, Vec::new())
+                                    .await
+                            }
+                            None => future.await,
+                        }
+                    })
+                    .await;
+            })
+        });
+
+        self.entries.push(Entry {
+            name,
+            member: preloader,
+            timing,
+            starter: Arc::new(Mutex::new(Some(starter))),
+            phase: self.phases.len(),
+        });
+    }
+
+    /// Starts loading every registered entry that has not been started yet.
+    ///
+    /// This returns as soon as the loaders of the first phase are registered; later phases are started in the
+    /// background as the earlier ones complete. Use [`wait_all`](Self::wait_all) to wait for completion.
+    pub async fn start(&self) {
+        self.started_at
+            .lock()
+            .unwrap()
+            .get_or_insert_with(tokio::time::Instant::now);
+        let semaphore = self.max_concurrency.map(|n| Arc::new(Semaphore::new(n)));
+        let mut runs = self.phase_runs().into_iter();
+        let Some(first) = runs.next() else {
+            return;
+        };
+        first.start(&semaphore, &self.watchdog).await;
+        let rest: Vec<_> = runs.collect();
+        if rest.is_empty() && first.timeout.is_none() {
+            return;
+        }
+
+        let (done, phased) = watch::channel(false);
+        *self.phased.lock().unwrap() = Some(phased);
+        let watchdog = self.watchdog.clone();
+        tokio::spawn(async move {
+            let mut failed = (!first.settle().await).then_some(first.name);
+            for run in rest {
+                if let Some(failed) = &failed {
+                    run.skip(failed);
+                    continue;
+                }
+                run.start(&semaphore, &watchdog).await;
+                if !run.settle().await {
+                    failed = Some(run.name);
+                }
+            }
+            _ = done.send(true);
+        });
+    }
+
+    /// Groups the entries by phase, skipping phases without entries.
+    fn phase_runs(&self) -> Vec<PhaseRun> {
+        let mut runs: Vec<_> = (0..=self.phases.len())
+            .map(|index| PhaseRun {
+                name: self.phase_name(index).to_owned(),
+                timeout: index
+                    .checked_sub(1)
+                    .and_then(|index| self.phases[index].timeout),
+                entries: Vec::new(),
+            })
+            .collect();
+        for entry in &self.entries {
+            runs[entry.phase]
+                .entries
+                .push((Arc::clone(&entry.member), Arc::clone(&entry.starter)));
+        }
+        runs.retain(|run| !run.entries.is_empty());
+        runs
+    }
+
+    /// Returns the name of the phase at `index`, where entries added before the first phase are at index 0.
+    fn phase_name(&self, index: usize) -> &str {
+        match index.checked_sub(1) {
+            Some(index) => &self.phases[index].name,
+            None => "default",
+        }
+    }
+
+    /// Waits until every entry has finished loading.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If every entry was loaded
+    /// - `Err(GroupError)`: The entries that failed to load or were never started
+    pub async fn wait_all(&self) -> Result<(), GroupError> {
+        // Entries of later phases are still `Idle` until their phase is started or skipped
+        let phased = self.phased.lock().unwrap().clone();
+        if let Some(mut phased) = phased {
+            _ = phased.wait_for(|done| *done).await;
+        }
+        let results = future::join_all(self.entries.iter().map(|entry| entry.member.wait())).await;
+
+        let failures: Vec<_> = self
+            .entries
+            .iter()
+            .zip(results)
+            .filter_map(|(entry, result)| result.err().map(|error| (entry.name.clone(), error)))
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(GroupError::new(failures))
+        }
+    }
+
+    /// Shuts the group down, draining in-flight loads for at most `timeout` and aborting the rest.
+    ///
+    /// Entries that have not been started yet are never started afterwards. Loads still running when
+    /// `timeout` elapses are aborted, and waiting callers receive `PreloaderError::Cancelled`.
+    /// Call this before the runtime shuts down, so detached loading tasks do not race against it.
+    ///
+    /// # Parameters
+    ///
+    /// - `timeout`: How long to wait for in-flight loads before aborting them
+    ///
+    /// # Returns
+    ///
+    /// A report of which entries completed, failed, were aborted, or were never started
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderGroup;
+    /// use std::time::Duration;
+    /// use tokio::{self, time::sleep};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut group = PreloaderGroup::new();
+    ///     group.add("fast", async { 1 });
+    ///     group.add("slow", async {
+    ///         sleep(Duration::from_secs(60)).await;
+    ///         2
+    ///     });
+    ///     group.start().await;
+    ///
+    ///     let report = group.shutdown(Duration::from_millis(50)).await;
+    ///     assert_eq!(report.completed, ["fast"]);
+    ///     assert_eq!(report.aborted, ["slow"]);
+    /// }
+    /// ```
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        for entry in &self.entries {
+            entry.starter.lock().unwrap().take();
+        }
+
+        let drain = future::join_all(self.entries.iter().map(|entry| entry.member.wait()));
+        let aborted: Vec<bool> = match tokio::time::timeout(timeout, drain).await {
+            Ok(_) => vec![false; self.entries.len()],
+            Err(_) => {
+                let aborted = self
+                    .entries
+                    .iter()
+                    .map(|entry| entry.member.abort())
+                    .collect();
+                // Receive the cancellation, so aborted entries settle in the `Failed` state
+                future::join_all(self.entries.iter().map(|entry| entry.member.wait())).await;
+                aborted
+            }
+        };
+
+        let mut report = ShutdownReport::default();
+        for (entry, aborted) in self.entries.iter().zip(aborted) {
+            let name = entry.name.clone();
+            match entry.member.state() {
+                _ if aborted => report.aborted.push(name),
+                EntryState::Loaded => report.completed.push(name),
+                EntryState::Failed(error) => report.failed.push((name, error)),
+                EntryState::Pending | EntryState::Loading => report.not_started.push(name),
+            }
+        }
+        report
+    }
+
+    /// Checks if every entry has been loaded.
+    pub fn is_ready(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|entry| matches!(entry.member.state(), EntryState::Loaded))
+    }
+
+    /// Reports how many entries are loaded, which failed, and which are still loading and for how long.
+    ///
+    /// Suited to health and readiness probes that should explain why the service is not ready yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderGroup;
+    /// use std::time::Duration;
+    /// use tokio::{self, time::sleep};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut group = PreloaderGroup::new();
+    ///     group.add("limit", async { 10 });
+    ///     group.add("prices", async {
+    ///         sleep(Duration::from_secs(60)).await;
+    ///         vec![1.25]
+    ///     });
+    ///     group.start().await;
+    ///     sleep(Duration::from_millis(10)).await;
+    ///
+    ///     let report = group.readiness();
+    ///     assert!(!report.is_ready());
+    ///     assert_eq!(report.to_string(), "1/2 preloaders loaded");
+    ///     assert_eq!(report.loading[0].0, "prices");
+    /// }
+    /// ```
+    pub fn readiness(&self) -> ReadinessReport {
+        let mut report = ReadinessReport {
+            total: self.entries.len(),
+            ..ReadinessReport::default()
+        };
+        for entry in &self.entries {
+            let name = entry.name.clone();
+            match entry.member.state() {
+                EntryState::Loaded => report.loaded += 1,
+                EntryState::Failed(error) => report.failed.push((name, error)),
+                EntryState::Loading => {
+                    let elapsed = entry
+                        .member
+                        .started_at()
+                        .map_or(Duration::ZERO, |started_at| started_at.elapsed());
+                    report.loading.push((name, elapsed));
+                }
+                EntryState::Pending => report.not_started.push(name),
+            }
+        }
+        report
+    }
+
+    /// Reports when each entry started, got a concurrency slot, and finished loading, relative to the group start.
+    ///
+    /// Entries that have not been started are left out. Suited to finding which loads dominate startup and how
+    /// many of them actually ran in parallel.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderGroup;
+    /// use std::time::Duration;
+    /// use tokio::{self, time::sleep};
+    ///
+    /// #[tokio::main(flavor = "current_thread", start_paused = true)]
+    /// async fn main() {
+    ///     let mut group = PreloaderGroup::new();
+    ///     group.add("users", async {
+    ///         sleep(Duration::from_secs(2)).await;
+    ///         vec!["alice"]
+    ///     });
+    ///     group.add("limit", async {
+    ///         sleep(Duration::from_secs(1)).await;
+    ///         10
+    ///     });
+    ///     group.start().await;
+    ///     group.wait_all().await.unwrap();
+    ///
+    ///     let report = group.timing();
+    ///     assert_eq!(report.total, Duration::from_secs(2));
+    ///     assert_eq!(report.slowest().unwrap().name, "users");
+    ///     assert_eq!(report.parallelism(), 1.5);
+    /// }
+    /// ```
+    pub fn timing(&self) -> TimingReport {
+        let Some(origin) = *self.started_at.lock().unwrap() else {
+            return TimingReport::default();
+        };
+        let offset = |instant: Option<tokio::time::Instant>| {
+            instant.map(|instant| instant.saturating_duration_since(origin))
+        };
+        let entries: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let timing = entry.timing.lock().unwrap();
+                Some(EntryTiming {
+                    name: entry.name.clone(),
+                    phase: self.phase_name(entry.phase).to_owned(),
+                    started: offset(timing.started)?,
+                    running: offset(timing.running),
+                    finished: offset(timing.finished),
+                    loaded: matches!(entry.member.state(), EntryState::Loaded),
+                })
+            })
+            .collect();
+        TimingReport {
+            total: entries
+                .iter()
+                .filter_map(|entry| entry.finished)
+                .max()
+                .unwrap_or_default(),
+            entries,
+        }
+    }
+
+    /// Waits until every entry has been started and has finished loading, returning whether all were loaded.
+    pub(crate) async fn ready(&self) -> bool {
+        future::join_all(self.entries.iter().map(|entry| entry.member.ready()))
+            .await
+            .into_iter()
+            .all(|ready| ready)
+    }
+
+    /// Returns the number of entries that have been loaded.
+    pub fn loaded_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.member.state(), EntryState::Loaded))
+            .count()
+    }
+
+    /// Returns the number of registered entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if no entries are registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the status of every entry, in registration order.
+    pub fn status(&self) -> Vec<EntryStatus> {
+        self.entries
+            .iter()
+            .map(|entry| EntryStatus {
+                name: entry.name.clone(),
+                state: entry.member.state(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,86 @@
+//! gRPC warmup module
+//!
+//! This module provides ready-made loaders that open a tonic channel during boot, so the first real request does
+//! not pay for DNS resolution, the TCP and TLS handshakes, and the HTTP/2 setup: [`Preloader::grpc_channel`]
+//! connects to an endpoint and [`Preloader::grpc_channel_with_warmup`] also sends a warmup RPC as a health check.
+
+use std::future::Future;
+
+use tonic::{
+    transport::{Channel, Endpoint},
+    Status,
+};
+
+use crate::{Preloader, PreloaderError};
+
+impl Preloader<Channel> {
+    /// Creates a new `Preloader` and immediately starts connecting a gRPC channel to `endpoint`.
+    ///
+    /// The channel is connected eagerly, so a loaded preloader means the server accepted the connection.
+    /// Connection errors make the load fail with `PreloaderError::Failed`. Channels are cheap to clone, so
+    /// clients are usually built from [`get_cloned`](Self::get_cloned).
+    ///
+    /// # Parameters
+    ///
+    /// - `endpoint`: The server to connect to, with its TLS, timeout, and keep-alive settings
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use preloader::Preloader;
+    /// use std::time::Duration;
+    /// use tokio;
+    /// use tonic::transport::{Channel, Endpoint};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let endpoint = Endpoint::from_static("http://inventory:50051")
+    ///         .connect_timeout(Duration::from_secs(5));
+    ///     let channel: Preloader<Channel> = Preloader::grpc_channel(endpoint);
+    ///
+    ///     // During boot
+    ///     let channel = channel.get_cloned().await.unwrap();
+    /// }
+    /// ```
+    pub fn grpc_channel(endpoint: Endpoint) -> Self {
+        Self::grpc_channel_with_warmup(endpoint, |_| async { Ok(()) })
+    }
+
+    /// Creates a new `Preloader` and immediately starts connecting a gRPC channel to `endpoint`, then sends a
+    /// warmup RPC on it.
+    ///
+    /// The warmup runs on the connected channel before it is stored, for example a call to the standard
+    /// `grpc.health.v1.Health/Check` service or to a cheap method of the real service. An error status returned by
+    /// `warmup` makes the load fail with `PreloaderError::Failed`, so an unhealthy server is reported during boot.
+    ///
+    /// # Parameters
+    ///
+    /// - `endpoint`: The server to connect to
+    /// - `warmup`: Function sending the warmup RPC on a clone of the connected channel
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn grpc_channel_with_warmup<F, Fut>(endpoint: Endpoint, warmup: F) -> Self
+    where
+        F: FnOnce(Channel) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), Status>> + Send + 'static,
+    {
+        let mut preloader = Self::new();
+        preloader.start_loader(async move {
+            let channel = endpoint
+                .connect()
+                .await
+                .map_err(|error| PreloaderError::Failed(error.to_string()))?;
+            warmup(channel.clone()).await.map_err(|status| {
+                PreloaderError::Failed(format!("warmup RPC failed: {}", status))
+            })?;
+            Ok(channel)
+        });
+        preloader
+    }
+}
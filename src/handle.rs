@@ -0,0 +1,108 @@
+//! Load handle module
+//!
+//! This module provides `LoadHandle`, returned by `load()` so callers can join on, inspect, or abort the loading
+//! task they started instead of polling the preloader.
+
+use std::future::IntoFuture;
+
+use futures::future::BoxFuture;
+use tokio::{sync::watch, task::AbortHandle};
+
+use crate::PreloaderError;
+
+/// Outcome of a loading task, `None` while it is running
+pub(crate) type Outcome = Option<Result<(), PreloaderError>>;
+
+/// Handle to a loading task
+///
+/// Awaiting the handle waits until the task has finished and reports whether it stored a value. The value itself
+/// is retrieved from the preloader. Dropping the handle does not affect the task.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::Preloader;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let preloader = Preloader::new();
+///     let handle = preloader.load(async { 42 }).await;
+///     handle.await.unwrap();
+///     assert!(preloader.is_loaded());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct LoadHandle {
+    /// Abort handle of the task, `None` if the value was not loaded by a task
+    task: Option<AbortHandle>,
+    /// Outcome reported by the task
+    outcome: watch::Receiver<Outcome>,
+}
+
+impl LoadHandle {
+    /// Creates a handle to a task.
+    ///
+    /// # Parameters
+    ///
+    /// - `task`: Abort handle of the task, or `None` if it finished before it had to be spawned
+    /// - `outcome`: Receiver for the outcome the task reports when it finishes
+    pub(crate) fn new(task: Option<AbortHandle>, outcome: watch::Receiver<Outcome>) -> Self {
+        Self { task, outcome }
+    }
+
+    /// Creates a handle that has already finished successfully, for values stored without a task.
+    pub(crate) fn finished() -> Self {
+        Self {
+            task: None,
+            outcome: watch::channel(Some(Ok(()))).1,
+        }
+    }
+
+    /// Creates a handle that has already finished with `error`, for loads that were rejected.
+    pub(crate) fn failed(error: PreloaderError) -> Self {
+        Self {
+            task: None,
+            outcome: watch::channel(Some(Err(error))).1,
+        }
+    }
+
+    /// Returns a receiver for the outcome of the task.
+    pub(crate) fn outcome(&self) -> watch::Receiver<Outcome> {
+        self.outcome.clone()
+    }
+
+    /// Aborts the loading task at its next await point, so the preloader reports `PreloaderError::Cancelled`.
+    ///
+    /// Does nothing if the task has already finished.
+    pub fn abort(&self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+
+    /// Checks if the loading task has finished, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.outcome.borrow().is_some() || self.task.as_ref().is_none_or(AbortHandle::is_finished)
+    }
+}
+
+impl IntoFuture for LoadHandle {
+    type Output = Result<(), PreloaderError>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    /// Waits until the loading task has finished.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the task stored a value
+    /// - `Err(PreloaderError)`: The error that ended loading, or `Cancelled` if the task was aborted
+    fn into_future(mut self) -> Self::IntoFuture {
+        Box::pin(async move {
+            match self.outcome.wait_for(Option::is_some).await {
+                Ok(outcome) => outcome.clone().unwrap(),
+                Err(_) => Err(PreloaderError::Cancelled),
+            }
+        })
+    }
+}
@@ -0,0 +1,190 @@
+//! Load history module
+//!
+//! This module keeps a bounded history of the loads of a preloader: when each one started, how long it took,
+//! how many attempts it made, what triggered it, and how it ended. It answers why a preloader was slow or failing
+//! at a given time, after the fact. An [`ArcPreloader`](crate::ArcPreloader) can also keep its previous values.
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{stats::Latency, PreloaderError};
+
+/// Number of loads kept in the history unless configured otherwise
+pub const DEFAULT_HISTORY_LEN: usize = 16;
+
+/// What started a load
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoadTrigger {
+    /// The first load of the value
+    Initial,
+    /// A load started again after the previous one failed
+    Retry,
+    /// A reload replacing a loaded value
+    Refresh,
+}
+
+/// A finished load, as reported by `Preloader::history`
+#[derive(Debug, Clone)]
+pub struct LoadAttempt {
+    /// Wall-clock time at which the load started
+    pub started_at: SystemTime,
+    /// How long the load took, including retries
+    pub duration: Duration,
+    /// Number of attempts made, including retries
+    pub attempts: u32,
+    /// What started the load
+    pub trigger: LoadTrigger,
+    /// The error that ended the load, or `None` if it stored a value
+    pub error: Option<PreloaderError>,
+}
+
+impl LoadAttempt {
+    /// Checks if the load stored a value.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Bounded history of the loads of a preloader, shared with its loading tasks
+#[derive(Debug, Clone)]
+pub(crate) struct History {
+    /// Maximum number of loads kept
+    capacity: usize,
+    /// Finished loads, oldest first
+    entries: Arc<Mutex<VecDeque<LoadAttempt>>>,
+    /// Recent load and wait durations, recorded even when no history is kept
+    latency: Arc<Latency>,
+}
+
+impl History {
+    /// Creates an empty history keeping at most `capacity` loads.
+    ///
+    /// # Parameters
+    ///
+    /// - `capacity`: Maximum number of loads kept
+    /// - `latency`: Statistics the duration of every finished load is added to
+    pub(crate) fn new(capacity: usize, latency: Arc<Latency>) -> Self {
+        Self {
+            capacity,
+            entries: Arc::default(),
+            latency,
+        }
+    }
+
+    /// Returns the recent load and wait durations.
+    pub(crate) fn latency(&self) -> &Latency {
+        &self.latency
+    }
+
+    /// Returns the trigger of a load starting now that does not replace a loaded value.
+    pub(crate) fn next_trigger(&self) -> LoadTrigger {
+        match self.entries.lock().unwrap().back() {
+            Some(last) if !last.is_success() => LoadTrigger::Retry,
+            _ => LoadTrigger::Initial,
+        }
+    }
+
+    /// Adds a finished load, dropping the oldest one if the history is full.
+    pub(crate) fn record(&self, attempt: LoadAttempt) {
+        self.latency.record_load(attempt.duration);
+        if !attempt.is_success() {
+            self.latency.record_failure();
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(attempt);
+    }
+
+    /// Returns the durations of the recorded loads that stored a value on their first attempt.
+    pub(crate) fn first_attempt_durations(&self) -> Vec<Duration> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.is_success() && entry.attempts == 1)
+            .map(|entry| entry.duration)
+            .collect()
+    }
+
+    /// Returns the recorded loads, oldest first.
+    pub(crate) fn entries(&self) -> Vec<LoadAttempt> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+/// A value held by an `ArcPreloader`, as reported by [`ArcPreloader::history_values`](crate::ArcPreloader::history_values)
+pub struct RetainedValue<T> {
+    /// The value
+    pub value: Arc<T>,
+    /// Generation of the value, counting the first load as generation 1
+    pub generation: u64,
+    /// Time at which the value was loaded or stored by a reload
+    pub loaded_at: Instant,
+}
+
+impl<T> Clone for RetainedValue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+            generation: self.generation,
+            loaded_at: self.loaded_at,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RetainedValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetainedValue")
+            .field("value", &self.value)
+            .field("generation", &self.generation)
+            .field("loaded_at", &self.loaded_at)
+            .finish()
+    }
+}
+
+/// Bounded history of the values a preloader held before they were replaced
+pub(crate) struct ValueHistory<T> {
+    /// Maximum number of values kept
+    capacity: usize,
+    /// Replaced values, oldest first
+    values: Mutex<VecDeque<RetainedValue<T>>>,
+}
+
+impl<T> ValueHistory<T> {
+    /// Creates an empty history keeping at most `capacity` values.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            values: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Adds a replaced value, dropping the oldest one if the history is full.
+    pub(crate) fn record(&self, value: RetainedValue<T>) {
+        let mut values = self.values.lock().unwrap();
+        // Concurrent reloads may finish out of order, so the values are kept sorted by generation
+        let index = values.partition_point(|kept| kept.generation < value.generation);
+        values.insert(index, value);
+        if values.len() > self.capacity {
+            values.pop_front();
+        }
+    }
+
+    /// Returns the `capacity` newest values, oldest first, ending with `current` if there is one.
+    pub(crate) fn values(&self, current: Option<RetainedValue<T>>) -> Vec<RetainedValue<T>> {
+        let mut values: Vec<_> = self.values.lock().unwrap().iter().cloned().collect();
+        values.extend(current);
+        let excess = values.len().saturating_sub(self.capacity);
+        values.drain(..excess);
+        values
+    }
+}
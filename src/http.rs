@@ -0,0 +1,405 @@
+//! HTTP loader module
+//!
+//! This module provides ready-made loaders that fetch a URL with reqwest:
+//! [`Preloader::fetch_json`] deserializes a JSON response and [`Preloader::fetch_bytes`] keeps the raw body.
+//! `HttpPreloader` keeps fetching the URL on refresh, with conditional requests that skip unchanged responses.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ::bytes::Bytes;
+use futures::future::BoxFuture;
+use reqwest::{
+    header::{HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    StatusCode,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{ArcPreloader, Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Function decoding the body of a successful response
+type Decoder<T> = Arc<dyn Fn(reqwest::Response) -> BoxFuture<'static, Result<T>> + Send + Sync>;
+
+/// HTTP GET request made by a fetch loader
+///
+/// A plain URL converts into a `Fetch` with no extra headers and no timeout,
+/// so `Preloader::fetch_json("https://...")` works without building one.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::Fetch;
+/// use std::time::Duration;
+///
+/// let request = Fetch::new("https://example.com/catalog.json")
+///     .header("authorization", "Bearer token")
+///     .timeout(Duration::from_secs(10));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fetch {
+    url: String,
+    client: Option<reqwest::Client>,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
+}
+
+impl Fetch {
+    /// Creates a GET request for `url`.
+    ///
+    /// # Parameters
+    ///
+    /// - `url`: The URL to fetch
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: None,
+            headers: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    /// Sends the request with `client` instead of a new default client, reusing its connection pool and settings.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Adds a request header.
+    ///
+    /// An invalid header name or value makes the load fail with `PreloaderError::Failed`.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the timeout of the whole request, from connecting until the body has been read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Builds the request without sending it.
+    fn request(&self) -> reqwest::RequestBuilder {
+        let client = self.client.clone().unwrap_or_default();
+        let mut request = client.get(&self.url);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        request
+    }
+
+    /// Sends the request and returns the response if its status is successful.
+    pub(crate) async fn send(self) -> Result<reqwest::Response> {
+        self.request()
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(failed)
+    }
+
+    /// Fetches the response body and deserializes it from JSON.
+    async fn json<T: DeserializeOwned>(self) -> Result<T> {
+        self.send().await?.json().await.map_err(failed)
+    }
+
+    /// Fetches the response body.
+    async fn bytes(self) -> Result<Bytes> {
+        self.send().await?.bytes().await.map_err(failed)
+    }
+}
+
+impl From<&str> for Fetch {
+    fn from(url: &str) -> Self {
+        Self::new(url)
+    }
+}
+
+impl From<String> for Fetch {
+    fn from(url: String) -> Self {
+        Self::new(url)
+    }
+}
+
+/// Converts a reqwest error into a load failure.
+fn failed(error: reqwest::Error) -> PreloaderError {
+    PreloaderError::Failed(error.to_string())
+}
+
+impl<T: DeserializeOwned + Send + 'static> Preloader<T> {
+    /// Creates a new `Preloader` and immediately starts fetching `request` and deserializing the JSON response.
+    ///
+    /// Connection errors, unsuccessful status codes, timeouts, and invalid JSON make the load fail
+    /// with `PreloaderError::Failed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: A URL or a configured [`Fetch`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use preloader::Preloader;
+    /// use std::collections::HashMap;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let prices: Preloader<HashMap<String, f64>> =
+    ///         Preloader::fetch_json("https://example.com/prices.json");
+    ///     println!("{:?}", prices.get().await);
+    /// }
+    /// ```
+    pub fn fetch_json(request: impl Into<Fetch>) -> Self {
+        let mut preloader = Self::new();
+        preloader.start_loader(request.into().json());
+        preloader
+    }
+}
+
+impl Preloader<Bytes> {
+    /// Creates a new `Preloader` and immediately starts fetching the response body of `request`.
+    ///
+    /// The body is stored as received, without copying it into another buffer.
+    /// Connection errors, unsuccessful status codes, and timeouts make the load fail with `PreloaderError::Failed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: A URL or a configured [`Fetch`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn fetch_bytes(request: impl Into<Fetch>) -> Self {
+        let mut preloader = Self::new();
+        preloader.start_loader(request.into().bytes());
+        preloader
+    }
+}
+
+/// Response validators of the last fetched value, sent back on refresh as conditional request headers
+struct Cached<T> {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    value: Arc<T>,
+}
+
+/// Refreshable holder of a value fetched over HTTP
+///
+/// The URL is fetched when the holder is created, and again by [`refresh`](Self::refresh). Refreshes are
+/// conditional: the `ETag` and `Last-Modified` headers of the last response are sent back as `If-None-Match` and
+/// `If-Modified-Since`, and a `304 Not Modified` response keeps the current value without downloading or decoding
+/// it again. Only a changed response replaces the value and increments the [generation](Self::generation).
+///
+/// Cloning an `HttpPreloader` is cheap; clones share the same value.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use preloader::HttpPreloader;
+/// use std::collections::HashMap;
+/// use std::time::Duration;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let prices: HttpPreloader<HashMap<String, f64>> =
+///         HttpPreloader::json("https://example.com/prices.json");
+///     println!("{:?}", prices.get().await);
+///
+///     let refresher = prices.clone();
+///     tokio::spawn(async move {
+///         loop {
+///             tokio::time::sleep(Duration::from_secs(60)).await;
+///             // Cheap while the prices have not changed
+///             _ = refresher.refresh().await;
+///         }
+///     });
+/// }
+/// ```
+pub struct HttpPreloader<T: Send + Sync + 'static> {
+    request: Fetch,
+    decode: Decoder<T>,
+    cached: Arc<Mutex<Option<Cached<T>>>>,
+    preloader: Arc<ArcPreloader<T>>,
+}
+
+impl<T: DeserializeOwned + Send + Sync + 'static> HttpPreloader<T> {
+    /// Creates an `HttpPreloader` and immediately starts fetching `request` and deserializing the JSON response.
+    ///
+    /// Connection errors, unsuccessful status codes, timeouts, and invalid JSON make the load fail
+    /// with `PreloaderError::Failed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: A URL or a configured [`Fetch`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn json(request: impl Into<Fetch>) -> Self {
+        Self::with_decoder(
+            request.into(),
+            Arc::new(|response| Box::pin(async move { response.json().await.map_err(failed) })),
+        )
+    }
+}
+
+impl HttpPreloader<Bytes> {
+    /// Creates an `HttpPreloader` and immediately starts fetching the response body of `request`.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: A URL or a configured [`Fetch`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn bytes(request: impl Into<Fetch>) -> Self {
+        Self::with_decoder(
+            request.into(),
+            Arc::new(|response| Box::pin(async move { response.bytes().await.map_err(failed) })),
+        )
+    }
+}
+
+impl<T: Send + Sync + 'static> HttpPreloader<T> {
+    /// Creates an `HttpPreloader` decoding responses with `decode` and starts the first fetch.
+    fn with_decoder(mut request: Fetch, decode: Decoder<T>) -> Self {
+        // Refreshes share one client, so they reuse its connections
+        request.client.get_or_insert_with(reqwest::Client::new);
+        let cached = Arc::default();
+        let mut inner = Preloader::new();
+        inner.start_loader(fetch(
+            request.clone(),
+            Arc::clone(&decode),
+            Arc::clone(&cached),
+        ));
+        Self {
+            request,
+            decode,
+            cached,
+            preloader: Arc::new(ArcPreloader::from_inner(inner)),
+        }
+    }
+
+    /// Retrieves the current value, waiting until the first fetch is complete.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Arc<T>)`: The latest fetched value
+    /// - `Err(PreloaderError::Failed)`: If no value could be fetched yet
+    pub async fn get(&self) -> Result<Arc<T>> {
+        self.preloader.get().await
+    }
+
+    /// Attempts to retrieve the current value immediately.
+    pub fn try_get(&self) -> Result<Arc<T>> {
+        self.preloader.try_get()
+    }
+
+    /// Returns the generation of the current value, incremented every time a changed response replaces it.
+    ///
+    /// See [`ArcPreloader::generation`].
+    pub fn generation(&self) -> u64 {
+        self.preloader.generation()
+    }
+
+    /// Fetches the URL again with a conditional request, replacing the value if the response changed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the server answered `304 Not Modified`, keeping the current value and generation, or a new
+    ///   value was stored
+    /// - `Err(PreloaderError::Failed)`: If the request or decoding failed; the current value is kept
+    pub async fn refresh(&self) -> Result<()> {
+        self.preloader
+            .reload_result(fetch(
+                self.request.clone(),
+                Arc::clone(&self.decode),
+                Arc::clone(&self.cached),
+            ))
+            .await
+    }
+
+    /// Returns the URL being fetched.
+    pub fn url(&self) -> &str {
+        &self.request.url
+    }
+
+    /// Returns the preloader holding the value.
+    pub fn preloader(&self) -> &ArcPreloader<T> {
+        &self.preloader
+    }
+}
+
+/// Fetches `request`, conditionally if a value was fetched before, and remembers the validators of the response.
+async fn fetch<T>(
+    request: Fetch,
+    decode: Decoder<T>,
+    cached: Arc<Mutex<Option<Cached<T>>>>,
+) -> Result<Arc<T>> {
+    let mut builder = request.request();
+    if let Some(cached) = cached.lock().unwrap().as_ref() {
+        if let Some(etag) = &cached.etag {
+            builder = builder.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            builder = builder.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = builder.send().await.map_err(failed)?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        // Returning the stored `Arc` keeps the current generation
+        return match cached.lock().unwrap().as_ref() {
+            Some(cached) => Ok(Arc::clone(&cached.value)),
+            None => Err(PreloaderError::Failed(
+                "304 Not Modified without a cached value".to_string(),
+            )),
+        };
+    }
+
+    let response = response.error_for_status().map_err(failed)?;
+    let etag = response.headers().get(ETAG).cloned();
+    let last_modified = response.headers().get(LAST_MODIFIED).cloned();
+    let value = Arc::new(decode(response).await?);
+    *cached.lock().unwrap() = Some(Cached {
+        etag,
+        last_modified,
+        value: Arc::clone(&value),
+    });
+    Ok(value)
+}
+
+impl<T: Send + Sync + 'static> Clone for HttpPreloader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            request: self.request.clone(),
+            decode: Arc::clone(&self.decode),
+            cached: Arc::clone(&self.cached),
+            preloader: Arc::clone(&self.preloader),
+        }
+    }
+}
+
+/// Shows the URL and the state of the value without requiring `T: Debug`.
+impl<T: Send + Sync + 'static> fmt::Debug for HttpPreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpPreloader")
+            .field("url", &self.request.url)
+            .field("preloader", &self.preloader)
+            .finish()
+    }
+}
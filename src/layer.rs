@@ -0,0 +1,177 @@
+//! tower middleware module
+//!
+//! This module provides `PreloadLayer`, a tower middleware that gates requests on a [`Readiness`],
+//! such as a preloader or a group. Requests are held until loading has finished,
+//! or rejected with a configurable response while it is in progress.
+
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::{self, BoxFuture};
+use tower::{Layer, Service};
+
+use crate::Readiness;
+
+/// Layer gating requests on a preloader or group being loaded
+///
+/// By default, requests arriving before loading has finished are held and forwarded once it has,
+/// whether it succeeded or not. With [`reject_with`](Self::reject_with), they are answered immediately
+/// with a fixed response instead, until the values are loaded.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{PreloadLayer, PreloaderGroup};
+/// use std::{convert::Infallible, sync::Arc};
+/// use tower::{service_fn, Layer};
+///
+/// let group = Arc::new(PreloaderGroup::new());
+///
+/// // Hold requests until the group is loaded
+/// let hold = PreloadLayer::new(Arc::clone(&group));
+/// let service = hold.layer(service_fn(|_: ()| async { Ok::<_, Infallible>("ok") }));
+///
+/// // Answer immediately while loading
+/// let reject = PreloadLayer::new(group).reject_with(|| "loading");
+/// let service = reject.layer(service_fn(|_: ()| async { Ok::<_, Infallible>("ok") }));
+/// ```
+pub struct PreloadLayer<F = ()> {
+    gate: Arc<dyn Readiness>,
+    reject: F,
+}
+
+impl PreloadLayer {
+    /// Creates a layer holding requests until `gate` has finished loading.
+    ///
+    /// # Parameters
+    ///
+    /// - `gate`: The preloader or group to wait for
+    pub fn new(gate: Arc<impl Readiness>) -> Self {
+        Self { gate, reject: () }
+    }
+
+    /// Rejects requests with the response returned by `reject` until `gate` is loaded, instead of holding them.
+    ///
+    /// # Parameters
+    ///
+    /// - `reject`: Function creating the response, such as `503 Service Unavailable`
+    pub fn reject_with<F>(self, reject: F) -> PreloadLayer<F> {
+        PreloadLayer {
+            gate: self.gate,
+            reject,
+        }
+    }
+}
+
+impl<F: Clone> Clone for PreloadLayer<F> {
+    fn clone(&self) -> Self {
+        Self {
+            gate: Arc::clone(&self.gate),
+            reject: self.reject.clone(),
+        }
+    }
+}
+
+impl<F> fmt::Debug for PreloadLayer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreloadLayer")
+            .field("ready", &self.gate.is_ready())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F: Clone> Layer<S> for PreloadLayer<F> {
+    type Service = PreloadService<S, F>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PreloadService {
+            inner,
+            gate: Arc::clone(&self.gate),
+            reject: self.reject.clone(),
+        }
+    }
+}
+
+/// Service created by [`PreloadLayer`]
+pub struct PreloadService<S, F = ()> {
+    inner: S,
+    gate: Arc<dyn Readiness>,
+    reject: F,
+}
+
+impl<S: Clone, F: Clone> Clone for PreloadService<S, F> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            gate: Arc::clone(&self.gate),
+            reject: self.reject.clone(),
+        }
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for PreloadService<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreloadService")
+            .field("inner", &self.inner)
+            .field("ready", &self.gate.is_ready())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, Request> Service<Request> for PreloadService<S>
+where
+    S: Service<Request> + Clone + Send + 'static,
+    S::Future: Send,
+    Request: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if self.gate.is_ready() {
+            return Box::pin(self.inner.call(request));
+        }
+
+        // The ready service is moved into the future and a fresh clone takes its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let gate = Arc::clone(&self.gate);
+        Box::pin(async move {
+            gate.wait_ready().await;
+            inner.call(request).await
+        })
+    }
+}
+
+impl<S, F, Request> Service<Request> for PreloadService<S, F>
+where
+    S: Service<Request>,
+    S::Future: Send + 'static,
+    S::Response: Send + 'static,
+    S::Error: Send + 'static,
+    F: Fn() -> S::Response,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if self.gate.is_ready() {
+            Box::pin(self.inner.call(request))
+        } else {
+            Box::pin(future::ready(Ok((self.reject)())))
+        }
+    }
+}
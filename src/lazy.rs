@@ -0,0 +1,296 @@
+//! Lazy preloader module
+//!
+//! This module provides the `LazyPreloader` struct, a preloader that only starts loading when its value is first requested,
+//! and `StaticPreloader`, its counterpart for process-wide statics.
+
+use std::{
+    fmt,
+    future::{Future, IntoFuture},
+    pin::Pin,
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+use futures::future::BoxFuture;
+
+use crate::{builder::Options, Preloader, PreloaderError, PreloaderState};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Stored function creating the loading task
+type Factory<T> = Box<dyn FnOnce() -> BoxFuture<'static, T> + Send>;
+
+/// Preloader that starts loading on first access
+///
+/// `LazyPreloader` stores a loader factory and only spawns the load on the first call to
+/// `get()`, `try_get()`, or [`start`](Self::start). After that it behaves like a [`Preloader`].
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::LazyPreloader;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let report = LazyPreloader::new(|| async { "expensive report".to_string() });
+///     assert!(!report.is_started());
+///
+///     assert_eq!(report.get().await.unwrap(), "expensive report");
+///     assert!(report.is_loaded());
+/// }
+/// ```
+///
+/// # Generic Type
+///
+/// - `T`: The type of data to load. Must satisfy `Send + 'static`.
+pub struct LazyPreloader<T: Send + 'static> {
+    /// Preloader holding the loaded value
+    inner: Preloader<T>,
+    /// Factory creating the loading task, taken on first access
+    factory: Mutex<Option<Factory<T>>>,
+}
+
+impl<T: Send + 'static> LazyPreloader<T> {
+    /// Creates a new `LazyPreloader` that loads with the future created by `factory` on first access.
+    ///
+    /// # Parameters
+    ///
+    /// - `factory`: Function creating the asynchronous task that loads the data
+    pub fn new<F, Fut>(factory: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self::with_options(Options::new(), factory)
+    }
+
+    /// Creates a new `LazyPreloader` with the given configuration.
+    pub(crate) fn with_options<F, Fut>(options: Options, factory: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self {
+            inner: Preloader::with_options(options),
+            factory: Mutex::new(Some(Box::new(move || Box::pin(factory())))),
+        }
+    }
+
+    /// Starts loading if it has not been started yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if loading has not been started yet and this is called outside of a Tokio runtime.
+    pub fn start(&self) {
+        let mut factory = self.factory.lock().unwrap_or_else(PoisonError::into_inner);
+        if factory.is_none() {
+            return;
+        }
+        // Checked before taking the factory, so a call outside of a runtime leaves the preloader startable
+        if !self.inner.can_spawn() {
+            drop(factory);
+            panic!("LazyPreloader::start must be called within a Tokio runtime");
+        }
+        if let Some(factory) = factory.take() {
+            self.inner.load_now(factory());
+        }
+    }
+
+    /// Retrieves the loaded data, starting the load if needed and waiting until it is complete.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the data was successfully loaded
+    /// - `Err(PreloaderError)`: If an error occurred during loading
+    ///
+    /// # Panics
+    ///
+    /// Panics if loading has not been started yet and this is called outside of a Tokio runtime.
+    pub async fn get(&self) -> Result<&T> {
+        self.start();
+        self.inner.get().await
+    }
+
+    /// Attempts to retrieve the loaded data immediately, starting the load if needed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the data was successfully loaded
+    /// - `Err(PreloaderError::Loading)`: If the data is still loading, including right after the first access
+    ///
+    /// # Panics
+    ///
+    /// Panics if loading has not been started yet and this is called outside of a Tokio runtime.
+    pub fn try_get(&self) -> Result<&T> {
+        self.start();
+        self.inner.try_get()
+    }
+
+    /// Checks if loading has been started.
+    pub fn is_started(&self) -> bool {
+        self.factory
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_none()
+    }
+
+    /// Checks if the preloader has completed loading and data is available.
+    pub fn is_loaded(&self) -> bool {
+        self.inner.is_loaded()
+    }
+
+    /// Returns the current state of the preloader without starting the load.
+    ///
+    /// See [`Preloader::state`].
+    pub fn state(&self) -> PreloaderState {
+        self.inner.state()
+    }
+
+    /// Returns the name set through the builder, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    /// Takes the loaded data, starting the load if needed and consuming the `LazyPreloader`.
+    pub async fn take(self) -> Result<T> {
+        self.start();
+        self.inner.take().await
+    }
+}
+
+/// Shows whether loading has started, the state, and timing without requiring `T: Debug`.
+impl<T: Send + 'static> fmt::Debug for LazyPreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyPreloader")
+            .field("started", &self.is_started())
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+/// Awaiting a reference to a `LazyPreloader` is equivalent to calling [`LazyPreloader::get`].
+impl<'a, T: Send + Sync + 'static> IntoFuture for &'a LazyPreloader<T> {
+    type Output = Result<&'a T>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Result<&'a T>> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.get())
+    }
+}
+
+/// Process-wide preloader for `static` items
+///
+/// `StaticPreloader` can be created in a const context, so it can be stored in a `static` and read from anywhere
+/// without `once_cell::Lazy` or blocking on a runtime. Loading starts on the first access, on the Tokio runtime
+/// of that caller, and the value is kept for the lifetime of the process. It is usually declared with
+/// [`static_preloader!`](crate::static_preloader).
+///
+/// The first access should happen on a runtime that outlives the load, such as the one driving `main`;
+/// if that runtime shuts down before the load finishes, the load fails with [`PreloaderError::Cancelled`].
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{static_preloader, StaticPreloader};
+/// use tokio;
+///
+/// static CONFIG: StaticPreloader<String> = static_preloader!(|| async { "debug=true".to_string() });
+///
+/// #[tokio::main]
+/// async fn main() {
+///     assert!(!CONFIG.is_started());
+///     assert_eq!(CONFIG.get().await.unwrap(), "debug=true");
+/// }
+/// ```
+///
+/// # Generic Type
+///
+/// - `T`: The type of data to load. Must satisfy `Send + 'static`.
+pub struct StaticPreloader<T: Send + 'static> {
+    /// Function creating the loading task
+    factory: fn() -> BoxFuture<'static, T>,
+    /// Preloader created on first access
+    inner: OnceLock<LazyPreloader<T>>,
+}
+
+impl<T: Send + 'static> StaticPreloader<T> {
+    /// Creates a new `StaticPreloader` that loads with the future returned by `factory` on first access.
+    ///
+    /// [`static_preloader!`](crate::static_preloader) boxes the future of a plain async closure for you.
+    ///
+    /// # Parameters
+    ///
+    /// - `factory`: Function creating the asynchronous task that loads the data
+    pub const fn new(factory: fn() -> BoxFuture<'static, T>) -> Self {
+        Self {
+            factory,
+            inner: OnceLock::new(),
+        }
+    }
+
+    /// Returns the preloader, creating it on first access.
+    fn lazy(&self) -> &LazyPreloader<T> {
+        let factory = self.factory;
+        self.inner.get_or_init(|| LazyPreloader::new(factory))
+    }
+
+    /// Starts loading if it has not been started yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if loading has not been started yet and this is called outside of a Tokio runtime.
+    pub fn start(&self) {
+        self.lazy().start();
+    }
+
+    /// Retrieves the loaded data, starting the load if needed and waiting until it is complete.
+    ///
+    /// See [`LazyPreloader::get`].
+    pub async fn get(&self) -> Result<&T> {
+        self.lazy().get().await
+    }
+
+    /// Attempts to retrieve the loaded data immediately, starting the load if needed.
+    ///
+    /// See [`LazyPreloader::try_get`].
+    pub fn try_get(&self) -> Result<&T> {
+        self.lazy().try_get()
+    }
+
+    /// Checks if loading has been started.
+    pub fn is_started(&self) -> bool {
+        self.inner.get().is_some_and(LazyPreloader::is_started)
+    }
+
+    /// Checks if the preloader has completed loading and data is available.
+    pub fn is_loaded(&self) -> bool {
+        self.inner.get().is_some_and(LazyPreloader::is_loaded)
+    }
+
+    /// Returns the current state of the preloader without starting the load.
+    pub fn state(&self) -> PreloaderState {
+        self.inner
+            .get()
+            .map_or(PreloaderState::Idle, LazyPreloader::state)
+    }
+}
+
+/// Shows whether loading has started and the state without requiring `T: Debug`.
+impl<T: Send + 'static> fmt::Debug for StaticPreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticPreloader")
+            .field("started", &self.is_started())
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+/// Awaiting a reference to a `StaticPreloader` is equivalent to calling [`StaticPreloader::get`].
+impl<'a, T: Send + Sync + 'static> IntoFuture for &'a StaticPreloader<T> {
+    type Output = Result<&'a T>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Result<&'a T>> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.get())
+    }
+}
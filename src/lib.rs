@@ -41,15 +41,408 @@
 //! }
 //! ```
 //!
+//! # Testing with Paused Time
+//!
+//! Timeouts, TTLs, retry backoff, error caching, and rate limits all measure time with `tokio::time`,
+//! so tests can control the clock with Tokio's `test-util` feature instead of sleeping in real time.
+//! In a `#[tokio::test(start_paused = true)]` test, `tokio::time::advance` moves the clock forward and
+//! expiry happens exactly when the configured duration has passed.
+//!
+//! ```rust,ignore
+//! #[tokio::test(start_paused = true)]
+//! async fn expires_after_ttl() {
+//!     let preloader = Preloader::builder().ttl(Duration::from_secs(60)).build();
+//!     preloader.load(async { 1 }).await;
+//!     assert!(preloader.get().await.is_ok());
+//!
+//!     tokio::time::advance(Duration::from_secs(60)).await;
+//!     assert!(matches!(preloader.try_get(), Err(PreloaderError::Expired)));
+//! }
+//! ```
+//!
 //! # Module Structure
 //!
 //! - [`Preloader`]: Main preloader struct
+//! - [`ArcPreloader`]: Preloader returning shared `Arc<T>` handles
+//! - [`ArcPreloader::get_guard`]: [`ReadGuard`] deferring reloads until every guard is dropped
+//! - [`LazyPreloader`]: Preloader that starts loading on first access
+//! - [`StaticPreloader`] / [`static_preloader!`]: Process-wide `static` preloaded on first access
+//! - [`SharedPreloader`]: Cloneable handle to a shared preloader
+//! - [`PreloaderWeak`]: Non-owning reference to a shared preloader
+//! - [`Preloader::map`] / [`Preloader::and_then`]: Combinators deriving new preloaders
+//! - [`Preloader::zip`] / [`join!`]: Wait on several preloaders at once
+//! - [`AdaptiveTimeout`]: Attempt timeouts computed from a percentile of past load durations
+//! - [`AnyPreloader`]: Type-erased preloader, for collections mixing value types
+//! - [`wait_all`]: Wait on a mixed set of differently-typed preloaders, reporting which ones failed
+//! - [`PreloaderBuilder`]: Builder for configuring a preloader
+//! - [`PreloaderConfig`]: Process-wide default configuration for new preloaders
+//! - [`PreloaderGroup`]: Group of preloaders started, awaited, and shut down together
+//! - [`PreloaderGraph`]: Preloaders loaded in dependency order
+//! - [`PreloaderPool`]: Concurrency limit with prioritized queueing for loaders
+//! - [`Pipeline`]: Load built from stages feeding each other, with per-stage timing
+//! - [`MultiPreloader`]: Many items loaded with bounded concurrency, readable as they finish
+//! - [`PreloaderArray`]: Fixed number of preloaders, loaded from and awaited as arrays
+//! - [`PreloaderCache`]: Preloaders keyed by value, loaded on first access
+//! - [`RateLimiter`]: Token-bucket limit on how many loads start per second
+//! - [`RetryPolicy`]: Policy for retrying failed load attempts
+//! - [`CircuitBreaker`]: Stops load attempts for a cool-down after repeated failures
+//! - [`Watchdog`]: Reports, and optionally restarts, loads that run longer than expected
+//! - [`Budget`] / [`TimeSlice`]: Cooperative yielding in long CPU-bound loaders, and warnings for loads that run too long without yielding
+//! - [`LoadHandle`]: Handle returned by `load()` to await, inspect, or abort the loading task
+//! - [`PreloaderBuilder::task_tracker`]: Loading tasks tracked by a [`TaskTracker`], waited for before shutdown
+//! - [`Preloader::load_cancellable`] / [`Preloader::cancel`]: Loaders that stop cooperatively through a [`CancellationToken`]
+//! - [`LoadContext`]: Progress, cancellation, attempt number, and deadline handed to `load_with_context()` loaders
+//! - [`LoadSource`]: Reusable data source with `load`, `refresh`, and `validate` hooks, run by `from_source()`
+//! - [`with_deadline`]: Caller deadline after which waiting `get()` calls fail fast
+//! - [`Preloader::get_deref`]: Access `Box<dyn Trait>` and other pointer values as the pointee
+//! - [`Preloader::events`]: Stream of typed lifecycle events ([`PreloaderEvent`])
+//! - [`MemoryFootprint`]: Approximate resident bytes of loaded values, reported by preloaders and caches and usable as a cache memory budget
+//! - [`Preloader::wait_stats`]: Number of blocked callers and distribution of their wait times ([`WaitStats`])
+//! - [`Preloader::history`]: Bounded log of finished loads for post-incident analysis ([`LoadAttempt`])
+//! - [`registry`]: Process-wide registry reporting what named preloaders are still waiting on
+//! - [`typestate`]: `TypedPreloader` tracking `Idle` / `Loading` / `Loaded` in its type, with an infallible `get()`
+//! - [`Readiness`]: Readiness of a preloader or group, used to gate requests
+//! - `snapshot()` / `restore()`: Serialize and restore loaded values, and `PreloaderCache::persist()` / `restore()`
+//!   whole keyed caches (requires the `serde` feature)
+//! - `LoadRecorder`: Record loader outputs to files and replay them in later test runs (requires the `replay` feature)
+//! - `DiskCache`: Persistent bincode cache for preloaders and keyed caches (requires the `disk-cache` feature)
+//! - `EncryptionKey`: AES-256-GCM encryption of `DiskCache` files (requires the `encryption` feature)
+//! - `RedisTier`: Shared Redis tier between the local memory of a `PreloaderCache` and its loader, optionally loading each missing key on a single instance of the fleet (requires the `redis` feature)
+//! - `persist_to_store()` / `restore_from_store()`: Warm cache snapshots in S3, GCS, or Azure through `object_store` (requires the `object-store` feature)
+//! - `load_mmap()` / `load_bytes()`: Zero-copy `Bytes` loading for `Preloader<Bytes>` (requires the `bytes` feature)
+//! - `from_stream()`: Collect or fold the items of a `Stream`, reporting each item as an event
+//! - `load_blocking()` / [`BlockingPool`]: Run blocking loaders on Tokio's blocking pool or a dedicated thread pool
+//! - [`BackgroundRuntime`]: Small crate-owned runtime for low-priority preloads, kept off the request-handling workers
+//! - `load_cpu()`: Run CPU-bound loaders on a rayon thread pool (requires the `rayon` feature)
+//! - `grpc_channel()` / `grpc_channel_with_warmup()`: gRPC channel warmup built on tonic, with an optional warmup RPC as health check (requires the `tonic` feature)
+//! - `fetch_json()` / `fetch_bytes()`: HTTP GET loaders built on reqwest (requires the `http` feature)
+//! - `HttpPreloader`: Refreshable HTTP value using ETag / Last-Modified conditional requests (requires the `http` feature)
+//! - `load_spilled()` / `SpilledFile`: Stream a value too large for memory to a temporary file, removed on drop
+//! - `load_download()`: Stream a large download to disk with progress and SHA-256 verification (requires the `download` feature)
+//! - `connect()` / `fetch_all()` / `fetch_one()`: Database pool and query warmup built on sqlx (requires the `sqlx` feature)
+//! - `from_file()` / `from_file_de()`: File loaders, reloading on changes with `watch_file_de()` (requires the `watch` feature)
+//! - `refresh_cron()`: Refresh an `ArcPreloader` on a cron schedule (requires the `cron` feature)
+//! - `refresh_on_sighup()` / `refresh_on_signal()`: Refresh preloaders when the process receives a Unix signal (requires the `signal` feature)
+//! - `ConfigPreloader`: Validated, hot-reloadable JSON/TOML/YAML configuration (requires the `config` feature)
+//! - `preload` tracing spans named after each preloader, around every load (requires the `tracing` feature)
+//! - Load and wait duration histograms, a failure counter, and a waiter gauge exported through the `metrics` crate (requires the `metrics` feature)
+//! - `install_prometheus()` / `prometheus_builder()`: Prometheus exporter for those metrics (requires the `prometheus` feature)
+//! - `otel.kind` and `otel.status_code` fields on the `preload` spans, exported as OpenTelemetry spans through `tracing-opentelemetry` (requires the `tracing` feature)
+//! - `PreloadLayer`: tower middleware holding or rejecting requests until loaded (requires the `tower` feature)
+//! - [`actix`]: Extractor and readiness route for actix-web servers (requires the `actix` feature)
+//! - [`axum`]: Extractor and readiness route for axum servers (requires the `axum` feature)
+//! - `PreloadPlugin`: Bevy plugin driving preloaders on Bevy's task pools (requires the `bevy` feature)
+//! - [`ExclusivePreloader`]: Preloader sharing a `Send` but not `Sync` value across threads through a lock
+//! - [`PinnedPreloader`]: Preloader owning a value that is not `Send` on a dedicated thread
+//! - `BlockingPreloader`: Preloader for synchronous code, loading on a `std::thread` (requires the `blocking` feature)
+//! - `Preloader::pending()`: Preloader completed by hand through a `TestHandle`, for tests (requires the `test-util` feature)
+//! - `ffi`: C API over byte-buffer preloaders for native applications (requires the `ffi` feature)
+//! - `chaos`: Name-keyed injection of latency, failures, and hangs into loads, for tests (requires the `test-util` feature)
+//! - `Preload`: Derive macro for structs of preloaders (requires the `derive` feature)
 
+mod macros;
+
+#[cfg(feature = "actix")]
+pub mod actix;
+mod adaptive;
+mod any;
+mod arc;
+mod array;
+#[cfg(feature = "axum")]
+pub mod axum;
+mod background;
+#[cfg(feature = "bevy")]
+mod bevy;
+#[cfg(feature = "blocking")]
+mod blocking;
+mod blocking_pool;
+mod budget;
+mod builder;
+mod cache;
+mod cell;
+#[cfg(feature = "test-util")]
+pub mod chaos;
+mod circuit;
+mod combinator;
+#[cfg(feature = "config")]
+mod config;
+mod context;
+#[cfg(feature = "rayon")]
+mod cpu;
+#[cfg(feature = "sqlx")]
+mod database;
+mod deadline;
+mod defaults;
+mod deref;
+#[cfg(feature = "disk-cache")]
+mod disk;
+#[cfg(feature = "download")]
+mod download;
+#[cfg(feature = "encryption")]
+mod encryption;
+mod events;
+mod exclusive;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod file;
+mod footprint;
+mod graph;
+mod group;
+#[cfg(feature = "tonic")]
+mod grpc;
+mod handle;
+mod history;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "tower")]
+mod layer;
+mod lazy;
+mod multi;
+mod pinned;
+mod pipeline;
+mod pool;
 mod preloader;
+#[cfg(feature = "prometheus")]
+mod prometheus;
+mod rate;
+mod readiness;
+#[cfg(feature = "redis")]
+mod redis;
+pub mod registry;
+#[cfg(feature = "object-store")]
+mod remote;
+#[cfg(feature = "replay")]
+mod replay;
+mod retry;
+#[cfg(feature = "cron")]
+mod schedule;
+mod shared;
+#[cfg(all(unix, feature = "signal"))]
+mod signal;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod source;
+mod spill;
+mod stats;
+mod stream;
+mod sync;
+#[cfg(feature = "test-util")]
+mod testing;
+pub mod typestate;
+mod waiters;
+mod watchdog;
+mod weak;
+#[cfg(feature = "bytes")]
+mod zero_copy;
 
+pub use adaptive::*;
+pub use any::*;
+pub use arc::*;
+pub use array::*;
+pub use background::*;
+#[cfg(feature = "bevy")]
+pub use bevy::*;
+#[cfg(feature = "blocking")]
+pub use blocking::*;
+pub use blocking_pool::*;
+pub use budget::*;
+pub use builder::*;
+pub use cache::*;
+pub use circuit::*;
+#[cfg(feature = "config")]
+pub use config::*;
+pub use context::*;
+pub use deadline::*;
+pub use defaults::*;
+#[cfg(feature = "disk-cache")]
+pub use disk::*;
+#[cfg(feature = "download")]
+pub use download::*;
+#[cfg(feature = "encryption")]
+pub use encryption::EncryptionKey;
+pub use events::*;
+pub use exclusive::*;
+pub use footprint::*;
+pub use graph::*;
+pub use group::*;
+pub use handle::*;
+pub use history::*;
+#[cfg(feature = "http")]
+pub use http::*;
+#[cfg(feature = "tower")]
+pub use layer::*;
+pub use lazy::*;
+pub use multi::*;
+pub use pinned::*;
+pub use pipeline::*;
+pub use pool::*;
 pub use preloader::*;
+#[cfg(feature = "prometheus")]
+pub use prometheus::*;
+pub use rate::*;
+pub use readiness::*;
+#[cfg(feature = "redis")]
+pub use redis::*;
+#[cfg(feature = "replay")]
+pub use replay::*;
+pub use retry::*;
+pub use shared::*;
+#[cfg(all(unix, feature = "signal"))]
+pub use signal::*;
+pub use source::*;
+pub use spill::*;
+pub use stats::*;
+#[cfg(feature = "test-util")]
+pub use testing::*;
+pub use waiters::*;
+pub use watchdog::*;
+pub use weak::*;
+
+#[cfg(feature = "bytes")]
+pub use ::bytes::Bytes;
+#[cfg(feature = "cron")]
+pub use ::cron::Schedule;
+#[cfg(feature = "prometheus")]
+pub use ::metrics_exporter_prometheus::PrometheusHandle;
+#[cfg(feature = "derive")]
+pub use preloader_derive::Preload;
+#[cfg(all(unix, feature = "signal"))]
+pub use tokio::signal::unix::SignalKind;
+pub use tokio_util::{sync::CancellationToken, task::TaskTracker};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use tokio;
+}
+
+// Model checks of the state machine, run with `RUSTFLAGS="--cfg preloader_loom" cargo test --release --lib loom`
+#[cfg(all(test, preloader_loom))]
+mod loom_tests {
+    use super::*;
+    use crate::cell::ValueCell;
+    use loom::{sync::Arc, thread};
+    use std::sync::atomic::Ordering;
+
+    /// Claims the load the way `load()` does, publishing the value if the claim succeeds.
+    fn load(cell: &ValueCell<String>, value: &str) -> bool {
+        let claimed = cell
+            .compare_exchange(
+                PreloaderState::Idle,
+                PreloaderState::Start,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_ok();
+        if claimed {
+            cell.store(PreloaderState::Loading, Ordering::Release);
+            unsafe { cell.publish(value.to_string()) };
+        }
+        claimed
+    }
+
+    /// Reads the value the way `try_get()` does.
+    fn try_get(cell: &ValueCell<String>) -> Option<&String> {
+        match cell.load(Ordering::Acquire) {
+            PreloaderState::Loaded => Some(unsafe { cell.get() }),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn loom_publish_is_visible_to_readers() {
+        loom::model(|| {
+            let cell = Arc::new(ValueCell::new(PreloaderState::Idle));
+
+            let reader = {
+                let cell = Arc::clone(&cell);
+                thread::spawn(move || {
+                    if let Some(value) = try_get(&cell) {
+                        assert_eq!(value, "data");
+                    }
+                })
+            };
+
+            assert!(load(&cell, "data"));
+            reader.join().unwrap();
+            assert_eq!(try_get(&cell).unwrap(), "data");
+        });
+    }
+
+    #[test]
+    fn loom_concurrent_loads_publish_once() {
+        loom::model(|| {
+            let cell = Arc::new(ValueCell::new(PreloaderState::Idle));
+
+            let other = {
+                let cell = Arc::clone(&cell);
+                thread::spawn(move || load(&cell, "second"))
+            };
+
+            let first = load(&cell, "first");
+            let second = other.join().unwrap();
+            assert!(first ^ second);
+
+            let expected = if first { "first" } else { "second" };
+            assert_eq!(try_get(&cell).unwrap(), expected);
+        });
+    }
+
+    #[test]
+    fn loom_get_waits_for_publication() {
+        loom::model(|| {
+            let cell = Arc::new(ValueCell::new(PreloaderState::Idle));
+
+            let waiter = {
+                let cell = Arc::clone(&cell);
+                thread::spawn(move || loop {
+                    if let Some(value) = try_get(&cell) {
+                        assert_eq!(value, "data");
+                        break;
+                    }
+                    thread::yield_now();
+                })
+            };
+
+            load(&cell, "data");
+            waiter.join().unwrap();
+        });
+    }
+
+    #[test]
+    fn loom_retry_after_failure_publishes_once() {
+        loom::model(|| {
+            let cell = Arc::new(ValueCell::new(PreloaderState::Failed));
 
-#[cfg(test)]
+            // Both threads race to restart the failed load, like concurrent `restart()` calls
+            let restart = |cell: &ValueCell<String>, value: &str| {
+                let claimed = cell
+                    .compare_exchange(
+                        PreloaderState::Failed,
+                        PreloaderState::Start,
+                        Ordering::Acquire,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok();
+                if claimed {
+                    unsafe { cell.publish(value.to_string()) };
+                }
+                claimed
+            };
+
+            let other = {
+                let cell = Arc::clone(&cell);
+                thread::spawn(move || restart(&cell, "second"))
+            };
+
+            let first = restart(&cell, "first");
+            let second = other.join().unwrap();
+            assert!(first ^ second);
+            assert!(try_get(&cell).is_some());
+        });
+    }
+}
+
+#[cfg(all(test, not(preloader_loom)))]
 mod tests {
     use super::*;
     use std::sync::Arc;
@@ -65,6 +458,20 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_ready_preloader() {
+        let preloader = Preloader::ready("ready data".to_string());
+        assert!(preloader.is_loaded());
+        assert_eq!(*preloader.try_get().unwrap(), "ready data");
+
+        // load() is ignored once a value is present
+        preloader.load(async { "other data".to_string() }).await;
+        assert_eq!(*preloader.get().await.unwrap(), "ready data");
+
+        let preloader: Preloader<i32> = 7.into();
+        assert_eq!(preloader.take().await.unwrap(), 7);
+    }
+
     #[tokio::test]
     async fn test_load_and_get() {
         let preloader = Preloader::new();
@@ -83,6 +490,42 @@ mod tests {
         assert_eq!(*result.unwrap(), "test data");
     }
 
+    #[tokio::test]
+    async fn test_await_reference() {
+        let preloader = Preloader::new();
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(10)).await;
+                "awaited data".to_string()
+            })
+            .await;
+
+        let result = (&preloader).await;
+        assert!(result.is_ok());
+        assert_eq!(*result.unwrap(), "awaited data");
+
+        let preloader = Preloader::<String>::new();
+        assert!(matches!((&preloader).await, Err(PreloaderError::NotLoaded)));
+    }
+
+    #[tokio::test]
+    async fn test_spawn() {
+        let preloader = Preloader::spawn(async {
+            sleep(Duration::from_millis(10)).await;
+            "spawned data".to_string()
+        });
+
+        // Already loading, no load() call needed
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Loading)));
+
+        // Later load() calls are ignored
+        preloader.load(async { "other data".to_string() }).await;
+
+        let result = preloader.get().await;
+        assert!(result.is_ok());
+        assert_eq!(*result.unwrap(), "spawned data");
+    }
+
     #[tokio::test]
     async fn test_try_get_before_load() {
         let preloader = Preloader::<String>::new();
@@ -111,6 +554,28 @@ mod tests {
         assert!(matches!(result.unwrap_err(), PreloaderError::Loading));
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_get_waits_through_start() {
+        for _ in 0..100 {
+            let preloader = Arc::new(Preloader::new());
+
+            let loader = Arc::clone(&preloader);
+            let handle = tokio::spawn(async move {
+                loader.load(async { "started".to_string() }).await;
+            });
+
+            // Once load() has claimed the preloader, get() must not report NotLoaded
+            while matches!(preloader.try_get(), Err(PreloaderError::NotLoaded)) {
+                tokio::task::yield_now().await;
+            }
+            let result = preloader.get().await;
+            assert!(result.is_ok());
+            assert_eq!(*result.unwrap(), "started");
+
+            handle.await.unwrap();
+        }
+    }
+
     #[tokio::test]
     async fn test_try_get_after_load() {
         let preloader = Preloader::new();
@@ -202,9 +667,15 @@ mod tests {
             })
             .await;
 
-        // Check error handling
+        // The panic is reported with its message
         let result = preloader.get().await;
-        assert!(result.is_err());
+        assert!(
+            matches!(result, Err(PreloaderError::Panicked(message)) if message == "intentional panic")
+        );
+        assert!(matches!(
+            preloader.try_get(),
+            Err(PreloaderError::Panicked(_))
+        ));
     }
 
     #[tokio::test]
@@ -303,6 +774,7 @@ mod tests {
         let mut handles = vec![];
         for i in 0..3 {
             let preloader = Arc::clone(&preloader);
+            #[allow(clippy::redundant_locals)]
             let i = i; // Move i into closure
             handles.push(tokio::spawn(async move {
                 preloader
@@ -344,7 +816,7 @@ mod tests {
         let result = preloader.take().await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "take test data");
-        
+
         // Note: preloader is consumed and cannot be used after take()
     }
 
@@ -356,7 +828,7 @@ mod tests {
         let result = preloader.take().await;
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), PreloaderError::NotLoaded));
-        
+
         // Note: preloader is consumed and cannot be used after take()
     }
 
@@ -376,17 +848,17 @@ mod tests {
         let result = preloader.take().await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "slow data for take");
-        
+
         // Note: preloader is consumed and cannot be used after take()
     }
 
     #[tokio::test]
     async fn test_is_loaded() {
         let preloader = Preloader::new();
-        
+
         // Initially not loaded
         assert!(!preloader.is_loaded());
-        
+
         // Start loading
         preloader
             .load(async {
@@ -394,14 +866,6254 @@ mod tests {
                 "loaded data".to_string()
             })
             .await;
-        
+
         // Still not loaded immediately after starting
         assert!(!preloader.is_loaded());
-        
+
         // Wait for completion
         preloader.get().await.unwrap();
-        
+
         // Now it should be loaded
         assert!(preloader.is_loaded());
     }
+
+    #[tokio::test]
+    async fn test_builder_timeout() {
+        let preloader = Preloader::builder()
+            .timeout(Duration::from_millis(10))
+            .build();
+
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(200)).await;
+                "too slow".to_string()
+            })
+            .await;
+
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Timeout)
+        ));
+        // The failure is remembered
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Timeout)));
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Timeout)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_load_with_retry() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let preloader = Preloader::builder()
+            .timeout(Duration::from_millis(20))
+            .retry(RetryPolicy::fixed(2, Duration::from_millis(1)))
+            .build();
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+        preloader
+            .load_with(move || {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match attempt {
+                        // First attempt times out
+                        0 => sleep(Duration::from_millis(200)).await,
+                        // Second attempt panics
+                        1 => panic!("intentional panic"),
+                        _ => {}
+                    }
+                    attempt
+                }
+            })
+            .await;
+
+        assert_eq!(*preloader.get().await.unwrap(), 2);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_load_with_retries_exhausted() {
+        let preloader = Preloader::builder()
+            .timeout(Duration::from_millis(5))
+            .retry(RetryPolicy::fixed(1, Duration::from_millis(1)))
+            .build();
+
+        preloader
+            .load_with(|| async {
+                sleep(Duration::from_millis(200)).await;
+                "too slow".to_string()
+            })
+            .await;
+
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Timeout)
+        ));
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::exponential(5, Duration::from_millis(100))
+            .with_max_backoff(Duration::from_millis(300));
+        assert_eq!(policy.max_retries(), 5);
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(300));
+        assert_eq!(policy.backoff(10), Duration::from_millis(300));
+
+        let policy = RetryPolicy::fixed(2, Duration::from_millis(50));
+        assert_eq!(policy.backoff(0), Duration::from_millis(50));
+        assert_eq!(policy.backoff(1), Duration::from_millis(50));
+
+        assert_eq!(RetryPolicy::default().max_retries(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_builder_abort_on_drop() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let flag = Arc::clone(&finished);
+        let preloader = Preloader::builder().abort_on_drop(true).spawn(async move {
+            sleep(Duration::from_millis(20)).await;
+            flag.store(true, Ordering::SeqCst);
+        });
+        drop(preloader);
+
+        sleep(Duration::from_millis(50)).await;
+        assert!(!finished.load(Ordering::SeqCst));
+
+        // Without abort_on_drop the task keeps running
+        let flag = Arc::clone(&finished);
+        let preloader = Preloader::spawn(async move {
+            sleep(Duration::from_millis(20)).await;
+            flag.store(true, Ordering::SeqCst);
+        });
+        drop(preloader);
+
+        sleep(Duration::from_millis(50)).await;
+        assert!(finished.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_builder_ttl() {
+        let preloader = Preloader::builder().ttl(Duration::from_millis(30)).build();
+        assert!(!preloader.is_expired());
+
+        preloader.load(async { "fresh".to_string() }).await;
+        assert_eq!(*preloader.get().await.unwrap(), "fresh");
+        assert!(!preloader.is_expired());
+
+        sleep(Duration::from_millis(50)).await;
+        assert!(preloader.is_expired());
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Expired)));
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Expired)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_builder_name() {
+        let preloader = Preloader::<String>::builder().name("config").build();
+        assert_eq!(preloader.name(), Some("config"));
+        assert_eq!(Preloader::<String>::new().name(), None);
+
+        // Named preloaders spawn their loading tasks under the name with `tokio_unstable`
+        preloader.load(async { "loaded".to_string() }).await;
+        assert_eq!(preloader.get().await.unwrap(), "loaded");
+    }
+
+    #[test]
+    fn test_builder_runtime() {
+        let background = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("preload-worker")
+            .enable_all()
+            .build()
+            .unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            let preloader = Preloader::builder()
+                .runtime(background.handle().clone())
+                .build();
+            preloader
+                .load(async { std::thread::current().name().map(str::to_string) })
+                .await;
+
+            let thread = preloader.get().await.unwrap();
+            assert_eq!(thread.as_deref(), Some("preload-worker"));
+        });
+    }
+
+    #[tokio::test]
+    async fn test_get_cloned() {
+        let preloader = Arc::new(Preloader::new());
+        assert!(matches!(
+            preloader.try_get_cloned(),
+            Err(PreloaderError::NotLoaded)
+        ));
+
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(10)).await;
+                vec![1, 2, 3]
+            })
+            .await;
+
+        let cloned = preloader.get_cloned().await.unwrap();
+        assert_eq!(cloned, vec![1, 2, 3]);
+        assert_eq!(preloader.try_get_cloned().unwrap(), vec![1, 2, 3]);
+
+        // The owned copy can outlive the preloader
+        drop(preloader);
+        assert_eq!(cloned.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_arc_preloader() {
+        let preloader = ArcPreloader::new();
+        assert!(matches!(
+            preloader.try_get(),
+            Err(PreloaderError::NotLoaded)
+        ));
+
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(10)).await;
+                "shared data".to_string()
+            })
+            .await;
+
+        let data = preloader.get().await.unwrap();
+        let again = preloader.try_get().unwrap();
+        assert!(Arc::ptr_eq(&data, &again));
+
+        // The handle can be moved into a spawned task
+        let handle = tokio::spawn(async move { data.len() });
+        assert_eq!(handle.await.unwrap(), 11);
+
+        let taken = preloader.take().await.unwrap();
+        assert_eq!(*taken, "shared data");
+    }
+
+    #[tokio::test]
+    async fn test_arc_preloader_builder() {
+        let preloader = Preloader::<String>::builder()
+            .name("shared")
+            .timeout(Duration::from_millis(10))
+            .build_arc();
+        assert_eq!(preloader.name(), Some("shared"));
+
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(200)).await;
+                "too slow".to_string()
+            })
+            .await;
+        assert!(matches!((&preloader).await, Err(PreloaderError::Timeout)));
+
+        let preloader = ArcPreloader::spawn(async { 5 });
+        assert_eq!(*preloader.get().await.unwrap(), 5);
+        assert_eq!(*ArcPreloader::from(6).try_get().unwrap(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_try_take() {
+        // Not loaded: the preloader is handed back
+        let preloader = Preloader::<String>::new();
+        let preloader = preloader.try_take().err().unwrap();
+
+        // Loading: handed back again
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(10)).await;
+                "try take data".to_string()
+            })
+            .await;
+        let preloader = preloader.try_take().err().unwrap();
+
+        // Loaded: the value is taken
+        preloader.get().await.unwrap();
+        assert_eq!(preloader.try_take().ok().unwrap(), "try take data");
+    }
+
+    #[tokio::test]
+    async fn test_take_timeout() {
+        let preloader = Preloader::spawn(async {
+            sleep(Duration::from_millis(100)).await;
+            "slow take data".to_string()
+        });
+
+        // Too short: the preloader is handed back, still loading
+        let preloader = preloader
+            .take_timeout(Duration::from_millis(10))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Loading)));
+
+        let result = preloader.take_timeout(Duration::from_secs(5)).await;
+        assert_eq!(result.ok().unwrap(), "slow take data");
+
+        // Never started: handed back without waiting for the timeout
+        let preloader = Preloader::<String>::new();
+        assert!(preloader
+            .take_timeout(Duration::from_secs(5))
+            .await
+            .is_err());
+
+        let preloader = ArcPreloader::ready(1);
+        assert_eq!(*preloader.try_take().ok().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_into_inner() {
+        assert_eq!(Preloader::<i32>::new().into_inner(), None);
+        assert_eq!(Preloader::ready(1).into_inner(), Some(1));
+
+        let preloader = Preloader::new();
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(10)).await;
+                2
+            })
+            .await;
+        preloader.get().await.unwrap();
+        assert_eq!(preloader.into_inner(), Some(2));
+
+        // A load that has not been observed yet is not in the Loaded state
+        let preloader = Preloader::spawn(async {
+            sleep(Duration::from_millis(50)).await;
+            3
+        });
+        assert_eq!(preloader.into_inner(), None);
+
+        assert_eq!(ArcPreloader::ready(4).into_inner().as_deref(), Some(&4));
+    }
+
+    #[tokio::test]
+    async fn test_get_mut_and_update() {
+        let mut preloader = Preloader::new();
+        assert!(preloader.get_mut().is_none());
+        assert_eq!(preloader.update(|v: &mut Vec<i32>| v.len()), None);
+
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(10)).await;
+                vec![1, 2]
+            })
+            .await;
+        preloader.get().await.unwrap();
+
+        preloader.get_mut().unwrap().push(3);
+        let len = preloader.update(|v| {
+            v.push(4);
+            v.len()
+        });
+        assert_eq!(len, Some(4));
+        assert_eq!(*preloader.get().await.unwrap(), vec![1, 2, 3, 4]);
+
+        let mut preloader = ArcPreloader::ready(1);
+        *preloader.get_mut().unwrap() += 1;
+        let shared = preloader.try_get().unwrap();
+        // Another handle exists, so mutable access is refused
+        assert!(preloader.get_mut().is_none());
+        assert_eq!(*shared, 2);
+    }
+
+    #[tokio::test]
+    async fn test_set() {
+        let preloader = Preloader::new();
+        assert!(preloader.set("pushed".to_string()).is_ok());
+        assert!(preloader.is_loaded());
+        assert_eq!(*preloader.get().await.unwrap(), "pushed");
+
+        // A value is already present
+        assert_eq!(
+            preloader.set("second".to_string()),
+            Err("second".to_string())
+        );
+        preloader.load(async { "loaded".to_string() }).await;
+        assert_eq!(*preloader.try_get().unwrap(), "pushed");
+
+        // A loader is already running
+        let preloader = Preloader::spawn(async {
+            sleep(Duration::from_millis(10)).await;
+            "loaded".to_string()
+        });
+        assert_eq!(
+            preloader.set("pushed".to_string()),
+            Err("pushed".to_string())
+        );
+        assert_eq!(*preloader.get().await.unwrap(), "loaded");
+
+        let preloader = ArcPreloader::new();
+        assert!(preloader.set(1).is_ok());
+        assert_eq!(preloader.set(2), Err(2));
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_map() {
+        let source = Arc::new(Preloader::new());
+        let derived = Arc::clone(&source).map(|text: &String| text.len());
+
+        // The derived preloader waits for the source to be loaded
+        sleep(Duration::from_millis(10)).await;
+        assert!(matches!(derived.try_get(), Err(PreloaderError::Loading)));
+
+        source
+            .load(async {
+                sleep(Duration::from_millis(10)).await;
+                "mapped".to_string()
+            })
+            .await;
+        assert_eq!(*derived.get().await.unwrap(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_and_then() {
+        let source = Arc::new(Preloader::ready(21));
+        let derived = Arc::clone(&source).and_then(|value: &i32| {
+            let value = *value;
+            async move {
+                sleep(Duration::from_millis(10)).await;
+                value * 2
+            }
+        });
+        assert_eq!(*derived.get().await.unwrap(), 42);
+
+        // Source failures propagate to the derived preloader
+        let source = Arc::new(
+            Preloader::builder()
+                .timeout(Duration::from_millis(5))
+                .spawn(async {
+                    sleep(Duration::from_millis(200)).await;
+                    0
+                }),
+        );
+        let derived = source.map(|value: &i32| value + 1);
+        assert!(matches!(derived.get().await, Err(PreloaderError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_zip_and_join() {
+        let a = Preloader::spawn(async {
+            sleep(Duration::from_millis(20)).await;
+            "a".to_string()
+        });
+        let b = Preloader::spawn(async {
+            sleep(Duration::from_millis(10)).await;
+            2
+        });
+        let c = Preloader::ready(vec![3]);
+
+        let (a_value, b_value) = a.zip(&b).await.unwrap();
+        assert_eq!(a_value, "a");
+        assert_eq!(*b_value, 2);
+
+        let (a_value, b_value, c_value) = crate::join!(a, b, c).unwrap();
+        assert_eq!(a_value, "a");
+        assert_eq!(*b_value, 2);
+        assert_eq!(*c_value, vec![3]);
+
+        // Any error is returned
+        let idle = Preloader::<i32>::new();
+        assert!(matches!(
+            Preloader::zip(&a, &idle).await,
+            Err(PreloaderError::NotLoaded)
+        ));
+        assert!(matches!(
+            crate::join!(a, idle),
+            Err(PreloaderError::NotLoaded)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_load_race() {
+        let preloader = Preloader::new();
+        preloader
+            .load_race((1..=3u64).rev().map(|i| async move {
+                sleep(Duration::from_millis(i * 20)).await;
+                i
+            }))
+            .await;
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+
+        // Failed futures do not win the race
+        let preloader = Preloader::builder()
+            .timeout(Duration::from_millis(50))
+            .build();
+        let futures: Vec<std::pin::Pin<Box<dyn std::future::Future<Output = &str> + Send>>> = vec![
+            Box::pin(async { panic!("intentional panic") }),
+            Box::pin(async {
+                sleep(Duration::from_millis(200)).await;
+                "too slow"
+            }),
+            Box::pin(async {
+                sleep(Duration::from_millis(20)).await;
+                "survivor"
+            }),
+        ];
+        preloader.load_race(futures).await;
+        assert_eq!(*preloader.get().await.unwrap(), "survivor");
+
+        // Every future failing fails the preloader
+        let preloader = Preloader::builder()
+            .timeout(Duration::from_millis(5))
+            .build();
+        preloader
+            .load_race([
+                sleep(Duration::from_millis(100)),
+                sleep(Duration::from_millis(100)),
+            ])
+            .await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Timeout)
+        ));
+
+        let preloader = ArcPreloader::new();
+        preloader
+            .load_race(Vec::<std::future::Ready<i32>>::new())
+            .await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::NotLoaded)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_load_with_hedge() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let preloader = Preloader::builder()
+            .hedge(Duration::from_millis(20))
+            .build();
+
+        // The first attempt stalls; the hedging attempt finishes first
+        let attempts = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&attempts);
+        preloader
+            .load_with(move || {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    let delay = if attempt == 0 { 500 } else { 10 };
+                    sleep(Duration::from_millis(delay)).await;
+                    attempt
+                }
+            })
+            .await;
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        // A fast first attempt does not trigger hedging
+        let preloader = Preloader::builder()
+            .hedge(Duration::from_millis(50))
+            .build();
+        let attempts = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&attempts);
+        preloader
+            .load_with(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async { "fast" }
+            })
+            .await;
+        assert_eq!(*preloader.get().await.unwrap(), "fast");
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        // A failing hedge falls back to the primary attempt
+        let preloader = Preloader::builder()
+            .hedge(Duration::from_millis(10))
+            .build();
+        let attempts = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&attempts);
+        preloader
+            .load_with(move || {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 1 {
+                        panic!("intentional panic");
+                    }
+                    sleep(Duration::from_millis(40)).await;
+                    "primary"
+                }
+            })
+            .await;
+        assert_eq!(*preloader.get().await.unwrap(), "primary");
+    }
+
+    #[tokio::test]
+    async fn test_group() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let mut group = PreloaderGroup::new().max_concurrency(2);
+        let mut preloaders = vec![];
+        for i in 0..5 {
+            let running = Arc::clone(&running);
+            let peak = Arc::clone(&peak);
+            preloaders.push(group.add(format!("entry-{}", i), async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                sleep(Duration::from_millis(10)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                i
+            }));
+        }
+        assert_eq!(group.len(), 5);
+        assert!(!group.is_ready());
+        assert!(group
+            .status()
+            .iter()
+            .all(|status| matches!(status.state, EntryState::Pending)));
+
+        group.start().await;
+        group.wait_all().await.unwrap();
+
+        assert!(group.is_ready());
+        assert_eq!(group.loaded_count(), 5);
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+        for (i, preloader) in preloaders.iter().enumerate() {
+            assert_eq!(*preloader.try_get().unwrap(), i);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_group_failures() {
+        let mut group = PreloaderGroup::new();
+        group.add("fast", async { 1 });
+        group.add_with(
+            "slow",
+            Arc::new(
+                Preloader::builder()
+                    .timeout(Duration::from_millis(5))
+                    .build(),
+            ),
+            async {
+                sleep(Duration::from_millis(200)).await;
+                2
+            },
+        );
+
+        group.start().await;
+        let error = group.wait_all().await.unwrap_err();
+        assert_eq!(error.failures().len(), 1);
+        assert_eq!(error.failures()[0].0, "slow");
+        assert!(matches!(error.failures()[0].1, PreloaderError::Timeout));
+
+        let status = group.status();
+        assert_eq!(status[0].name, "fast");
+        assert!(matches!(status[0].state, EntryState::Loaded));
+        assert!(matches!(
+            status[1].state,
+            EntryState::Failed(PreloaderError::Timeout)
+        ));
+        assert!(!group.is_ready());
+        assert_eq!(group.loaded_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_preload_macros() {
+        let single = crate::preload!(async { 1 });
+        let (a, b) = crate::preload_all!(
+            async {
+                sleep(Duration::from_millis(10)).await;
+                "a"
+            },
+            async { 2u8 },
+        );
+
+        assert_eq!(*single.get().await.unwrap(), 1);
+        let (a, b) = crate::join!(a, b).unwrap();
+        assert_eq!(*a, "a");
+        assert_eq!(*b, 2);
+    }
+
+    #[tokio::test]
+    async fn test_graph() {
+        let mut builder = PreloaderGraph::builder();
+        let total = builder.add("total", &["a", "b"], |inputs| {
+            let sum = inputs.get::<u32>("a") + inputs.get::<u32>("b");
+            async move { sum }
+        });
+        builder.add("a", &["base"], |inputs| {
+            let base = *inputs.get::<u32>("base");
+            async move {
+                sleep(Duration::from_millis(10)).await;
+                base + 1
+            }
+        });
+        builder.add("b", &["base"], |inputs| {
+            let base = *inputs.get::<u32>("base");
+            async move { base + 2 }
+        });
+        builder.add("base", &[], |_| async { 10u32 });
+
+        let graph = builder.build().unwrap();
+        let order = graph.order();
+        assert_eq!(order[0], "base");
+        assert_eq!(order[3], "total");
+
+        graph.start().await;
+        graph.wait_all().await.unwrap();
+        assert_eq!(*total.try_get().unwrap(), 23);
+    }
+
+    #[tokio::test]
+    async fn test_graph_dependency_failure() {
+        let mut builder = PreloaderGraph::builder();
+        let failing: Arc<Preloader<u32>> = builder.add("failing", &[], |_| async {
+            panic!("load failed");
+        });
+        let dependent = builder.add("dependent", &["failing"], |inputs| {
+            let value = *inputs.get::<u32>("failing");
+            async move { value }
+        });
+
+        let graph = builder.build().unwrap();
+        graph.start().await;
+        let error = graph.wait_all().await.unwrap_err();
+        assert_eq!(error.failures().len(), 2);
+        assert!(failing.try_get().is_err());
+        assert!(dependent.try_get().is_err());
+    }
+
+    #[test]
+    fn test_graph_validation() {
+        let mut builder = PreloaderGraph::builder();
+        builder.add("a", &["c"], |_| async { 1 });
+        builder.add("b", &["a"], |_| async { 2 });
+        builder.add("c", &["b"], |_| async { 3 });
+        builder.add("d", &[], |_| async { 4 });
+        assert_eq!(
+            builder.build().err().unwrap(),
+            GraphError::Cycle(vec![
+                "a".to_string(),
+                "c".to_string(),
+                "b".to_string(),
+                "a".to_string()
+            ])
+        );
+
+        let mut builder = PreloaderGraph::builder();
+        builder.add("a", &["missing"], |_| async { 1 });
+        assert_eq!(
+            builder.build().err().unwrap(),
+            GraphError::UnknownDependency {
+                node: "a".to_string(),
+                dependency: "missing".to_string()
+            }
+        );
+
+        let mut builder = PreloaderGraph::builder();
+        builder.add("a", &[], |_| async { 1 });
+        builder.add("a", &[], |_| async { 2 });
+        assert_eq!(
+            builder.build().err().unwrap(),
+            GraphError::DuplicateName("a".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pool_priorities() {
+        let pool = PreloaderPool::new(1);
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let blocker = Preloader::new();
+        pool.load(&blocker, Priority::Normal, async {
+            sleep(Duration::from_millis(20)).await;
+        })
+        .await;
+        sleep(Duration::from_millis(5)).await;
+        assert_eq!(pool.running(), 1);
+
+        let mut preloaders = vec![];
+        for (name, priority) in [
+            ("low", Priority::Low),
+            ("normal", Priority::Normal),
+            ("high", Priority::High),
+            ("high-2", Priority::High),
+        ] {
+            let preloader = Preloader::new();
+            let order = Arc::clone(&order);
+            pool.load(&preloader, priority, async move {
+                order.lock().unwrap().push(name);
+            })
+            .await;
+            preloaders.push(preloader);
+        }
+        sleep(Duration::from_millis(5)).await;
+        assert_eq!(pool.queued(), 4);
+
+        for preloader in &preloaders {
+            preloader.get().await.unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), ["high", "high-2", "normal", "low"]);
+        assert_eq!(pool.running(), 0);
+        assert_eq!(pool.queued(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pool_concurrency_limit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let pool = PreloaderPool::new(3);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let mut preloaders = vec![];
+        for _ in 0..10 {
+            let preloader = Preloader::new();
+            let running = Arc::clone(&running);
+            let peak = Arc::clone(&peak);
+            pool.load(&preloader, Priority::Normal, async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                sleep(Duration::from_millis(5)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            })
+            .await;
+            preloaders.push(preloader);
+        }
+
+        for preloader in &preloaders {
+            preloader.get().await.unwrap();
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(pool.running(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let pool =
+            PreloaderPool::new(10).with_rate_limit(RateLimiter::per_second(50).with_burst(2));
+        let started = tokio::time::Instant::now();
+
+        let mut preloaders = vec![];
+        for _ in 0..6 {
+            let preloader = Preloader::new();
+            pool.load(&preloader, Priority::Normal, async {
+                tokio::time::Instant::now()
+            })
+            .await;
+            preloaders.push(preloader);
+        }
+
+        let mut times = vec![];
+        for preloader in &preloaders {
+            times.push(preloader.get().await.unwrap().duration_since(started));
+        }
+        times.sort();
+        // Two starts from the burst, then one every 20ms.
+        assert!(times[1] < Duration::from_millis(15));
+        assert!(times[5] >= Duration::from_millis(75));
+    }
+
+    #[tokio::test]
+    async fn test_lazy_preloader() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let lazy = Arc::new(LazyPreloader::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async {
+                sleep(Duration::from_millis(10)).await;
+                42
+            }
+        }));
+
+        sleep(Duration::from_millis(20)).await;
+        assert!(!lazy.is_started());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        assert!(matches!(lazy.try_get(), Err(PreloaderError::Loading)));
+        assert!(lazy.is_started());
+
+        let waiters: Vec<_> = (0..4)
+            .map(|_| {
+                let lazy = Arc::clone(&lazy);
+                tokio::spawn(async move { *lazy.get().await.unwrap() })
+            })
+            .collect();
+        for waiter in waiters {
+            assert_eq!(waiter.await.unwrap(), 42);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_static_preloader() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static VALUE: StaticPreloader<u32> = crate::static_preloader!(|| async {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            sleep(Duration::from_millis(10)).await;
+            7
+        });
+
+        assert!(!VALUE.is_started());
+        assert_eq!(VALUE.state(), PreloaderState::Idle);
+
+        let waiters: Vec<_> = (0..4)
+            .map(|_| tokio::spawn(async { *VALUE.get().await.unwrap() }))
+            .collect();
+        for waiter in waiters {
+            assert_eq!(waiter.await.unwrap(), 7);
+        }
+        assert!(VALUE.is_loaded());
+        assert_eq!(*(&VALUE).await.unwrap(), 7);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_lazy_preloader_start_outside_runtime() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        static VALUE: StaticPreloader<u32> = crate::static_preloader!(|| async { 2 });
+        let lazy = LazyPreloader::new(|| async { 1 });
+
+        // Starting outside of a runtime panics without consuming the factory
+        assert!(catch_unwind(AssertUnwindSafe(|| lazy.start())).is_err());
+        assert!(catch_unwind(|| VALUE.start()).is_err());
+        assert!(!lazy.is_started() && !VALUE.is_started());
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            assert_eq!(*lazy.get().await.unwrap(), 1);
+            assert_eq!(*VALUE.get().await.unwrap(), 2);
+        });
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_try_get_races_error_retry() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let preloader: Arc<Preloader<u32>> = Arc::new(
+            Preloader::builder()
+                .timeout(Duration::from_millis(1))
+                // Open after the first timeout, so every retry fails at once
+                .circuit_breaker(CircuitBreaker::new(1, Duration::from_secs(60)))
+                .error_policy(ErrorPolicy::Retry)
+                .build_with_loader(std::future::pending),
+        );
+        preloader.start().await;
+
+        // Readers keep observing `Failed` while every get() clears the error and retries
+        let stop = Arc::new(AtomicBool::new(false));
+        let readers: Vec<_> = (0..3)
+            .map(|_| {
+                let preloader = Arc::clone(&preloader);
+                let stop = Arc::clone(&stop);
+                std::thread::spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        _ = preloader.try_get();
+                    }
+                })
+            })
+            .collect();
+        let retries = async {
+            for _ in 0..20_000 {
+                assert!(preloader.get().await.is_err());
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(30), retries)
+            .await
+            .unwrap();
+        stop.store(true, Ordering::Relaxed);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lazy_preloader_builder() {
+        let lazy = Preloader::builder()
+            .name("lazy")
+            .timeout(Duration::from_millis(5))
+            .build_lazy(|| async {
+                sleep(Duration::from_millis(100)).await;
+                1
+            });
+        assert_eq!(lazy.name(), Some("lazy"));
+        assert!(matches!((&lazy).await, Err(PreloaderError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_stored_loader_restart() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+        let preloader = Preloader::builder()
+            .timeout(Duration::from_millis(10))
+            .build_with_loader(move || {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        sleep(Duration::from_millis(100)).await;
+                    }
+                    attempt
+                }
+            });
+
+        assert!(matches!(
+            preloader.try_get(),
+            Err(PreloaderError::NotLoaded)
+        ));
+        preloader.start().await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Timeout)
+        ));
+
+        // A plain `load()` does not recover a failed preloader.
+        preloader.load(async { 99 }).await;
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Timeout)));
+
+        assert!(preloader.restart().await);
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+        assert!(!preloader.restart().await);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stored_loader_retry() {
+        let mut attempts = 0;
+        let preloader = ArcPreloader::from_inner(
+            Preloader::builder()
+                .retry(RetryPolicy::fixed(2, Duration::from_millis(1)))
+                .build_with_loader(move || {
+                    attempts += 1;
+                    let attempt = attempts;
+                    async move {
+                        if attempt < 3 {
+                            panic!("attempt {} failed", attempt);
+                        }
+                        Arc::new(attempt)
+                    }
+                }),
+        );
+
+        assert!(!Preloader::<u32>::new().restart().await);
+        preloader.start().await;
+        assert_eq!(*preloader.get().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_error_policy() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let loader = |attempts: Arc<AtomicUsize>| {
+            move || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        panic!("first attempt fails");
+                    }
+                    attempt
+                }
+            }
+        };
+
+        // Errors are cached by default
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let preloader = Preloader::with_loader(loader(Arc::clone(&attempts)));
+        preloader.start().await;
+        assert!(preloader.get().await.is_err());
+        assert!(preloader.get().await.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        // Retry starts a fresh load on the next get()
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let preloader = Preloader::builder()
+            .error_policy(ErrorPolicy::Retry)
+            .build_with_loader(loader(Arc::clone(&attempts)));
+        preloader.start().await;
+        assert!(preloader.get().await.is_err());
+        assert!(matches!(
+            preloader.try_get(),
+            Err(PreloaderError::Panicked(_))
+        ));
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        // CacheFor keeps the error until the negative TTL has elapsed
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let preloader = Preloader::builder()
+            .error_policy(ErrorPolicy::CacheFor(Duration::from_millis(30)))
+            .build_with_loader(loader(Arc::clone(&attempts)));
+        preloader.start().await;
+        assert!(preloader.get().await.is_err());
+        assert!(preloader.get().await.is_err());
+        sleep(Duration::from_millis(40)).await;
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failure_modes() {
+        // Poisoned preloaders reject every access and every later load
+        let preloader: Preloader<u32> = Preloader::builder()
+            .on_failure(FailureMode::Poisoned)
+            .build_with_loader(|| async { panic!("broken") });
+        preloader.start().await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Panicked(_))
+        ));
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Poisoned)
+        ));
+        assert!(!preloader.restart().await);
+        preloader.load(async { 1 }).await;
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Poisoned)));
+
+        // Idle preloaders accept a new load after the failure has been observed
+        let preloader: Preloader<u32> = Preloader::builder()
+            .on_failure(FailureMode::Idle)
+            .timeout(Duration::from_millis(5))
+            .build();
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(100)).await;
+                1
+            })
+            .await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Timeout)
+        ));
+        assert!(matches!(
+            preloader.try_get(),
+            Err(PreloaderError::NotLoaded)
+        ));
+        preloader.load(async { 2 }).await;
+        assert_eq!(*preloader.get().await.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_cancelled_load() {
+        let loader_runtime = tokio::runtime::Runtime::new().unwrap();
+        let preloader = Preloader::builder()
+            .runtime(loader_runtime.handle().clone())
+            .build();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            preloader
+                .load(async {
+                    sleep(Duration::from_secs(60)).await;
+                    1
+                })
+                .await;
+        });
+        drop(loader_runtime);
+
+        runtime.block_on(async {
+            assert!(matches!(
+                preloader.get().await,
+                Err(PreloaderError::Cancelled)
+            ));
+        });
+    }
+
+    #[tokio::test]
+    async fn test_state_introspection() {
+        let preloader = Preloader::new();
+        assert_eq!(preloader.state(), PreloaderState::Idle);
+        assert!(preloader.is_idle());
+
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(10)).await;
+                1
+            })
+            .await;
+        assert_eq!(preloader.state(), PreloaderState::Loading);
+        assert!(preloader.is_loading());
+
+        // A finished load is reported without retrieving it first
+        sleep(Duration::from_millis(30)).await;
+        assert_eq!(preloader.state(), PreloaderState::Loaded);
+        assert!(!preloader.is_loading());
+
+        let failing: ArcPreloader<u32> = ArcPreloader::new();
+        failing.load(async { panic!("broken") }).await;
+        sleep(Duration::from_millis(10)).await;
+        assert!(failing.is_failed());
+        assert_eq!(failing.state(), PreloaderState::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_timing() {
+        let preloader = Preloader::new();
+        assert!(preloader.started_at().is_none());
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(20)).await;
+                1
+            })
+            .await;
+        assert!(preloader.started_at().is_some());
+        assert!(preloader.loaded_at().is_none());
+
+        // Timing reflects when loading finished, not when the result was retrieved
+        sleep(Duration::from_millis(50)).await;
+        preloader.get().await.unwrap();
+        let duration = preloader.load_duration().unwrap();
+        assert!(duration >= Duration::from_millis(20));
+        assert!(duration < Duration::from_millis(50));
+        assert!(preloader.age().unwrap() >= Duration::from_millis(20));
+        assert!(preloader.loaded_at().unwrap() >= preloader.started_at().unwrap());
+
+        let ready = Preloader::ready(1);
+        assert!(ready.loaded_at().is_some());
+        assert!(ready.load_duration().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_debug() {
+        struct Opaque;
+
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct AppState {
+            opaque: Preloader<Opaque>,
+            shared: ArcPreloader<Opaque>,
+            lazy: LazyPreloader<Opaque>,
+        }
+
+        let state = AppState {
+            opaque: Preloader::builder().name("opaque").build(),
+            shared: ArcPreloader::ready(Opaque),
+            lazy: LazyPreloader::new(|| async { Opaque }),
+        };
+        let output = format!("{:?}", state);
+        assert!(output.contains("name: \"opaque\""));
+        assert!(output.contains("state: Idle"));
+        assert!(output.contains("state: Loaded, has_value: true"));
+        assert!(output.contains("started: false"));
+
+        let failed: Preloader<Opaque> = Preloader::new();
+        failed.load(async { panic!("broken") }).await;
+        assert!(failed.get().await.is_err());
+        assert!(format!("{:?}", failed).contains("Panicked(\"broken\")"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn test_snapshot_restore() {
+        let preloader = Preloader::spawn(async { vec!["alice".to_string(), "bob".to_string()] });
+        preloader.get().await.unwrap();
+        let bytes = serde_json::to_vec(&SnapshotOf(&preloader)).unwrap();
+
+        let restored: ArcPreloader<Vec<String>> = ArcPreloader::new();
+        assert!(restored
+            .restore(&mut serde_json::Deserializer::from_slice(&bytes))
+            .unwrap());
+        assert_eq!(restored.try_get().unwrap().len(), 2);
+        // A restored preloader ignores later loads
+        restored.load(async { vec![] }).await;
+        assert_eq!(restored.get().await.unwrap().len(), 2);
+        assert!(!restored
+            .restore(&mut serde_json::Deserializer::from_slice(&bytes))
+            .unwrap());
+
+        // Snapshots of unloaded preloaders fail, and broken snapshots leave the preloader idle
+        let empty: Preloader<Vec<String>> = Preloader::new();
+        assert!(serde_json::to_vec(&SnapshotOf(&empty)).is_err());
+        assert!(empty
+            .restore(&mut serde_json::Deserializer::from_slice(b"{"))
+            .is_err());
+        assert!(empty.is_idle());
+
+        struct SnapshotOf<'a, T: Send + 'static>(&'a Preloader<T>);
+
+        impl<T: serde::Serialize + Send + 'static> serde::Serialize for SnapshotOf<'_, T> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0.snapshot(serializer)
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn test_serde_preloader_fields() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct State {
+            limit: Preloader<u32>,
+            users: ArcPreloader<Vec<String>>,
+            pending: Preloader<u32>,
+        }
+
+        let state = State {
+            limit: Preloader::spawn(async { 10 }),
+            users: ArcPreloader::ready(vec!["alice".to_string()]),
+            pending: Preloader::new(),
+        };
+        state.limit.get().await.unwrap();
+        state.users.reload(async { vec![] }).await.unwrap();
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(
+            json,
+            r#"{"limit":{"Loaded":10},"users":{"Loaded":[]},"pending":"Idle"}"#
+        );
+
+        let restored: State = serde_json::from_str(&json).unwrap();
+        assert_eq!(*restored.limit.try_get().unwrap(), 10);
+        assert!(restored.users.try_get().unwrap().is_empty());
+        assert!(restored.pending.is_idle());
+        restored.pending.load(async { 1 }).await;
+        assert_eq!(*restored.pending.get().await.unwrap(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_preloader_loaded_none() {
+        let loaded: Preloader<Option<u32>> = Preloader::ready(None);
+        let json = serde_json::to_string(&loaded).unwrap();
+        let restored: Preloader<Option<u32>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(*restored.try_get().unwrap(), None);
+
+        let unit: ArcPreloader<()> = ArcPreloader::ready(());
+        let restored: ArcPreloader<()> =
+            serde_json::from_str(&serde_json::to_string(&unit).unwrap()).unwrap();
+        assert!(restored.is_loaded());
+
+        let idle: Preloader<Option<u32>> = Preloader::new();
+        let restored: Preloader<Option<u32>> =
+            serde_json::from_str(&serde_json::to_string(&idle).unwrap()).unwrap();
+        assert!(restored.is_idle());
+    }
+
+    #[tokio::test]
+    async fn test_preloader_cache() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let cache = PreloaderCache::new(move |key: &String| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let value = key.len();
+            async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                value
+            }
+        });
+
+        assert!(matches!(
+            cache.try_get(&"abc".to_string()),
+            Err(PreloaderError::NotLoaded)
+        ));
+        let key = "abc".to_string();
+        let (a, b) = tokio::join!(cache.get(&key), cache.get(&key));
+        assert_eq!((*a.unwrap(), *b.unwrap()), (3, 3));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(*cache.try_get(&key).unwrap(), 3);
+
+        assert_eq!(*cache.get(&"hello".to_string()).await.unwrap(), 5);
+        assert_eq!(cache.len(), 2);
+
+        // Removed keys are loaded again
+        assert!(cache.remove(&key));
+        assert!(!cache.contains_key(&key));
+        assert_eq!(*cache.get(&key).await.unwrap(), 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[tokio::test]
+    async fn test_disk_cache() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let dir = std::env::temp_dir().join(format!("preloader-test-disk-{}", std::process::id()));
+        let disk = DiskCache::new(&dir);
+        assert_eq!(disk.path("a/b c"), dir.join("a%2Fb%20c.bin"));
+
+        let preloader = Preloader::new();
+        disk.load(&preloader, "list", async { vec!["a".to_string()] })
+            .await;
+        assert_eq!(preloader.get().await.unwrap(), &["a"]);
+        assert!(disk.path("list").exists());
+
+        // Stored values are read instead of running the loader
+        let cached: Preloader<Vec<String>> = Preloader::new();
+        disk.load(&cached, "list", async { vec![] }).await;
+        assert_eq!(cached.get().await.unwrap(), &["a"]);
+
+        // Corrupt files are treated as missing and replaced
+        std::fs::write(disk.path("list"), b"\xff").unwrap();
+        let reloaded: Preloader<Vec<String>> = Preloader::new();
+        disk.load(&reloaded, "list", async { vec!["b".to_string()] })
+            .await;
+        assert_eq!(reloaded.get().await.unwrap(), &["b"]);
+        assert!(disk.remove("list").await.unwrap());
+        assert!(!disk.remove("list").await.unwrap());
+
+        // Keyed caches persist every key in its own file
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let keyed = || {
+            let counter = Arc::clone(&counter);
+            PreloaderCache::new(move |id: &u32| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let id = *id;
+                async move { id * 10 }
+            })
+            .with_disk_cache(disk.clone())
+        };
+        assert_eq!(*keyed().get(&4).await.unwrap(), 40);
+        assert_eq!(*keyed().get(&4).await.unwrap(), 40);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_disk_cache_concurrent_writes() {
+        let dir =
+            std::env::temp_dir().join(format!("preloader-test-concurrent-{}", std::process::id()));
+        let disk = DiskCache::new(&dir);
+
+        // Every preloader writes the same key at once, each with a value large enough to overlap
+        let writers: Vec<_> = (0..8u8)
+            .map(|writer| {
+                let disk = disk.clone();
+                tokio::spawn(async move {
+                    let preloader = Preloader::new();
+                    disk.load(&preloader, "shared", async move { vec![writer; 1 << 20] })
+                        .await;
+                    preloader.get().await.unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap();
+        }
+
+        let stored: Preloader<Vec<u8>> = Preloader::new();
+        disk.load(&stored, "shared", async { vec![] }).await;
+        let stored = stored.get().await.unwrap();
+        assert_eq!(stored.len(), 1 << 20);
+        assert!(stored.iter().all(|&byte| byte == stored[0]));
+
+        let entries: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[tokio::test]
+    async fn test_disk_cache_corrupt_length() {
+        let dir =
+            std::env::temp_dir().join(format!("preloader-test-corrupt-{}", std::process::id()));
+        let disk = DiskCache::new(&dir);
+        assert_eq!(disk.max_value_size(), DEFAULT_MAX_VALUE_SIZE);
+
+        // An empty version tag followed by a length prefix claiming a terabyte
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut contents = 0u64.to_le_bytes().to_vec();
+        contents.extend((1u64 << 40).to_le_bytes());
+        std::fs::write(disk.path("bytes"), contents).unwrap();
+
+        let preloader: Preloader<Vec<u8>> = Preloader::new();
+        disk.load(&preloader, "bytes", async { vec![1, 2, 3] })
+            .await;
+        assert_eq!(preloader.get().await.unwrap(), &[1, 2, 3]);
+
+        // Values larger than the limit are treated as missing
+        let bounded = disk.clone().with_max_value_size(8);
+        let reloaded: Preloader<Vec<u8>> = Preloader::new();
+        bounded.load(&reloaded, "bytes", async { vec![4] }).await;
+        assert_eq!(reloaded.get().await.unwrap(), &[4]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(all(feature = "gzip", feature = "zstd"))]
+    #[tokio::test]
+    async fn test_disk_cache_compression() {
+        let dir =
+            std::env::temp_dir().join(format!("preloader-test-compression-{}", std::process::id()));
+        let payload: Vec<String> = (0..1000).map(|i| format!("row-{}", i % 10)).collect();
+
+        let plain = DiskCache::new(&dir);
+        let gzip = plain.clone().with_compression(Compression::Gzip(6));
+        let zstd = plain.clone().with_compression(Compression::Zstd(3));
+        assert_eq!(gzip.path("rows"), dir.join("rows.bin.gz"));
+        assert_eq!(zstd.path("rows"), dir.join("rows.bin.zst"));
+
+        for disk in [&plain, &gzip, &zstd] {
+            let value = payload.clone();
+            let preloader = Preloader::new();
+            disk.load(&preloader, "rows", async move { value }).await;
+            preloader.get().await.unwrap();
+
+            let cached: Preloader<Vec<String>> = Preloader::new();
+            disk.load(&cached, "rows", async { vec![] }).await;
+            assert_eq!(cached.get().await.unwrap(), &payload);
+        }
+
+        let size = |disk: &DiskCache| std::fs::metadata(disk.path("rows")).unwrap().len();
+        assert!(size(&gzip) < size(&plain));
+        assert!(size(&zstd) < size(&plain));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[tokio::test]
+    async fn test_disk_cache_version() {
+        let dir =
+            std::env::temp_dir().join(format!("preloader-test-version-{}", std::process::id()));
+        let v1 = DiskCache::new(&dir).with_version("1.0.0");
+        let v2 = DiskCache::new(&dir).with_version("2.0.0");
+        assert_eq!(v2.version(), "2.0.0");
+
+        let first = Preloader::new();
+        v1.load(&first, "config", async { "old".to_string() }).await;
+        assert_eq!(first.get().await.unwrap(), "old");
+
+        // A different version discards the stored value and reloads it
+        let second = Preloader::new();
+        v2.load(&second, "config", async { "new".to_string() })
+            .await;
+        assert_eq!(second.get().await.unwrap(), "new");
+
+        let third: Preloader<String> = Preloader::new();
+        v2.load(&third, "config", async { unreachable!() }).await;
+        assert_eq!(third.get().await.unwrap(), "new");
+
+        let untagged: Preloader<String> = Preloader::new();
+        DiskCache::new(&dir)
+            .load(&untagged, "config", async { "untagged".to_string() })
+            .await;
+        assert_eq!(untagged.get().await.unwrap(), "untagged");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "bytes")]
+    #[tokio::test]
+    async fn test_zero_copy_bytes() {
+        let path =
+            std::env::temp_dir().join(format!("preloader-test-mmap-{}.bin", std::process::id()));
+        std::fs::write(&path, vec![7u8; 4096]).unwrap();
+
+        let mapped: Preloader<Bytes> = Preloader::new();
+        unsafe { mapped.load_mmap(&path).await };
+        let first = mapped.get_cloned().await.unwrap();
+        let second = mapped.get_cloned().await.unwrap();
+        assert_eq!(first.len(), 4096);
+        assert_eq!(first.as_ptr(), second.as_ptr());
+        drop((first, second, mapped));
+        std::fs::remove_file(&path).unwrap();
+
+        let missing: Preloader<Bytes> = Preloader::new();
+        unsafe { missing.load_mmap(&path).await };
+        assert!(matches!(
+            missing.get().await,
+            Err(PreloaderError::Failed(_))
+        ));
+
+        let body = vec![1u8, 2, 3];
+        let pointer = body.as_ptr();
+        let downloaded: Preloader<Bytes> = Preloader::new();
+        downloaded.load_bytes(async move { body }).await;
+        assert_eq!(downloaded.get().await.unwrap().as_ptr(), pointer);
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_fetch() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        // Serves one response per connection and echoes the received `x-token` header
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 4096];
+                let read = socket.read(&mut request).await.unwrap();
+                let request = String::from_utf8_lossy(&request[..read]).to_lowercase();
+                let (status, body) = if request.starts_with("get /missing") {
+                    ("404 Not Found", String::new())
+                } else {
+                    let token = request
+                        .lines()
+                        .find_map(|line| line.strip_prefix("x-token: "))
+                        .unwrap_or("none")
+                        .to_string();
+                    ("200 OK", format!("[\"{}\", \"b\"]", token))
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let url = format!("http://{}/list", address);
+        let list: Preloader<Vec<String>> =
+            Preloader::fetch_json(Fetch::new(&url).header("x-token", "secret"));
+        assert_eq!(list.get().await.unwrap(), &["secret", "b"]);
+
+        let raw = Preloader::fetch_bytes(url.as_str());
+        assert_eq!(&raw.get().await.unwrap()[..], b"[\"none\", \"b\"]");
+
+        let missing: Preloader<Vec<String>> =
+            Preloader::fetch_json(format!("http://{}/missing", address));
+        assert!(matches!(
+            missing.get().await,
+            Err(PreloaderError::Failed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_from_file() {
+        let dir = std::env::temp_dir().join(format!("preloader-test-file-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.txt");
+        std::fs::write(&path, "42").unwrap();
+
+        let raw = Preloader::from_file(&path);
+        assert_eq!(raw.get().await.unwrap(), b"42");
+
+        let parsed = ArcPreloader::from_file_de(&path, |contents| {
+            std::str::from_utf8(contents).unwrap().parse::<u32>()
+        });
+        assert_eq!(*parsed.get().await.unwrap(), 42);
+
+        let invalid: Preloader<u8> = Preloader::from_file_de(&path, |_| Err("not a byte"));
+        match invalid.get().await {
+            Err(PreloaderError::Failed(message)) => assert!(message.ends_with("not a byte")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let missing = Preloader::from_file(dir.join("missing.txt"));
+        assert!(matches!(
+            missing.get().await,
+            Err(PreloaderError::Failed(_))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "watch")]
+    #[tokio::test]
+    async fn test_watch_file() {
+        let dir = std::env::temp_dir().join(format!("preloader-test-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("value.txt");
+        std::fs::write(&path, "1").unwrap();
+
+        let parse = |contents: &[u8]| std::str::from_utf8(contents).unwrap().trim().parse::<u32>();
+        let preloader = ArcPreloader::watch_file_de(&path, parse).unwrap();
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+
+        async fn wait_for(preloader: &ArcPreloader<u32>, expected: u32) {
+            for _ in 0..100 {
+                if preloader.try_get().ok().as_deref() == Some(&expected) {
+                    return;
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+            panic!("value did not change to {}", expected);
+        }
+
+        std::fs::write(&path, "2").unwrap();
+        wait_for(&preloader, 2).await;
+
+        // Invalid contents keep the previous value
+        std::fs::write(&path, "invalid").unwrap();
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(*preloader.get().await.unwrap(), 2);
+
+        // Replacing the file through a rename is picked up as well
+        let temp = dir.join("value.tmp");
+        std::fs::write(&temp, "3").unwrap();
+        std::fs::rename(&temp, &path).unwrap();
+        wait_for(&preloader, 3).await;
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "config")]
+    #[tokio::test]
+    async fn test_config_preloader() {
+        #[derive(serde::Deserialize)]
+        struct Settings {
+            name: String,
+            workers: usize,
+        }
+
+        let dir =
+            std::env::temp_dir().join(format!("preloader-test-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.json");
+        std::fs::write(&path, r#"{"name": "api", "workers": 4}"#).unwrap();
+
+        let config = ConfigPreloader::<Settings>::builder(&path)
+            .validate(|settings| match settings.workers {
+                0 => Err("workers must be positive"),
+                _ => Ok(()),
+            })
+            .build()
+            .unwrap();
+        let settings = config.get().await.unwrap();
+        assert_eq!((settings.name.as_str(), settings.workers), ("api", 4));
+
+        std::fs::write(&path, r#"{"name": "api", "workers": 8}"#).unwrap();
+        config.refresh().await.unwrap();
+        assert_eq!(config.get().await.unwrap().workers, 8);
+
+        // Invalid or malformed files keep the previous configuration
+        std::fs::write(&path, r#"{"name": "api", "workers": 0}"#).unwrap();
+        let result = config.refresh().await;
+        assert!(
+            matches!(result, Err(PreloaderError::Failed(message)) if message.contains("workers must be positive"))
+        );
+        std::fs::write(&path, "{").unwrap();
+        assert!(config.refresh().await.is_err());
+        assert_eq!(config.get().await.unwrap().workers, 8);
+
+        // A first load that fails validation fails the preloader
+        let invalid = ConfigPreloader::<Settings>::builder(&path)
+            .format(ConfigFormat::Json)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            invalid.get().await,
+            Err(PreloaderError::Failed(_))
+        ));
+
+        assert!(ConfigPreloader::<Settings>::load(dir.join("settings.ini")).is_err());
+
+        #[cfg(feature = "toml")]
+        {
+            let path = dir.join("settings.toml");
+            std::fs::write(&path, "name = \"worker\"\nworkers = 2\n").unwrap();
+            let config = ConfigPreloader::<Settings>::load(&path).unwrap();
+            assert_eq!(config.get().await.unwrap().name, "worker");
+        }
+
+        #[cfg(feature = "yaml")]
+        {
+            let path = dir.join("settings.yml");
+            std::fs::write(&path, "name: cron\nworkers: 1\n").unwrap();
+            let config = ConfigPreloader::<Settings>::load(&path).unwrap();
+            assert_eq!(config.get().await.unwrap().name, "cron");
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[tokio::test]
+    async fn test_database_warmup() {
+        use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+
+        let pool: Preloader<SqlitePool> = Preloader::connect_with(
+            SqlitePoolOptions::new().max_connections(1),
+            "sqlite::memory:",
+        );
+        let pool = pool.get_cloned().await.unwrap();
+        sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO users (name) VALUES ('alice'), ('bob')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let users: Preloader<Vec<(i64, String)>> =
+            Preloader::fetch_all(pool.clone(), "SELECT id, name FROM users ORDER BY id");
+        let count: Preloader<(i64,)> =
+            Preloader::fetch_one(pool.clone(), "SELECT COUNT(*) FROM users");
+        let name: Preloader<String> = Preloader::from_query(pool.clone(), |pool| async move {
+            sqlx::query_scalar("SELECT name FROM users WHERE id = ?")
+                .bind(2)
+                .fetch_one(&pool)
+                .await
+        });
+
+        assert_eq!(
+            *users.get().await.unwrap(),
+            vec![(1, "alice".to_string()), (2, "bob".to_string())]
+        );
+        assert_eq!(count.get().await.unwrap().0, 2);
+        assert_eq!(name.get().await.unwrap(), "bob");
+
+        let missing: Preloader<(i64,)> =
+            Preloader::fetch_one(pool.clone(), "SELECT id FROM users WHERE id = 3");
+        assert!(matches!(
+            missing.get().await,
+            Err(PreloaderError::Failed(_))
+        ));
+        let unreachable: Preloader<SqlitePool> =
+            Preloader::connect("sqlite:///nonexistent/dir/db.sqlite");
+        assert!(matches!(
+            unreachable.get().await,
+            Err(PreloaderError::Failed(_))
+        ));
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn test_axum_integration() {
+        use crate::axum::{readiness_route, Preloaded};
+        use ::axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        async fn call(app: &Router, uri: &str) -> u16 {
+            let request = Request::get(uri).body(Body::empty()).unwrap();
+            app.clone()
+                .oneshot(request)
+                .await
+                .unwrap()
+                .status()
+                .as_u16()
+        }
+
+        let mut group = PreloaderGroup::new();
+        let greeting = group.add("greeting", async {
+            sleep(Duration::from_millis(50)).await;
+            "hello".to_string()
+        });
+        let group = Arc::new(group);
+
+        let app = Router::new()
+            .route(
+                "/greeting",
+                get(|greeting: Preloaded<String>| async move { greeting.to_uppercase() }),
+            )
+            .route("/ready", readiness_route(Arc::clone(&group)))
+            .with_state(greeting);
+
+        assert_eq!(call(&app, "/greeting").await, 503);
+        assert_eq!(call(&app, "/ready").await, 503);
+
+        group.start().await;
+        group.wait_all().await.unwrap();
+        assert_eq!(call(&app, "/greeting").await, 200);
+        assert_eq!(call(&app, "/ready").await, 200);
+
+        let failed: Arc<Preloader<String>> = Arc::new(Preloader::new());
+        failed
+            .load_result(async { Err(PreloaderError::Failed("down".to_string())) })
+            .await;
+        assert!(failed.get().await.is_err());
+        let app = Router::new()
+            .route(
+                "/",
+                get(|value: Preloaded<String>| async move { value.clone() }),
+            )
+            .with_state(failed);
+        assert_eq!(call(&app, "/").await, 500);
+    }
+
+    #[cfg(feature = "tower")]
+    #[tokio::test]
+    async fn test_preload_layer() {
+        use std::convert::Infallible;
+        use tower::{service_fn, Layer, ServiceExt};
+
+        let preloader: Arc<Preloader<u32>> = Arc::new(Preloader::new());
+        let value = Arc::clone(&preloader);
+        let echo = service_fn(move |request: u32| {
+            let value = Arc::clone(&value);
+            async move { Ok::<_, Infallible>(request + *value.get().await.unwrap()) }
+        });
+
+        // Rejected immediately while loading
+        let reject = PreloadLayer::new(Arc::clone(&preloader))
+            .reject_with(|| 0)
+            .layer(echo.clone());
+        assert_eq!(reject.clone().oneshot(1).await.unwrap(), 0);
+
+        // Held until loaded, including before loading has been started
+        let hold = PreloadLayer::new(Arc::clone(&preloader)).layer(echo);
+        let held = tokio::spawn(hold.clone().oneshot(1));
+        sleep(Duration::from_millis(20)).await;
+        assert!(!held.is_finished());
+
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(20)).await;
+                10
+            })
+            .await;
+        assert_eq!(held.await.unwrap().unwrap(), 11);
+        assert_eq!(hold.oneshot(2).await.unwrap(), 12);
+        assert_eq!(reject.oneshot(3).await.unwrap(), 13);
+
+        // Groups gate on every entry
+        let mut group = PreloaderGroup::new();
+        group.add("slow", async {
+            sleep(Duration::from_millis(30)).await;
+        });
+        let group = Arc::new(group);
+        let service = PreloadLayer::new(Arc::clone(&group))
+            .layer(service_fn(|()| async { Ok::<_, Infallible>("ok") }));
+        let held = tokio::spawn(service.oneshot(()));
+        group.start().await;
+        assert_eq!(held.await.unwrap().unwrap(), "ok");
+        assert!(group.is_ready());
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test(start_paused = true)]
+    async fn test_axum_extractor_past_ttl() {
+        use crate::axum::Preloaded;
+        use ::axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        let rates: Arc<Preloader<u32>> =
+            Arc::new(Preloader::builder().ttl(Duration::from_secs(60)).build());
+        rates.load(async { 7 }).await;
+        rates.get().await.unwrap();
+
+        // The value stays readable through the extractor after it expires mid-request
+        let app = Router::new()
+            .route(
+                "/",
+                get(|rate: Preloaded<u32>| async move {
+                    sleep(Duration::from_secs(90)).await;
+                    rate.to_string()
+                }),
+            )
+            .with_state(Arc::clone(&rates));
+        let request = Request::get("/").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(matches!(rates.try_get(), Err(PreloaderError::Expired)));
+    }
+
+    #[cfg(feature = "actix")]
+    #[tokio::test(start_paused = true)]
+    async fn test_actix_extractor_past_ttl() {
+        use crate::actix::{app_data, Preloaded};
+        use actix_web::{test as web_test, web, App};
+
+        let rates: Arc<Preloader<u32>> =
+            Arc::new(Preloader::builder().ttl(Duration::from_secs(60)).build());
+        rates.load(async { 7 }).await;
+        rates.get().await.unwrap();
+
+        // The value stays readable through the extractor after it expires mid-request
+        let app = web_test::init_service(App::new().app_data(app_data(Arc::clone(&rates))).route(
+            "/",
+            web::get().to(|rate: Preloaded<u32>| async move {
+                sleep(Duration::from_secs(90)).await;
+                rate.to_string()
+            }),
+        ))
+        .await;
+        let response =
+            web_test::call_service(&app, web_test::TestRequest::get().uri("/").to_request()).await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(web_test::read_body(response).await, "7");
+        assert!(matches!(rates.try_get(), Err(PreloaderError::Expired)));
+    }
+
+    #[cfg(feature = "actix")]
+    #[tokio::test]
+    async fn test_actix_integration() {
+        use crate::actix::{app_data, readiness_route, Preloaded, PreloadedConfig};
+        use actix_web::{test as web_test, web, App};
+
+        let mut group = PreloaderGroup::new();
+        let greeting = group.add("greeting", async {
+            sleep(Duration::from_millis(50)).await;
+            "hello".to_string()
+        });
+        let group = Arc::new(group);
+
+        let app = web_test::init_service(
+            App::new()
+                .app_data(app_data(greeting))
+                .route("/ready", readiness_route(Arc::clone(&group)))
+                .route(
+                    "/greeting",
+                    web::get()
+                        .to(|greeting: Preloaded<String>| async move { greeting.to_uppercase() }),
+                ),
+        )
+        .await;
+        let call = |uri: &str| web_test::TestRequest::get().uri(uri).to_request();
+
+        assert_eq!(
+            web_test::call_service(&app, call("/greeting"))
+                .await
+                .status(),
+            503
+        );
+        assert_eq!(
+            web_test::call_service(&app, call("/ready")).await.status(),
+            503
+        );
+
+        group.start().await;
+        group.wait_all().await.unwrap();
+        let response = web_test::call_service(&app, call("/greeting")).await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(web_test::read_body(response).await, "HELLO");
+        assert_eq!(
+            web_test::call_service(&app, call("/ready")).await.status(),
+            200
+        );
+
+        // With a wait configured, the extractor waits for loading to finish
+        let slow: Arc<Preloader<u32>> = Arc::new(Preloader::new());
+        let app = web_test::init_service(
+            App::new()
+                .app_data(app_data(Arc::clone(&slow)))
+                .app_data(PreloadedConfig::default().wait(Duration::from_secs(1)))
+                .route(
+                    "/",
+                    web::get().to(|value: Preloaded<u32>| async move { value.to_string() }),
+                ),
+        )
+        .await;
+        let loader = Arc::clone(&slow);
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(20)).await;
+            loader.load(async { 7 }).await;
+        });
+        let response = web_test::call_service(&app, call("/")).await;
+        assert_eq!(web_test::read_body(response).await, "7");
+    }
+
+    #[cfg(feature = "bevy")]
+    #[test]
+    fn test_bevy_plugin() {
+        use bevy_app::{App, TaskPoolPlugin, Update};
+        use bevy_ecs::{message::MessageReader, resource::Resource, system::ResMut};
+
+        #[derive(Resource, Default)]
+        struct Finished(Vec<String>);
+
+        let mut app = App::new();
+        app.add_plugins((TaskPoolPlugin::default(), PreloadPlugin))
+            .init_resource::<Finished>()
+            .add_systems(
+                Update,
+                |mut messages: MessageReader<PreloadFinished>, mut finished: ResMut<Finished>| {
+                    finished
+                        .0
+                        .extend(messages.read().map(|message| message.name.clone()));
+                },
+            );
+
+        let (tx, rx) = futures::channel::oneshot::channel::<u32>();
+        let mut preloads = app.world_mut().resource_mut::<Preloads>();
+        let level = preloads.add("level", async move { rx.await.unwrap() });
+        let broken: Arc<Preloader<u32>> = preloads.add("broken", async { panic!("missing file") });
+
+        app.update();
+        assert!(matches!(level.try_get(), Err(PreloaderError::Loading)));
+        assert!(!app.world().resource::<Preloads>().is_ready());
+
+        tx.send(42).unwrap();
+        for _ in 0..100 {
+            app.update();
+            if app.world().resource::<Preloads>().progress() == 1.0 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let preloads = app.world().resource::<Preloads>();
+        assert_eq!(preloads.loaded_count(), 1);
+        assert!(!preloads.is_ready());
+        assert_eq!(*level.try_get().unwrap(), 42);
+        assert!(
+            matches!(broken.try_get(), Err(PreloaderError::Panicked(message)) if message == "missing file")
+        );
+
+        let mut finished = app.world().resource::<Finished>().0.clone();
+        finished.sort();
+        assert_eq!(finished, ["broken", "level"]);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[tokio::test]
+    async fn test_pending_handle() {
+        let (preloader, handle) = Preloader::<String>::pending();
+        assert_eq!(preloader.state(), PreloaderState::Loading);
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Loading)));
+        handle.complete("done".to_string());
+        assert_eq!(preloader.get().await.unwrap(), "done");
+
+        let (preloader, handle) = Preloader::<String>::pending();
+        handle.fail(PreloaderError::Failed("unreachable".to_string()));
+        assert!(
+            matches!(preloader.get().await, Err(PreloaderError::Failed(message)) if message == "unreachable")
+        );
+
+        let (preloader, handle) = Preloader::<String>::pending();
+        handle.never();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), preloader.get())
+                .await
+                .is_err()
+        );
+        assert!(preloader.is_loading());
+
+        let (preloader, handle) = Preloader::<String>::pending();
+        drop(handle);
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Cancelled)
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_paused_time_ttl() {
+        let preloader = Preloader::builder().ttl(Duration::from_secs(60)).build();
+        preloader.load(async { 1 }).await;
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+
+        tokio::time::advance(Duration::from_secs(59)).await;
+        assert!(!preloader.is_expired());
+        assert_eq!(preloader.age(), Some(Duration::from_secs(59)));
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(preloader.is_expired());
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Expired)));
+
+        // Replaced values of ArcPreloader expire relative to the replacement
+        let preloader = Preloader::<u32>::builder()
+            .ttl(Duration::from_secs(60))
+            .build_arc();
+        preloader.load(async { 1 }).await;
+        preloader.get().await.unwrap();
+        tokio::time::advance(Duration::from_secs(30)).await;
+        preloader
+            .reload_result(async { Ok(Arc::new(2)) })
+            .await
+            .unwrap();
+        tokio::time::advance(Duration::from_secs(45)).await;
+        assert_eq!(*preloader.try_get().unwrap(), 2);
+        tokio::time::advance(Duration::from_secs(15)).await;
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Expired)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_paused_time_timeout_and_retry() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Hour-long loads time out without waiting in real time
+        let started = std::time::Instant::now();
+        let preloader = Preloader::builder()
+            .timeout(Duration::from_secs(10))
+            .retry(RetryPolicy::exponential(2, Duration::from_secs(5)))
+            .build();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+        let loading = tokio::time::Instant::now();
+        preloader
+            .load_with(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                sleep(Duration::from_secs(3600))
+            })
+            .await;
+
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Timeout)
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        // Three timed-out attempts with backoffs of 5s and 10s in between
+        assert_eq!(loading.elapsed(), Duration::from_secs(3 * 10 + 5 + 10));
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // Cached errors are retried once the negative TTL has elapsed
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+        let preloader = Preloader::builder()
+            .error_policy(ErrorPolicy::CacheFor(Duration::from_secs(300)))
+            .build_with_loader(move || {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        panic!("first attempt fails");
+                    }
+                    attempt
+                }
+            });
+        preloader.start().await;
+        assert!(preloader.get().await.is_err());
+        tokio::time::advance(Duration::from_secs(299)).await;
+        assert!(preloader.get().await.is_err());
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_arc_refresh_every() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&loads);
+        let preloader = Arc::new(ArcPreloader::with_loader(move || {
+            let count = counter.fetch_add(1, Ordering::SeqCst);
+            async move { count }
+        }));
+        preloader.start().await;
+        assert_eq!(*preloader.get().await.unwrap(), 0);
+
+        assert!(preloader.refresh_every(Duration::from_secs(10)));
+        assert!(preloader.is_refreshing());
+
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert_eq!(*preloader.try_get().unwrap(), 0);
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(*preloader.try_get().unwrap(), 2);
+
+        preloader.stop_refresh();
+        assert!(!preloader.is_refreshing());
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(*preloader.try_get().unwrap(), 2);
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_arc_refresh_every_loads_failed_preloader() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let fail = Arc::new(AtomicBool::new(true));
+        let flag = Arc::clone(&fail);
+        let preloader = Arc::new(ArcPreloader::with_loader(move || {
+            let fail = flag.load(Ordering::SeqCst);
+            async move {
+                if fail {
+                    panic!("unavailable");
+                }
+                "ready"
+            }
+        }));
+        preloader.start().await;
+        assert!(preloader.get().await.is_err());
+
+        fail.store(false, Ordering::SeqCst);
+        preloader.refresh_every(Duration::from_secs(1));
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(*preloader.get().await.unwrap(), "ready");
+    }
+
+    #[tokio::test]
+    async fn test_arc_refresh_every_without_loader() {
+        let preloader = Arc::new(ArcPreloader::ready(1));
+        assert!(!preloader.refresh_every(Duration::from_secs(1)));
+        assert!(!preloader.is_refreshing());
+    }
+
+    #[cfg(feature = "cron")]
+    #[tokio::test]
+    async fn test_arc_refresh_cron() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&loads);
+        let preloader = Arc::new(ArcPreloader::with_loader(move || {
+            let count = counter.fetch_add(1, Ordering::SeqCst);
+            async move { count }
+        }));
+        preloader.start().await;
+        assert_eq!(*preloader.get().await.unwrap(), 0);
+        assert!(preloader.next_refresh().is_none());
+
+        let every_second: Schedule = "* * * * * *".parse().unwrap();
+        assert!(preloader.refresh_cron(every_second));
+        sleep(Duration::from_millis(100)).await;
+        let next = preloader.next_refresh().unwrap();
+        assert!(next <= std::time::Instant::now() + Duration::from_secs(1));
+
+        sleep(Duration::from_millis(2100)).await;
+        assert!(*preloader.try_get().unwrap() >= 1);
+
+        preloader.stop_refresh();
+        assert!(preloader.next_refresh().is_none());
+        let refreshed = loads.load(Ordering::SeqCst);
+        sleep(Duration::from_millis(1100)).await;
+        assert_eq!(loads.load(Ordering::SeqCst), refreshed);
+    }
+
+    #[cfg(feature = "cron")]
+    #[test]
+    fn test_cron_schedule_rejects_invalid_expression() {
+        assert!("not a schedule".parse::<Schedule>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_arc_reload_serves_previous_value() {
+        let preloader = Arc::new(ArcPreloader::ready(1));
+        let before = preloader.try_get().unwrap();
+
+        let (release, wait) = tokio::sync::oneshot::channel::<()>();
+        let reloading = {
+            let preloader = Arc::clone(&preloader);
+            tokio::spawn(async move {
+                preloader
+                    .reload(async move {
+                        _ = wait.await;
+                        2
+                    })
+                    .await
+            })
+        };
+
+        sleep(Duration::from_millis(20)).await;
+        // No loading gap while the new value is being loaded
+        assert!(preloader.is_loaded());
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+
+        release.send(()).unwrap();
+        reloading.await.unwrap().unwrap();
+        assert_eq!(*preloader.try_get().unwrap(), 2);
+        assert_eq!(*before, 1);
+    }
+
+    #[tokio::test]
+    async fn test_arc_reload_rejects_invalid_value() {
+        let preloader = ArcPreloader::ready(vec![1]).validate(|values: &Vec<i32>| {
+            if values.is_empty() {
+                Err("empty")
+            } else {
+                Ok(())
+            }
+        });
+
+        let error = preloader.reload(async { Vec::new() }).await.unwrap_err();
+        assert!(
+            matches!(error, PreloaderError::Failed(ref message) if message == "invalid value: empty")
+        );
+        assert_eq!(*preloader.try_get().unwrap(), vec![1]);
+
+        preloader.reload(async { vec![2, 3] }).await.unwrap();
+        assert_eq!(*preloader.try_get().unwrap(), vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_arc_reload_keeps_value_on_panic() {
+        let preloader = ArcPreloader::ready(1);
+        let error = preloader
+            .reload(async { panic!("reload failed") })
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, PreloaderError::Panicked(ref message) if message == "reload failed")
+        );
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_arc_refresh_with_stored_loader() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let loads = Arc::clone(&counter);
+        let preloader = ArcPreloader::with_loader(move || {
+            let count = loads.fetch_add(1, Ordering::SeqCst);
+            async move { count }
+        });
+
+        // Loads an idle preloader
+        preloader.refresh().await.unwrap();
+        assert_eq!(*preloader.try_get().unwrap(), 0);
+        preloader.refresh().await.unwrap();
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+
+        assert!(ArcPreloader::ready(1).refresh().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generation_and_versioned_reads() {
+        let preloader = Preloader::new();
+        assert_eq!(preloader.generation(), 0);
+        assert!(preloader.try_get_versioned().is_err());
+        preloader.load(async { 7 }).await;
+        assert_eq!(preloader.get_versioned().await.unwrap(), (1, &7));
+        assert_eq!(preloader.generation(), 1);
+
+        let preloader = ArcPreloader::new();
+        assert_eq!(preloader.generation(), 0);
+        preloader.load(async { "first" }).await;
+        let (generation, value) = preloader.get_versioned().await.unwrap();
+        assert_eq!((generation, *value), (1, "first"));
+
+        preloader.reload(async { "second" }).await.unwrap();
+        preloader.reload(async { "third" }).await.unwrap();
+        let (generation, value) = preloader.try_get_versioned().unwrap();
+        assert_eq!((generation, *value), (3, "third"));
+        assert_eq!(preloader.generation(), 3);
+
+        // A rejected reload does not change the generation
+        assert!(preloader.reload(async { panic!("broken") }).await.is_err());
+        assert_eq!(preloader.generation(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_preloader_weak() {
+        let preloader = Arc::new(Preloader::new());
+        let weak = Preloader::downgrade(&preloader);
+        assert_eq!(weak.strong_count(), 1);
+        assert!(weak.ptr_eq(&weak.clone()));
+
+        preloader.load(async { vec![1u8; 16] }).await;
+        let upgraded = weak.upgrade().unwrap();
+        assert_eq!(upgraded.get().await.unwrap().len(), 16);
+        drop(upgraded);
+
+        // The loaded value is dropped with the last owner
+        drop(preloader);
+        assert!(weak.is_dropped());
+        assert!(weak.upgrade().is_none());
+        assert_eq!(format!("{:?}", weak), "PreloaderWeak(Dropped)");
+        assert!(PreloaderWeak::<u8>::new().upgrade().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shared_preloader() {
+        let shared = SharedPreloader::new();
+        let clone = shared.clone();
+        assert!(shared.ptr_eq(&clone));
+
+        clone.load(async { "value".to_string() }).await;
+        let reader = shared.clone();
+        let len = tokio::spawn(async move { reader.get().await.unwrap().len() })
+            .await
+            .unwrap();
+        assert_eq!(len, 5);
+        assert_eq!((&shared).await.unwrap(), "value");
+
+        let weak = shared.downgrade();
+        assert_eq!(weak.strong_count(), 2);
+        drop(clone);
+        let arc = shared.into_arc();
+        assert!(weak.upgrade().is_some());
+        drop(arc);
+        assert!(weak.is_dropped());
+
+        let built: SharedPreloader<u8> = Preloader::builder().name("built").build().into();
+        assert_eq!(built.name(), Some("built"));
+    }
+
+    #[tokio::test]
+    async fn test_load_scoped() {
+        let preloader = Preloader::new();
+        let mut tasks = tokio::task::JoinSet::new();
+        preloader.load_scoped(&mut tasks, async { 5 }).await;
+        assert_eq!(tasks.len(), 1);
+        tasks.join_all().await;
+        assert_eq!(*preloader.try_get().unwrap(), 5);
+
+        // Dropping the set aborts loads that have not finished
+        let preloader = Preloader::new();
+        let mut tasks = tokio::task::JoinSet::new();
+        preloader
+            .load_scoped(&mut tasks, async {
+                sleep(Duration::from_secs(60)).await;
+                5
+            })
+            .await;
+        drop(tasks);
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Cancelled)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_or_load() {
+        let preloader = Arc::new(Preloader::new());
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let preloader = Arc::clone(&preloader);
+                let calls = Arc::clone(&calls);
+                tokio::spawn(async move {
+                    *preloader
+                        .get_or_load(async move {
+                            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            sleep(Duration::from_millis(20)).await;
+                            i
+                        })
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for handle in handles {
+            results.push(handle.await.unwrap());
+        }
+        // Every caller sees the value of the single load that ran
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(results.iter().all(|&value| value == results[0]));
+
+        let arc = ArcPreloader::new();
+        assert_eq!(*arc.get_or_load(async { 1 }).await.unwrap(), 1);
+        assert_eq!(*arc.get_or_load(async { 2 }).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_try_load() {
+        let preloader: Preloader<u32> = Preloader::new();
+
+        let failed: std::result::Result<_, PreloaderError> = preloader
+            .get_or_try_load(|| async { Err(PreloaderError::Failed("refused".to_string())) })
+            .await;
+        assert!(matches!(failed, Err(PreloaderError::Failed(_))));
+        assert!(preloader.is_idle());
+
+        let value = preloader
+            .get_or_try_load(|| async { Ok::<_, PreloaderError>(3) })
+            .await
+            .unwrap();
+        assert_eq!(*value, 3);
+        let value = preloader
+            .get_or_try_load(|| async { Ok::<_, PreloaderError>(4) })
+            .await
+            .unwrap();
+        assert_eq!(*value, 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_try_load_waiter_retries_after_failure() {
+        let preloader: Arc<Preloader<u8>> = Arc::new(Preloader::new());
+
+        let first = {
+            let preloader = Arc::clone(&preloader);
+            tokio::spawn(async move {
+                preloader
+                    .get_or_try_load(|| async {
+                        sleep(Duration::from_millis(30)).await;
+                        Err::<u8, PreloaderError>(PreloaderError::Failed("first".to_string()))
+                    })
+                    .await
+                    .copied()
+            })
+        };
+        sleep(Duration::from_millis(10)).await;
+        assert!(preloader.is_loading());
+
+        // Waits for the first initialization, then runs its own after it fails
+        let second = preloader
+            .get_or_try_load(|| async { Ok::<_, PreloaderError>(2) })
+            .await
+            .unwrap();
+        assert_eq!(*second, 2);
+        assert!(first.await.unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_or_try_load_cancelled_caller_releases() {
+        let preloader: Preloader<u8> = Preloader::builder()
+            .timeout(Duration::from_millis(20))
+            .build();
+
+        let timed_out = preloader
+            .get_or_try_load(|| async {
+                sleep(Duration::from_secs(60)).await;
+                Ok::<_, PreloaderError>(1)
+            })
+            .await;
+        assert!(matches!(timed_out, Err(PreloaderError::Timeout)));
+        assert!(preloader.is_idle());
+
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(10),
+            preloader.get_or_try_load(|| async {
+                sleep(Duration::from_secs(60)).await;
+                Ok::<_, PreloaderError>(1)
+            }),
+        )
+        .await;
+        assert!(cancelled.is_err());
+        assert!(preloader.is_idle());
+
+        let arc = ArcPreloader::new();
+        let value = arc
+            .get_or_try_load(|| async { Ok::<_, PreloaderError>(2) })
+            .await
+            .unwrap();
+        assert_eq!(*value, 2);
+    }
+
+    #[tokio::test]
+    async fn test_multi_preloader_bounds_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let multi = MultiPreloader::from_iter(
+            (0..8).map(|index| {
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    index * 2
+                }
+            }),
+            3,
+        );
+
+        assert_eq!(multi.len(), 8);
+        assert_eq!(
+            multi.into_vec().await.unwrap(),
+            vec![0, 2, 4, 6, 8, 10, 12, 14]
+        );
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_multi_preloader_reads_finished_items_early() {
+        use std::{future::Future, pin::Pin};
+
+        let (sender, receiver) = tokio::sync::oneshot::channel::<u32>();
+        let multi = MultiPreloader::from_iter(
+            vec![
+                Box::pin(async { 1u32 }) as Pin<Box<dyn Future<Output = u32> + Send>>,
+                Box::pin(async move { receiver.await.unwrap() }),
+            ],
+            2,
+        );
+
+        assert_eq!(*multi.item(0).unwrap().get().await.unwrap(), 1);
+        assert!(multi.item(1).unwrap().try_get().is_err());
+        assert_eq!(multi.loaded_count(), 1);
+        assert_eq!(multi.progress(), 0.5);
+        assert!(!multi.is_ready());
+
+        sender.send(2).unwrap();
+        assert_eq!(multi.get_all().await.unwrap(), vec![&1, &2]);
+        assert!(multi.is_ready());
+        assert_eq!(multi.progress(), 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_multi_preloader_reports_failed_items() {
+        let multi = MultiPreloader::from_iter(
+            (0..3).map(|index| async move {
+                if index == 1 {
+                    panic!("broken item");
+                }
+                index
+            }),
+            1,
+        );
+
+        assert!(matches!(
+            multi.get_all().await,
+            Err(PreloaderError::Panicked(_))
+        ));
+        assert_eq!(multi.failed_count(), 1);
+        assert_eq!(*multi.item(2).unwrap().get().await.unwrap(), 2);
+        assert_eq!(multi.progress(), 1.0);
+        assert!(MultiPreloader::from_iter(Vec::<std::future::Ready<u8>>::new(), 4).is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_pipeline_records_stage_timings() {
+        let pipeline = Pipeline::new("fetch", async {
+            sleep(Duration::from_secs(2)).await;
+            vec![3u8, 4]
+        })
+        .then("decompress", |bytes| async move {
+            sleep(Duration::from_secs(1)).await;
+            bytes.into_iter().map(u32::from).collect::<Vec<_>>()
+        })
+        .then_blocking("index", |numbers| numbers.iter().sum::<u32>());
+        let timings = pipeline.timings();
+
+        let preloader = Preloader::new();
+        preloader.load_pipeline(pipeline).await;
+        assert_eq!(*preloader.get().await.unwrap(), 7);
+
+        let names: Vec<_> = timings.stages().iter().map(|timing| timing.name).collect();
+        assert_eq!(names, ["fetch", "decompress", "index"]);
+        assert_eq!(timings.stage("fetch"), Some(Duration::from_secs(2)));
+        assert_eq!(timings.stage("decompress"), Some(Duration::from_secs(1)));
+        assert!(timings.total() >= Duration::from_secs(3));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_stops_at_failing_stage() {
+        let reached = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = Arc::clone(&reached);
+        let pipeline = Pipeline::new("fetch", async { "x".to_string() })
+            .try_then_blocking("parse", |text| text.parse::<u32>())
+            .then("index", move |number| async move {
+                flag.store(true, std::sync::atomic::Ordering::SeqCst);
+                number
+            });
+        let timings = pipeline.timings();
+
+        match pipeline.run().await {
+            Err(PreloaderError::Failed(message)) => assert!(message.starts_with("parse: ")),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(!reached.load(std::sync::atomic::Ordering::SeqCst));
+        assert_eq!(timings.stages().len(), 2);
+        assert_eq!(timings.stage("index"), None);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_blocking_panic_fails_preloader() {
+        let pipeline = Pipeline::try_new("fetch", async { Ok::<_, std::io::Error>(1u32) })
+            .then_blocking("parse", |_: u32| -> u32 { panic!("bad input") });
+
+        let preloader = Preloader::new();
+        preloader.load_pipeline(pipeline).await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Panicked(_))
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_circuit_breaker_opens_and_probes() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(10));
+        let attempts = Arc::new(AtomicU32::new(0));
+        let healthy = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let (counter, flag) = (Arc::clone(&attempts), Arc::clone(&healthy));
+        let preloader: Preloader<u32> = Preloader::builder()
+            .retry(RetryPolicy::fixed(10, Duration::from_secs(1)))
+            .circuit_breaker(breaker.clone())
+            .error_policy(ErrorPolicy::Retry)
+            .build_with_loader(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let healthy = flag.load(Ordering::SeqCst);
+                async move {
+                    if !healthy {
+                        panic!("upstream down");
+                    }
+                    7
+                }
+            });
+
+        preloader.start().await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::CircuitOpen)
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Still open: the loader is not called
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::CircuitOpen)
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        // A failing probe opens the circuit again
+        sleep(Duration::from_secs(10)).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::CircuitOpen)
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // A successful probe closes it
+        healthy.store(true, Ordering::SeqCst);
+        sleep(Duration::from_secs(10)).await;
+        assert_eq!(*preloader.get().await.unwrap(), 7);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_is_shared_between_preloaders() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let first: Preloader<u32> = Preloader::builder()
+            .circuit_breaker(breaker.clone())
+            .build();
+        let second: Preloader<u32> = Preloader::builder()
+            .circuit_breaker(breaker.clone())
+            .build();
+
+        first.load_with(|| async { panic!("down") }).await;
+        assert!(matches!(
+            first.get().await,
+            Err(PreloaderError::Panicked(_))
+        ));
+
+        second.load_with(|| async { 1 }).await;
+        assert!(matches!(
+            second.get().await,
+            Err(PreloaderError::CircuitOpen)
+        ));
+
+        breaker.reset();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_pool_queue_limit_rejects() {
+        let pool = PreloaderPool::new(1).with_queue_limit(1, QueuePolicy::Reject);
+        let (running, handle) = tokio::sync::oneshot::channel::<()>();
+        let blocker = Preloader::new();
+        pool.load(&blocker, Priority::Normal, async move {
+            _ = handle.await;
+        })
+        .await;
+
+        let queued = Preloader::new();
+        pool.load(&queued, Priority::Normal, async { 1 }).await;
+        assert_eq!(pool.queued(), 1);
+        assert!(pool.is_queue_full());
+        assert_eq!(pool.queue_capacity(), Some(1));
+
+        let rejected = Preloader::new();
+        pool.load(&rejected, Priority::High, async { 2 }).await;
+        assert!(matches!(
+            rejected.get().await,
+            Err(PreloaderError::QueueFull)
+        ));
+
+        running.send(()).unwrap();
+        assert_eq!(*queued.get().await.unwrap(), 1);
+        assert!(!pool.is_queue_full());
+    }
+
+    #[tokio::test]
+    async fn test_pool_queue_limit_drops_lowest() {
+        let pool = PreloaderPool::new(1).with_queue_limit(2, QueuePolicy::DropLowest);
+        let (running, handle) = tokio::sync::oneshot::channel::<()>();
+        let blocker = Preloader::new();
+        pool.load(&blocker, Priority::Normal, async move {
+            _ = handle.await;
+        })
+        .await;
+
+        let low = Preloader::new();
+        let normal = Preloader::new();
+        pool.load(&low, Priority::Low, async { 1 }).await;
+        pool.load(&normal, Priority::Normal, async { 2 }).await;
+
+        // Rejected: nothing queued has a lower priority
+        let late_low = Preloader::new();
+        pool.load(&late_low, Priority::Low, async { 3 }).await;
+        assert!(matches!(
+            late_low.get().await,
+            Err(PreloaderError::QueueFull)
+        ));
+
+        let high = Preloader::new();
+        pool.load(&high, Priority::High, async { 4 }).await;
+        assert!(matches!(low.get().await, Err(PreloaderError::QueueFull)));
+        assert_eq!(pool.queued(), 2);
+
+        running.send(()).unwrap();
+        assert_eq!(*high.get().await.unwrap(), 4);
+        assert_eq!(*normal.get().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pool_queue_limit_waits_for_space() {
+        let pool = PreloaderPool::new(1).with_queue_limit(1, QueuePolicy::Wait);
+        let (running, handle) = tokio::sync::oneshot::channel::<()>();
+        let blocker = Preloader::new();
+        pool.load(&blocker, Priority::Normal, async move {
+            _ = handle.await;
+        })
+        .await;
+        let queued = Preloader::new();
+        pool.load(&queued, Priority::Normal, async { 1 }).await;
+
+        let waiting = Arc::new(Preloader::new());
+        let task = {
+            let (pool, waiting) = (pool.clone(), Arc::clone(&waiting));
+            tokio::spawn(async move { pool.load(&waiting, Priority::Normal, async { 2 }).await })
+        };
+        sleep(Duration::from_millis(10)).await;
+        assert!(!task.is_finished());
+        assert!(waiting.is_idle());
+
+        running.send(()).unwrap();
+        task.await.unwrap();
+        assert_eq!(*waiting.get().await.unwrap(), 2);
+        assert_eq!(*queued.get().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_registry_reports_registered_preloaders() {
+        let config = Arc::new(Preloader::new());
+        let broken: Arc<Preloader<u32>> = Arc::new(Preloader::new());
+        let cache = Arc::new(ArcPreloader::new());
+        registry::register("registry-test/config", &config);
+        registry::register("registry-test/broken", &broken);
+        registry::register("registry-test/cache", &cache);
+
+        config.load(async { 1u32 }).await;
+        config.get().await.unwrap();
+        broken.load(async { panic!("missing file") }).await;
+        _ = broken.get().await;
+        cache
+            .load(async { std::future::pending::<u32>().await })
+            .await;
+
+        let reports: Vec<_> = registry::snapshot()
+            .into_iter()
+            .filter(|report| report.name.starts_with("registry-test/"))
+            .collect();
+        let names: Vec<_> = reports.iter().map(|report| report.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "registry-test/broken",
+                "registry-test/cache",
+                "registry-test/config"
+            ]
+        );
+        assert_eq!(reports[0].state, PreloaderState::Failed);
+        assert!(matches!(
+            reports[0].last_error,
+            Some(PreloaderError::Panicked(_))
+        ));
+        assert_eq!(reports[1].state, PreloaderState::Loading);
+        assert!(reports[1].elapsed.is_some());
+        assert_eq!(reports[2].state, PreloaderState::Loaded);
+        assert!(!reports[2].is_pending());
+
+        let pending: Vec<_> = registry::pending()
+            .into_iter()
+            .filter(|name| name.starts_with("registry-test/"))
+            .collect();
+        assert_eq!(pending, ["registry-test/broken", "registry-test/cache"]);
+
+        assert!(registry::unregister("registry-test/broken"));
+        drop(cache);
+        assert!(!registry::snapshot()
+            .iter()
+            .any(|report| report.name == "registry-test/broken"
+                || report.name == "registry-test/cache"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_events_report_load_lifecycle() {
+        use futures::StreamExt;
+
+        let preloader = Arc::new(Preloader::new());
+        let mut events = preloader.events();
+
+        let loading = Arc::clone(&preloader);
+        preloader
+            .load(async move {
+                sleep(Duration::from_secs(2)).await;
+                loading.report_progress(1.5);
+                7u32
+            })
+            .await;
+
+        // Events arrive without anyone calling get()
+        assert!(matches!(events.next().await, Some(PreloaderEvent::Started)));
+        assert!(matches!(
+            events.next().await,
+            Some(PreloaderEvent::ProgressUpdated { progress }) if progress == 1.0
+        ));
+        match events.next().await {
+            Some(PreloaderEvent::Completed { duration }) => {
+                assert_eq!(duration, Duration::from_secs(2))
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        let failing: Preloader<u32> = Preloader::new();
+        let mut events = failing.events();
+        failing.load(async { panic!("offline") }).await;
+        assert!(matches!(events.next().await, Some(PreloaderEvent::Started)));
+        assert!(matches!(
+            events.next().await,
+            Some(PreloaderEvent::Failed {
+                error: PreloaderError::Panicked(_)
+            })
+        ));
+        drop(failing);
+        assert!(events.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_events_report_refresh_and_eviction() {
+        use futures::StreamExt;
+
+        let preloader = ArcPreloader::new();
+        preloader.load(async { 1u32 }).await;
+        preloader.get().await.unwrap();
+
+        let mut events = preloader.events();
+        preloader.reload(async { 2 }).await.unwrap();
+        assert!(matches!(
+            events.next().await,
+            Some(PreloaderEvent::Refreshed { generation: 2 })
+        ));
+
+        let cache = PreloaderCache::new(|key: &u32| {
+            let key = *key;
+            async move { key * 10 }
+        });
+        cache.get(&1).await.unwrap();
+        let mut events = cache.preloader(&1).events();
+        assert!(cache.remove(&1));
+        assert!(matches!(events.next().await, Some(PreloaderEvent::Evicted)));
+    }
+
+    #[tokio::test]
+    async fn test_cache_memory_budget_evicts_least_recently_used() {
+        let cache = PreloaderCache::new(|size: &usize| {
+            let size = *size;
+            async move { vec![0u8; size] }
+        })
+        .with_memory_budget(1000, |bytes: &Vec<u8>| bytes.len());
+        assert_eq!(cache.memory_budget(), Some(1000));
+
+        cache.get(&300).await.unwrap();
+        cache.get(&400).await.unwrap();
+        assert_eq!(cache.weight(), 700);
+
+        // Touch 300 so 400 becomes the least recently used
+        cache.try_get(&300).unwrap();
+        cache.get(&500).await.unwrap();
+        assert!(cache.contains_key(&300));
+        assert!(!cache.contains_key(&400));
+        assert_eq!(cache.weight(), 800);
+
+        // A value heavier than the budget is returned but not kept
+        assert_eq!(cache.get(&2000).await.unwrap().len(), 2000);
+        assert!(!cache.contains_key(&2000));
+        assert_eq!(cache.weight(), 800);
+
+        assert!(cache.remove(&500));
+        cache.clear();
+        assert_eq!(cache.weight(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cache_stats_count_hits_misses_and_coalescing() {
+        let cache = Arc::new(
+            PreloaderCache::new(|n: &u64| {
+                let n = *n;
+                async move {
+                    sleep(Duration::from_secs(n)).await;
+                    vec![0u8; n as usize * 100]
+                }
+            })
+            .with_memory_budget(500, |bytes: &Vec<u8>| bytes.len()),
+        );
+
+        let waiters: Vec<_> = (0..3)
+            .map(|_| {
+                let cache = Arc::clone(&cache);
+                tokio::spawn(async move { cache.get(&2).await.unwrap().len() })
+            })
+            .collect();
+        for waiter in waiters {
+            assert_eq!(waiter.await.unwrap(), 200);
+        }
+        cache.get(&2).await.unwrap();
+        cache.get(&4).await.unwrap();
+
+        let stats = cache.stats();
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.coalesced, 2);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.loads, 2);
+        assert_eq!(stats.average_load_time(), Some(Duration::from_secs(3)));
+        assert_eq!(stats.hit_rate(), 0.6);
+
+        cache.reset_stats();
+        assert_eq!(cache.stats(), CacheStats::default());
+        assert_eq!(CacheStats::default().average_load_time(), None);
+    }
+
+    #[tokio::test]
+    async fn test_preloader_of_trait_objects_derefs_to_target() {
+        trait Source: Send + Sync {
+            fn rows(&self) -> usize;
+        }
+
+        struct Table(usize);
+
+        impl Source for Table {
+            fn rows(&self) -> usize {
+                self.0
+            }
+        }
+
+        let boxed: Preloader<Box<dyn Source>> = Preloader::new();
+        assert!(matches!(
+            boxed.try_get_deref(),
+            Err(PreloaderError::NotLoaded)
+        ));
+        boxed
+            .load(async { Box::new(Table(3)) as Box<dyn Source> })
+            .await;
+        let source: &dyn Source = boxed.get_deref().await.unwrap();
+        assert_eq!(source.rows(), 3);
+
+        let shared: Preloader<Arc<dyn Source>> = Preloader::ready(Arc::new(Table(5)));
+        assert_eq!(shared.try_get_deref().unwrap().rows(), 5);
+
+        let text: Preloader<String> = Preloader::ready("plugin".to_string());
+        let text: &str = text.try_get_deref().unwrap();
+        assert_eq!(text, "plugin");
+    }
+
+    #[cfg(feature = "download")]
+    #[tokio::test]
+    async fn test_download_file_verifies_checksum() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let dir =
+            std::env::temp_dir().join(format!("preloader-test-download-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("source.bin");
+        std::fs::write(&source, vec![7u8; 200 * 1024]).unwrap();
+
+        let written = Arc::new(AtomicU64::new(0));
+        let seen = Arc::clone(&written);
+        let model: Preloader<std::path::PathBuf> = Preloader::new();
+        let mut events = model.events();
+        model
+            .load_download(
+                Download::file(&source, dir.join("model.bin"))
+                    .sha256("ea98ea443eae5d2e6425c146f2735902f6c4cb813af691cc8320f7918aaa35ca")
+                    .on_progress(move |bytes, _| seen.store(bytes, Ordering::SeqCst)),
+            )
+            .await;
+        let result = model.get().await.cloned();
+
+        let bad: Preloader<std::path::PathBuf> = Preloader::new();
+        bad.load_download(Download::file(&source, dir.join("bad.bin")).sha256("00"))
+            .await;
+        let error = bad.get().await.unwrap_err();
+        let left = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(result.unwrap(), dir.join("model.bin"));
+        assert_eq!(written.load(Ordering::SeqCst), 200 * 1024);
+        assert!(matches!(events.next().await, Some(PreloaderEvent::Started)));
+        assert!(matches!(
+            events.next().await,
+            Some(PreloaderEvent::ProgressUpdated { .. })
+        ));
+        assert!(
+            matches!(error, PreloaderError::Failed(message) if message.contains("checksum mismatch"))
+        );
+        // Only the source and the verified download are left behind
+        assert_eq!(left, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_deadline_fails_waiting_get() {
+        use tokio::time::Instant;
+
+        let slow = Arc::new(Preloader::spawn(async {
+            sleep(Duration::from_secs(10)).await;
+            "data"
+        }));
+
+        let deadline = Instant::now() + Duration::from_millis(200);
+        let result = with_deadline(deadline, async {
+            assert_eq!(current_deadline(), Some(deadline));
+            // A nested deadline cannot extend the outer one
+            with_deadline(deadline + Duration::from_secs(60), slow.get())
+                .await
+                .copied()
+        })
+        .await;
+        assert!(matches!(result, Err(PreloaderError::DeadlineExceeded)));
+        assert_eq!(current_deadline(), None);
+        assert!(slow.is_loading());
+
+        let short = slow
+            .get_until(Instant::now() + Duration::from_millis(10))
+            .await;
+        assert!(matches!(short, Err(PreloaderError::DeadlineExceeded)));
+
+        // The load keeps running, and loaded values are returned even past the deadline
+        assert_eq!(slow.get().await.unwrap(), &"data");
+        assert_eq!(slow.get_until(Instant::now()).await.unwrap(), &"data");
+
+        let shared = ArcPreloader::new();
+        let result = with_deadline(Instant::now() + Duration::from_millis(50), async {
+            shared.load(std::future::pending::<u8>()).await;
+            shared.get().await
+        })
+        .await;
+        assert!(matches!(result, Err(PreloaderError::DeadlineExceeded)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_group_shutdown_drains_then_aborts() {
+        let mut group = PreloaderGroup::new().max_concurrency(2);
+        let fast = group.add("fast", async { 1 });
+        group.add("broken", async {
+            panic!("broken");
+            #[allow(unreachable_code)]
+            0
+        });
+        let slow = group.add("slow", async {
+            sleep(Duration::from_secs(60)).await;
+            2
+        });
+        group.start().await;
+        let mut late = PreloaderGroup::new();
+        let never = late.add("never", async { 3 });
+
+        let report = group.shutdown(Duration::from_secs(1)).await;
+        assert_eq!(report.completed, ["fast"]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "broken");
+        assert_eq!(report.aborted, ["slow"]);
+        assert!(!report.is_drained());
+        assert_eq!(fast.try_get().unwrap(), &1);
+        assert!(matches!(slow.get().await, Err(PreloaderError::Cancelled)));
+
+        let report = late.shutdown(Duration::from_secs(1)).await;
+        assert_eq!(report.not_started, ["never"]);
+        assert!(report.is_drained());
+        late.start().await;
+        assert!(never.is_idle());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_stats_track_blocked_callers() {
+        let preloader = Arc::new(ArcPreloader::new());
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(50)).await;
+                "data".to_string()
+            })
+            .await;
+
+        let waiters: Vec<_> = (0..3)
+            .map(|_| {
+                let preloader = Arc::clone(&preloader);
+                tokio::spawn(async move { preloader.get().await.is_ok() })
+            })
+            .collect();
+        tokio::task::yield_now().await;
+        assert_eq!(preloader.wait_stats().waiting, 3);
+        registry::register("waited", &preloader);
+        assert_eq!(registry::snapshot()[0].waits.waiting, 3);
+
+        // A cancelled wait is still recorded
+        _ = tokio::time::timeout(Duration::from_millis(5), preloader.get()).await;
+        for waiter in waiters {
+            assert!(waiter.await.unwrap());
+        }
+        preloader.get().await.unwrap();
+
+        let stats = preloader.wait_stats();
+        assert_eq!(stats.waiting, 0);
+        assert_eq!(stats.waits, 4);
+        assert_eq!(stats.max_wait, Duration::from_millis(50));
+        assert_eq!(stats.histogram, [0, 1, 3, 0, 0, 0]);
+        assert_eq!(
+            stats.buckets().nth(2),
+            Some((Some(Duration::from_millis(100)), 3))
+        );
+        assert!(stats.average_wait().unwrap() < Duration::from_millis(50));
+        registry::unregister("waited");
+    }
+
+    #[tokio::test]
+    async fn test_once_cell_compatible_api() {
+        let cell: Arc<Preloader<String>> = Arc::new(Preloader::new());
+        assert!(!cell.initialized());
+
+        let waiter = {
+            let cell = Arc::clone(&cell);
+            tokio::spawn(async move { cell.wait().await.cloned() })
+        };
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        // A non-'static initializer borrowing local state, as with `OnceCell`
+        let name = String::from("config");
+        let value = cell.get_or_init(|| async { name.clone() }).await.unwrap();
+        assert_eq!(value, "config");
+        assert_eq!(
+            cell.get_or_init(|| async { String::new() }).await.unwrap(),
+            "config"
+        );
+        assert!(cell.initialized());
+        assert_eq!(waiter.await.unwrap().unwrap(), "config");
+
+        let shared: ArcPreloader<u8> = ArcPreloader::new();
+        assert_eq!(*shared.get_or_init(|| async { 7 }).await.unwrap(), 7);
+        assert!(shared.initialized());
+        assert_eq!(*shared.wait().await.unwrap(), 7);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_blocking_preloader_without_runtime() {
+        use std::sync::mpsc;
+
+        let idle: BlockingPreloader<u8> = BlockingPreloader::new();
+        assert!(matches!(idle.get(), Err(PreloaderError::NotLoaded)));
+
+        let (release, gate) = mpsc::channel::<()>();
+        let preloader = Arc::new(BlockingPreloader::spawn(move || {
+            gate.recv().unwrap();
+            "index".to_string()
+        }));
+        assert!(matches!(preloader.try_get(), Err(PreloaderError::Loading)));
+        assert!(matches!(
+            preloader.get_timeout(Duration::from_millis(10)),
+            Err(PreloaderError::DeadlineExceeded)
+        ));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let preloader = Arc::clone(&preloader);
+                std::thread::spawn(move || preloader.get().cloned())
+            })
+            .collect();
+        release.send(()).unwrap();
+        for reader in readers {
+            assert_eq!(reader.join().unwrap().unwrap(), "index");
+        }
+        assert_eq!(preloader.state(), PreloaderState::Loaded);
+
+        let broken: BlockingPreloader<u8> = BlockingPreloader::spawn(|| panic!("corrupt index"));
+        assert!(
+            matches!(broken.get(), Err(PreloaderError::Panicked(message)) if message == "corrupt index")
+        );
+        assert_eq!(BlockingPreloader::ready(3).into_inner(), Some(3));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[tokio::test]
+    async fn test_load_cpu_runs_on_rayon() {
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .thread_name(|_| "index-builder".to_string())
+                .build()
+                .unwrap(),
+        );
+        let thread: Preloader<Option<String>> = Preloader::new();
+        thread
+            .load_cpu_on(&pool, || std::thread::current().name().map(String::from))
+            .await;
+        assert_eq!(
+            thread.get().await.unwrap().as_deref(),
+            Some("index-builder")
+        );
+
+        let sum = Preloader::new();
+        sum.load_cpu(|| (1..=100u32).sum::<u32>()).await;
+        assert_eq!(sum.get().await.unwrap(), &5050);
+
+        let broken: Preloader<u32> = Preloader::new();
+        broken.load_cpu(|| panic!("bad input")).await;
+        assert!(
+            matches!(broken.get().await, Err(PreloaderError::Panicked(message)) if message == "bad input")
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_error_policy_per_error_kind() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+        let preloader: Preloader<u32> = Preloader::builder()
+            .error_policy(ErrorPolicy::Retry)
+            .error_policy_with(|error| match error {
+                PreloaderError::Panicked(message) if message == "not found" => {
+                    ErrorPolicy::CacheFor(Duration::from_secs(600))
+                }
+                PreloaderError::Panicked(_) => ErrorPolicy::Retry,
+                _ => ErrorPolicy::Cache,
+            })
+            .build_with_loader(move || {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match attempt {
+                        0 => panic!("not found"),
+                        1 => panic!("busy"),
+                        _ => 7,
+                    }
+                }
+            });
+        preloader.start().await;
+
+        assert!(preloader.get().await.is_err());
+        // NotFound is remembered for ten minutes
+        sleep(Duration::from_secs(599)).await;
+        assert!(
+            matches!(preloader.get().await, Err(PreloaderError::Panicked(message)) if message == "not found")
+        );
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        // The busy error is retried on the next call
+        sleep(Duration::from_secs(1)).await;
+        assert!(
+            matches!(preloader.get().await, Err(PreloaderError::Panicked(message)) if message == "busy")
+        );
+        assert_eq!(preloader.get().await.unwrap(), &7);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_error_context_and_source_chain() {
+        use std::error::Error;
+
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+        let error = PreloaderError::from_error(io);
+        assert_eq!(error.to_string(), "Preloader failed to load: refused");
+        assert_eq!(error.source().unwrap().to_string(), "refused");
+        assert!(!error.is_retryable());
+        assert!(PreloaderError::Timeout.is_retryable());
+        assert!(!PreloaderError::Poisoned.is_retryable());
+
+        let preloader: Preloader<u32> = Preloader::builder()
+            .name("catalog")
+            .timeout(Duration::from_millis(10))
+            .retry(RetryPolicy::fixed(2, Duration::from_millis(5)))
+            .build_with_loader(std::future::pending);
+        assert!(preloader.error_context().is_none());
+        preloader.start().await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Timeout)
+        ));
+
+        let context = preloader.error_context().unwrap();
+        assert_eq!(context.name.as_deref(), Some("catalog"));
+        assert_eq!(context.attempts, 3);
+        assert!(context.is_retryable());
+        assert_eq!(context.to_string(), "catalog failed after 3 attempt(s)");
+        assert!(matches!(
+            context.source().unwrap().downcast_ref::<PreloaderError>(),
+            Some(PreloaderError::Timeout)
+        ));
+
+        let single: Preloader<u8> = Preloader::new();
+        single.load(async { panic!("boom") }).await;
+        assert!(single.get().await.is_err());
+        let context = single.error_context().unwrap();
+        assert_eq!(context.attempts, 1);
+        assert_eq!(context.to_string(), "preloader failed after 1 attempt(s)");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_get_stale_during_refresh_and_after_expiry() {
+        let preloader: Arc<ArcPreloader<u32>> = Arc::new(
+            Preloader::builder()
+                .ttl(Duration::from_secs(60))
+                .build_arc(),
+        );
+        assert!(preloader.try_get_stale().is_none());
+        assert!(matches!(
+            preloader.get_stale().await,
+            Err(PreloaderError::NotLoaded)
+        ));
+        preloader.load(async { 1 }).await;
+        let (value, age) = preloader.get_stale().await.unwrap();
+        assert_eq!((*value, age), (1, Duration::ZERO));
+
+        let refresh = {
+            let preloader = Arc::clone(&preloader);
+            tokio::spawn(async move {
+                preloader
+                    .reload(async {
+                        sleep(Duration::from_secs(10)).await;
+                        panic!("upstream down")
+                    })
+                    .await
+            })
+        };
+        sleep(Duration::from_secs(5)).await;
+        assert_eq!(*preloader.try_get_stale().unwrap().0, 1);
+        assert!(refresh.await.unwrap().is_err());
+
+        sleep(Duration::from_secs(60)).await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Expired)
+        ));
+        let (value, age) = preloader.get_stale().await.unwrap();
+        assert_eq!((*value, age), (1, Duration::from_secs(70)));
+
+        preloader.reload(async { 2 }).await.unwrap();
+        let (value, age) = preloader.try_get_stale().unwrap();
+        assert_eq!((*value, age), (2, Duration::ZERO));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_typed_preloader_transitions() {
+        use crate::typestate::{Loaded, Loading, TypedPreloader};
+
+        fn total(table: &TypedPreloader<Vec<u32>, Loaded>) -> u32 {
+            table.get().iter().sum()
+        }
+
+        let loading: TypedPreloader<Vec<u32>, Loading> = TypedPreloader::spawn(async {
+            sleep(Duration::from_millis(10)).await;
+            vec![1, 2, 3]
+        });
+        let loading = loading.try_loaded().unwrap_err();
+        let table = loading.wait().await.unwrap();
+        assert_eq!(total(&table), 6);
+        assert_eq!(table.len(), 3);
+        assert!(table.as_preloader().load_duration().is_some());
+        assert_eq!(table.into_inner(), [1, 2, 3]);
+
+        let failed = TypedPreloader::<u8>::new().load(async { panic!("broken") });
+        assert!(matches!(
+            failed.wait().await,
+            Err(PreloaderError::Panicked(_))
+        ));
+
+        let ready = TypedPreloader::ready(5u8);
+        assert_eq!(*ready, 5);
+        assert!(format!("{ready:?}").contains("Loaded"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_load_cancellable() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let preloader: Preloader<u32> = Preloader::new();
+        assert!(!preloader.cancel());
+
+        let flag = stopped.clone();
+        preloader
+            .load_cancellable(|token| async move {
+                tokio::select! {
+                    _ = token.cancelled() => {
+                        flag.store(true, Ordering::SeqCst);
+                        None
+                    }
+                    _ = tokio::time::sleep(Duration::from_secs(60)) => Some(1),
+                }
+            })
+            .await;
+        tokio::task::yield_now().await;
+
+        assert!(preloader.cancel());
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Cancelled)
+        ));
+        assert!(stopped.load(Ordering::SeqCst));
+        assert!(!preloader.cancel());
+
+        // A loader finishing despite the token still delivers its value
+        let preloader: Preloader<u32> = Preloader::new();
+        preloader.load_cancellable(|_token| async { Some(7) }).await;
+        assert_eq!(preloader.get().await.unwrap(), &7);
+
+        // Loads without a token are aborted
+        let preloader: Preloader<u32> = Preloader::new();
+        preloader.load(std::future::pending()).await;
+        assert!(preloader.cancel());
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Cancelled)
+        ));
+
+        let shared: ArcPreloader<u32> = ArcPreloader::new();
+        shared
+            .load_cancellable(|token| async move {
+                token.cancelled().await;
+                None
+            })
+            .await;
+        assert!(shared.cancel());
+        assert!(matches!(shared.get().await, Err(PreloaderError::Cancelled)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_load_with_context() {
+        use futures::StreamExt;
+
+        let preloader: Preloader<u32> = Preloader::builder()
+            .timeout(Duration::from_secs(5))
+            .retry(RetryPolicy::fixed(2, Duration::from_millis(10)))
+            .build();
+        let mut events = preloader.events();
+        preloader
+            .load_with_context(|ctx| async move {
+                assert_eq!(ctx.remaining(), Some(Duration::from_secs(5)));
+                if ctx.attempt() == 1 {
+                    tokio::time::sleep(Duration::from_secs(10)).await;
+                }
+                ctx.report_progress(2.0);
+                Some(ctx.attempt())
+            })
+            .await;
+        assert_eq!(*preloader.get().await.unwrap(), 2);
+        assert!(matches!(events.next().await, Some(PreloaderEvent::Started)));
+        assert!(matches!(
+            events.next().await,
+            Some(PreloaderEvent::ProgressUpdated { progress }) if progress == 1.0
+        ));
+
+        // Cancelling stops the loader cooperatively, without retrying
+        let preloader: ArcPreloader<u32> = Preloader::builder()
+            .retry(RetryPolicy::fixed(3, Duration::from_millis(10)))
+            .build_arc();
+        preloader
+            .load_with_context(|ctx| async move {
+                assert!(ctx.deadline().is_none());
+                tokio::select! {
+                    _ = ctx.cancelled() => None,
+                    _ = tokio::time::sleep(Duration::from_secs(60)) => Some(1),
+                }
+            })
+            .await;
+        tokio::task::yield_now().await;
+        assert!(preloader.cancel());
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Cancelled)
+        ));
+        assert_eq!(preloader.error_context().unwrap().attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_names_in_diagnostics() {
+        let preloader: Arc<Preloader<u32>> =
+            Arc::new(Preloader::builder().name("names-in-diagnostics").build());
+        assert!(format!("{preloader:?}").contains("names-in-diagnostics"));
+        assert!(registry::register_named(&preloader));
+        assert!(registry::pending().contains(&"names-in-diagnostics".to_string()));
+
+        preloader
+            .load_with_context(|ctx| async move {
+                assert_eq!(ctx.name(), Some("names-in-diagnostics"));
+                panic!("unreachable backend")
+            })
+            .await;
+        assert!(preloader.get().await.is_err());
+        let context = preloader.error_context().unwrap();
+        assert!(context
+            .to_string()
+            .starts_with("names-in-diagnostics failed"));
+        assert!(registry::unregister("names-in-diagnostics"));
+
+        let anonymous = Arc::new(ArcPreloader::<u32>::new());
+        assert!(!registry::register_named(&anonymous));
+    }
+
+    #[tokio::test]
+    async fn test_wait_all_heterogeneous() {
+        let numbers = Preloader::spawn(async { vec![1, 2, 3] });
+        let name = ArcPreloader::spawn(async { "preloader".to_string() });
+        let idle: Preloader<u8> = Preloader::new();
+        let mut group = PreloaderGroup::new();
+        group.add("ok", async { 1u8 });
+        let _: Arc<Preloader<u8>> = group.add("broken", async { panic!("broken entry") });
+
+        let preloaders: [&dyn Readiness; 3] = [&numbers, &name, &idle];
+        let (result, _) = tokio::join!(wait_all(&preloaders), async {
+            tokio::task::yield_now().await;
+            idle.load(async { 7 }).await;
+        });
+        assert!(result.is_ok());
+
+        group.start().await;
+        let broken: Preloader<u8> = Preloader::spawn(async { panic!("broken") });
+        let failed = wait_all(&[&numbers, &group, &broken]).await.unwrap_err();
+        assert_eq!(failed.len(), 2);
+        assert!(
+            matches!(&failed[0], (1, PreloaderError::Failed(message)) if message.contains("1 preloader"))
+        );
+        assert!(matches!(failed[1], (2, PreloaderError::Panicked(_))));
+        assert!(wait_all(&[]).await.is_ok());
+    }
+
+    #[test]
+    fn test_preloader_config_install() {
+        // Only instrumentation differs from the built-in defaults, so concurrently running tests are unaffected
+        let profile = PreloaderConfig::new().instrument(false);
+        assert_ne!(profile, PreloaderConfig::default());
+        profile.clone().install();
+        assert_eq!(PreloaderConfig::current(), profile);
+        PreloaderConfig::uninstall();
+        assert_eq!(PreloaderConfig::current(), PreloaderConfig::new());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_arc_refresh_ahead() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&loads);
+        let preloader = Arc::new(
+            Preloader::builder()
+                .ttl(Duration::from_secs(100))
+                .build_arc_with_loader(move || {
+                    let count = counter.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        // The third load fails once, then refreshing recovers
+                        if count == 2 {
+                            panic!("upstream unavailable");
+                        }
+                        count
+                    }
+                }),
+        );
+        assert!(preloader.refresh_ahead(0.8));
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(*preloader.get().await.unwrap(), 0);
+
+        tokio::time::sleep(Duration::from_secs(79)).await;
+        assert_eq!(*preloader.try_get().unwrap(), 0);
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+
+        // The refresh due at 160s fails and is retried 5s later, before the value expires
+        tokio::time::sleep(Duration::from_secs(80)).await;
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert_eq!(*preloader.try_get().unwrap(), 3);
+        assert!(!preloader.is_expired());
+
+        let untimed = Arc::new(ArcPreloader::with_loader(|| async { 1 }));
+        assert!(!untimed.refresh_ahead(0.8));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_arc_refresh_jitter() {
+        let start = tokio::time::Instant::now().into_std();
+        let preloaders: Vec<_> = (0..8)
+            .map(|_| {
+                let preloader = Arc::new(
+                    Preloader::builder()
+                        .refresh_jitter(Duration::from_secs(5))
+                        .build_arc_with_loader(|| async { 1 }),
+                );
+                assert!(preloader.refresh_every(Duration::from_secs(10)));
+                preloader
+            })
+            .collect();
+        tokio::task::yield_now().await;
+
+        let offsets: Vec<_> = preloaders
+            .iter()
+            .map(|preloader| preloader.next_refresh().unwrap() - start)
+            .collect();
+        assert!(offsets
+            .iter()
+            .all(|offset| (Duration::from_secs(10)..=Duration::from_secs(15)).contains(offset)));
+        assert!(offsets.iter().any(|offset| *offset != offsets[0]));
+
+        registry::register("refresh-jitter", &preloaders[0]);
+        let report = registry::snapshot()
+            .into_iter()
+            .find(|report| report.name == "refresh-jitter")
+            .unwrap();
+        assert_eq!(report.next_refresh, preloaders[0].next_refresh());
+        registry::unregister("refresh-jitter");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cache_negative_ttl() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&loads);
+        let cache = PreloaderCache::new(move |key: &String| {
+            let count = counter.fetch_add(1, Ordering::SeqCst);
+            let key = key.clone();
+            // "new" exists from its third load on
+            async move { (key != "new" || count >= 2).then_some(key) }
+        })
+        .with_negative_ttl(Duration::from_secs(30));
+
+        assert_eq!(*cache.get(&"new".to_string()).await.unwrap(), None);
+        assert_eq!(*cache.get(&"new".to_string()).await.unwrap(), None);
+        assert!(matches!(cache.try_get(&"new".to_string()), Ok(value) if value.is_none()));
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.stats().negative_hits, 1);
+
+        // Present values do not expire
+        assert_eq!(
+            cache.get(&"old".to_string()).await.unwrap().as_deref(),
+            Some("old")
+        );
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert_eq!(
+            cache.get(&"old".to_string()).await.unwrap().as_deref(),
+            Some("old")
+        );
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+
+        // The absence expired, so the key is loaded again and found
+        assert!(matches!(
+            cache.try_get(&"new".to_string()),
+            Err(PreloaderError::NotLoaded)
+        ));
+        assert_eq!(
+            cache.get(&"new".to_string()).await.unwrap().as_deref(),
+            Some("new")
+        );
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cache_get_many() {
+        use std::collections::HashMap;
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        };
+
+        let cache = PreloaderCache::new(|key: &u32| {
+            let key = *key;
+            async move { key * 10 }
+        });
+        cache.get(&1).await.unwrap();
+        let found = cache.get_many([1, 2, 3, 2]).await;
+        assert_eq!(found.len(), 3);
+        assert_eq!(*found[&3].as_ref().unwrap().as_ref(), 30);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 3));
+
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&batches);
+        let singles = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&singles);
+        let cache = PreloaderCache::new(move |key: &u32| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let key = *key;
+            async move { key * 10 }
+        })
+        .with_batch_loader(move |mut keys: Vec<u32>| {
+            keys.sort();
+            seen.lock().unwrap().push(keys.clone());
+            async move {
+                keys.into_iter()
+                    .filter(|key| *key != 4)
+                    .map(|key| (key, key * 100))
+                    .collect::<HashMap<_, _>>()
+            }
+        });
+        cache.get(&1).await.unwrap();
+        let found = cache.get_many([1, 2, 3, 4]).await;
+        assert_eq!(*found[&1].as_ref().unwrap().as_ref(), 10);
+        assert_eq!(*found[&2].as_ref().unwrap().as_ref(), 200);
+        assert!(matches!(found[&4], Err(PreloaderError::Failed(_))));
+        assert_eq!(*batches.lock().unwrap(), vec![vec![2, 3, 4]]);
+        assert_eq!(singles.load(Ordering::SeqCst), 1);
+        assert_eq!(*cache.get(&3).await.unwrap(), 300);
+
+        // A panicking batch fails every key it claimed
+        let cache = PreloaderCache::new(|key: &u32| {
+            let key = *key;
+            async move { key }
+        })
+        .with_batch_loader(|_: Vec<u32>| async { panic!("batch") });
+        let found = cache.get_many([5, 6]).await;
+        assert!(found
+            .values()
+            .all(|result| matches!(result, Err(PreloaderError::Panicked(_)))));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cache_removal_listener() {
+        use std::sync::Mutex;
+
+        let removed = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&removed);
+        let cache = PreloaderCache::new(|key: &usize| {
+            let key = *key;
+            async move { (key > 0).then(|| vec![0u8; key]) }
+        })
+        .with_memory_budget(100, |value: &Option<Vec<u8>>| {
+            value.as_ref().map_or(0, Vec::len)
+        })
+        .with_negative_ttl(Duration::from_secs(10))
+        .with_removal_listener(move |key, value, cause| {
+            log.lock().unwrap().push((*key, value.is_some(), cause));
+        });
+
+        cache.get(&60).await.unwrap();
+        cache.get(&50).await.unwrap();
+        cache.get(&0).await.unwrap();
+        tokio::time::advance(Duration::from_secs(10)).await;
+        cache.get(&0).await.unwrap();
+        cache.get(&30).await.unwrap();
+        // Not loaded yet, so not reported
+        cache.preloader(&40);
+        cache.clear();
+
+        let mut removed = removed.lock().unwrap().clone();
+        removed[2..].sort_by_key(|(key, _, _)| *key);
+        assert_eq!(
+            removed,
+            [
+                (60, true, RemovalCause::Size),
+                (0, false, RemovalCause::Expired),
+                (0, false, RemovalCause::Explicit),
+                (30, true, RemovalCause::Explicit),
+                (50, true, RemovalCause::Explicit),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cache_time_to_idle() {
+        use std::sync::Mutex;
+
+        let expired = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&expired);
+        let cache = PreloaderCache::new(|key: &u32| {
+            let key = *key;
+            async move { key }
+        })
+        .with_time_to_idle(Duration::from_secs(60))
+        .with_removal_listener(move |key, _, cause| {
+            log.lock().unwrap().push((*key, cause));
+        });
+
+        cache.get(&1).await.unwrap();
+        cache.get(&2).await.unwrap();
+        cache.get(&3).await.unwrap();
+        // Key 1 stays hot while the others go idle
+        for _ in 0..3 {
+            tokio::time::advance(Duration::from_secs(40)).await;
+            cache.try_get(&1).unwrap();
+        }
+        assert_eq!(cache.len(), 1);
+        let mut removed = expired.lock().unwrap().clone();
+        removed.sort_by_key(|(key, _)| *key);
+        assert_eq!(
+            removed,
+            [(2, RemovalCause::Expired), (3, RemovalCause::Expired)]
+        );
+
+        // An idle key found by a lookup is loaded again
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert!(matches!(cache.try_get(&1), Err(PreloaderError::NotLoaded)));
+        assert_eq!(*cache.get(&1).await.unwrap(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cache_expiry_policy() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        };
+
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&loads);
+        let expired = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&expired);
+        // The value is the TTL in seconds, and zero never expires
+        let cache = PreloaderCache::new(move |key: &u64| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let key = *key;
+            async move { key }
+        })
+        .with_expiry_policy(|_: &u64, ttl: &u64| (*ttl > 0).then(|| Duration::from_secs(*ttl)))
+        .with_removal_listener(move |key, _, cause| {
+            log.lock().unwrap().push((*key, cause));
+        });
+
+        for key in [0, 5, 30] {
+            cache.get(&key).await.unwrap();
+        }
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert!(matches!(cache.try_get(&5), Err(PreloaderError::NotLoaded)));
+        assert_eq!(*cache.get(&5).await.unwrap(), 5);
+        assert_eq!(loads.load(Ordering::SeqCst), 4);
+
+        // Key 30 is never read again, and the periodic check removes it
+        tokio::time::advance(Duration::from_secs(30)).await;
+        cache.try_get(&0).unwrap();
+        assert!(!cache.contains_key(&30));
+        let mut removed = expired.lock().unwrap().clone();
+        removed.sort_by_key(|(key, _)| *key);
+        assert_eq!(
+            removed,
+            [
+                (5, RemovalCause::Expired),
+                (5, RemovalCause::Expired),
+                (30, RemovalCause::Expired)
+            ]
+        );
+
+        let fixed = PreloaderCache::new(|key: &u32| {
+            let key = *key;
+            async move { key }
+        })
+        .with_expiry_policy(Duration::from_secs(60));
+        fixed.get(&1).await.unwrap();
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert!(fixed.try_get(&1).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_cache_shards() {
+        let cache = Arc::new(
+            PreloaderCache::new(|key: &u32| {
+                let key = *key;
+                async move { key * 2 }
+            })
+            .with_shards(16)
+            .with_memory_budget(64, |_| 1),
+        );
+
+        let tasks: Vec<_> = (0..8)
+            .map(|task| {
+                let cache = Arc::clone(&cache);
+                tokio::spawn(async move {
+                    for key in 0..100 {
+                        let key = (key + task * 10) % 100;
+                        assert_eq!(*cache.get(&key).await.unwrap(), key * 2);
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        // The budget holds across shards
+        assert_eq!(cache.len(), 64);
+        assert_eq!(cache.weight(), 64);
+        let stats = cache.stats();
+        assert_eq!(stats.hits + stats.misses + stats.coalesced, 800);
+        assert_eq!(stats.misses as usize - stats.evictions as usize, 64);
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn test_cache_persist_restore() {
+        let source = PreloaderCache::new(|key: &String| {
+            let key = key.clone();
+            async move { (key != "missing").then_some(key.len()) }
+        })
+        .with_negative_ttl(Duration::ZERO);
+        source.get(&"alice".to_string()).await.unwrap();
+        source.get(&"bob".to_string()).await.unwrap();
+        source.get(&"missing".to_string()).await.unwrap();
+        // Not loaded, so not persisted
+        source.preloader(&"idle".to_string());
+        let mut bytes = Vec::new();
+        source
+            .persist(&mut serde_json::Serializer::new(&mut bytes))
+            .unwrap();
+
+        let restored = PreloaderCache::new(|_: &String| async { unreachable!() })
+            .with_negative_ttl(Duration::ZERO);
+        restored.get(&"bob".to_string()).await.ok();
+        // The cached absence expired, and "bob" was already requested
+        let count = restored
+            .restore(&mut serde_json::Deserializer::from_slice(&bytes))
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(*restored.try_get(&"alice".to_string()).unwrap(), Some(5));
+        assert!(!restored.contains_key(&"missing".to_string()));
+
+        // Entries idle longer than the time-to-idle are skipped
+        std::thread::sleep(Duration::from_millis(10));
+        let idle = PreloaderCache::new(|_: &String| async { None::<usize> })
+            .with_time_to_idle(Duration::from_millis(1));
+        let count = idle
+            .restore(&mut serde_json::Deserializer::from_slice(&bytes))
+            .unwrap();
+        assert_eq!(count, 0);
+        assert!(idle
+            .restore(&mut serde_json::Deserializer::from_slice(b"["))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_from_stream() {
+        use futures::{stream, StreamExt};
+
+        let preloader = Preloader::from_stream(stream::iter(["a", "b", "c"]));
+        assert_eq!(preloader.get().await.unwrap(), &["a", "b", "c"]);
+
+        let preloader = Preloader::new();
+        let mut events = preloader.events();
+        preloader.load_stream(stream::iter(1..=4)).await;
+        assert_eq!(preloader.get().await.unwrap(), &[1, 2, 3, 4]);
+        assert!(matches!(events.next().await, Some(PreloaderEvent::Started)));
+        for count in 1..=4u64 {
+            assert!(matches!(
+                events.next().await,
+                Some(PreloaderEvent::ItemsLoaded { count: received }) if received == count
+            ));
+            assert!(matches!(
+                events.next().await,
+                Some(PreloaderEvent::ProgressUpdated { progress }) if progress == count as f32 / 4.0
+            ));
+        }
+        assert!(matches!(
+            events.next().await,
+            Some(PreloaderEvent::Completed { .. })
+        ));
+
+        // A fold sees every item, and an unbounded stream reports only item counts
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let longest = Preloader::from_stream_fold(
+            receiver_stream(rx),
+            String::new(),
+            |longest: String, word: String| {
+                if word.len() > longest.len() {
+                    word
+                } else {
+                    longest
+                }
+            },
+        );
+        for word in ["tea", "coffee", "mate"] {
+            tx.send(word.to_string()).unwrap();
+        }
+        assert!(longest.is_loading());
+        drop(tx);
+        assert_eq!(longest.get().await.unwrap(), "coffee");
+
+        fn receiver_stream<T: Send + 'static>(
+            mut rx: tokio::sync::mpsc::UnboundedReceiver<T>,
+        ) -> impl futures::Stream<Item = T> {
+            stream::poll_fn(move |cx| rx.poll_recv(cx))
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_arc_preloader_placeholder() {
+        let preloader = ArcPreloader::new().with_placeholder(vec!["loading"]);
+        assert_eq!(*preloader.try_get().unwrap(), ["loading"]);
+        assert!(!preloader.is_loaded());
+
+        preloader
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                vec!["alice", "bob"]
+            })
+            .await;
+        let (generation, value) = preloader.get_versioned().await.unwrap();
+        assert_eq!((generation, value.len()), (0, 1));
+        assert_eq!(preloader.wait().await.unwrap().len(), 2);
+        assert_eq!(preloader.try_get_versioned().unwrap().0, 1);
+
+        // A failed load reports its error instead of the placeholder
+        let failed = ArcPreloader::new().with_placeholder(0u32);
+        failed.load(async { panic!("broken") }).await;
+        assert!(failed.wait().await.is_err());
+        assert!(failed.try_get().is_err());
+        assert!(failed.get().await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_arc_try_take_keeps_configuration() {
+        let preloader = ArcPreloader::new().with_placeholder(vec!["loading"]);
+        preloader
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                vec!["alice", "bob"]
+            })
+            .await;
+
+        // A preloader handed back still serves its placeholder and later its value
+        let preloader = preloader.try_take().unwrap_err();
+        assert_eq!(*preloader.try_get().unwrap(), ["loading"]);
+        let preloader = preloader
+            .take_timeout(Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert_eq!(*preloader.try_get().unwrap(), ["loading"]);
+        assert_eq!(preloader.wait().await.unwrap().len(), 2);
+        assert_eq!(preloader.try_take().unwrap().len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_poll_get() {
+        use std::future::poll_fn;
+
+        let preloader = Arc::new(Preloader::new());
+        assert!(matches!(
+            poll_fn(|cx| preloader.poll_get(cx).map(|value| value.map(|_: &u32| ()))).await,
+            Err(PreloaderError::NotLoaded)
+        ));
+        preloader
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                7u32
+            })
+            .await;
+
+        // A cancelled `get()` waiter must not strand the pollers
+        let waiter = tokio::spawn({
+            let preloader = Arc::clone(&preloader);
+            async move { preloader.get().await.copied().ok() }
+        });
+        tokio::task::yield_now().await;
+        let pollers: Vec<_> = (0..3)
+            .map(|_| {
+                let preloader = Arc::clone(&preloader);
+                tokio::spawn(async move {
+                    poll_fn(|cx| preloader.poll_get(cx).map(|value| value.copied().ok())).await
+                })
+            })
+            .collect();
+        tokio::task::yield_now().await;
+        waiter.abort();
+        for poller in pollers {
+            assert_eq!(poller.await.unwrap(), Some(7));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_get_cancelled_waiters_keep_value() {
+        let preloader = Arc::new(Preloader::new());
+        preloader
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                "loaded".to_string()
+            })
+            .await;
+
+        // The first waiter holds the receiver when it is dropped
+        let cancelled = tokio::spawn({
+            let preloader = Arc::clone(&preloader);
+            async move { preloader.get().await.is_ok() }
+        });
+        tokio::task::yield_now().await;
+        let waiter = tokio::spawn({
+            let preloader = Arc::clone(&preloader);
+            async move { preloader.get().await.cloned() }
+        });
+        tokio::task::yield_now().await;
+        cancelled.abort();
+        assert!(cancelled.await.unwrap_err().is_cancelled());
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), preloader.get())
+                .await
+                .is_err()
+        );
+        assert_eq!(waiter.await.unwrap().unwrap(), "loaded");
+
+        // A finished load is kept even when every waiter was dropped
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let preloader = Preloader::spawn(async { rx.await.unwrap() });
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), preloader.get())
+                .await
+                .is_err()
+        );
+        tx.send(3u32).unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(*preloader.try_get().unwrap(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_load_handle() {
+        let preloader = Preloader::new();
+        let handle = preloader
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                1u32
+            })
+            .await;
+        assert!(!handle.is_finished());
+        // An ignored call returns the handle to the running load
+        let ignored = preloader.load(async { 2 }).await;
+        ignored.await.unwrap();
+        assert!(handle.is_finished());
+        handle.await.unwrap();
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+
+        let failed = Preloader::<u32>::new();
+        let handle = failed.load(async { panic!("broken") }).await;
+        assert!(matches!(handle.await, Err(PreloaderError::Panicked(_))));
+
+        let aborted = ArcPreloader::new();
+        let handle = aborted
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                3u32
+            })
+            .await;
+        handle.abort();
+        assert!(matches!(handle.await, Err(PreloaderError::Cancelled)));
+        assert!(matches!(
+            aborted.get().await,
+            Err(PreloaderError::Cancelled)
+        ));
+
+        let stored = Preloader::new();
+        stored.set(4u32).unwrap();
+        let handle = stored.load(async { 5 }).await;
+        assert!(handle.is_finished());
+        handle.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_load_policy() {
+        // Ignore keeps the first load
+        let ignored = Preloader::new();
+        ignored.load(async { 1u32 }).await;
+        ignored.load(async { 2 }).await.await.unwrap();
+        assert_eq!(*ignored.get().await.unwrap(), 1);
+
+        let rejecting = Preloader::builder().load_policy(LoadPolicy::Error).build();
+        rejecting.load(async { 1u32 }).await;
+        let second = rejecting.load(async { 2 }).await;
+        assert!(second.is_finished());
+        assert!(matches!(second.await, Err(PreloaderError::OverlappingLoad)));
+
+        // A plain preloader only replaces a failed load
+        let restarting = Preloader::builder()
+            .load_policy(LoadPolicy::ReplaceWhenReady)
+            .build();
+        assert!(restarting
+            .load(async { panic!("broken") })
+            .await
+            .await
+            .is_err());
+        restarting.load(async { 3u32 }).await.await.unwrap();
+        assert_eq!(*restarting.get().await.unwrap(), 3);
+        assert!(matches!(
+            restarting.load(async { 4 }).await.await,
+            Err(PreloaderError::OverlappingLoad)
+        ));
+
+        let replacing = Preloader::builder()
+            .load_policy(LoadPolicy::ReplaceWhenReady)
+            .build_arc()
+            .validate(|value: &u32| match *value {
+                0 => Err("zero"),
+                _ => Ok(()),
+            });
+        replacing.load(async { 1 }).await.await.unwrap();
+        let slow = replacing
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                2
+            })
+            .await;
+        let latest = replacing
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                3
+            })
+            .await;
+        assert_eq!(*replacing.try_get().unwrap(), 1);
+        latest.await.unwrap();
+        assert_eq!(replacing.try_get_versioned().unwrap(), (2, Arc::new(3)));
+        // The replacement superseded by a later call is never swapped in
+        slow.await.unwrap();
+        assert_eq!(*replacing.get().await.unwrap(), 3);
+
+        let rejected = replacing.load(async { 0 }).await;
+        assert!(matches!(rejected.await, Err(PreloaderError::Failed(_))));
+        assert_eq!(*replacing.try_get().unwrap(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_load_policy_entry_points() {
+        let rejecting = Preloader::builder().load_policy(LoadPolicy::Error).build();
+        rejecting.load_with(|| async { 1u32 }).await.await.unwrap();
+        assert!(matches!(
+            rejecting.load_with(|| async { 2 }).await.await,
+            Err(PreloaderError::OverlappingLoad)
+        ));
+        let mut tasks = tokio::task::JoinSet::new();
+        assert!(matches!(
+            rejecting.load_scoped(&mut tasks, async { 3 }).await.await,
+            Err(PreloaderError::OverlappingLoad)
+        ));
+        assert!(matches!(
+            rejecting.load_race([async { 4 }]).await.await,
+            Err(PreloaderError::OverlappingLoad)
+        ));
+        assert!(tasks.is_empty());
+        assert_eq!(*rejecting.get().await.unwrap(), 1);
+
+        // Ignore hands back the handle of the running load
+        let ignored = Preloader::new();
+        let first = ignored
+            .load_cancellable(|_token| async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                Some(5u32)
+            })
+            .await;
+        let second = ignored.load_with_context(|_ctx| async { Some(6) }).await;
+        assert!(!second.is_finished());
+        second.await.unwrap();
+        assert!(first.is_finished());
+        assert_eq!(*ignored.get().await.unwrap(), 5);
+
+        // ReplaceWhenReady starts a new load after a failed one
+        let restarting = Preloader::builder()
+            .load_policy(LoadPolicy::ReplaceWhenReady)
+            .build();
+        assert!(restarting
+            .load_with(|| async { panic!("broken") })
+            .await
+            .await
+            .is_err());
+        restarting.load_race([async { 7u32 }]).await.await.unwrap();
+        assert_eq!(*restarting.get().await.unwrap(), 7);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reload_coalescing() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let preloader = Arc::new(ArcPreloader::ready(0u32));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let reload = |value: u32| {
+            let preloader = Arc::clone(&preloader);
+            let runs = Arc::clone(&runs);
+            tokio::spawn(async move {
+                preloader
+                    .reload(async move {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        value
+                    })
+                    .await
+            })
+        };
+
+        let first = reload(1);
+        tokio::task::yield_now().await;
+        let burst: Vec<_> = (2..=5).map(reload).collect();
+        first.await.unwrap().unwrap();
+        for request in burst {
+            request.await.unwrap().unwrap();
+        }
+        // The burst ran one more reload, with the latest request's loader
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(preloader.try_get_versioned().unwrap(), (3, Arc::new(5)));
+
+        // A failed reload is reported to every request coalesced into it
+        let running = reload(6);
+        tokio::task::yield_now().await;
+        let failing = (0..2)
+            .map(|_| {
+                let preloader = Arc::clone(&preloader);
+                tokio::spawn(async move { preloader.reload(async { panic!("broken") }).await })
+            })
+            .collect::<Vec<_>>();
+        running.await.unwrap().unwrap();
+        for request in failing {
+            assert!(matches!(
+                request.await.unwrap(),
+                Err(PreloaderError::Panicked(_))
+            ));
+        }
+        assert_eq!(*preloader.try_get().unwrap(), 6);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unload() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let loads = Arc::new(AtomicU32::new(0));
+        let mut preloader = Preloader::with_loader({
+            let loads = Arc::clone(&loads);
+            move || {
+                let load = loads.fetch_add(1, Ordering::SeqCst) + 1;
+                async move {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    vec![load; 4]
+                }
+            }
+        });
+        assert!(!preloader.unload());
+        preloader.start().await;
+        assert_eq!(preloader.get().await.unwrap()[0], 1);
+
+        assert!(preloader.unload());
+        assert!(preloader.is_idle());
+        assert!(preloader.loaded_at().is_none());
+        // Checking the state does not load the value again
+        assert!(matches!(
+            preloader.try_get(),
+            Err(PreloaderError::NotLoaded)
+        ));
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert_eq!(preloader.get().await.unwrap()[0], 2);
+
+        // Without a loader factory the preloader is loaded again explicitly
+        let mut plain = ArcPreloader::new();
+        plain.load(async { 1u32 }).await;
+        assert_eq!(*plain.get().await.unwrap(), 1);
+        assert!(plain.unload());
+        assert!(matches!(plain.get().await, Err(PreloaderError::NotLoaded)));
+        plain.load(async { 2 }).await;
+        assert_eq!(*plain.get().await.unwrap(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_load_history() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let preloader = Preloader::builder()
+            .retry(RetryPolicy::fixed(1, Duration::from_millis(10)))
+            .history(3)
+            .build_arc_with_loader({
+                let calls = Arc::clone(&calls);
+                move || {
+                    let call = calls.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        assert!(call != 0 && call != 2 && call != 3, "unavailable");
+                        call
+                    }
+                }
+            });
+        preloader.start().await;
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+        assert!(preloader.refresh().await.is_err());
+        assert!(preloader.reload(async { 10 }).await.is_ok());
+
+        let history = preloader.history();
+        let summary: Vec<_> = history
+            .iter()
+            .map(|load| (load.trigger, load.attempts, load.is_success()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (LoadTrigger::Initial, 2, true),
+                (LoadTrigger::Refresh, 2, false),
+                (LoadTrigger::Refresh, 1, true),
+            ]
+        );
+        assert!(history[0].duration >= Duration::from_secs(2));
+        assert!(matches!(
+            history[1].error,
+            Some(PreloaderError::Panicked(_))
+        ));
+
+        // The history is bounded, and a load after a failure is a retry
+        let failing = Preloader::builder()
+            .history(2)
+            .on_failure(FailureMode::Idle)
+            .build();
+        for _ in 0..3 {
+            failing.load(async { panic!("broken") }).await;
+            assert!(failing.get().await.is_err());
+        }
+        let triggers: Vec<_> = failing.history().iter().map(|load| load.trigger).collect();
+        assert_eq!(triggers, [LoadTrigger::Retry, LoadTrigger::Retry]);
+        assert!(Preloader::<u8>::builder()
+            .history(0)
+            .build()
+            .history()
+            .is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_arc_preloader_swap() {
+        let preloader = ArcPreloader::new();
+        assert!(preloader.swap(1u32).is_none());
+        assert!(preloader.is_loaded());
+        let reader = preloader.try_get().unwrap();
+        assert_eq!(preloader.swap(2).as_deref(), Some(&1));
+        assert_eq!(preloader.swap(3).as_deref(), Some(&2));
+        assert_eq!(*reader, 1);
+        assert_eq!(preloader.try_get_versioned().unwrap(), (3, Arc::new(3)));
+
+        // A failed preloader is recovered
+        let failed = ArcPreloader::new();
+        failed.load(async { panic!("broken") }).await.await.ok();
+        assert!(failed.swap(4u32).is_none());
+        assert_eq!(failed.state(), PreloaderState::Loaded);
+        assert_eq!(*failed.get().await.unwrap(), 4);
+
+        // The swapped value takes precedence over a first load still in progress
+        let loading = ArcPreloader::new();
+        loading
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                5u32
+            })
+            .await;
+        assert!(loading.swap(6).is_none());
+        assert_eq!(*loading.try_get().unwrap(), 6);
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(*loading.get().await.unwrap(), 6);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_arc_preloader_dedupe() {
+        use futures::StreamExt;
+
+        let preloader = ArcPreloader::ready(vec![1u32, 2])
+            .dedupe_by(|current: &Vec<u32>, new| current.len() == new.len());
+        let mut events = preloader.events();
+        let original = preloader.try_get().unwrap();
+        tokio::time::advance(Duration::from_secs(10)).await;
+
+        preloader.reload(async { vec![3, 4] }).await.unwrap();
+        assert!(!preloader.changed_since(1));
+        assert!(Arc::ptr_eq(&preloader.try_get().unwrap(), &original));
+        assert!(preloader.age().unwrap() < Duration::from_secs(1));
+
+        preloader.reload(async { vec![5] }).await.unwrap();
+        assert!(preloader.changed_since(1));
+        assert_eq!(
+            preloader.try_get_versioned().unwrap(),
+            (2, Arc::new(vec![5]))
+        );
+        assert!(matches!(
+            events.next().await,
+            Some(PreloaderEvent::Refreshed { generation: 2 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_arc_get_mut_after_reload() {
+        let mut preloader = ArcPreloader::ready(vec![1]);
+        preloader.reload(async { vec![1, 2] }).await.unwrap();
+        assert_eq!(preloader.generation(), 2);
+
+        // The reloaded value is modified in place and keeps its generation
+        preloader.get_mut().unwrap().push(3);
+        assert_eq!(*preloader.try_get().unwrap(), vec![1, 2, 3]);
+        assert_eq!(preloader.generation(), 2);
+
+        preloader.reload(async { vec![4] }).await.unwrap();
+        assert_eq!(preloader.generation(), 3);
+
+        // A handle still held by a reader rules out mutable access
+        let held = preloader.try_get().unwrap();
+        assert!(preloader.get_mut().is_none());
+        drop(held);
+        assert_eq!(*preloader.try_get().unwrap(), vec![4]);
+        assert_eq!(preloader.generation(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_task_tracker() {
+        use crate::TaskTracker;
+
+        let tracker = TaskTracker::new();
+        let preloader = Preloader::builder()
+            .task_tracker(tracker.clone())
+            .build_arc();
+        preloader
+            .load(async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                1
+            })
+            .await;
+        assert_eq!(tracker.len(), 1);
+
+        // Waiting for the tracker waits for the loading task to store its result
+        tracker.close();
+        tracker.wait().await;
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+    }
+
+    #[cfg(all(unix, feature = "signal"))]
+    #[tokio::test]
+    async fn test_refresh_on_sighup() {
+        use crate::{refresh_on_sighup, Refresh};
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let loads = Arc::new(AtomicU32::new(0));
+        let config = Arc::new(ArcPreloader::with_loader({
+            let loads = Arc::clone(&loads);
+            move || {
+                let loads = Arc::clone(&loads);
+                async move { loads.fetch_add(1, Ordering::SeqCst) + 1 }
+            }
+        }));
+        config.start().await;
+        assert_eq!(*config.get().await.unwrap(), 1);
+
+        let listener = refresh_on_sighup([Arc::clone(&config) as Arc<dyn Refresh>]).unwrap();
+        let status = std::process::Command::new("kill")
+            .args(["-HUP", &std::process::id().to_string()])
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while config.generation() < 2 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(*config.try_get().unwrap(), 2);
+        listener.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_group_readiness() {
+        let mut group = PreloaderGroup::new().max_concurrency(1);
+        group.add("limit", async { 10 });
+        group.add("users", async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            vec!["alice"]
+        });
+        group.add("broken", async {
+            panic!("unreachable database");
+        });
+        let report = group.readiness();
+        assert_eq!(report.total, 3);
+        assert_eq!(report.not_started, ["limit", "users", "broken"]);
+
+        group.start().await;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let report = group.readiness();
+        assert_eq!(report.loaded, 1);
+        // "broken" waits for the slot held by "users"
+        let loading: Vec<_> = report
+            .loading
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(loading, ["users", "broken"]);
+        assert!(!report.is_ready());
+
+        _ = group.wait_all().await;
+        let report = group.readiness();
+        assert_eq!(report.to_string(), "2/3 preloaders loaded");
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "broken");
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_value(&report).unwrap();
+            assert_eq!(json["ready"], false);
+            assert_eq!(json["loaded"], 2);
+            assert!(json["failed"]["broken"].is_string());
+            assert_eq!(json["not_started"], serde_json::json!([]));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_footprint() {
+        use crate::MemoryFootprint;
+        use std::{collections::HashMap, mem::size_of};
+
+        assert_eq!(7u64.memory_footprint(), 8);
+        let name = String::with_capacity(32);
+        assert_eq!(name.memory_footprint(), size_of::<String>() + 32);
+        let names: Vec<_> = (0..2).map(|_| String::with_capacity(10)).collect();
+        assert_eq!(
+            names.heap_size(),
+            names.capacity() * size_of::<String>() + 2 * 10
+        );
+        let map: HashMap<u32, Vec<u8>> = HashMap::from([(1, vec![0; 100])]);
+        assert!(map.heap_size() >= 100 + size_of::<(u32, Vec<u8>)>());
+
+        let preloader = Preloader::new();
+        assert_eq!(preloader.memory_footprint(), None);
+        preloader.set(vec![0u8; 1000]).unwrap();
+        assert_eq!(
+            preloader.memory_footprint(),
+            Some(size_of::<Vec<u8>>() + 1000)
+        );
+
+        let pages = PreloaderCache::new(|id: &usize| {
+            let id = *id;
+            async move { vec![0u8; id * 100] }
+        })
+        .with_footprint_budget(1024);
+        pages.get(&4).await.unwrap();
+        pages.get(&3).await.unwrap();
+        assert_eq!(pages.memory_footprint(), 2 * size_of::<Vec<u8>>() + 700);
+        assert_eq!(pages.weight(), pages.memory_footprint());
+
+        // Loading a third page exceeds the budget and evicts the least recently used one
+        pages.get(&5).await.unwrap();
+        assert!(!pages.contains_key(&4));
+        assert!(pages.memory_footprint() <= 1024);
+    }
+
+    #[cfg(all(feature = "encryption", feature = "zstd"))]
+    #[tokio::test]
+    async fn test_disk_cache_encryption() {
+        use crate::{Compression, EncryptionKey};
+
+        let dir =
+            std::env::temp_dir().join(format!("preloader-test-encryption-{}", std::process::id()));
+        let key = EncryptionKey::from_bytes([1; 32]);
+        let disk = DiskCache::new(&dir).with_encryption(key.clone());
+        assert!(disk.is_encrypted());
+        assert_eq!(disk.path("token"), dir.join("token.bin.enc"));
+        assert!(format!("{:?}", key).ends_with("(..)"));
+
+        let first = Preloader::new();
+        disk.load(&first, "token", async { "hunter2-token".to_string() })
+            .await;
+        assert_eq!(first.get().await.unwrap(), "hunter2-token");
+        let stored = std::fs::read(disk.path("token")).unwrap();
+        assert!(!stored.windows(7).any(|bytes| bytes == b"hunter2"));
+
+        // The same key reads the value back, with or without compression
+        let second: Preloader<String> = Preloader::new();
+        disk.load(&second, "token", async { unreachable!() }).await;
+        assert_eq!(second.get().await.unwrap(), "hunter2-token");
+        let zstd = disk.clone().with_compression(Compression::Zstd(3));
+        assert_eq!(zstd.path("token"), dir.join("token.bin.zst.enc"));
+
+        // Another key, a moved file, or a tampered file is treated as missing
+        let other = DiskCache::new(&dir).with_encryption(EncryptionKey::generate());
+        let third = Preloader::new();
+        other
+            .load(&third, "token", async { "fresh".to_string() })
+            .await;
+        assert_eq!(third.get().await.unwrap(), "fresh");
+
+        std::fs::copy(disk.path("token"), disk.path("moved")).unwrap();
+        let moved = Preloader::new();
+        disk.load(&moved, "moved", async { "reloaded".to_string() })
+            .await;
+        assert_eq!(moved.get().await.unwrap(), "reloaded");
+
+        let mut tampered = std::fs::read(disk.path("moved")).unwrap();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        std::fs::write(disk.path("moved"), tampered).unwrap();
+        let fourth = Preloader::new();
+        disk.load(&fourth, "moved", async { "again".to_string() })
+            .await;
+        assert_eq!(fourth.get().await.unwrap(), "again");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "object-store")]
+    #[tokio::test]
+    async fn test_cache_object_store() {
+        use object_store::{memory::InMemory, path::Path, ObjectStore};
+
+        let store = InMemory::new();
+        let location = Path::from("snapshots/squares.json");
+        let new_cache = || {
+            PreloaderCache::new(|n: &u64| {
+                let n = *n;
+                async move { n * n }
+            })
+        };
+
+        // The first instance finds no snapshot
+        let first = new_cache();
+        assert_eq!(
+            first.restore_from_store(&store, &location).await.unwrap(),
+            0
+        );
+        first.get_many([2, 3]).await;
+        first.persist_to_store(&store, &location).await.unwrap();
+
+        let second = new_cache();
+        assert_eq!(
+            second.restore_from_store(&store, &location).await.unwrap(),
+            2
+        );
+        assert_eq!(*second.try_get(&3).unwrap(), 9);
+        assert_eq!(second.stats().loads, 0);
+
+        store.put(&location, "not json".into()).await.unwrap();
+        assert!(new_cache()
+            .restore_from_store(&store, &location)
+            .await
+            .is_err());
+    }
+
+    #[cfg(feature = "redis")]
+    #[tokio::test]
+    async fn test_cache_redis_unreachable() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        // Nothing listens on port 1, so every Redis command fails
+        let client = ::redis::Client::open("redis://127.0.0.1:1/").unwrap();
+        let tier = RedisTier::new(client)
+            .with_prefix("squares:")
+            .with_timeout(Duration::from_secs(5));
+        assert_eq!(tier.key("3"), "squares:3");
+
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&loads);
+        let cache = PreloaderCache::new(move |n: &u64| {
+            let n = *n;
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { n * n }
+        })
+        .with_redis(tier);
+
+        // The loader still runs, and its value is kept in local memory
+        assert_eq!(*cache.get(&3).await.unwrap(), 9);
+        assert_eq!(*cache.get(&3).await.unwrap(), 9);
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "redis")]
+    #[tokio::test]
+    async fn test_cache_redis_singleflight_unreachable() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        let client = ::redis::Client::open("redis://127.0.0.1:1/").unwrap();
+        let tier = RedisTier::new(client).with_singleflight(Duration::from_secs(30));
+
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&loads);
+        let cache = PreloaderCache::new(move |n: &u64| {
+            let n = *n;
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { n + 1 }
+        })
+        .with_redis(tier);
+
+        // Without a lock to take, the key is loaded without coordination instead of waiting
+        assert_eq!(*cache.get(&1).await.unwrap(), 2);
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "tonic")]
+    #[tokio::test]
+    async fn test_grpc_channel_warmup() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use tonic::transport::{Channel, Endpoint};
+
+        // Accepts connections without answering, which is enough for the channel to connect
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((connection, _)) = listener.accept().await {
+                connections.push(connection);
+            }
+        });
+        let endpoint = Endpoint::from_shared(format!("http://{}", address)).unwrap();
+
+        let warmed = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&warmed);
+        let channel: Preloader<Channel> =
+            Preloader::grpc_channel_with_warmup(endpoint.clone(), move |_| async move {
+                flag.store(true, Ordering::SeqCst);
+                Ok(())
+            });
+        assert!(channel.get().await.is_ok());
+        assert!(warmed.load(Ordering::SeqCst));
+
+        let unhealthy: Preloader<Channel> =
+            Preloader::grpc_channel_with_warmup(endpoint, |_| async {
+                Err(tonic::Status::unavailable("draining"))
+            });
+        assert!(matches!(
+            unhealthy.get().await,
+            Err(PreloaderError::Failed(message)) if message.contains("draining")
+        ));
+
+        let unreachable: Preloader<Channel> =
+            Preloader::grpc_channel(Endpoint::from_static("http://127.0.0.1:1"));
+        assert!(matches!(
+            unreachable.get().await,
+            Err(PreloaderError::Failed(_))
+        ));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_http_preloader_conditional_refresh() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        // Serves `[version]` with an ETag, answering 304 to requests that already have it
+        let version = Arc::new(AtomicUsize::new(1));
+        let full_responses = Arc::new(AtomicUsize::new(0));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (current, sent) = (Arc::clone(&version), Arc::clone(&full_responses));
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 4096];
+                let read = socket.read(&mut request).await.unwrap();
+                let request = String::from_utf8_lossy(&request[..read]).to_lowercase();
+                let version = current.load(Ordering::SeqCst);
+                let etag = format!("\"v{}\"", version);
+                let response = if request.contains(&format!("if-none-match: {}", etag)) {
+                    "HTTP/1.1 304 Not Modified\r\nconnection: close\r\n\r\n".to_string()
+                } else {
+                    sent.fetch_add(1, Ordering::SeqCst);
+                    let body = format!("[{}]", version);
+                    format!(
+                        "HTTP/1.1 200 OK\r\netag: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        etag,
+                        body.len(),
+                        body
+                    )
+                };
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let versions: HttpPreloader<Vec<usize>> =
+            HttpPreloader::json(format!("http://{}/versions", address));
+        let first = versions.get().await.unwrap();
+        assert_eq!(*first, [1]);
+        assert_eq!(versions.generation(), 1);
+
+        // Unchanged: the same value and generation are kept
+        versions.refresh().await.unwrap();
+        assert!(Arc::ptr_eq(&versions.try_get().unwrap(), &first));
+        assert_eq!(versions.generation(), 1);
+        assert_eq!(full_responses.load(Ordering::SeqCst), 1);
+
+        version.store(2, Ordering::SeqCst);
+        versions.refresh().await.unwrap();
+        assert_eq!(*versions.try_get().unwrap(), [2]);
+        assert_eq!(versions.generation(), 2);
+        assert_eq!(full_responses.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_spilled() {
+        use futures::stream;
+        use tokio::io::AsyncReadExt;
+
+        let artifact: Preloader<SpilledFile> = Preloader::new();
+        let chunks = (0..4).map(|chunk| Ok::<_, std::io::Error>(vec![chunk as u8; 1024]));
+        artifact.load_spilled(stream::iter(chunks)).await;
+        let file = artifact.get().await.unwrap();
+        assert_eq!(file.len(), 4096);
+        let path = file.path().to_path_buf();
+        let mut contents = Vec::new();
+        file.open()
+            .await
+            .unwrap()
+            .read_to_end(&mut contents)
+            .await
+            .unwrap();
+        assert_eq!(contents[3000], 2);
+
+        // Dropping the value removes the file
+        drop(artifact);
+        assert!(!path.exists());
+
+        let copied: Preloader<SpilledFile> = Preloader::new();
+        copied.load_spilled_reader(&b"from a reader"[..]).await;
+        assert_eq!(copied.get().await.unwrap().len(), 13);
+
+        let broken: Preloader<SpilledFile> = Preloader::new();
+        let chunks = stream::iter([
+            Ok(vec![1; 16]),
+            Err(std::io::Error::other("connection reset")),
+        ]);
+        broken.load_spilled(chunks).await;
+        assert!(matches!(
+            broken.get().await,
+            Err(PreloaderError::Failed(message)) if message == "connection reset"
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_validate_before_loaded() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        // The first attempt produces an invalid value and is retried
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&calls);
+        let config = Preloader::builder()
+            .retry(RetryPolicy::fixed(2, Duration::from_millis(10)))
+            .build_with_loader(move || {
+                let call = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if call == 0 {
+                        ""
+                    } else {
+                        "level = info"
+                    }
+                }
+            })
+            .validate(|config: &&str| match config.is_empty() {
+                true => Err("empty configuration"),
+                false => Ok(()),
+            });
+        config.start().await;
+        assert_eq!(*config.get().await.unwrap(), "level = info");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Without retries the load fails, and the invalid value is never visible
+        let invalid = Preloader::new().validate_async(|value: &u32| {
+            let value = *value;
+            async move {
+                if value < 10 {
+                    Err(format!("{} is too small", value))
+                } else {
+                    Ok(())
+                }
+            }
+        });
+        invalid.load(async { 3 }).await;
+        assert!(matches!(
+            invalid.get().await,
+            Err(PreloaderError::Failed(message)) if message == "invalid value: 3 is too small"
+        ));
+        assert!(invalid.try_get().is_err());
+
+        let valid = Preloader::new().validate_async(|value: &u32| {
+            let value = *value;
+            async move {
+                if value < 10 {
+                    Err("too small")
+                } else {
+                    Ok(())
+                }
+            }
+        });
+        valid.load(async { 30 }).await;
+        assert_eq!(*valid.get().await.unwrap(), 30);
+    }
+
+    #[tokio::test]
+    async fn test_any_preloader() {
+        let users: Preloader<Vec<&str>> = Preloader::builder().name("users").build();
+        let stalled = Preloader::spawn(std::future::pending::<u32>());
+        let preloaders: Vec<Arc<dyn AnyPreloader>> = vec![
+            Arc::new(users),
+            Arc::new(ArcPreloader::spawn(async { 42u64 })),
+            Arc::new(stalled),
+        ];
+
+        assert_eq!(preloaders[0].state(), PreloaderState::Idle);
+        assert_eq!(preloaders[0].name(), Some("users"));
+        assert_eq!(preloaders[1].type_name(), "u64");
+        assert!(preloaders[1].wait_ready().await);
+        assert!(preloaders[1].loaded_at().is_some());
+
+        // Type-erased preloaders still work with `wait_all`
+        let loaded = [preloaders[1].as_ref() as &dyn Readiness];
+        assert!(wait_all(&loaded).await.is_ok());
+
+        assert!(preloaders[2].cancel());
+        assert!(!preloaders[2].wait_ready().await);
+        assert!(preloaders[2].last_error().is_some());
+
+        let users = preloaders[0]
+            .as_any()
+            .downcast_ref::<Preloader<Vec<&str>>>()
+            .unwrap();
+        users.load(async { vec!["alice"] }).await;
+        assert!(preloaders[0].wait_ready().await);
+    }
+
+    #[tokio::test]
+    async fn test_any_preloader_downcast() {
+        let erased: Vec<Arc<dyn AnyPreloader>> = vec![
+            Arc::new(Preloader::ready(String::from("config"))),
+            Arc::new(ArcPreloader::ready(vec![1u32, 2])),
+        ];
+
+        assert_eq!(
+            *erased[0].downcast::<String>().unwrap().try_get().unwrap(),
+            "config"
+        );
+        assert!(erased[0].downcast::<&str>().is_none());
+        assert!(erased[0].downcast_arc::<String>().is_none());
+        assert!(erased[1].holds::<Vec<u32>>());
+        assert!(!erased[1].holds::<Vec<u64>>());
+        assert_eq!(
+            erased[1]
+                .downcast_arc::<Vec<u32>>()
+                .unwrap()
+                .try_get()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Owned handles keep the preloader alive after the collection is dropped
+        let config = Arc::clone(&erased[0])
+            .into_any()
+            .downcast::<Preloader<String>>()
+            .unwrap();
+        drop(erased);
+        assert_eq!(*config.try_get().unwrap(), "config");
+    }
+
+    #[tokio::test]
+    async fn test_load_source() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        struct Counter {
+            loads: AtomicU32,
+        }
+
+        impl LoadSource<u32> for Counter {
+            type Error = String;
+
+            async fn load(&self, ctx: &LoadContext) -> std::result::Result<u32, String> {
+                match self.loads.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err("unavailable".to_string()),
+                    _ => Ok(ctx.attempt() * 10),
+                }
+            }
+
+            async fn refresh(
+                &self,
+                _ctx: &LoadContext,
+                current: &u32,
+            ) -> std::result::Result<u32, String> {
+                Ok(current + 1)
+            }
+
+            fn validate(&self, value: &u32) -> std::result::Result<(), String> {
+                if *value == 12 {
+                    return Err("unlucky".to_string());
+                }
+                Ok(())
+            }
+        }
+
+        // Without retries, the first error fails the load
+        let failed = Preloader::from_source(Counter {
+            loads: AtomicU32::new(0),
+        });
+        assert!(
+            matches!(failed.get().await, Err(PreloaderError::Failed(message)) if message == "unavailable")
+        );
+
+        let source = Arc::new(Counter {
+            loads: AtomicU32::new(0),
+        });
+        let preloader = Preloader::builder()
+            .retry(RetryPolicy::fixed(3, Duration::from_millis(1)))
+            .build();
+        preloader.load_source(Arc::clone(&source)).await;
+        assert_eq!(*preloader.get().await.unwrap(), 20);
+        assert_eq!(source.loads.load(Ordering::SeqCst), 2);
+
+        let shared = ArcPreloader::from_source(Arc::clone(&source));
+        assert_eq!(*shared.get().await.unwrap(), 10);
+        // Refreshing goes through the refresh hook with the current value
+        shared.refresh_source(Arc::clone(&source)).await.unwrap();
+        assert_eq!(*shared.try_get().unwrap(), 11);
+        // A value rejected by the validation hook is not stored
+        assert!(shared.refresh_source(Arc::clone(&source)).await.is_err());
+        assert_eq!(*shared.try_get().unwrap(), 11);
+    }
+
+    #[tokio::test]
+    async fn test_blocking_pool() {
+        let started = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&started);
+        let pool = BlockingPool::builder()
+            .threads(2)
+            .thread_name("warmup")
+            .on_thread_start(move || {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            })
+            .build()
+            .unwrap();
+        assert_eq!(pool.threads(), 2);
+
+        let preloader = Preloader::new();
+        preloader
+            .load_blocking_on(&pool, || std::thread::current().name().map(String::from))
+            .await;
+        let name = preloader.get().await.unwrap().clone().unwrap();
+        assert!(name.starts_with("warmup-"));
+        assert!(started.load(std::sync::atomic::Ordering::SeqCst) >= 1);
+
+        // Panics are reported without killing the thread
+        let panicking: Preloader<u32> = Preloader::new();
+        panicking
+            .load_blocking_on(&pool, || panic!("corrupt index"))
+            .await;
+        assert!(matches!(
+            panicking.get().await,
+            Err(PreloaderError::Panicked(_))
+        ));
+
+        let pipeline = Pipeline::new("read", async { "1,2".to_string() })
+            .blocking_pool(&pool)
+            .then_blocking("thread", |text| {
+                (text, std::thread::current().name().map(String::from))
+            });
+        let (text, thread) = pipeline.run().await.unwrap();
+        assert_eq!(text, "1,2");
+        assert!(thread.unwrap().starts_with("warmup-"));
+
+        let fallback = Preloader::new();
+        fallback.load_blocking(|| 7).await;
+        assert_eq!(*fallback.get().await.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_stampede_coalesced() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let loads = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&loads);
+        let prices = Arc::new(
+            Preloader::builder()
+                .ttl(Duration::from_millis(30))
+                .build_arc_with_loader(move || {
+                    let counter = Arc::clone(&counter);
+                    async move {
+                        sleep(Duration::from_millis(20)).await;
+                        counter.fetch_add(1, Ordering::SeqCst) + 1
+                    }
+                }),
+        );
+        prices.start().await;
+        assert_eq!(*prices.get().await.unwrap(), 1);
+
+        // Every caller arrives right after the value expired
+        sleep(Duration::from_millis(40)).await;
+        assert!(matches!(prices.try_get(), Err(PreloaderError::Expired)));
+        let mut callers = Vec::new();
+        for i in 0..16 {
+            let prices = Arc::clone(&prices);
+            let loads = Arc::clone(&loads);
+            callers.push(tokio::spawn(async move {
+                if i % 2 == 0 {
+                    prices.refresh().await.unwrap();
+                    *prices.try_get().unwrap()
+                } else {
+                    *prices
+                        .get_or_load(async move { loads.fetch_add(1, Ordering::SeqCst) + 1 })
+                        .await
+                        .unwrap()
+                }
+            }));
+        }
+        for caller in callers {
+            assert_eq!(caller.await.unwrap(), 2);
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+
+        // A later refresh runs a new load
+        prices.refresh().await.unwrap();
+        assert_eq!(*prices.try_get().unwrap(), 3);
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_soft_and_hard_timeout() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let prices = Preloader::builder()
+            .ttl(Duration::from_secs(10))
+            .soft_timeout(Duration::from_millis(20))
+            .timeout(Duration::from_millis(100))
+            .build_arc();
+        prices.load(async { 1 }).await;
+        sleep(Duration::from_secs(11)).await;
+
+        // A reload slower than the hard timeout serves the stale value, then is aborted with an error
+        let loads = Arc::new(AtomicU32::new(0));
+        let slow = |loads: Arc<AtomicU32>| async move {
+            loads.fetch_add(1, Ordering::SeqCst);
+            sleep(Duration::from_secs(1)).await;
+            2
+        };
+        let (a, b) = tokio::join!(
+            prices.get_or_load(slow(Arc::clone(&loads))),
+            prices.get_or_load(slow(Arc::clone(&loads)))
+        );
+        assert_eq!((*a.unwrap(), *b.unwrap()), (1, 1));
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        sleep(Duration::from_millis(200)).await;
+        assert!(matches!(prices.try_get(), Err(PreloaderError::Expired)));
+        assert!(matches!(
+            prices.history().last().unwrap().error,
+            Some(PreloaderError::Timeout)
+        ));
+
+        // A reload within the soft timeout is waited for
+        let fast = prices
+            .get_or_load(async {
+                sleep(Duration::from_millis(5)).await;
+                3
+            })
+            .await
+            .unwrap();
+        assert_eq!(*fast, 3);
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_adaptive_timeout() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let delay = Arc::new(AtomicU64::new(0));
+        let loader_delay = Arc::clone(&delay);
+        let preloader = Preloader::builder()
+            .adaptive_timeout(
+                AdaptiveTimeout::new(Duration::from_millis(50), Duration::from_secs(5))
+                    .with_min_samples(3),
+            )
+            .build_arc_with_loader(move || {
+                let delay = loader_delay.load(Ordering::SeqCst);
+                async move {
+                    sleep(Duration::from_millis(delay)).await;
+                    delay
+                }
+            });
+
+        // Until enough loads are recorded, the ceiling applies
+        assert_eq!(preloader.attempt_timeout(), Some(Duration::from_secs(5)));
+        preloader.start().await;
+        preloader.get().await.unwrap();
+        for _ in 0..2 {
+            preloader.refresh().await.unwrap();
+        }
+        assert_eq!(preloader.attempt_timeout(), Some(Duration::from_millis(50)));
+
+        // A sudden stall is cut off by the learned timeout instead of the ceiling
+        delay.store(1_000, Ordering::SeqCst);
+        assert!(matches!(
+            preloader.refresh().await,
+            Err(PreloaderError::Timeout)
+        ));
+        assert_eq!(*preloader.try_get().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_latency_stats() {
+        let preloader = Preloader::builder()
+            .history(0)
+            .build_arc_with_loader(|| async {
+                sleep(Duration::from_millis(5)).await;
+                1
+            });
+        assert_eq!(preloader.stats(), PreloaderStats::default());
+
+        preloader.start().await;
+        let waiters: Vec<_> = (0..3).map(|_| preloader.get()).collect();
+        for result in futures::future::join_all(waiters).await {
+            result.unwrap();
+        }
+        for _ in 0..4 {
+            preloader.refresh().await.unwrap();
+        }
+
+        // Durations are recorded even though no history is kept
+        let stats = preloader.stats();
+        let loads = stats.loads.unwrap();
+        assert_eq!(loads.count, 5);
+        assert!(loads.p50 >= Duration::from_millis(5));
+        assert!(loads.p50 <= loads.p95 && loads.p95 <= loads.p99 && loads.p99 <= loads.max);
+        let waits = stats.waits.unwrap();
+        assert_eq!(waits.count, 3);
+        assert!(waits.max > Duration::ZERO);
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[tokio::test]
+    async fn test_prometheus_load_duration_histogram() {
+        let recorder = prometheus_builder().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let preloader = Preloader::builder().name("prometheus-loads").build();
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(5)).await;
+                1
+            })
+            .await;
+        preloader.get().await.unwrap();
+
+        let rendered = handle.render();
+        assert!(rendered.contains(
+            r#"preloader_load_duration_seconds_bucket{preloader="prometheus-loads",le="0.1"} 1"#
+        ));
+        assert!(rendered
+            .contains(r#"preloader_load_duration_seconds_count{preloader="prometheus-loads"} 1"#));
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[tokio::test]
+    async fn test_prometheus_failures_total() {
+        let recorder = prometheus_builder().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let preloader = Preloader::builder()
+            .name("prometheus-failures")
+            .timeout(Duration::from_millis(5))
+            .build();
+        preloader.load(sleep(Duration::from_secs(1))).await;
+        assert!(matches!(
+            preloader.get().await,
+            Err(PreloaderError::Timeout)
+        ));
+
+        assert!(handle
+            .render()
+            .contains(r#"preloader_failures_total{preloader="prometheus-failures"} 1"#));
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[tokio::test]
+    async fn test_prometheus_waiters_gauge() {
+        let recorder = prometheus_builder().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let preloader = Arc::new(Preloader::builder().name("prometheus-waiters").build());
+        preloader
+            .load(async {
+                sleep(Duration::from_millis(50)).await;
+                1
+            })
+            .await;
+        let waiter = tokio::spawn({
+            let preloader = Arc::clone(&preloader);
+            async move { *preloader.get().await.unwrap() }
+        });
+        sleep(Duration::from_millis(10)).await;
+        assert!(handle
+            .render()
+            .contains(r#"preloader_waiters{preloader="prometheus-waiters"} 1"#));
+
+        assert_eq!(waiter.await.unwrap(), 1);
+        assert!(handle
+            .render()
+            .contains(r#"preloader_waiters{preloader="prometheus-waiters"} 0"#));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_load_span_otel_status() {
+        use std::{fmt, sync::Mutex};
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+            Subscriber,
+        };
+        use tracing_subscriber::{
+            layer::{Context, SubscriberExt},
+            Layer,
+        };
+
+        /// Fields of every `preload` span, in the order they were recorded
+        #[derive(Clone, Default)]
+        struct Spans(Arc<Mutex<Vec<(String, String)>>>);
+
+        struct Fields<'a>(&'a mut Vec<(String, String)>);
+
+        impl Visit for Fields<'_> {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.push((field.name().to_string(), value.to_string()));
+            }
+
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                self.0
+                    .push((field.name().to_string(), format!("{value:?}")));
+            }
+        }
+
+        impl<S: Subscriber> Layer<S> for Spans {
+            fn on_new_span(&self, attrs: &Attributes<'_>, _: &Id, _: Context<'_, S>) {
+                if attrs.metadata().name() == "preload" {
+                    attrs.record(&mut Fields(&mut self.0.lock().unwrap()));
+                }
+            }
+
+            fn on_record(&self, _: &Id, values: &Record<'_>, _: Context<'_, S>) {
+                values.record(&mut Fields(&mut self.0.lock().unwrap()));
+            }
+        }
+
+        let spans = Spans::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(spans.clone()));
+
+        let loaded = Preloader::builder().name("span-loaded").build();
+        loaded.load(async { 1 }).await;
+        loaded.get().await.unwrap();
+        let failed = Preloader::builder()
+            .name("span-failed")
+            .timeout(Duration::from_millis(5))
+            .build();
+        failed.load(sleep(Duration::from_secs(1))).await;
+        assert!(failed.get().await.is_err());
+
+        let fields = spans.0.lock().unwrap().clone();
+        let field =
+            |name: &str, value: &str| fields.contains(&(name.to_string(), value.to_string()));
+        assert!(field("name", "span-loaded") && field("name", "span-failed"));
+        assert!(field("otel.kind", "internal"));
+        assert!(field("otel.status_code", "OK") && field("otel.status_code", "ERROR"));
+        assert!(field(
+            "otel.status_message",
+            &PreloaderError::Timeout.to_string()
+        ));
+    }
+
+    #[cfg(feature = "test-util")]
+    #[tokio::test(start_paused = true)]
+    async fn test_chaos_faults_keyed_by_name() {
+        use chaos::Fault;
+
+        chaos::inject("chaos-test/failing", Fault::new().failure(1.0));
+        chaos::inject(
+            "chaos-test/slow",
+            Fault::new().latency(1.0, Duration::from_secs(5)),
+        );
+        chaos::inject("chaos-test/hanging", Fault::new().hang(1.0));
+
+        let failing = Preloader::builder().name("chaos-test/failing").build();
+        failing.load(async { 1 }).await;
+        assert!(matches!(
+            failing.get().await,
+            Err(PreloaderError::Failed(_))
+        ));
+
+        let slow = Preloader::builder().name("chaos-test/slow").build();
+        let start = tokio::time::Instant::now();
+        slow.load(async { 2 }).await;
+        assert_eq!(*slow.get().await.unwrap(), 2);
+        assert!(start.elapsed() >= Duration::from_secs(5));
+
+        let hanging = Preloader::builder()
+            .name("chaos-test/hanging")
+            .timeout(Duration::from_secs(1))
+            .build();
+        hanging.load(async { 3 }).await;
+        assert!(matches!(hanging.get().await, Err(PreloaderError::Timeout)));
+
+        let healthy = Preloader::builder().name("chaos-test/healthy").build();
+        healthy.load(async { 4 }).await;
+        assert_eq!(*healthy.get().await.unwrap(), 4);
+
+        assert!(chaos::clear("chaos-test/failing"));
+        let recovered = Preloader::builder().name("chaos-test/failing").build();
+        recovered.load(async { 5 }).await;
+        assert_eq!(*recovered.get().await.unwrap(), 5);
+        chaos::clear("chaos-test/slow");
+        chaos::clear("chaos-test/hanging");
+    }
+
+    #[cfg(feature = "replay")]
+    #[tokio::test]
+    async fn test_record_and_replay_loads() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let dir = std::env::temp_dir().join(format!("preloader-replay-{}", std::process::id()));
+        let runs = Arc::new(AtomicU32::new(0));
+        let loader = |runs: &Arc<AtomicU32>| {
+            let runs = Arc::clone(runs);
+            async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                vec![1u32, 2, 3]
+            }
+        };
+
+        // Nothing recorded yet, so replaying fails without running the loader
+        let replay = LoadRecorder::new(&dir, RecordMode::Replay);
+        let missing: Preloader<Vec<u32>> = Preloader::builder().name("replay/ids").build();
+        missing.load_recorded(&replay, loader(&runs)).await;
+        assert!(matches!(
+            missing.get().await,
+            Err(PreloaderError::Failed(_))
+        ));
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+        let auto = LoadRecorder::new(&dir, RecordMode::Auto);
+        let recorded = Preloader::builder().name("replay/ids").build();
+        recorded.load_recorded(&auto, loader(&runs)).await;
+        assert_eq!(*recorded.get().await.unwrap(), [1, 2, 3]);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert!(auto.path("replay/ids").exists());
+
+        let replayed = Preloader::builder().name("replay/ids").build();
+        replayed.load_recorded(&auto, loader(&runs)).await;
+        assert_eq!(*replayed.get().await.unwrap(), [1, 2, 3]);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // Unnamed preloaders are keyed by their value type
+        let unnamed = ArcPreloader::<Vec<u32>>::new();
+        unnamed.load_recorded(&auto, loader(&runs)).await;
+        assert_eq!(*unnamed.get().await.unwrap(), [1, 2, 3]);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_or_else_falls_back_on_failure() {
+        use std::borrow::Cow;
+
+        let loaded = Preloader::new();
+        loaded.load(async { 7 }).await;
+        assert!(matches!(loaded.get_or(0).await, Cow::Borrowed(7)));
+
+        let failing: Preloader<u32> = Preloader::builder()
+            .timeout(Duration::from_millis(10))
+            .build();
+        failing.load(std::future::pending()).await;
+        let value = failing
+            .get_or_else(|error| match error {
+                PreloaderError::Timeout => 1,
+                _ => 2,
+            })
+            .await;
+        assert!(matches!(value, Cow::Owned(1)));
+
+        let arc: ArcPreloader<u32> = ArcPreloader::new();
+        assert_eq!(*arc.get_or(3).await, 3);
+        arc.load(async { 4 }).await;
+        assert_eq!(*arc.get_or_else(|_| 5).await, 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watchdog_reports_and_restarts_stalled_loads() {
+        use futures::StreamExt;
+        use std::sync::Mutex;
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let watchdog = |reports: &Arc<Mutex<Vec<StalledLoad>>>| {
+            let reports = Arc::clone(reports);
+            Watchdog::new(Duration::from_secs(1))
+                .on_stalled(move |stalled| reports.lock().unwrap().push(stalled.clone()))
+        };
+
+        // Reported once, then left running
+        let slow = Preloader::builder()
+            .name("slow")
+            .watchdog(watchdog(&reports))
+            .build();
+        let mut events = slow.events();
+        slow.load(async {
+            sleep(Duration::from_secs(3)).await;
+            "slow"
+        })
+        .await;
+        assert_eq!(*slow.get().await.unwrap(), "slow");
+        assert!(matches!(events.next().await, Some(PreloaderEvent::Started)));
+        assert!(matches!(
+            events.next().await,
+            Some(PreloaderEvent::Stalled {
+                restarting: false,
+                ..
+            })
+        ));
+        assert_eq!(
+            reports.lock().unwrap().drain(..).collect::<Vec<_>>(),
+            [StalledLoad {
+                name: Some("slow".to_string()),
+                budget: Duration::from_secs(1),
+                restarts: 0,
+                restarting: false,
+            }]
+        );
+
+        // Restarted with the loader factory until an attempt finishes in time
+        let mut attempts = 0;
+        let hanging = Preloader::builder()
+            .watchdog(watchdog(&reports).restart(3))
+            .build_with_loader(move || {
+                attempts += 1;
+                let attempt = attempts;
+                async move {
+                    if attempt < 3 {
+                        std::future::pending::<()>().await;
+                    }
+                    attempt
+                }
+            });
+        hanging.start().await;
+        assert_eq!(*hanging.get().await.unwrap(), 3);
+        let restarts: Vec<_> = reports
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(|stalled| (stalled.restarts, stalled.restarting))
+            .collect();
+        assert_eq!(restarts, [(0, true), (1, true)]);
+
+        // Groups report their stalled entries by name
+        let mut group = PreloaderGroup::new().watchdog(watchdog(&reports));
+        let fast = group.add("fast", async { 1 });
+        let stuck = group.add("stuck", async {
+            sleep(Duration::from_secs(2)).await;
+            2
+        });
+        group.start().await;
+        group.wait_all().await.unwrap();
+        assert_eq!(
+            (*fast.try_get().unwrap(), *stuck.try_get().unwrap()),
+            (1, 2)
+        );
+        let names: Vec<_> = reports
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|stalled| stalled.name.clone())
+            .collect();
+        assert_eq!(names, [Some("stuck".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_pinned_preloader_keeps_non_send_value() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let counter = PinnedPreloader::spawn_named("pinned-test", || async {
+            tokio::task::yield_now().await;
+            Rc::new(RefCell::new(vec![std::thread::current()
+                .name()
+                .map(String::from)]))
+        })
+        .unwrap();
+        let owner = counter
+            .with(|value| value.borrow()[0].clone())
+            .await
+            .unwrap();
+        assert_eq!(owner.as_deref(), Some("pinned-test"));
+        assert!(counter.is_loaded());
+
+        counter
+            .with(|value| value.borrow_mut().push(None))
+            .await
+            .unwrap();
+        assert_eq!(counter.with(|value| value.borrow().len()).await.unwrap(), 2);
+
+        // A panicking closure does not take the owning thread down
+        let panicked = counter.with(|_| -> usize { panic!("reader failed") }).await;
+        assert!(matches!(panicked, Err(PreloaderError::Panicked(_))));
+        assert_eq!(counter.with(|value| value.borrow().len()).await.unwrap(), 2);
+
+        let failing =
+            PinnedPreloader::<Rc<u32>>::spawn(|| async { panic!("load failed") }).unwrap();
+        let error = failing.with(|value| **value).await.unwrap_err();
+        assert!(matches!(error, PreloaderError::Panicked(message) if message == "load failed"));
+        assert_eq!(failing.state(), PreloaderState::Failed);
+
+        let words = PinnedPreloader::spawn(|| async { Rc::new(vec!["a".to_string()]) }).unwrap();
+        assert_eq!(words.get_cloned(|words| &words[0]).await.unwrap(), "a");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_exclusive_preloader_shares_non_sync_value() {
+        use std::cell::RefCell;
+
+        fn assert_send_sync<S: Send + Sync>(_: &S) {}
+
+        let log = Arc::new(ExclusivePreloader::from(
+            Preloader::builder().name("log").build(),
+        ));
+        assert_send_sync(&log);
+        assert_eq!(log.state(), PreloaderState::Idle);
+        log.load(async { RefCell::new(Vec::new()) }).await;
+
+        let tasks: Vec<_> = (0..8)
+            .map(|i| {
+                let log = Arc::clone(&log);
+                tokio::spawn(async move {
+                    let log = log.lock().await.unwrap();
+                    log.borrow_mut().push(i);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert!(log.is_loaded());
+        let mut entries = log.get_cloned().await.unwrap().into_inner();
+        entries.sort_unstable();
+        assert_eq!(entries, (0..8).collect::<Vec<_>>());
+
+        let failed: ExclusivePreloader<RefCell<u32>> = ExclusivePreloader::new();
+        assert!(matches!(
+            failed.lock().await,
+            Err(PreloaderError::NotLoaded)
+        ));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_loads_byte_buffers() {
+        use ffi::*;
+        use std::ffi::{c_int, c_void};
+
+        unsafe extern "C" fn load(user_data: *mut c_void, sink: *mut PreloaderSink) -> c_int {
+            let text = unsafe { &*(user_data as *const &str) };
+            for chunk in text.as_bytes().chunks(3) {
+                unsafe { preloader_sink_write(sink, chunk.as_ptr(), chunk.len()) };
+            }
+            0
+        }
+
+        unsafe extern "C" fn fail(_: *mut c_void, _: *mut PreloaderSink) -> c_int {
+            7
+        }
+
+        let try_get = |preloader| {
+            let mut data = std::ptr::null();
+            let mut len = 0;
+            let status = unsafe { preloader_try_get(preloader, &mut data, &mut len) };
+            let bytes = match data.is_null() {
+                true => Vec::new(),
+                false => unsafe { std::slice::from_raw_parts(data, len) }.to_vec(),
+            };
+            (status, bytes)
+        };
+        let wait = |preloader| loop {
+            match try_get(preloader) {
+                (PRELOADER_PENDING, _) => std::thread::sleep(Duration::from_millis(1)),
+                done => return done,
+            }
+        };
+
+        static TEXT: &str = "preloaded over ffi";
+        let preloader = preloader_new();
+        assert_eq!(try_get(preloader), (PRELOADER_PENDING, Vec::new()));
+        let status = unsafe {
+            preloader_load_callback(preloader, load, &TEXT as *const &str as *mut c_void)
+        };
+        assert_eq!(status, PRELOADER_LOADED);
+        assert_eq!(
+            wait(preloader),
+            (PRELOADER_LOADED, TEXT.as_bytes().to_vec())
+        );
+        unsafe { preloader_free(preloader) };
+
+        let failing = preloader_new();
+        unsafe { preloader_load_callback(failing, fail, std::ptr::null_mut()) };
+        assert_eq!(wait(failing), (PRELOADER_FAILED, Vec::new()));
+        unsafe { preloader_free(failing) };
+
+        assert_eq!(
+            unsafe { preloader_load_callback(std::ptr::null(), fail, std::ptr::null_mut()) },
+            PRELOADER_INVALID
+        );
+        unsafe { preloader_free(std::ptr::null_mut()) };
+    }
+
+    #[tokio::test]
+    async fn test_preloader_array_waits_for_every_slot() {
+        let array: PreloaderArray<u32, 3> = PreloaderArray::new();
+        assert_eq!(array.len(), 3);
+        let handles = array
+            .load_all([1, 2, 3].map(|n| async move {
+                sleep(Duration::from_millis(10 * n as u64)).await;
+                n * 10
+            }))
+            .await;
+        assert_eq!(handles.len(), 3);
+        assert_eq!(*array[2].get().await.unwrap(), 30);
+        let [a, b, c] = array.wait_all().await.unwrap();
+        assert_eq!((*a, *b, *c), (10, 20, 30));
+        assert!(array.is_ready());
+
+        let failing: PreloaderArray<u32, 2> = PreloaderArray::new();
+        failing
+            .load_all([0, 1].map(|n| async move {
+                if n == 1 {
+                    panic!("slot down");
+                }
+                n
+            }))
+            .await;
+        let failed = failing.wait_all().await.unwrap_err();
+        assert_eq!(failed.len(), 1);
+        assert!(matches!(failed[0], (1, PreloaderError::Panicked(_))));
+        assert_eq!(failing.loaded_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_time_slice_reports_loads_that_do_not_yield() {
+        use std::sync::Mutex;
+
+        let overruns = Arc::new(Mutex::new(Vec::<SliceOverrun>::new()));
+        let reports = Arc::clone(&overruns);
+        let time_slice = TimeSlice::new(Duration::from_millis(1))
+            .warn_after(Duration::from_millis(20))
+            .on_overrun(move |overrun| reports.lock().unwrap().push(overrun.clone()));
+
+        // Yields every slice, so no poll exceeds the limit
+        let cooperative = Preloader::builder()
+            .name("cooperative")
+            .time_slice(time_slice.clone())
+            .build();
+        cooperative
+            .load_with_context(|ctx: LoadContext| async move {
+                let mut budget = ctx.budget();
+                let mut yields = 0;
+                for _ in 0..20 {
+                    std::thread::sleep(Duration::from_millis(2));
+                    if budget.should_yield() {
+                        yields += 1;
+                    }
+                    budget.maybe_yield().await;
+                }
+                Some(yields)
+            })
+            .await;
+        assert_eq!(*cooperative.get().await.unwrap(), 20);
+        assert!(overruns.lock().unwrap().is_empty());
+
+        // Blocks the worker without yielding
+        let blocking = Preloader::builder()
+            .name("blocking")
+            .time_slice(time_slice)
+            .build();
+        blocking
+            .load(async {
+                std::thread::sleep(Duration::from_millis(40));
+                "done"
+            })
+            .await;
+        assert_eq!(*blocking.get().await.unwrap(), "done");
+        let overruns = overruns.lock().unwrap();
+        assert_eq!(overruns.len(), 1);
+        assert_eq!(overruns[0].name.as_deref(), Some("blocking"));
+        assert!(overruns[0].elapsed >= Duration::from_millis(40));
+        assert_eq!(overruns[0].limit, Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn test_background_runtime_runs_loads_off_the_caller_runtime() {
+        let thread_name = || async { std::thread::current().name().map(String::from) };
+
+        let background = BackgroundRuntime::new(2).unwrap();
+        assert_eq!(background.worker_threads(), 2);
+        let warmup = Preloader::builder().background_runtime(&background).build();
+        drop(background);
+        warmup.load(thread_name()).await;
+        assert_eq!(
+            warmup.get().await.unwrap().as_deref(),
+            Some("preloader-background")
+        );
+
+        let global = Preloader::builder().background().build();
+        global.load(thread_name()).await;
+        assert_eq!(
+            global.get().await.unwrap().as_deref(),
+            Some("preloader-background")
+        );
+
+        // Dropping the last clone inside an async context shuts the runtime down without panicking
+        let single = BackgroundRuntime::current_thread().unwrap();
+        let value = single.handle().spawn(async { 7 }).await.unwrap();
+        assert_eq!(value, 7);
+        drop(single);
+    }
+
+    #[tokio::test]
+    async fn test_read_guard_defers_reloads() {
+        let preloader = ArcPreloader::new();
+        let pending = preloader.get_guard();
+        preloader.load(async { 1 }).await;
+        let first = pending.await.unwrap();
+        assert_eq!((*first, first.generation()), (1, 1));
+
+        let second = preloader.get_guard().await.unwrap();
+        preloader.reload(async { 2 }).await.unwrap();
+        preloader.reload(async { 3 }).await.unwrap();
+        assert_eq!(*preloader.get().await.unwrap(), 1);
+        assert_eq!(preloader.generation(), 1);
+
+        drop(first);
+        assert_eq!(*preloader.try_get().unwrap(), 1);
+        let kept = second.to_arc();
+        drop(second);
+        // Only the latest deferred value is stored
+        assert_eq!(*preloader.get().await.unwrap(), 3);
+        assert_eq!(preloader.generation(), 2);
+        assert_eq!(*kept, 1);
+
+        let failed: ArcPreloader<u32> = ArcPreloader::new();
+        failed.load(async { panic!("source is down") }).await;
+        assert!(matches!(
+            failed.get_guard().await,
+            Err(PreloaderError::Panicked(_))
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_group_phases() {
+        let mut group = PreloaderGroup::new();
+        group.phase("infra");
+        let pool = group.add("pool", async {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            "db"
+        });
+        group.phase("data");
+        let users = group.add("users", async { vec!["alice"] });
+
+        group.start().await;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(pool.is_loading());
+        assert!(users.is_idle());
+        group.wait_all().await.unwrap();
+        assert_eq!(*users.try_get().unwrap(), ["alice"]);
+
+        let mut group = PreloaderGroup::new();
+        group.phase_with_timeout("infra", Duration::from_secs(1));
+        group.add("pool", async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            "db"
+        });
+        group.phase("data");
+        let users = group.add("users", async { vec!["alice"] });
+
+        group.start().await;
+        let error = group.wait_all().await.unwrap_err();
+        assert!(matches!(
+            error.failures(),
+            [
+                (_, PreloaderError::Cancelled),
+                (_, PreloaderError::Failed(_))
+            ]
+        ));
+        assert_eq!(
+            users.try_get().unwrap_err().to_string(),
+            "Preloader failed to load: phase `infra` did not complete"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_group_timing() {
+        let mut group = PreloaderGroup::new().max_concurrency(1);
+        assert!(group.timing().entries.is_empty());
+        group.add("users", async {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            vec!["alice"]
+        });
+        group.add("broken", async {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            panic!("unreachable database");
+        });
+        group.phase("derived");
+        group.add("index", async { 1 });
+
+        group.start().await;
+        _ = group.wait_all().await;
+        let report = group.timing();
+        assert_eq!(report.total, Duration::from_secs(3));
+        assert_eq!(report.parallelism(), 1.0);
+
+        let [users, broken] = &report.entries[..] else {
+            panic!("expected the two started entries");
+        };
+        assert_eq!((users.phase.as_str(), users.loaded), ("default", true));
+        // "broken" waited for the slot held by "users", and its panic still ends its timing
+        assert_eq!(broken.queued(), Some(Duration::from_secs(2)));
+        assert_eq!(broken.duration(), Some(Duration::from_secs(1)));
+        assert!(!broken.loaded);
+
+        let trace = report.to_chrome_trace();
+        assert!(trace.contains(
+            r#""name":"broken","cat":"default","ph":"X","ts":2000000,"dur":1000000,"pid":1,"tid":1"#
+        ));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_value(&report).unwrap();
+            assert_eq!(json["total"], 3.0);
+            assert_eq!(json["entries"][1]["running"], 2.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_arc_value_history() {
+        let preloader = ArcPreloader::new().with_value_history(3).dedupe();
+        assert!(preloader.history_values().is_empty());
+        preloader.load(async { 1 }).await;
+        preloader.wait().await.unwrap();
+
+        preloader.reload(async { 2 }).await.unwrap();
+        // Equal to the current value, so nothing is replaced
+        preloader.reload(async { 2 }).await.unwrap();
+        preloader.swap(3);
+        preloader.reload(async { 4 }).await.unwrap();
+
+        let history = preloader.history_values();
+        let values: Vec<_> = history
+            .iter()
+            .map(|kept| (*kept.value, kept.generation))
+            .collect();
+        assert_eq!(values, [(2, 2), (3, 3), (4, 4)]);
+        assert!(history[0].loaded_at <= history[2].loaded_at);
+
+        let untracked = ArcPreloader::ready(1);
+        untracked.swap(2);
+        assert!(untracked.history_values().is_empty());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_ready_future_loads_inline() {
+        use futures::StreamExt;
+
+        // A spawned task could not run before the next await point on this runtime
+        let preloader = Preloader::new();
+        let mut events = preloader.events();
+        let handle = preloader.load(async { 42 }).await;
+        assert!(handle.is_finished());
+        assert_eq!(*preloader.try_get().unwrap(), 42);
+        assert!(matches!(events.next().await, Some(PreloaderEvent::Started)));
+        assert!(matches!(
+            events.next().await,
+            Some(PreloaderEvent::Completed { .. })
+        ));
+        assert_eq!(preloader.history().len(), 1);
+
+        let failed: Preloader<u32> = Preloader::new();
+        failed.load(async { panic!("no config") }).await;
+        assert!(matches!(failed.try_get(), Err(PreloaderError::Panicked(_))));
+
+        // A pending future continues in a spawned task
+        let pending = Preloader::new();
+        pending
+            .load(async {
+                tokio::task::yield_now().await;
+                7
+            })
+            .await;
+        assert!(matches!(pending.try_get(), Err(PreloaderError::Loading)));
+        assert_eq!(*pending.get().await.unwrap(), 7);
+    }
 }
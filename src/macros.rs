@@ -0,0 +1,108 @@
+//! Macro module
+//!
+//! This module provides convenience macros for creating preloaders and working with several of them at once.
+
+/// Waits until all given preloaders are loaded.
+///
+/// The preloaders are awaited concurrently and the macro evaluates to a `Result` holding a tuple of references,
+/// or the first error returned by any of them. It must be used inside an async context.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::Preloader;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let users = Preloader::spawn(async { vec!["alice", "bob"] });
+///     let limit = Preloader::spawn(async { 10 });
+///     let name = Preloader::ready("service".to_string());
+///
+///     let (users, limit, name) = preloader::join!(users, limit, name).unwrap();
+///     assert_eq!(users.len(), 2);
+///     assert_eq!(*limit, 10);
+///     assert_eq!(name, "service");
+/// }
+/// ```
+#[macro_export]
+macro_rules! join {
+    ($($preloader:expr),+ $(,)?) => {
+        $crate::__private::tokio::try_join!($(($preloader).get()),+)
+    };
+}
+
+/// Creates a preloader and immediately starts loading the given future.
+///
+/// `preload!(future)` is shorthand for [`Preloader::spawn(future)`](crate::Preloader::spawn).
+/// It must be used inside a Tokio runtime.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::preload;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = preload!(async { "debug=true".to_string() });
+///     assert_eq!(config.get().await.unwrap(), "debug=true");
+/// }
+/// ```
+#[macro_export]
+macro_rules! preload {
+    ($future:expr $(,)?) => {
+        $crate::Preloader::spawn($future)
+    };
+}
+
+/// Creates one preloader per given future and immediately starts loading all of them.
+///
+/// The macro evaluates to a tuple of preloaders, in the order of the futures.
+/// It must be used inside a Tokio runtime.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::preload_all;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (users, limit) = preload_all!(async { vec!["alice", "bob"] }, async { 10 });
+///
+///     let (users, limit) = preloader::join!(users, limit).unwrap();
+///     assert_eq!(users.len(), 2);
+///     assert_eq!(*limit, 10);
+/// }
+/// ```
+#[macro_export]
+macro_rules! preload_all {
+    ($($future:expr),+ $(,)?) => {
+        ($($crate::Preloader::spawn($future),)+)
+    };
+}
+
+/// Creates a [`StaticPreloader`](crate::StaticPreloader) for a `static` item from an async closure.
+///
+/// The closure must not capture anything; it is called once, on the first access, to create the loading future.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{static_preloader, StaticPreloader};
+/// use tokio;
+///
+/// static USERS: StaticPreloader<Vec<&str>> = static_preloader!(|| async { vec!["alice", "bob"] });
+///
+/// #[tokio::main]
+/// async fn main() {
+///     assert_eq!(USERS.get().await.unwrap().len(), 2);
+/// }
+/// ```
+#[macro_export]
+macro_rules! static_preloader {
+    ($factory:expr $(,)?) => {
+        $crate::StaticPreloader::new(|| ::std::boxed::Box::pin(($factory)()))
+    };
+}
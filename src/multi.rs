@@ -0,0 +1,189 @@
+//! Multi preloader module
+//!
+//! This module provides the `MultiPreloader` struct, which loads many items concurrently with a limit
+//! on how many run at once. Each item gets its own preloader, so finished items can be read before
+//! the rest, and the whole set can be awaited as a `Vec`.
+
+use std::{fmt, future::Future, panic::AssertUnwindSafe, sync::Arc};
+
+use futures::{stream, FutureExt, StreamExt};
+use tokio::task::AbortHandle;
+
+use crate::{preloader::panicked, Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Loads a collection of items with bounded concurrency
+///
+/// Items are started in order, with at most `max_concurrency` of them loading at the same time.
+/// Loading stops when the `MultiPreloader` is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::MultiPreloader;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let pages = MultiPreloader::from_iter((1..=10).map(|page| async move { page * 100 }), 3);
+///
+///     // The first page can be shown while the others are still loading
+///     assert_eq!(*pages.item(0).unwrap().get().await.unwrap(), 100);
+///
+///     let all = pages.get_all().await.unwrap();
+///     assert_eq!(all.len(), 10);
+///     println!("{:.0}% loaded", pages.progress() * 100.0);
+/// }
+/// ```
+pub struct MultiPreloader<T: Send + 'static> {
+    items: Arc<Vec<Preloader<T>>>,
+    task: AbortHandle,
+}
+
+impl<T: Send + 'static> MultiPreloader<T> {
+    /// Creates a new `MultiPreloader` and immediately starts loading `futures`.
+    ///
+    /// # Parameters
+    ///
+    /// - `futures`: The asynchronous tasks loading each item, in order
+    /// - `max_concurrency`: Maximum number of items loading at the same time; values below 1 are treated as 1
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn from_iter<I, F>(futures: I, max_concurrency: usize) -> Self
+    where
+        T: Sync,
+        I: IntoIterator<Item = F>,
+        F: Future<Output = T> + Send + 'static,
+    {
+        let futures: Vec<F> = futures.into_iter().collect();
+        let items: Arc<Vec<Preloader<T>>> =
+            Arc::new(futures.iter().map(|_| Preloader::new()).collect());
+        for item in items.iter() {
+            item.claim_external();
+        }
+
+        let loading = Arc::clone(&items);
+        let task = tokio::spawn(
+            stream::iter(futures.into_iter().enumerate()).for_each_concurrent(
+                max_concurrency.max(1),
+                move |(index, future)| {
+                    let items = Arc::clone(&loading);
+                    async move {
+                        let result = AssertUnwindSafe(future).catch_unwind().await;
+                        items[index].finish(result.map_err(panicked));
+                    }
+                },
+            ),
+        );
+
+        Self {
+            items,
+            task: task.abort_handle(),
+        }
+    }
+
+    /// Returns the preloader of the item at `index`, or `None` if out of bounds.
+    pub fn item(&self, index: usize) -> Option<&Preloader<T>> {
+        self.items.get(index)
+    }
+
+    /// Returns the preloaders of all items, in order.
+    pub fn items(&self) -> &[Preloader<T>] {
+        &self.items
+    }
+
+    /// Waits until every item is loaded and returns references to their values, in order.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<&T>)`: The values of all items
+    /// - `Err(PreloaderError)`: The error of the first item, in order, that failed to load
+    pub async fn get_all(&self) -> Result<Vec<&T>> {
+        let mut values = Vec::with_capacity(self.items.len());
+        for item in self.items.iter() {
+            values.push(item.get().await?);
+        }
+        Ok(values)
+    }
+
+    /// Waits until every item is loaded and returns their values, in order, consuming the `MultiPreloader`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<T>)`: The values of all items
+    /// - `Err(PreloaderError)`: The error of the first item, in order, that failed to load
+    pub async fn into_vec(mut self) -> Result<Vec<T>> {
+        self.get_all().await?;
+        let mut items = std::mem::take(&mut self.items);
+        // The loading task drops its reference once the last item is stored
+        let items = loop {
+            match Arc::try_unwrap(items) {
+                Ok(items) => break items,
+                Err(shared) => {
+                    items = shared;
+                    tokio::task::yield_now().await;
+                }
+            }
+        };
+        items
+            .into_iter()
+            .map(|item| item.into_inner().ok_or(PreloaderError::NotLoaded))
+            .collect()
+    }
+
+    /// Returns the number of items that have been loaded successfully.
+    pub fn loaded_count(&self) -> usize {
+        self.items.iter().filter(|item| item.is_loaded()).count()
+    }
+
+    /// Returns the number of items that failed to load.
+    pub fn failed_count(&self) -> usize {
+        self.items.iter().filter(|item| item.is_failed()).count()
+    }
+
+    /// Returns the fraction of items that have finished loading, from `0.0` to `1.0`.
+    ///
+    /// Failed items count as finished. Returns `1.0` if there are no items.
+    pub fn progress(&self) -> f32 {
+        if self.items.is_empty() {
+            return 1.0;
+        }
+        let finished = self.loaded_count() + self.failed_count();
+        finished as f32 / self.items.len() as f32
+    }
+
+    /// Checks if every item has been loaded successfully.
+    pub fn is_ready(&self) -> bool {
+        self.items.iter().all(Preloader::is_loaded)
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T: Send + 'static> Drop for MultiPreloader<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Shows the progress without requiring `T: Debug`.
+impl<T: Send + 'static> fmt::Debug for MultiPreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiPreloader")
+            .field("len", &self.items.len())
+            .field("loaded", &self.loaded_count())
+            .field("failed", &self.failed_count())
+            .finish()
+    }
+}
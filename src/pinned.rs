@@ -0,0 +1,166 @@
+//! Thread-pinned preloader module
+//!
+//! This module provides `PinnedPreloader`, which runs its loader and owns the loaded value on a dedicated thread.
+//! Values that are not `Send`, such as FFI handles or `Rc`-based structures, are then preloaded and cached like any
+//! other, and accessed by sending closures to the owning thread.
+
+use std::{
+    fmt,
+    future::Future,
+    io,
+    panic::{self, AssertUnwindSafe},
+    sync::{atomic::Ordering, Arc},
+    thread,
+};
+
+use futures::FutureExt;
+use tokio::{
+    runtime,
+    sync::{mpsc, oneshot},
+    task::LocalSet,
+};
+
+use crate::{preloader::panicked, AtomicPreloaderState, PreloaderError, PreloaderState};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Closure run on the owning thread with the result of the load
+type Job<T> = Box<dyn FnOnce(std::result::Result<&T, &PreloaderError>) + Send>;
+
+/// Preloader whose loader runs and whose value lives on a dedicated thread
+///
+/// The thread runs a single-threaded Tokio runtime, so the loader future does not need to be `Send` either. Access
+/// goes through [`with`](Self::with), which runs a closure on the owning thread and sends its result back. The
+/// value is dropped on its thread when the preloader is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::PinnedPreloader;
+/// use std::rc::Rc;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let words = PinnedPreloader::spawn(|| async { Rc::new(vec!["alpha", "beta"]) }).unwrap();
+///
+///     let count = words.with(|words| words.len()).await.unwrap();
+///     assert_eq!(count, 2);
+///     let first = words.with(|words| words[0].to_string()).await.unwrap();
+///     assert_eq!(first, "alpha");
+/// }
+/// ```
+pub struct PinnedPreloader<T: 'static> {
+    jobs: mpsc::UnboundedSender<Job<T>>,
+    state: Arc<AtomicPreloaderState>,
+}
+
+impl<T: 'static> PinnedPreloader<T> {
+    /// Starts a thread running `loader` and keeping its value.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: Function creating the loading future on the owning thread
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(PinnedPreloader)`: The preloader, loading on its thread
+    /// - `Err(io::Error)`: If the thread or its runtime could not be started
+    pub fn spawn<F, Fut>(loader: F) -> io::Result<Self>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + 'static,
+    {
+        Self::spawn_named("preloader-pinned", loader)
+    }
+
+    /// Starts a thread named `name` running `loader` and keeping its value.
+    ///
+    /// See [`spawn`](Self::spawn).
+    pub fn spawn_named<F, Fut>(name: impl Into<String>, loader: F) -> io::Result<Self>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + 'static,
+    {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let (jobs, mut receiver) = mpsc::unbounded_channel::<Job<T>>();
+        let state = Arc::new(AtomicPreloaderState::new(PreloaderState::Loading));
+        let owner_state = Arc::clone(&state);
+        thread::Builder::new().name(name.into()).spawn(move || {
+            LocalSet::new().block_on(&runtime, async move {
+                let result = AssertUnwindSafe(async move { loader().await })
+                    .catch_unwind()
+                    .await
+                    .map_err(panicked);
+                let state = match result {
+                    Ok(_) => PreloaderState::Loaded,
+                    Err(_) => PreloaderState::Failed,
+                };
+                owner_state.store(state, Ordering::Release);
+                // Jobs queued while loading run once the result is known
+                while let Some(job) = receiver.recv().await {
+                    job(result.as_ref());
+                }
+            });
+        })?;
+        Ok(Self { jobs, state })
+    }
+
+    /// Runs `f` with the loaded value on the owning thread, waiting until loading is complete.
+    ///
+    /// Calls are run one at a time, in the order they were made.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: Function reading the value, whose result is sent back
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(R)`: The result of `f`
+    /// - `Err(PreloaderError)`: The error of the load, or `PreloaderError::Panicked` if `f` panicked
+    pub async fn with<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&T) -> R + Send + 'static,
+    ) -> Result<R> {
+        let (tx, rx) = oneshot::channel();
+        let job: Job<T> = Box::new(move |value| {
+            let result = match value {
+                Ok(value) => panic::catch_unwind(AssertUnwindSafe(|| f(value))).map_err(panicked),
+                Err(error) => Err(error.clone()),
+            };
+            _ = tx.send(result);
+        });
+        self.jobs.send(job).map_err(|_| PreloaderError::Cancelled)?;
+        rx.await.map_err(|_| PreloaderError::Cancelled)?
+    }
+
+    /// Returns a clone of a part of the loaded value that can leave the owning thread.
+    ///
+    /// Shorthand for [`with`](Self::with) with a cloning projection.
+    pub async fn get_cloned<R>(&self, project: impl FnOnce(&T) -> &R + Send + 'static) -> Result<R>
+    where
+        R: Clone + Send + 'static,
+    {
+        self.with(move |value| project(value).clone()).await
+    }
+
+    /// Returns the current state of the preloader.
+    pub fn state(&self) -> PreloaderState {
+        self.state.load(Ordering::Acquire)
+    }
+
+    /// Checks if the value has been loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.state() == PreloaderState::Loaded
+    }
+}
+
+impl<T: 'static> fmt::Debug for PinnedPreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PinnedPreloader")
+            .field("state", &self.state())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,290 @@
+//! Pipeline module
+//!
+//! This module provides the `Pipeline` struct, which builds a load out of stages where the output of one stage
+//! feeds the next, such as `fetch → decompress → parse → index`. CPU-heavy stages can run on Tokio's blocking
+//! thread pool, and the time spent in each stage is recorded.
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+use crate::{blocking_pool::run_blocking, BlockingPool, LoadHandle, Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+type Stage<T> = Pin<Box<dyn Future<Output = Result<T>> + Send>>;
+
+/// Time spent in one stage of a [`Pipeline`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageTiming {
+    /// Name of the stage
+    pub name: &'static str,
+    /// Time from the start of the stage until it produced its output or failed
+    pub elapsed: Duration,
+}
+
+/// Handle reading the stage timings of a [`Pipeline`] while or after it runs
+#[derive(Clone, Default)]
+pub struct PipelineTimings(Arc<Mutex<Vec<StageTiming>>>);
+
+impl PipelineTimings {
+    /// Returns the timings of the stages finished so far, in order.
+    pub fn stages(&self) -> Vec<StageTiming> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Returns the timing of the stage named `name`, if it has finished.
+    pub fn stage(&self, name: &str) -> Option<Duration> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|timing| timing.name == name)
+            .map(|timing| timing.elapsed)
+    }
+
+    /// Returns the time spent in all finished stages.
+    pub fn total(&self) -> Duration {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|timing| timing.elapsed)
+            .sum()
+    }
+
+    fn record(&self, name: &'static str, started: Instant) {
+        self.0.lock().unwrap().push(StageTiming {
+            name,
+            elapsed: started.elapsed(),
+        });
+    }
+}
+
+impl fmt::Debug for PipelineTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.stages()).finish()
+    }
+}
+
+/// Load built from stages run one after another
+///
+/// Each stage receives the output of the previous one. A stage failing with an error stops the pipeline,
+/// and the error names the stage. Stages added with `then_blocking` or `try_then_blocking` run on
+/// [`tokio::task::spawn_blocking`], or on the pool set with [`blocking_pool`](Self::blocking_pool), so they do
+/// not stall the runtime.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{Pipeline, Preloader};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let pipeline = Pipeline::new("fetch", async { b"1,2,3".to_vec() })
+///         .then_blocking("decode", |bytes| String::from_utf8(bytes).unwrap())
+///         .try_then_blocking("parse", |text| {
+///             text.split(',').map(str::parse::<u32>).collect::<Result<Vec<_>, _>>()
+///         })
+///         .then("index", |numbers| async move { numbers.iter().sum::<u32>() });
+///     let timings = pipeline.timings();
+///
+///     let preloader = Preloader::new();
+///     preloader.load_pipeline(pipeline).await;
+///     assert_eq!(*preloader.get().await.unwrap(), 6);
+///     assert_eq!(timings.stages().len(), 4);
+/// }
+/// ```
+pub struct Pipeline<T> {
+    future: Stage<T>,
+    timings: PipelineTimings,
+    pool: Option<BlockingPool>,
+}
+
+impl<T: Send + 'static> Pipeline<T> {
+    /// Creates a pipeline whose first stage is `future`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the stage, used in timings and errors
+    /// - `future`: The asynchronous task producing the first output
+    pub fn new(name: &'static str, future: impl Future<Output = T> + Send + 'static) -> Self {
+        Self::try_new(name, async move { Ok::<_, PreloaderError>(future.await) })
+    }
+
+    /// Creates a pipeline whose first stage is a fallible `future`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the stage, used in timings and errors
+    /// - `future`: The asynchronous task producing the first output or an error
+    pub fn try_new<E: fmt::Display>(
+        name: &'static str,
+        future: impl Future<Output = std::result::Result<T, E>> + Send + 'static,
+    ) -> Self {
+        let timings = PipelineTimings::default();
+        let recorder = timings.clone();
+        Self {
+            future: Box::pin(async move {
+                let started = Instant::now();
+                let result = future.await;
+                recorder.record(name, started);
+                result.map_err(|error| stage_failed(name, error))
+            }),
+            timings,
+            pool: None,
+        }
+    }
+
+    /// Adds an asynchronous stage transforming the previous output.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the stage, used in timings and errors
+    /// - `f`: Function creating the stage's task from the previous output
+    pub fn then<U, F, Fut>(self, name: &'static str, f: F) -> Pipeline<U>
+    where
+        U: Send + 'static,
+        F: FnOnce(T) -> Fut + Send + 'static,
+        Fut: Future<Output = U> + Send + 'static,
+    {
+        self.try_then(name, move |input| {
+            let future = f(input);
+            async move { Ok::<_, PreloaderError>(future.await) }
+        })
+    }
+
+    /// Adds a fallible asynchronous stage transforming the previous output.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the stage, used in timings and errors
+    /// - `f`: Function creating the stage's task from the previous output
+    pub fn try_then<U, E, F, Fut>(self, name: &'static str, f: F) -> Pipeline<U>
+    where
+        U: Send + 'static,
+        E: fmt::Display,
+        F: FnOnce(T) -> Fut + Send + 'static,
+        Fut: Future<Output = std::result::Result<U, E>> + Send + 'static,
+    {
+        self.stage(name, move |input| async move {
+            f(input).await.map_err(|error| stage_failed(name, error))
+        })
+    }
+
+    /// Runs the blocking stages added after this call on `pool` instead of Tokio's blocking thread pool.
+    ///
+    /// # Parameters
+    ///
+    /// - `pool`: The dedicated pool for the blocking stages
+    pub fn blocking_pool(mut self, pool: &BlockingPool) -> Self {
+        self.pool = Some(pool.clone());
+        self
+    }
+
+    /// Adds a stage running `f` on the blocking thread pool.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the stage, used in timings and errors
+    /// - `f`: Function transforming the previous output
+    pub fn then_blocking<U, F>(self, name: &'static str, f: F) -> Pipeline<U>
+    where
+        U: Send + 'static,
+        F: FnOnce(T) -> U + Send + 'static,
+    {
+        self.try_then_blocking(name, move |input| Ok::<_, PreloaderError>(f(input)))
+    }
+
+    /// Adds a fallible stage running `f` on the blocking thread pool.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the stage, used in timings and errors
+    /// - `f`: Function transforming the previous output or failing
+    pub fn try_then_blocking<U, E, F>(self, name: &'static str, f: F) -> Pipeline<U>
+    where
+        U: Send + 'static,
+        E: fmt::Display + Send + 'static,
+        F: FnOnce(T) -> std::result::Result<U, E> + Send + 'static,
+    {
+        let pool = self.pool.clone();
+        self.stage(name, move |input| async move {
+            run_blocking(pool, move || f(input))
+                .await?
+                .map_err(|error| stage_failed(name, error))
+        })
+    }
+
+    /// Returns a handle reading the stage timings.
+    pub fn timings(&self) -> PipelineTimings {
+        self.timings.clone()
+    }
+
+    /// Runs every stage and returns the output of the last one.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(T)`: The output of the last stage
+    /// - `Err(PreloaderError)`: The error of the first stage that failed
+    pub async fn run(self) -> Result<T> {
+        self.future.await
+    }
+
+    fn stage<U, Fut>(
+        self,
+        name: &'static str,
+        f: impl FnOnce(T) -> Fut + Send + 'static,
+    ) -> Pipeline<U>
+    where
+        U: Send + 'static,
+        Fut: Future<Output = Result<U>> + Send + 'static,
+    {
+        let previous = self.future;
+        let recorder = self.timings.clone();
+        Pipeline {
+            future: Box::pin(async move {
+                let input = previous.await?;
+                let started = Instant::now();
+                let result = f(input).await;
+                recorder.record(name, started);
+                result
+            }),
+            timings: self.timings,
+            pool: self.pool,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Pipeline<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("timings", &self.timings)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Starts loading with a [`Pipeline`], following the load policy if loading was already started.
+    ///
+    /// The preloader fails with the error of the first failing stage.
+    ///
+    /// # Parameters
+    ///
+    /// - `pipeline`: The stages producing the value
+    pub async fn load_pipeline(&self, pipeline: Pipeline<T>) -> LoadHandle {
+        self.load_result(pipeline.run()).await
+    }
+}
+
+/// Converts a stage error into a `PreloaderError` naming the stage.
+fn stage_failed(name: &str, error: impl fmt::Display) -> PreloaderError {
+    PreloaderError::Failed(format!("{name}: {error}"))
+}
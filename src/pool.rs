@@ -0,0 +1,403 @@
+//! Preloader pool module
+//!
+//! This module provides the `PreloaderPool` struct, which limits how many loaders run at the same time
+//! and starts queued loaders in priority order.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::{oneshot, Notify};
+
+use crate::{LoadHandle, LoadPolicy, Preloader, PreloaderError, RateLimiter};
+
+/// Scheduling priority of a load queued in a [`PreloaderPool`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Started after every queued `Normal` and `High` load
+    Low,
+    /// The default priority
+    #[default]
+    Normal,
+    /// Started before every queued `Normal` and `Low` load
+    High,
+}
+
+/// Behavior of a [`PreloaderPool`] whose queue is full
+///
+/// Set with [`PreloaderPool::with_queue_limit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum QueuePolicy {
+    /// The new load fails with `PreloaderError::QueueFull`
+    #[default]
+    Reject,
+    /// `load()` waits until the queue has room, pushing back on the caller
+    Wait,
+    /// The queued load with the lowest priority, the most recent one among equals, fails with
+    /// `PreloaderError::QueueFull` to make room; the new load is rejected instead if it has the lowest priority
+    DropLowest,
+}
+
+/// A load waiting for a free slot
+struct Waiter {
+    priority: Priority,
+    sequence: u64,
+    wake: oneshot::Sender<()>,
+}
+
+impl Waiter {
+    /// Higher priorities first, then first come, first served.
+    fn key(&self) -> (Priority, Reverse<u64>) {
+        (self.priority, Reverse(self.sequence))
+    }
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Mutable scheduling state of a pool
+struct State {
+    running: usize,
+    sequence: u64,
+    queue: BinaryHeap<Waiter>,
+}
+
+impl State {
+    /// Returns the number of queued loads that have not been cancelled.
+    fn queued(&mut self) -> usize {
+        self.queue.retain(|waiter| !waiter.wake.is_closed());
+        self.queue.len()
+    }
+}
+
+/// State shared by all clones of a pool
+struct Shared {
+    max_concurrency: usize,
+    queue_limit: Option<(usize, QueuePolicy)>,
+    state: Mutex<State>,
+    /// Notified whenever a queued load leaves the queue
+    space: Notify,
+}
+
+impl Shared {
+    /// Hands a freed slot to the next waiting load, or returns it to the pool.
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        while let Some(waiter) = state.queue.pop() {
+            if waiter.wake.send(()).is_ok() {
+                self.space.notify_waiters();
+                return;
+            }
+        }
+        state.running -= 1;
+    }
+}
+
+/// A running slot, released when dropped
+struct Permit {
+    shared: Arc<Shared>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.shared.release();
+    }
+}
+
+/// A queued request for a slot
+///
+/// If the request is cancelled after a slot was handed to it, the slot is passed on when it is dropped.
+struct Ticket {
+    shared: Arc<Shared>,
+    wake: oneshot::Receiver<()>,
+    granted: bool,
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        if self.granted {
+            return;
+        }
+        if self.wake.try_recv().is_ok() {
+            self.shared.release();
+        } else {
+            self.shared.space.notify_waiters();
+        }
+    }
+}
+
+/// Result of asking the pool for a slot
+enum Admission {
+    /// A slot was free
+    Running(Permit),
+    /// The load was queued
+    Queued(Ticket),
+}
+
+impl Admission {
+    /// Waits until the load may run.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Permit)`: The slot the load runs in
+    /// - `Err(PreloaderError::QueueFull)`: The load was dropped from the queue to make room for another
+    async fn ready(self) -> Result<Permit, PreloaderError> {
+        let mut ticket = match self {
+            Self::Running(permit) => return Ok(permit),
+            Self::Queued(ticket) => ticket,
+        };
+        // The sender is only dropped without sending when the waiter is evicted.
+        (&mut ticket.wake)
+            .await
+            .map_err(|_| PreloaderError::QueueFull)?;
+        ticket.granted = true;
+        Ok(Permit {
+            shared: Arc::clone(&ticket.shared),
+        })
+    }
+}
+
+/// Pool limiting how many loaders run at the same time
+///
+/// Loads started through the pool move their preloader to the `Loading` state immediately,
+/// but their future only runs once one of the pool's slots is free.
+/// Queued loads are started in [`Priority`] order, and in the order they were queued within the same priority.
+/// A configured timeout of the preloader includes the time spent waiting in the queue.
+///
+/// Cloning a pool is cheap; clones share the same slots and queue.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{Preloader, PreloaderPool, Priority};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let pool = PreloaderPool::new(2);
+///     let users = Preloader::new();
+///     let report = Preloader::new();
+///
+///     pool.load(&users, Priority::High, async { vec!["alice", "bob"] }).await;
+///     pool.load(&report, Priority::Low, async { "report".to_string() }).await;
+///
+///     assert_eq!(users.get().await.unwrap().len(), 2);
+///     assert_eq!(report.get().await.unwrap(), "report");
+///     assert_eq!(pool.running(), 0);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct PreloaderPool {
+    shared: Arc<Shared>,
+    rate_limit: Option<RateLimiter>,
+}
+
+impl PreloaderPool {
+    /// Creates a pool running at most `max_concurrency` loaders at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrency` is zero.
+    pub fn new(max_concurrency: usize) -> Self {
+        assert!(max_concurrency > 0, "max_concurrency must be positive");
+        Self {
+            shared: Arc::new(Shared {
+                max_concurrency,
+                queue_limit: None,
+                state: Mutex::new(State {
+                    running: 0,
+                    sequence: 0,
+                    queue: BinaryHeap::new(),
+                }),
+                space: Notify::new(),
+            }),
+            rate_limit: None,
+        }
+    }
+
+    /// Limits how many loads the pool starts per second, in addition to the concurrency limit.
+    ///
+    /// A load waits for a free slot first and then for the rate limiter.
+    ///
+    /// # Parameters
+    ///
+    /// - `limiter`: The rate limiter, which may be shared with other pools
+    pub fn with_rate_limit(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limit = Some(limiter);
+        self
+    }
+
+    /// Limits how many loads may wait in the queue.
+    ///
+    /// A load arriving while `capacity` loads are queued is handled according to `policy`.
+    /// With [`QueuePolicy::Wait`], `load()` only returns once the load has been queued, so callers
+    /// issuing many loads are slowed down to the pace of the pool.
+    ///
+    /// # Parameters
+    ///
+    /// - `capacity`: Maximum number of queued loads
+    /// - `policy`: What happens to a load arriving while the queue is full
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool has already been cloned.
+    pub fn with_queue_limit(mut self, capacity: usize, policy: QueuePolicy) -> Self {
+        Arc::get_mut(&mut self.shared)
+            .expect("queue limit must be set before the pool is cloned")
+            .queue_limit = Some((capacity, policy));
+        self
+    }
+
+    /// Starts loading `preloader` with `future` once a slot is free.
+    ///
+    /// Like [`Preloader::load`], a load of a preloader that is already loading or loaded follows its
+    /// [`LoadPolicy`](crate::LoadPolicy) and is not queued.
+    /// If the queue is full, the load is handled according to the [`QueuePolicy`]; a rejected or dropped
+    /// load fails the preloader with `PreloaderError::QueueFull`.
+    ///
+    /// # Parameters
+    ///
+    /// - `preloader`: The preloader to load
+    /// - `priority`: Priority of the load while it is queued
+    /// - `future`: The asynchronous task to execute
+    pub async fn load<T: Send + 'static>(
+        &self,
+        preloader: &Preloader<T>,
+        priority: Priority,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> LoadHandle {
+        // Only a failed load can be replaced, which `load_result_timed` checks once the load is admitted
+        if !preloader.is_idle() && preloader.load_policy() != LoadPolicy::ReplaceWhenReady {
+            return preloader.overlapping_load();
+        }
+
+        let admission = self.admit(priority).await;
+        let rate_limit = self.rate_limit.clone();
+        preloader
+            .load_result_timed(async move {
+                let _permit = admission?.ready().await?;
+                if let Some(limiter) = &rate_limit {
+                    limiter.acquire().await;
+                }
+                Ok(future.await)
+            })
+            .await
+    }
+
+    /// Returns the maximum number of loaders running at the same time.
+    pub fn max_concurrency(&self) -> usize {
+        self.shared.max_concurrency
+    }
+
+    /// Returns the number of loaders currently running.
+    pub fn running(&self) -> usize {
+        self.shared.state.lock().unwrap().running
+    }
+
+    /// Returns the number of loads waiting for a free slot.
+    ///
+    /// Together with [`queue_capacity`](Self::queue_capacity), this lets callers hold back new loads
+    /// while the pool is saturated.
+    pub fn queued(&self) -> usize {
+        self.shared.state.lock().unwrap().queued()
+    }
+
+    /// Returns the maximum number of queued loads, or `None` if the queue is unbounded.
+    pub fn queue_capacity(&self) -> Option<usize> {
+        self.shared.queue_limit.map(|(capacity, _)| capacity)
+    }
+
+    /// Checks if the queue is bounded and full.
+    pub fn is_queue_full(&self) -> bool {
+        self.queue_capacity()
+            .is_some_and(|capacity| self.queued() >= capacity)
+    }
+
+    /// Takes a free slot or a place in the queue, applying the queue policy if the queue is full.
+    async fn admit(&self, priority: Priority) -> Result<Admission, PreloaderError> {
+        loop {
+            let space = self.shared.space.notified();
+            tokio::pin!(space);
+            space.as_mut().enable();
+
+            match self.try_admit(priority) {
+                Some(admission) => return admission,
+                None => space.await,
+            }
+        }
+    }
+
+    /// Takes a free slot or a place in the queue without waiting.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the queue is full and the policy is [`QueuePolicy::Wait`]
+    fn try_admit(&self, priority: Priority) -> Option<Result<Admission, PreloaderError>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.running < self.shared.max_concurrency && state.queued() == 0 {
+            state.running += 1;
+            return Some(Ok(Admission::Running(Permit {
+                shared: Arc::clone(&self.shared),
+            })));
+        }
+
+        let sequence = state.sequence;
+        if let Some((capacity, policy)) = self.shared.queue_limit {
+            if state.queued() >= capacity {
+                match policy {
+                    QueuePolicy::Reject => return Some(Err(PreloaderError::QueueFull)),
+                    QueuePolicy::Wait => return None,
+                    QueuePolicy::DropLowest => {
+                        let key = (priority, Reverse(sequence));
+                        let mut waiters = std::mem::take(&mut state.queue).into_vec();
+                        let lowest = waiters
+                            .iter()
+                            .enumerate()
+                            .min_by_key(|(_, waiter)| waiter.key())
+                            .map(|(index, _)| index)
+                            .filter(|&index| waiters[index].key() < key);
+                        // Dropping the evicted waiter's sender fails its load
+                        let evicted = lowest.map(|index| waiters.swap_remove(index));
+                        state.queue = waiters.into();
+                        if evicted.is_none() {
+                            return Some(Err(PreloaderError::QueueFull));
+                        }
+                    }
+                }
+            }
+        }
+
+        let (wake, rx) = oneshot::channel();
+        state.sequence += 1;
+        state.queue.push(Waiter {
+            priority,
+            sequence,
+            wake,
+        });
+        Some(Ok(Admission::Queued(Ticket {
+            shared: Arc::clone(&self.shared),
+            wake: rx,
+            granted: false,
+        })))
+    }
+}
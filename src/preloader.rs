@@ -3,36 +3,181 @@
 //! This module provides the `Preloader` struct for asynchronously loading and caching data.
 //! You can perform other tasks while the data is loading, and retrieve the result immediately once loading is complete.
 
-use std::{cell::UnsafeCell, future::Future, sync::atomic::Ordering};
+use std::{
+    borrow::Cow,
+    fmt,
+    future::{Future, IntoFuture},
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc, OnceLock,
+    },
+    task::{Context, Poll, Wake, Waker},
+    time::{Duration, SystemTime},
+};
 
 use atomic_enum::atomic_enum;
-use tokio::sync::{
-    oneshot::{self, Receiver},
-    Mutex,
+use futures::{
+    future::{BoxFuture, Either},
+    stream::FuturesUnordered,
+    FutureExt, StreamExt,
+};
+use tokio::{
+    sync::{watch, Notify},
+    task::{AbortHandle, JoinHandle, JoinSet},
+    time::Instant,
+};
+use tokio_util::task::task_tracker::TrackedFuture;
+
+use crate::{
+    builder::Options,
+    cell::ValueCell,
+    deadline,
+    events::Events,
+    history::History,
+    stats::Latency,
+    sync::{
+        oneshot::{self, error::TryRecvError, Receiver},
+        Mutex,
+    },
+    waiters::Waiters,
+    CancellationToken, CircuitBreaker, ErrorPolicy, FailureMode, LoadAttempt, LoadContext,
+    LoadHandle, LoadPolicy, PreloaderBuilder, PreloaderEvent, PreloaderStats, WaitStats,
 };
 
 // preloader error define
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum PreloaderError {
     #[error("Preloader is not loaded")]
     NotLoaded,
     #[error("Preloader is loading")]
     Loading,
+    #[error("Preloader timed out while loading")]
+    Timeout,
+    #[error("Preloader value has expired")]
+    Expired,
+    #[error("Preloader panicked while loading: {0}")]
+    Panicked(String),
+    #[error("Preloader loading task was cancelled")]
+    Cancelled,
+    #[error("Preloader is poisoned by a failed load")]
+    Poisoned,
+    #[error("Preloader failed to load: {0}")]
+    Failed(String),
+    #[error("Preloader circuit breaker is open")]
+    CircuitOpen,
+    #[error("Preloader pool queue is full")]
+    QueueFull,
+    #[error("Preloader deadline was exceeded while waiting")]
+    DeadlineExceeded,
+    #[error("Preloader is already loading or loaded")]
+    OverlappingLoad,
+    #[error("Preloader failed to load: {0}")]
+    Source(#[source] Arc<dyn std::error::Error + Send + Sync>),
+}
+
+impl PreloaderError {
+    /// Creates a load failure that keeps `error` as its source, so error reports can walk the cause chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderError;
+    /// use std::error::Error;
+    ///
+    /// let io = std::io::Error::new(std::io::ErrorKind::NotFound, "catalog.json");
+    /// let error = PreloaderError::from_error(io);
+    /// assert_eq!(error.to_string(), "Preloader failed to load: catalog.json");
+    /// assert_eq!(error.source().unwrap().to_string(), "catalog.json");
+    ///
+    /// if let PreloaderError::Source(source) = &error {
+    ///     assert!(source.downcast_ref::<std::io::Error>().is_some());
+    /// }
+    /// ```
+    pub fn from_error(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self::Source(Arc::new(error))
+    }
+
+    /// Checks if asking again later may succeed.
+    ///
+    /// Transient conditions such as a load in progress, a timeout, a cancelled task, an open circuit breaker,
+    /// a full pool queue, or an exceeded deadline are retryable. Panics, poisoning, and errors reported by
+    /// the loader itself are not, since repeating the same load is expected to fail the same way.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::NotLoaded
+            | Self::Loading
+            | Self::Timeout
+            | Self::Expired
+            | Self::Cancelled
+            | Self::CircuitOpen
+            | Self::QueueFull
+            | Self::DeadlineExceeded => true,
+            Self::Panicked(_)
+            | Self::Poisoned
+            | Self::Failed(_)
+            | Self::Source(_)
+            | Self::OverlappingLoad => false,
+        }
+    }
+}
+
+/// Latest failed load of a preloader with its context, as reported by [`Preloader::error_context`]
+///
+/// Implements `std::error::Error` with the [`PreloaderError`] as its source, so it can be handed to error
+/// reporting directly.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{} failed after {attempts} attempt(s)", name.as_deref().unwrap_or("preloader"))]
+pub struct ErrorContext {
+    /// Name set through the builder
+    pub name: Option<String>,
+    /// Number of attempts made by the failed load, including retries
+    pub attempts: u32,
+    /// Time at which the load failed
+    pub failed_at: std::time::Instant,
+    /// The error that ended the load
+    #[source]
+    pub error: PreloaderError,
+}
+
+impl ErrorContext {
+    /// Checks if asking again later may succeed; see [`PreloaderError::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.error.is_retryable()
+    }
 }
 
 type Result<T> = std::result::Result<T, PreloaderError>;
 
+/// Result of a loading task together with the time it finished
+type Completion<T> = (Result<T>, Instant);
+
+/// Stored function creating the loading task for each load
+type Loader<T> = Box<dyn FnMut() -> BoxFuture<'static, T> + Send>;
+
+/// Callback checking a loaded value before it is stored, returning an error describing why it is invalid
+type Validator<T> =
+    Arc<dyn Fn(&T) -> BoxFuture<'static, std::result::Result<(), String>> + Send + Sync>;
+
 /// Enum representing the current state of the preloader
+///
+/// Returned by [`Preloader::state`].
 #[atomic_enum]
-enum PreloaderState {
+#[derive(PartialEq, Eq, Hash)]
+pub enum PreloaderState {
     /// Initial state - loading has not started yet
     Idle,
-    /// Start state - loading process has started
+    /// Start state - `load()` has been called and the loading task is being registered
     Start,
     /// Loading state - data is being loaded asynchronously
     Loading,
     /// Loaded state - data has been successfully loaded and is available
     Loaded,
+    /// Failed state - loading finished with an error
+    Failed,
+    /// Poisoned state - loading failed and every access returns an error
+    Poisoned,
 }
 
 /// Asynchronous data preloader
@@ -49,22 +194,63 @@ enum PreloaderState {
 ///
 /// # Thread Safety
 ///
-/// `Preloader` implements `Send` and `Sync`, so it can be safely used across multiple threads.
+/// `Preloader<T>` is `Send` when `T` is `Send`, and `Sync` when `T` is also `Sync`, since every caller of `get()`
+/// receives a `&T` to the same value. Values that are `Send` but not `Sync`, such as `Cell`, cannot be shared
+/// across threads through a `Preloader`:
+///
+/// ```compile_fail
+/// use preloader::Preloader;
+/// use std::{cell::Cell, sync::Arc};
+///
+/// let hits = Arc::new(Preloader::ready(Cell::new(0)));
+/// let shared = Arc::clone(&hits);
+/// std::thread::spawn(move || shared.try_get().unwrap().set(1));
+/// ```
+///
+/// Share them with [`ExclusivePreloader`](crate::ExclusivePreloader), which serializes access, instead.
 ///
 /// # Generic Type
 ///
 /// - `T`: The type of data to load. Must satisfy `Send + 'static`.
 pub struct Preloader<T: Send + 'static> {
-    /// Current state of the preloader
-    state: AtomicPreloaderState,
+    /// Current state of the preloader and the loaded data
+    cell: ValueCell<T>,
     /// Handle for the asynchronous task
-    handle: Mutex<Option<Receiver<T>>>,
-    /// Cell storing the loaded data
-    value: UnsafeCell<Option<T>>,
+    handle: Mutex<Option<Receiver<Completion<T>>>>,
+    /// Error recorded when loading failed, with the time of the failure
+    error: std::sync::Mutex<Option<(PreloaderError, Instant)>>,
+    /// Time at which the latest load was started
+    started_at: std::sync::Mutex<Option<Instant>>,
+    /// Time at which the value finished loading
+    loaded_at: OnceLock<Instant>,
+    /// Handle to the latest loading task
+    task: std::sync::Mutex<Option<LoadHandle>>,
+    /// Stored loader factory used by `start()` and `restart()`
+    loader: Option<Arc<std::sync::Mutex<Loader<T>>>>,
+    /// Notification fired when the state leaves `Start`
+    started: Notify,
+    /// Wakers of `poll_get()` callers waiting for the state to change
+    pollers: Arc<Pollers>,
+    /// Lifecycle events sent to subscribers of `events()`
+    events: Events,
+    /// Callers currently blocked in `get()` and their wait times
+    waiters: Waiters,
+    /// Finished loads, kept for `history()`
+    history: History,
+    /// Whether the value was dropped by `unload()` and is loaded again with the stored loader on the next `get()`
+    unloaded: AtomicBool,
+    /// Number of attempts made by the latest load
+    attempts: Arc<AtomicU32>,
+    /// Cancellation token handed to the running loader by `load_cancellable()`
+    cancellation: std::sync::Mutex<Option<CancellationToken>>,
+    /// Callbacks that must accept a loaded value before it is stored
+    validators: Vec<Validator<T>>,
+    /// Configuration set through the builder
+    options: Options,
 }
 
 unsafe impl<T: Send + 'static> Send for Preloader<T> {}
-unsafe impl<T: Send + 'static> Sync for Preloader<T> {}
+unsafe impl<T: Send + Sync + 'static> Sync for Preloader<T> {}
 
 impl<T: Send + 'static> Preloader<T> {
     /// Creates a new `Preloader` instance.
@@ -80,21 +266,58 @@ impl<T: Send + 'static> Preloader<T> {
     /// let preloader: Preloader<String> = Preloader::new();
     /// ```
     pub fn new() -> Self {
+        Self::with_options(Options::new())
+    }
+
+    /// Returns a builder for configuring a new `Preloader`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::time::Duration;
+    ///
+    /// let preloader: Preloader<String> = Preloader::builder()
+    ///     .timeout(Duration::from_secs(5))
+    ///     .build();
+    /// ```
+    pub fn builder() -> PreloaderBuilder<T> {
+        PreloaderBuilder::new()
+    }
+
+    /// Creates a new `Preloader` in the `Idle` state with the given configuration.
+    pub(crate) fn with_options(options: Options) -> Self {
+        let latency = Arc::new(Latency::new(options.name.as_deref()));
         Self {
-            state: AtomicPreloaderState::new(PreloaderState::Idle),
+            cell: ValueCell::new(PreloaderState::Idle),
             handle: Mutex::new(None),
-            value: UnsafeCell::new(None),
+            error: std::sync::Mutex::new(None),
+            started_at: std::sync::Mutex::new(None),
+            loaded_at: OnceLock::new(),
+            task: std::sync::Mutex::new(None),
+            loader: None,
+            started: Notify::new(),
+            pollers: Arc::default(),
+            events: Events::default(),
+            waiters: Waiters::new(Arc::clone(&latency)),
+            history: History::new(options.history, latency),
+            unloaded: AtomicBool::new(false),
+            attempts: Arc::new(AtomicU32::new(0)),
+            cancellation: std::sync::Mutex::new(None),
+            validators: Vec::new(),
+            options,
         }
     }
 
-    /// Starts an asynchronous task to load data.
+    /// Creates a new `Preloader` in the `Idle` state that stores a loader factory.
     ///
-    /// This method can only be called in the `Idle` state. If loading is already in progress or completed,
-    /// it does nothing and returns immediately.
+    /// The factory is called by [`start`](Self::start) and again by [`restart`](Self::restart) after a failed load,
+    /// and for every retry or hedging attempt of a load. Unlike `load()`, loading does not have to be
+    /// started from the place that knows how to load the data.
     ///
     /// # Parameters
     ///
-    /// - `future`: The asynchronous task to execute. Must implement `Future<Output = T> + Send + 'static`.
+    /// - `factory`: Function creating the asynchronous task for each attempt
     ///
     /// # Example
     ///
@@ -104,126 +327,134 @@ impl<T: Send + 'static> Preloader<T> {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let preloader = Preloader::new();
-    ///     preloader.load(async {
-    ///         // Simulate a time-consuming task
-    ///         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-    ///         42
-    ///     }).await;
+    ///     let preloader = Preloader::with_loader(|| async { 42 });
+    ///     preloader.start().await;
+    ///     assert_eq!(*preloader.get().await.unwrap(), 42);
     /// }
     /// ```
-    pub async fn load(&self, future: impl Future<Output = T> + Send + 'static) {
-        let Ok(PreloaderState::Idle) = self.state.compare_exchange(
-            PreloaderState::Idle,
-            PreloaderState::Start,
-            Ordering::Relaxed,
-            Ordering::Relaxed,
-        ) else {
-            return;
-        };
-
-        let (tx, rx) = oneshot::channel();
-
-        tokio::spawn(async move {
-            let value = future.await;
-            _ = tx.send(value);
-        });
+    pub fn with_loader<F, Fut>(factory: F) -> Self
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self::with_options(Options::new()).stored(factory)
+    }
 
-        self.set_handle(rx).await;
+    /// Stores `factory` as the loader factory.
+    pub(crate) fn stored<F, Fut>(mut self, mut factory: F) -> Self
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let loader: Loader<T> = Box::new(move || Box::pin(factory()));
+        self.loader = Some(Arc::new(std::sync::Mutex::new(loader)));
+        self
     }
 
-    /// Retrieves the loaded data.
+    /// Adds a validation callback, run on every loaded value before the preloader enters the `Loaded` state.
     ///
-    /// Returns an error if the data is not yet loaded.
-    /// If the data is still loading, waits until loading is complete.
+    /// A value only becomes visible if every callback accepts it; otherwise the attempt fails with
+    /// `PreloaderError::Failed` and is retried according to the retry policy, like any failed attempt. Nothing
+    /// can read an invalid value in the meantime. Callbacks apply to loads started after they are added, so add
+    /// them before starting the load. Values stored directly, such as with [`ready`](Self::ready) or
+    /// [`set`](Self::set), are not validated.
     ///
-    /// # Returns
+    /// # Parameters
     ///
-    /// - `Ok(&T)`: If the data was successfully loaded
-    /// - `Err(String)`: If the data is not loaded or an error occurred during loading
+    /// - `validate`: Function returning an error describing why the value is invalid
     ///
     /// # Example
     ///
     /// ```rust
-    /// use preloader::Preloader;
+    /// use preloader::{Preloader, PreloaderError};
     /// use tokio;
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let preloader = Preloader::new();
-    ///     // Start loading first
-    ///     preloader.load(async { "data".to_string() }).await;
-    ///     // Retrieve data
-    ///     match preloader.get().await {
-    ///         Ok(data) => println!("Loaded data: {}", data),
-    ///         Err(e) => println!("Error: {}", e),
-    ///     }
+    ///     let ports = Preloader::with_loader(|| async { vec![8080, 0] })
+    ///         .validate(|ports| match ports.contains(&0) {
+    ///             true => Err("port 0 is not allowed"),
+    ///             false => Ok(()),
+    ///         });
+    ///     ports.start().await;
+    ///
+    ///     assert!(matches!(ports.get().await, Err(PreloaderError::Failed(_))));
     /// }
     /// ```
-    pub async fn get(&self) -> Result<&T> {
-        match self.state.load(Ordering::Relaxed) {
-            PreloaderState::Idle | PreloaderState::Start => {
-                return Err(PreloaderError::NotLoaded);
-            }
-            PreloaderState::Loading => {
-                let mut handle = self.handle.lock().await;
-                if let Some(handle) = handle.take() {
-                    let value = handle.await.map_err(|_| PreloaderError::Loading)?;
-                    self.set_value(value);
-                    return Ok(self.get_value());
-                } else {
-                    // If handle is already None, just return the value
-                    return Ok(self.get_value());
-                }
-            }
-            PreloaderState::Loaded => {
-                return Ok(self.get_value());
-            }
-        }
+    pub fn validate<F, E>(self, validate: F) -> Self
+    where
+        F: Fn(&T) -> std::result::Result<(), E> + Send + Sync + 'static,
+        E: fmt::Display,
+    {
+        self.validate_async(move |value| {
+            std::future::ready(validate(value).map_err(|error| error.to_string()))
+        })
     }
 
-    /// Takes the loaded data, consuming it.
+    /// Adds an asynchronous validation callback, run on every loaded value before the preloader enters the
+    /// `Loaded` state.
     ///
-    /// This method consumes the loaded data, leaving None in its place.
-    /// Returns an error if the data is not yet loaded.
-    /// If the data is still loading, waits until loading is complete.
+    /// The returned future cannot borrow the value, so copy what it needs to check, for example to look up
+    /// referenced IDs in a database. See [`validate`](Self::validate).
     ///
-    /// # Returns
+    /// # Parameters
     ///
-    /// - `Ok(T)`: If the data was successfully loaded and taken
-    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    /// - `validate`: Function returning a future that resolves to an error describing why the value is invalid
+    pub fn validate_async<F, Fut, E>(mut self, validate: F) -> Self
+    where
+        F: Fn(&T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: fmt::Display,
+    {
+        self.validators.push(Arc::new(move |value| {
+            let check = validate(value);
+            Box::pin(async move {
+                check
+                    .await
+                    .map_err(|error| format!("invalid value: {}", error))
+            })
+        }));
+        self
+    }
+
+    /// Creates a `Preloader` that already holds the given value.
+    ///
+    /// The returned instance is in the `Loaded` state, so `get()` and `try_get()` return the value immediately
+    /// and any later `load()` call is ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: The already available data
     ///
     /// # Example
     ///
     /// ```rust
     /// use preloader::Preloader;
-    /// use tokio;
-    ///
-    /// #[tokio::main]
-    /// async fn main() {
-    ///     let preloader = Preloader::new();
-    ///     // Start loading
-    ///     preloader.load(async { "data".to_string() }).await;
-    ///     // Take data, consuming the preloader
-    ///     match preloader.take().await {
-    ///         Ok(data) => println!("Taken data: {}", data),
-    ///         Err(e) => println!("Error: {}", e),
-    ///     }
-    ///     // Note: preloader is consumed and cannot be used after take()
-    ///     // The following code would not compile:
-    ///     // match preloader.try_get() { ... } // Error: use of moved value
-    /// }
+    /// let preloader = Preloader::ready(42);
+    /// assert_eq!(*preloader.try_get().unwrap(), 42);
     /// ```
-    /// Takes the loaded data, consuming the Preloader.
+    pub fn ready(value: T) -> Self {
+        let preloader = Self::new();
+        preloader.set_value(value);
+        preloader
+    }
+
+    /// Creates a new `Preloader` and immediately starts loading data.
     ///
-    /// This method consumes the Preloader itself, ensuring it cannot be used after taking the value.
-    /// Returns an error if the data is not yet loaded.
-    /// If the data is still loading, waits until loading is complete.
+    /// This is equivalent to calling `new()` followed by `load(future)`, but does not need to be awaited,
+    /// which makes it convenient for initializing struct fields in synchronous constructors.
+    ///
+    /// # Parameters
+    ///
+    /// - `future`: The asynchronous task to execute. Must implement `Future<Output = T> + Send + 'static`.
     ///
     /// # Returns
     ///
-    /// - `Ok(T)`: If the data was successfully loaded and taken
-    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    /// A new `Preloader` instance in the `Loading` state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
     ///
     /// # Example
     ///
@@ -233,52 +464,29 @@ impl<T: Send + 'static> Preloader<T> {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let preloader = Preloader::new();
-    ///     // Start loading
-    ///     preloader.load(async { "data".to_string() }).await;
-    ///     // Take data, consuming the preloader
-    ///     match preloader.take().await {
-    ///         Ok(data) => println!("Taken data: {}", data),
-    ///         Err(e) => println!("Error: {}", e),
-    ///     }
-    ///     // Preloader cannot be used after take() as it has been consumed
+    ///     let preloader = Preloader::spawn(async { "data".to_string() });
+    ///     assert_eq!(preloader.get().await.unwrap(), "data");
     /// }
     /// ```
-    pub async fn take(self) -> Result<T> {
-        match self.get().await {
-            Ok(_) => self.take_value(),
-            Err(e) => Err(e),
-        }
+    pub fn spawn(future: impl Future<Output = T> + Send + 'static) -> Self {
+        let mut preloader = Self::new();
+        preloader.begin(future);
+        preloader
     }
 
-    /// Retrieves the loaded data without checking the state.
-    ///
-    /// This method is unsafe and should only be used when you are sure that the data is loaded.
+    /// Starts an asynchronous task to load data.
     ///
-    /// # Returns
+    /// This method can only be called in the `Idle` state. If loading is already in progress or completed,
+    /// it follows the configured [`LoadPolicy`]: by default it does not start another task and returns the handle
+    /// to the latest load.
     ///
-    pub unsafe fn get_unchecked(&self) -> &T {
-        match self.state.load(Ordering::Relaxed) {
-            PreloaderState::Idle | PreloaderState::Start => {
-                panic!("Preloader is not loaded");
-            }
-            PreloaderState::Loading => {
-                return self.get_value();
-            }
-            PreloaderState::Loaded => {
-                return self.get_value();
-            }
-        }
-    }
-
-    /// Attempts to retrieve the loaded data immediately.
+    /// # Parameters
     ///
-    /// Unlike `get()`, this method does not block. If the data is not yet loaded or is still loading, returns an error immediately.
+    /// - `future`: The asynchronous task to execute. Must implement `Future<Output = T> + Send + 'static`.
     ///
     /// # Returns
     ///
-    /// - `Ok(&T)`: If the data was successfully loaded
-    /// - `Err(String)`: If the data is not loaded or is still loading
+    /// A [`LoadHandle`] to await, inspect, or abort the loading task; it can be dropped to let the task run detached
     ///
     /// # Example
     ///
@@ -289,142 +497,2523 @@ impl<T: Send + 'static> Preloader<T> {
     /// #[tokio::main]
     /// async fn main() {
     ///     let preloader = Preloader::new();
-    ///     // Try before loading
-    ///     match preloader.try_get() {
-    ///         Ok(data) => println!("Data: {}", data),
-    ///         Err(e) => println!("Not loaded yet: {}", e),
-    ///     }
-    ///     // Start loading
-    ///     preloader.load(async { "data".to_string() }).await;
-    ///     // Try while loading
-    ///     match preloader.try_get() {
-    ///         Ok(data) => println!("Data: {}", data),
-    ///         Err(e) => println!("Still loading: {}", e),
-    ///     }
+    ///     let handle = preloader.load(async {
+    ///         // Simulate a time-consuming task
+    ///         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    ///         42
+    ///     }).await;
+    ///     assert!(!handle.is_finished());
+    ///     handle.await.unwrap();
+    ///     assert_eq!(*preloader.try_get().unwrap(), 42);
     /// }
     /// ```
-    pub fn try_get(&self) -> Result<&T> {
-        match self.state.load(Ordering::Relaxed) {
-            PreloaderState::Idle | PreloaderState::Start => {
-                return Err(PreloaderError::NotLoaded);
-            }
-            PreloaderState::Loading => {
-                let mut handle = self
-                    .handle
-                    .try_lock()
-                    .map_err(|_| PreloaderError::Loading)?;
-
-                if let Some(handle) = handle.as_mut() {
-                    let value = handle.try_recv().map_err(|_| PreloaderError::Loading)?;
-                    self.set_value(value);
-                }
-                return Ok(self.get_value());
-            }
-            PreloaderState::Loaded => {
-                return Ok(self.get_value());
-            }
+    pub async fn load(&self, future: impl Future<Output = T> + Send + 'static) -> LoadHandle {
+        match self.claim_load(future).await {
+            Ok(handle) => handle,
+            Err(_) => self.overlapping_load(),
         }
     }
 
-    /// Retrieves the loaded data without checking the state.
-    ///
-    /// This method is unsafe and should only be used when you are sure that the data is loaded.
+    /// Starts `future` if the preloader is `Idle`, or `Failed` under `LoadPolicy::ReplaceWhenReady`.
     ///
     /// # Returns
     ///
-    /// Reference to the stored value
-    pub unsafe fn try_get_unchecked(&self) -> &T {
-        match self.state.load(Ordering::Relaxed) {
-            PreloaderState::Idle | PreloaderState::Start => {
-                panic!("Preloader is not loaded");
-            }
-            PreloaderState::Loading => {
-                panic!("Preloader is loading");
-            }
-            PreloaderState::Loaded => self.get_value(),
+    /// - `Ok(LoadHandle)`: The handle to the started load
+    /// - `Err(F)`: The future, if the preloader is already loading or loaded
+    pub(crate) async fn claim_load<F>(&self, future: F) -> std::result::Result<LoadHandle, F>
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        if !self.claim_for_load() {
+            return Err(future);
         }
-    }
 
-    /// Sets the handle for the asynchronous task and changes the state to `Loading`.
-    ///
-    /// # Parameters
-    ///
-    /// - `handle`: Receiver for the asynchronous task
-    #[inline]
-    async fn set_handle(&self, handle: Receiver<T>) {
-        *self.handle.lock().await = Some(handle);
-        self.state.store(PreloaderState::Loading, Ordering::Release);
+        self.set_handle(self.spawn_task(future)).await;
+        Ok(self.load_handle())
     }
 
-    /// Safely retrieves the stored value.
+    /// Claims the preloader for a load if it is `Idle`, or `Failed` under `LoadPolicy::ReplaceWhenReady`.
     ///
     /// # Returns
     ///
-    /// Reference to the stored value
-    ///
-    /// # Safety
-    ///
-    /// This method should only be called in the `Loaded` state, and the value is guaranteed to exist.
-    #[inline]
-    fn get_value(&self) -> &T {
-        unsafe { &*self.value.get() }.as_ref().unwrap()
-    }
-
-    /// Stores the value and changes the state to `Loaded`.
-    ///
-    /// # Parameters
-    ///
-    /// - `value`: The value to store
-    #[inline]
-    fn set_value(&self, value: T) {
-        unsafe { *self.value.get() = Some(value) };
-        // Set handle to None to prevent duplicate receiving
-        if let Ok(mut handle) = self.handle.try_lock() {
-            *handle = None;
+    /// `true` if the caller is now responsible for loading, `false` if the load overlaps another one
+    fn claim_for_load(&self) -> bool {
+        let replace = self.options.load_policy == LoadPolicy::ReplaceWhenReady;
+        if replace {
+            // A load that has just failed is stored first, so it can be replaced right away
+            _ = self.poll_result();
         }
-        self.state.store(PreloaderState::Loaded, Ordering::Release);
+        self.claim() || (replace && self.claim_failed())
     }
 
-    /// Takes the stored value, leaving None in its place.
-    ///
-    /// # Returns
-    ///
-    /// Result containing the stored value or an error if the value is None
-    ///
-    /// # Safety
-    ///
-    /// This method should only be called when the value is guaranteed to exist.
-    #[inline]
-    fn take_value(self) -> Result<T> {
-        unsafe {
-            let value = (*self.value.get()).take();
-            if let Some(value) = value {
-                Ok(value)
-            } else {
-                Err(PreloaderError::NotLoaded)
+    /// Returns the handle answering a `load()` call that did not start a load, according to the load policy.
+    pub(crate) fn overlapping_load(&self) -> LoadHandle {
+        match self.options.load_policy {
+            LoadPolicy::Ignore => self.load_handle(),
+            LoadPolicy::ReplaceWhenReady | LoadPolicy::Error => {
+                LoadHandle::failed(PreloaderError::OverlappingLoad)
             }
         }
     }
 
-    /// Checks if the preloader has completed loading and data is available.
+    /// Returns the configured load policy.
+    pub(crate) fn load_policy(&self) -> LoadPolicy {
+        self.options.load_policy
+    }
+
+    /// Returns the handle to the latest loading task, or a finished handle if no task was spawned.
+    pub(crate) fn load_handle(&self) -> LoadHandle {
+        self.task
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(LoadHandle::finished)
+    }
+
+    /// Starts an asynchronous task to load data, spawning it on `tasks` instead of detaching it.
     ///
-    /// This method returns true if the preloader is in the `Loaded` state,
-    /// indicating that data is ready to be accessed without blocking.
+    /// Behaves like `load()`, but the loading task belongs to the caller's `JoinSet`, so the owning task
+    /// controls its lifetime: dropping or shutting down the set aborts the load, and the preloader fails
+    /// with `PreloaderError::Cancelled`. The set's own output is `()`; the value is read from the preloader.
     ///
-    /// # Returns
+    /// # Parameters
     ///
-    /// - `true`: If the data is loaded and ready for immediate access
-    /// - `false`: If the data is not loaded yet or is still loading
+    /// - `tasks`: The set the loading task is spawned on
+    /// - `future`: The asynchronous task to execute
     ///
     /// # Example
     ///
     /// ```rust
     /// use preloader::Preloader;
-    /// use tokio;
+    /// use tokio::{self, task::JoinSet};
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let preloader = Preloader::new();
+    ///     let config = Preloader::new();
+    ///     let users = Preloader::new();
+    ///
+    ///     let mut tasks = JoinSet::new();
+    ///     config.load_scoped(&mut tasks, async { "config" }).await;
+    ///     users.load_scoped(&mut tasks, async { vec!["admin"] }).await;
+    ///     // Nothing started here outlives the set
+    ///     tasks.join_all().await;
+    ///
+    ///     assert_eq!(*config.try_get().unwrap(), "config");
+    ///     assert_eq!(users.try_get().unwrap().len(), 1);
+    /// }
+    /// ```
+    pub async fn load_scoped(
+        &self,
+        tasks: &mut JoinSet<()>,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> LoadHandle {
+        if !self.claim_for_load() {
+            return self.overlapping_load();
+        }
+
+        let loader = self.validating(attempt(future, self.attempt_timeout()));
+        let handle = self.spawn_loader_with(loader, |task| {
+            let task = self.track(task);
+            Some(match &self.options.runtime {
+                Some(runtime) => tasks.spawn_on(task, runtime),
+                None => tasks.spawn(task),
+            })
+        });
+        self.set_handle(handle).await;
+        self.load_handle()
+    }
+
+    /// Starts an asynchronous task to load data, handing the loader a [`CancellationToken`].
+    ///
+    /// Long-running loads such as big downloads or database scans can select on the token to stop cooperatively
+    /// when [`cancel`](Self::cancel) is called, instead of being aborted between await points.
+    /// The loader returns `None` to report that it stopped, which fails loading with `PreloaderError::Cancelled`.
+    /// Like `load()`, it returns a [`LoadHandle`] and follows the configured [`LoadPolicy`] if loading was already
+    /// started.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: Function creating the asynchronous task from the cancellation token
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Preloader, PreloaderError};
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let scan: Preloader<u64> = Preloader::new();
+    ///     scan.load_cancellable(|token| async move {
+    ///         let mut rows = 0;
+    ///         loop {
+    ///             tokio::select! {
+    ///                 _ = token.cancelled() => return None,
+    ///                 _ = tokio::time::sleep(Duration::from_millis(1)) => rows += 100,
+    ///             }
+    ///         }
+    ///     })
+    ///     .await;
+    ///
+    ///     assert!(scan.cancel());
+    ///     assert!(matches!(scan.get().await, Err(PreloaderError::Cancelled)));
+    /// }
+    /// ```
+    pub async fn load_cancellable<F, Fut>(&self, loader: F) -> LoadHandle
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = Option<T>> + Send + 'static,
+    {
+        if !self.claim_for_load() {
+            return self.overlapping_load();
+        }
+
+        let token = CancellationToken::new();
+        let future = loader(token.clone());
+        let timeout = self.attempt_timeout();
+        let handle = self.spawn_loader(async move {
+            attempt(future, timeout)
+                .await?
+                .ok_or(PreloaderError::Cancelled)
+        });
+        *self.cancellation.lock().unwrap() = Some(token);
+        self.set_handle(handle).await;
+        self.load_handle()
+    }
+
+    /// Starts an asynchronous task to load data, passing a [`LoadContext`] to the loader for every attempt.
+    ///
+    /// Behaves like [`load_with`](Self::load_with), including timeouts, retries, and hedging. Through the
+    /// context, the loader can report progress, learn its attempt number and deadline, and stop cooperatively
+    /// when [`cancel`](Self::cancel) is called. The loader returns `None` to report that it stopped, which fails
+    /// loading with `PreloaderError::Cancelled` without further retries.
+    ///
+    /// # Parameters
+    ///
+    /// - `factory`: Function creating the asynchronous task for each attempt from its context
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{LoadContext, Preloader, RetryPolicy};
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::builder()
+    ///         .timeout(Duration::from_secs(1))
+    ///         .retry(RetryPolicy::fixed(3, Duration::from_millis(10)))
+    ///         .build();
+    ///     preloader
+    ///         .load_with_context(|ctx: LoadContext| async move {
+    ///             if ctx.attempt() < 2 {
+    ///                 panic!("first attempt fails");
+    ///             }
+    ///             ctx.report_progress(1.0);
+    ///             Some(ctx.attempt())
+    ///         })
+    ///         .await;
+    ///     assert_eq!(*preloader.get().await.unwrap(), 2);
+    /// }
+    /// ```
+    pub async fn load_with_context<F, Fut>(&self, mut factory: F) -> LoadHandle
+    where
+        F: FnMut(LoadContext) -> Fut + Send + 'static,
+        Fut: Future<Output = Option<T>> + Send + 'static,
+    {
+        if !self.claim_for_load() {
+            return self.overlapping_load();
+        }
+
+        let token = CancellationToken::new();
+        let context = LoadContext::new(
+            self.options.name.clone(),
+            self.attempt_timeout(),
+            token.clone(),
+            self.events.clone(),
+            self.options.time_slice.as_ref(),
+        );
+        let attempts = Arc::clone(&self.attempts);
+        let load = self.retrying(
+            move || factory(context.for_attempt(attempts.load(Ordering::Relaxed))),
+            |value| Ok(value.as_ref()),
+        );
+        let handle =
+            self.spawn_validated(async move { load.await?.ok_or(PreloaderError::Cancelled) });
+        *self.cancellation.lock().unwrap() = Some(token);
+        self.set_handle(handle).await;
+        self.load_handle()
+    }
+
+    /// Starts an asynchronous task to load data, re-creating the future when an attempt fails.
+    ///
+    /// Behaves like `load()`, except that an attempt which panics or exceeds the configured timeout
+    /// is retried according to the configured [`RetryPolicy`](crate::RetryPolicy).
+    /// If a hedging delay is configured, a second attempt is started when the first one has not finished
+    /// within the delay, and whichever succeeds first provides the value.
+    ///
+    /// # Parameters
+    ///
+    /// - `factory`: Function creating the asynchronous task for each attempt
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Preloader, RetryPolicy};
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::builder()
+    ///         .timeout(Duration::from_secs(1))
+    ///         .retry(RetryPolicy::fixed(3, Duration::from_millis(10)))
+    ///         .build();
+    ///     preloader.load_with(|| async { 42 }).await;
+    ///     assert_eq!(*preloader.get().await.unwrap(), 42);
+    /// }
+    /// ```
+    pub async fn load_with<F, Fut>(&self, factory: F) -> LoadHandle
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        if !self.claim_for_load() {
+            return self.overlapping_load();
+        }
+
+        self.set_handle(self.spawn_retrying(factory)).await;
+        self.load_handle()
+    }
+
+    /// Starts loading with the stored loader factory.
+    ///
+    /// Loading uses the configured timeout, retry policy, and hedging delay like [`load_with`](Self::load_with).
+    /// Does nothing if no loader factory is stored or the preloader is not in the `Idle` state.
+    pub async fn start(&self) {
+        if self.loader.is_none() || !self.claim() {
+            return;
+        }
+
+        self.set_handle(self.spawn_stored()).await;
+    }
+
+    /// Starts loading again with the stored loader factory after a failed load.
+    ///
+    /// Starts loading if the preloader is in the `Idle` or `Failed` state; a loaded value is never replaced.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a load was started, `false` if no loader factory is stored or the preloader is loading or loaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut attempts = 0;
+    ///     let preloader = Preloader::builder()
+    ///         .timeout(Duration::from_millis(10))
+    ///         .build_with_loader(move || {
+    ///             attempts += 1;
+    ///             let delay = if attempts == 1 { 100 } else { 0 };
+    ///             async move {
+    ///                 tokio::time::sleep(Duration::from_millis(delay)).await;
+    ///                 "data"
+    ///             }
+    ///         });
+    ///
+    ///     preloader.start().await;
+    ///     assert!(preloader.get().await.is_err());
+    ///
+    ///     assert!(preloader.restart().await);
+    ///     assert_eq!(*preloader.get().await.unwrap(), "data");
+    /// }
+    /// ```
+    pub async fn restart(&self) -> bool {
+        if self.loader.is_none() {
+            return false;
+        }
+        if !self.claim() && !self.claim_failed() {
+            return false;
+        }
+
+        self.set_handle(self.spawn_stored()).await;
+        true
+    }
+
+    /// Starts loading with several futures concurrently; the first one to succeed provides the value.
+    ///
+    /// Once a future completes, the remaining ones are cancelled. A future that panics or exceeds the
+    /// configured timeout does not win the race. If every future fails, the preloader fails with the error
+    /// of the last one to finish, and fails with `PreloaderError::NotLoaded` if `futures` is empty.
+    /// Like `load()`, a call made while loading was already started follows the configured [`LoadPolicy`].
+    ///
+    /// # Parameters
+    ///
+    /// - `futures`: The competing asynchronous tasks, e.g. requests to different mirrors
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// async fn fetch(mirror: &'static str, delay: u64) -> String {
+    ///     tokio::time::sleep(Duration::from_millis(delay)).await;
+    ///     format!("data from {}", mirror)
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
+    ///     preloader
+    ///         .load_race([
+    ///             Box::pin(fetch("slow-mirror", 500)),
+    ///             Box::pin(fetch("fast-mirror", 10)),
+    ///         ])
+    ///         .await;
+    ///     assert_eq!(*preloader.get().await.unwrap(), "data from fast-mirror");
+    /// }
+    /// ```
+    pub async fn load_race<F>(&self, futures: impl IntoIterator<Item = F>) -> LoadHandle
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        if !self.claim_for_load() {
+            return self.overlapping_load();
+        }
+
+        let timeout = self.attempt_timeout();
+        let futures: Vec<F> = futures.into_iter().collect();
+        let handle = self.spawn_loader(async move {
+            let mut attempts: FuturesUnordered<_> = futures
+                .into_iter()
+                .map(|future| AssertUnwindSafe(attempt(future, timeout)).catch_unwind())
+                .collect();
+
+            let mut failure = None;
+            while let Some(outcome) = attempts.next().await {
+                match outcome {
+                    Ok(Ok(value)) => return Ok(value),
+                    outcome => failure = Some(outcome),
+                }
+            }
+            match failure {
+                Some(Ok(Err(error))) => Err(error),
+                Some(Err(payload)) => Err(panicked(payload)),
+                _ => Err(PreloaderError::NotLoaded),
+            }
+        });
+        self.set_handle(handle).await;
+        self.load_handle()
+    }
+
+    /// Stores a value directly, bypassing any loader.
+    ///
+    /// This method only succeeds in the `Idle` state. The Preloader moves to `Loaded`,
+    /// callers waiting in `get()` are woken up, and later `load()` calls are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: The value to store
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the value was stored
+    /// - `Err(T)`: The value, handed back if loading was already started or a value is already present
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    ///
+    /// let preloader = Preloader::new();
+    /// assert!(preloader.set("pushed".to_string()).is_ok());
+    /// assert_eq!(preloader.set("again".to_string()), Err("again".to_string()));
+    /// assert_eq!(*preloader.try_get().unwrap(), "pushed");
+    /// ```
+    pub fn set(&self, value: T) -> std::result::Result<(), T> {
+        if !self.claim() {
+            return Err(value);
+        }
+
+        self.store_claimed(value);
+        Ok(())
+    }
+
+    /// Stores a value directly like [`set`](Self::set), also replacing the error of a failed load.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the value was stored
+    /// - `Err(T)`: The value, handed back if a load is in progress or a value is already present
+    pub(crate) fn set_or_recover(&self, value: T) -> std::result::Result<(), T> {
+        // A load that has just failed is stored first, so it can be replaced right away
+        _ = self.poll_result();
+        if !self.claim() && !self.claim_failed() {
+            return Err(value);
+        }
+
+        self.store_claimed(value);
+        Ok(())
+    }
+
+    /// Stores a value in a claimed preloader and wakes waiting callers.
+    fn store_claimed(&self, value: T) {
+        self.set_value(value);
+        self.notify_state();
+        self.events.emit(PreloaderEvent::Completed {
+            duration: Duration::ZERO,
+        });
+    }
+
+    /// Retrieves the loaded data.
+    ///
+    /// Returns an error if loading has not been started.
+    /// If loading has been started or is still in progress, waits until loading is complete.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the data was successfully loaded
+    /// - `Err(String)`: If the data is not loaded or an error occurred during loading
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. The result of the loading task stays in the preloader until a caller stores
+    /// it, so dropping a waiting caller, for example in `tokio::select!` or `tokio::time::timeout`, leaves the
+    /// value to the remaining and later callers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
+    ///     // Start loading first
+    ///     preloader.load(async { "data".to_string() }).await;
+    ///     // Retrieve data
+    ///     match preloader.get().await {
+    ///         Ok(data) => println!("Loaded data: {}", data),
+    ///         Err(e) => println!("Error: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn get(&self) -> Result<&T> {
+        if let Some(value) = self.loaded() {
+            return Ok(value);
+        }
+        // Boxed so the future of `get()` stays small for the fast path
+        Box::pin(self.resolve(false)).await
+    }
+
+    /// Starts loading with `future` if the preloader is `Idle`, then waits for the result.
+    ///
+    /// Combines `load()` and `get()` in one call. If loading was already started elsewhere, `future` is dropped
+    /// without being run and the result of that load is returned, so concurrent callers share a single load.
+    ///
+    /// # Parameters
+    ///
+    /// - `future`: The asynchronous task to execute if nothing is loading yet
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the data was successfully loaded
+    /// - `Err(PreloaderError)`: If an error occurred during loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
+    ///     let data = preloader.get_or_load(async { "data".to_string() }).await.unwrap();
+    ///     assert_eq!(data, "data");
+    ///
+    ///     // Already loaded, so the second future is not run
+    ///     let data = preloader.get_or_load(async { "other".to_string() }).await.unwrap();
+    ///     assert_eq!(data, "data");
+    /// }
+    /// ```
+    pub async fn get_or_load(
+        &self,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> Result<&T> {
+        self.load(future).await;
+        self.get().await
+    }
+
+    /// Initializes the preloader with a fallible `init` if it is `Idle`, then returns the value.
+    ///
+    /// Follows the semantics of `tokio::sync::OnceCell::get_or_try_init`: `init` runs in the calling task
+    /// rather than a spawned one, and only one caller runs it at a time while the others wait. If it fails,
+    /// times out, panics, or the calling future is dropped, the preloader goes back to `Idle`, so a later call
+    /// can try again; a waiting caller then runs its own `init`.
+    ///
+    /// # Parameters
+    ///
+    /// - `init`: Function creating the asynchronous initialization
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: The loaded value
+    /// - `Err(E)`: The error of `init`, `PreloaderError::Timeout` if the configured timeout elapsed,
+    ///   or the error of a load started in another way
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Preloader, PreloaderError};
+    /// use tokio;
+    ///
+    /// #[derive(Debug)]
+    /// enum ConnectError {
+    ///     Refused,
+    ///     Preloader(PreloaderError),
+    /// }
+    ///
+    /// impl From<PreloaderError> for ConnectError {
+    ///     fn from(error: PreloaderError) -> Self {
+    ///         ConnectError::Preloader(error)
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let connection: Preloader<String> = Preloader::new();
+    ///
+    ///     let first = connection.get_or_try_load(|| async { Err(ConnectError::Refused) }).await;
+    ///     assert!(matches!(first, Err(ConnectError::Refused)));
+    ///     assert!(connection.is_idle());
+    ///
+    ///     let second = connection
+    ///         .get_or_try_load(|| async { Ok::<_, ConnectError>("connected".to_string()) })
+    ///         .await;
+    ///     assert_eq!(second.unwrap(), "connected");
+    /// }
+    /// ```
+    pub async fn get_or_try_load<F, Fut, E>(&self, init: F) -> std::result::Result<&T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = std::result::Result<T, E>>,
+        E: From<PreloaderError>,
+    {
+        loop {
+            if self.claim() {
+                let release = Release(self);
+                self.mark_started();
+                let value = attempt(init(), self.attempt_timeout()).await??;
+                std::mem::forget(release);
+                self.finish(Ok(value));
+                return Ok(self.get_value());
+            }
+
+            match self.cell.load(Ordering::Acquire) {
+                PreloaderState::Idle => {}
+                PreloaderState::Start => self.wait_while(PreloaderState::Start).await,
+                _ => return self.get().await.map_err(E::from),
+            }
+        }
+    }
+
+    /// Initializes the preloader with `init` if it is `Idle`, then returns the value.
+    ///
+    /// Mirrors `tokio::sync::OnceCell::get_or_init`, so code using a `OnceCell` can switch to a `Preloader`
+    /// by replacing the type. `init` runs in the calling task and only one caller runs it at a time.
+    /// Unlike `OnceCell`, a load started in another way can fail, so the value is returned as a `Result`.
+    ///
+    /// # Parameters
+    ///
+    /// - `init`: Function creating the asynchronous initialization
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: The loaded value
+    /// - `Err(PreloaderError)`: The configured timeout elapsed, or a load started in another way failed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let cell: Preloader<u32> = Preloader::new();
+    ///     assert!(!cell.initialized());
+    ///
+    ///     assert_eq!(cell.get_or_init(|| async { 42 }).await.unwrap(), &42);
+    ///     assert_eq!(cell.get_or_init(|| async { 0 }).await.unwrap(), &42);
+    ///     assert!(cell.initialized());
+    ///     assert_eq!(cell.wait().await.unwrap(), &42);
+    /// }
+    /// ```
+    pub async fn get_or_init<F, Fut>(&self, init: F) -> Result<&T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        self.get_or_try_load(|| {
+            let init = init();
+            async move { Ok::<_, PreloaderError>(init.await) }
+        })
+        .await
+    }
+
+    /// Checks if the value has been loaded, like `tokio::sync::OnceCell::initialized`.
+    ///
+    /// Same as [`is_loaded`](Self::is_loaded).
+    pub fn initialized(&self) -> bool {
+        self.is_loaded()
+    }
+
+    /// Waits until the value has been loaded, like `tokio::sync::OnceCell::wait`.
+    ///
+    /// Unlike `get()`, this does not return `NotLoaded` while the preloader is `Idle`,
+    /// but waits for loading to be started elsewhere.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: The loaded value
+    /// - `Err(PreloaderError)`: If loading failed
+    pub async fn wait(&self) -> Result<&T> {
+        self.resolve(true).await
+    }
+
+    /// Waits for the result of loading.
+    ///
+    /// # Parameters
+    ///
+    /// - `wait_idle`: Whether to wait for loading to be started instead of returning `NotLoaded` in the `Idle` state
+    pub(crate) async fn resolve(&self, wait_idle: bool) -> Result<&T> {
+        let _waiter = self.must_wait(wait_idle).then(|| self.waiters.enter());
+        deadline::bounded(self.wait_result(wait_idle)).await
+    }
+
+    /// Checks if waiting for the result of loading would block.
+    fn must_wait(&self, wait_idle: bool) -> bool {
+        match self.cell.load(Ordering::Acquire) {
+            PreloaderState::Idle => wait_idle || self.unloaded.load(Ordering::Acquire),
+            PreloaderState::Start | PreloaderState::Loading => true,
+            PreloaderState::Failed => self.should_retry_error(),
+            PreloaderState::Loaded | PreloaderState::Poisoned => false,
+        }
+    }
+
+    /// Waits for the result of loading, ignoring the caller's deadline.
+    async fn wait_result(&self, wait_idle: bool) -> Result<&T> {
+        loop {
+            match self.cell.load(Ordering::Acquire) {
+                // The next iteration waits for the load started here
+                PreloaderState::Idle if self.reload_unloaded() => {}
+                PreloaderState::Idle if wait_idle => {
+                    self.wait_while(PreloaderState::Idle).await;
+                }
+                PreloaderState::Idle => {
+                    return Err(PreloaderError::NotLoaded);
+                }
+                PreloaderState::Start => {
+                    self.wait_while(PreloaderState::Start).await;
+                }
+                // Without a lock that can be held across an await point, every caller polls the receiver in turn
+                #[cfg(feature = "std-sync")]
+                PreloaderState::Loading => {
+                    return std::future::poll_fn(|cx| self.poll_get(cx)).await;
+                }
+                #[cfg(not(feature = "std-sync"))]
+                PreloaderState::Loading => {
+                    let mut handle = self.handle.lock().await;
+                    // Pollers cannot poll the receiver while it is held here, so they are woken once it is released
+                    let _release = WakeOnDrop(&self.pollers);
+                    // The receiver stays in place while awaiting, so a cancelled caller does not lose the result
+                    if let Some(receiver) = handle.as_mut() {
+                        let completion = receiver.await;
+                        *handle = None;
+                        match completion.unwrap_or_else(|_| cancelled()) {
+                            (Ok(value), finished_at) => self.set_value_at(value, finished_at),
+                            (Err(error), _) => return Err(self.set_error(error)),
+                        }
+                    }
+                    // If handle is already None, the result has been stored by another caller
+                    return self.settled();
+                }
+                PreloaderState::Failed if self.should_retry_error() => {
+                    // If another caller restarts first, the next iteration waits for its load
+                    if self.claim_failed() {
+                        self.set_handle(self.spawn_stored()).await;
+                    }
+                }
+                PreloaderState::Loaded | PreloaderState::Failed | PreloaderState::Poisoned => {
+                    return self.settled();
+                }
+            }
+        }
+    }
+
+    /// Retrieves a clone of the loaded data.
+    ///
+    /// Behaves like `get()`, but returns an owned copy so the caller does not hold a borrow of the preloader.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(T)`: A clone of the loaded data
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
+    ///     preloader.load(async { "data".to_string() }).await;
+    ///     let data: String = preloader.get_cloned().await.unwrap();
+    ///     assert_eq!(data, "data");
+    /// }
+    /// ```
+    pub async fn get_cloned(&self) -> Result<T>
+    where
+        T: Clone,
+    {
+        self.get().await.cloned()
+    }
+
+    /// Attempts to retrieve a clone of the loaded data immediately.
+    ///
+    /// Behaves like `try_get()`, but returns an owned copy.
+    pub fn try_get_cloned(&self) -> Result<T>
+    where
+        T: Clone,
+    {
+        self.try_get().cloned()
+    }
+
+    /// Retrieves the loaded data, waiting until loading is complete, or `fallback` if loading failed.
+    ///
+    /// See [`get_or_else`](Self::get_or_else).
+    pub async fn get_or(&self, fallback: T) -> Cow<'_, T>
+    where
+        T: Clone,
+    {
+        self.get_or_else(|_| fallback).await
+    }
+
+    /// Retrieves the loaded data, waiting until loading is complete, or the value computed by `fallback` from the
+    /// error if loading failed.
+    ///
+    /// The error is logged as a warning when the `tracing` feature is enabled and the preloader is instrumented,
+    /// so call sites degrading to a default value don't each repeat the same match and logging.
+    ///
+    /// # Parameters
+    ///
+    /// - `fallback`: Function computing the value to use from the error of [`get`](Self::get)
+    ///
+    /// # Returns
+    ///
+    /// A borrowed loaded value, or the owned fallback value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Preloader, PreloaderError};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // Never loaded, so `get()` fails with `PreloaderError::NotLoaded`
+    ///     let limits: Preloader<Vec<u32>> = Preloader::new();
+    ///     let current = limits
+    ///         .get_or_else(|error| {
+    ///             assert!(matches!(error, PreloaderError::NotLoaded));
+    ///             vec![100]
+    ///         })
+    ///         .await;
+    ///     assert_eq!(*current, [100]);
+    /// }
+    /// ```
+    pub async fn get_or_else(&self, fallback: impl FnOnce(PreloaderError) -> T) -> Cow<'_, T>
+    where
+        T: Clone,
+    {
+        match self.get().await {
+            Ok(value) => Cow::Borrowed(value),
+            Err(error) => {
+                self.log_fallback(&error);
+                Cow::Owned(fallback(error))
+            }
+        }
+    }
+
+    /// Logs that `error` made a caller fall back to a default value.
+    pub(crate) fn log_fallback(&self, error: &PreloaderError) {
+        #[cfg(feature = "tracing")]
+        if self.options.instrument {
+            tracing::warn!(name = self.name(), %error, "preloader failed, using fallback value");
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = error;
+    }
+
+    /// Returns a mutable reference to the loaded data.
+    ///
+    /// Since this requires exclusive access, no other caller can observe the value while it is being modified.
+    /// A result that finished loading but has not been received yet is received first, as with `try_get()`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&mut T)`: If the data is loaded
+    /// - `None`: If the data is not loaded, is still loading, failed to load, or has expired
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    ///
+    /// let mut preloader = Preloader::ready(vec![1, 2]);
+    /// preloader.get_mut().unwrap().push(3);
+    /// assert_eq!(*preloader.try_get().unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.try_get().ok()?;
+        self.cell.get_mut()
+    }
+
+    /// Replaces the loaded value, keeping the time at which `value` was loaded.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&mut T)`: The stored value
+    /// - `Err(T)`: `value`, handed back if no value is loaded
+    pub(crate) fn replace_loaded(
+        &mut self,
+        value: T,
+        loaded_at: Instant,
+    ) -> std::result::Result<&mut T, T> {
+        let Some(slot) = self.cell.get_mut() else {
+            return Err(value);
+        };
+        *slot = value;
+        self.loaded_at = OnceLock::from(loaded_at);
+        Ok(slot)
+    }
+
+    /// Modifies the loaded data in place.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: Function applied to the loaded data
+    ///
+    /// # Returns
+    ///
+    /// - `Some(R)`: The result of `f`, if the data is loaded
+    /// - `None`: If the data is not available, in which case `f` is not called
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut preloader = Preloader::ready(HashMap::from([("port", 8080)]));
+    /// preloader.update(|config| config.insert("port", 9090));
+    /// assert_eq!(preloader.try_get().unwrap()["port"], 9090);
+    /// ```
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.get_mut().map(f)
+    }
+
+    /// Drops the loaded value to reclaim its memory, moving the preloader back to the `Idle` state.
+    ///
+    /// With a stored loader factory, the next `get()` loads the value again, so callers do not notice the
+    /// value was dropped beyond the wait. Without one, the preloader can be loaded again with `load()`.
+    /// Subscribers of `events()` receive `Evicted`.
+    ///
+    /// # Returns
+    ///
+    /// Whether a value was dropped
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut dataset = Preloader::with_loader(|| async { vec![0u8; 1024] });
+    ///     dataset.start().await;
+    ///     assert_eq!(dataset.get().await.unwrap().len(), 1024);
+    ///
+    ///     assert!(dataset.unload());
+    ///     assert!(dataset.is_idle());
+    ///     assert_eq!(dataset.get().await.unwrap().len(), 1024);
+    /// }
+    /// ```
+    pub fn unload(&mut self) -> bool {
+        // A load that has just finished is stored first, so it is dropped as well
+        _ = self.poll_result();
+        if self.cell.load(Ordering::Acquire) != PreloaderState::Loaded {
+            return false;
+        }
+
+        drop(self.cell.take());
+        self.cell.store(PreloaderState::Idle, Ordering::Release);
+        self.loaded_at = OnceLock::new();
+        *self.started_at.get_mut().unwrap() = None;
+        *self.unloaded.get_mut() = self.loader.is_some();
+        self.events.emit(PreloaderEvent::Evicted);
+        true
+    }
+
+    /// Takes the loaded data, consuming it.
+    ///
+    /// This method consumes the loaded data, leaving None in its place.
+    /// Returns an error if the data is not yet loaded.
+    /// If the data is still loading, waits until loading is complete.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(T)`: If the data was successfully loaded and taken
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
+    ///     // Start loading
+    ///     preloader.load(async { "data".to_string() }).await;
+    ///     // Take data, consuming the preloader
+    ///     match preloader.take().await {
+    ///         Ok(data) => println!("Taken data: {}", data),
+    ///         Err(e) => println!("Error: {}", e),
+    ///     }
+    ///     // Note: preloader is consumed and cannot be used after take()
+    ///     // The following code would not compile:
+    ///     // match preloader.try_get() { ... } // Error: use of moved value
+    /// }
+    /// ```
+    /// Takes the loaded data, consuming the Preloader.
+    ///
+    /// This method consumes the Preloader itself, ensuring it cannot be used after taking the value.
+    /// Returns an error if the data is not yet loaded.
+    /// If the data is still loading, waits until loading is complete.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(T)`: If the data was successfully loaded and taken
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
+    ///     // Start loading
+    ///     preloader.load(async { "data".to_string() }).await;
+    ///     // Take data, consuming the preloader
+    ///     match preloader.take().await {
+    ///         Ok(data) => println!("Taken data: {}", data),
+    ///         Err(e) => println!("Error: {}", e),
+    ///     }
+    ///     // Preloader cannot be used after take() as it has been consumed
+    /// }
+    /// ```
+    pub async fn take(self) -> Result<T> {
+        match self.get().await {
+            Ok(_) => self.take_value(),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Takes the loaded data if it is available right now, consuming the Preloader.
+    ///
+    /// Unlike `take()`, this method does not wait, and the Preloader is handed back
+    /// if the data is not loaded yet, is still loading, or failed to load.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(T)`: If the data was loaded
+    /// - `Err(Preloader<T>)`: The unchanged Preloader, if the data is not available
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    ///
+    /// let preloader = Preloader::<String>::new();
+    /// // Not loaded yet, so the preloader is handed back
+    /// let preloader = preloader.try_take().unwrap_err();
+    /// assert!(!preloader.is_loaded());
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn try_take(self) -> std::result::Result<T, Self> {
+        if self.try_get().is_err() {
+            return Err(self);
+        }
+        self.take_value().map_err(|_| unreachable!())
+    }
+
+    /// Takes the loaded data, waiting at most `timeout` for loading to complete.
+    ///
+    /// The Preloader is handed back if the data does not become available within the timeout,
+    /// so the caller can keep waiting or inspect the state with `try_get()`.
+    ///
+    /// # Parameters
+    ///
+    /// - `timeout`: Maximum duration to wait
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(T)`: If the data was loaded within the timeout
+    /// - `Err(Preloader<T>)`: The Preloader, if the data is not available
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::spawn(async { "data".to_string() });
+    ///     match preloader.take_timeout(Duration::from_secs(1)).await {
+    ///         Ok(data) => println!("Taken data: {}", data),
+    ///         Err(preloader) => println!("Still not ready: {:?}", preloader.try_get().err()),
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub async fn take_timeout(self, timeout: Duration) -> std::result::Result<T, Self> {
+        let loaded = matches!(tokio::time::timeout(timeout, self.get()).await, Ok(Ok(_)));
+        if !loaded {
+            return Err(self);
+        }
+        self.take_value().map_err(|_| unreachable!())
+    }
+
+    /// Extracts the loaded data, consuming the Preloader.
+    ///
+    /// Returns the value if and only if the Preloader is in the `Loaded` state.
+    /// This method never waits and never receives a pending result, which makes it suitable
+    /// for shutdown paths where loading is known to have finished.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(T)`: If the data was loaded
+    /// - `None`: If loading has not completed or failed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    ///
+    /// let preloader = Preloader::ready(42);
+    /// assert_eq!(preloader.into_inner(), Some(42));
+    ///
+    /// let preloader = Preloader::<i32>::new();
+    /// assert_eq!(preloader.into_inner(), None);
+    /// ```
+    pub fn into_inner(mut self) -> Option<T> {
+        match self.cell.load(Ordering::Acquire) {
+            PreloaderState::Loaded => self.cell.take(),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the loaded data together with its generation, waiting until loading is complete.
+    ///
+    /// A `Preloader` stores its value once, so the generation is always 1; it exists so code reading
+    /// a `Preloader` and an [`ArcPreloader`](crate::ArcPreloader), whose reloads increment the generation,
+    /// can detect changes the same way.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((u64, &T))`: The generation and the data
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    pub async fn get_versioned(&self) -> Result<(u64, &T)> {
+        self.get().await.map(|value| (1, value))
+    }
+
+    /// Attempts to retrieve the loaded data together with its generation immediately.
+    ///
+    /// See [`get_versioned`](Self::get_versioned).
+    pub fn try_get_versioned(&self) -> Result<(u64, &T)> {
+        self.try_get().map(|value| (1, value))
+    }
+
+    /// Returns the generation of the value: 0 before it is loaded and 1 once it is.
+    ///
+    /// See [`get_versioned`](Self::get_versioned).
+    pub fn generation(&self) -> u64 {
+        u64::from(self.cell.load(Ordering::Acquire) == PreloaderState::Loaded)
+    }
+
+    /// Returns the name set through the builder, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.options.name.as_deref()
+    }
+
+    /// Checks if the loaded value is older than the configured TTL.
+    ///
+    /// Always returns `false` if no TTL is configured or no value has been loaded yet.
+    pub fn is_expired(&self) -> bool {
+        match (self.options.ttl, self.loaded_at.get()) {
+            (Some(ttl), Some(loaded_at)) => loaded_at.elapsed() >= ttl,
+            _ => false,
+        }
+    }
+
+    /// Returns the configured TTL.
+    pub(crate) fn ttl(&self) -> Option<Duration> {
+        self.options.ttl
+    }
+
+    /// Returns the timeout of a load attempt starting now.
+    ///
+    /// This is the configured [`timeout`](crate::PreloaderBuilder::timeout), or the one computed by the
+    /// configured [`AdaptiveTimeout`](crate::AdaptiveTimeout) from the load history.
+    ///
+    /// # Returns
+    ///
+    /// The timeout, or `None` if attempts are not bounded
+    pub fn attempt_timeout(&self) -> Option<Duration> {
+        let Some(adaptive) = &self.options.adaptive_timeout else {
+            return self.options.timeout;
+        };
+        adaptive
+            .compute(&self.history.first_attempt_durations())
+            .or(self.options.timeout)
+            .or(Some(adaptive.ceiling()))
+    }
+
+    /// Wraps `loader` with the faults injected into this preloader through [`chaos::inject`](crate::chaos::inject).
+    #[cfg(feature = "test-util")]
+    pub(crate) fn chaotic<U>(
+        &self,
+        loader: impl Future<Output = Result<U>> + Send + 'static,
+    ) -> impl Future<Output = Result<U>> + Send + 'static {
+        let fault = crate::chaos::fault_for(self.name());
+        let timeout = self.attempt_timeout();
+        async move {
+            match fault {
+                Some(fault) => fault.apply(timeout, loader).await,
+                None => loader.await,
+            }
+        }
+    }
+
+    /// Returns `loader` as is, since faults are only injected with the `test-util` feature.
+    #[cfg(not(feature = "test-util"))]
+    pub(crate) fn chaotic<U>(
+        &self,
+        loader: impl Future<Output = Result<U>> + Send + 'static,
+    ) -> impl Future<Output = Result<U>> + Send + 'static {
+        loader
+    }
+
+    /// Wraps `loader` with the injected faults and the configured [`Watchdog`](crate::Watchdog).
+    ///
+    /// Restarts use the stored loader factory, so a preloader without one only reports its stalled loads.
+    pub(crate) fn supervised(
+        &self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> impl Future<Output = Result<T>> + Send + 'static {
+        let loader = self.chaotic(loader);
+        let loader = match self.options.time_slice.clone() {
+            Some(time_slice) if time_slice.warn_limit().is_some() => {
+                Either::Left(time_slice.watch_polls(self.options.name.clone(), loader))
+            }
+            _ => Either::Right(loader),
+        };
+        let Some(watchdog) = self.options.watchdog.clone() else {
+            return Either::Left(loader);
+        };
+        let restarts = (0..watchdog.max_restarts())
+            .map_while(|_| Some(self.chaotic(self.stored_load()?).boxed()))
+            .collect();
+        Either::Right(watchdog.watch(
+            self.options.name.clone(),
+            self.events.clone(),
+            loader,
+            restarts,
+        ))
+    }
+
+    /// Returns the configured hard and soft timeouts.
+    pub(crate) fn timeouts(&self) -> (Option<Duration>, Option<Duration>) {
+        (self.attempt_timeout(), self.options.soft_timeout)
+    }
+
+    /// Returns the upper bound of the random delay added to scheduled refreshes.
+    pub(crate) fn refresh_jitter(&self) -> Duration {
+        self.options.refresh_jitter
+    }
+
+    /// Returns the time at which the latest load was started.
+    ///
+    /// Returns `None` if no load has been started, including preloaders created with `ready()` or filled with `set()`.
+    pub fn started_at(&self) -> Option<std::time::Instant> {
+        self.started_at.lock().unwrap().map(Instant::into_std)
+    }
+
+    /// Returns the error of the latest failed load, if it is still recorded.
+    ///
+    /// The error is cleared when a failed preloader starts loading again.
+    pub fn last_error(&self) -> Option<PreloaderError> {
+        self.error
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|(error, _)| error.clone())
+    }
+
+    /// Returns the error of the latest failed load together with the preloader name and the number of attempts.
+    ///
+    /// Like [`last_error`](Self::last_error), this is `None` unless a failure is recorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Preloader, RetryPolicy};
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader: Preloader<u32> = Preloader::builder()
+    ///         .name("catalog")
+    ///         .retry(RetryPolicy::fixed(2, Duration::from_millis(1)))
+    ///         .build_with_loader(|| async { panic!("unreachable upstream") });
+    ///     preloader.start().await;
+    ///     assert!(preloader.get().await.is_err());
+    ///
+    ///     let context = preloader.error_context().unwrap();
+    ///     assert_eq!(context.to_string(), "catalog failed after 3 attempt(s)");
+    ///     assert!(!context.is_retryable());
+    /// }
+    /// ```
+    pub fn error_context(&self) -> Option<ErrorContext> {
+        let error = self.error.lock().unwrap();
+        let (error, failed_at) = error.as_ref()?;
+        Some(ErrorContext {
+            name: self.options.name.clone(),
+            attempts: self.attempts.load(Ordering::Relaxed),
+            failed_at: failed_at.into_std(),
+            error: error.clone(),
+        })
+    }
+
+    /// Returns how many callers are blocked in `get()` right now and how long past waits took.
+    ///
+    /// Only calls that had to wait are counted, so a preloader with many or long waits is on the critical path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::spawn(async { "data" });
+    ///     preloader.get().await.unwrap();
+    ///     preloader.get().await.unwrap();
+    ///
+    ///     let stats = preloader.wait_stats();
+    ///     assert_eq!(stats.waiting, 0);
+    ///     assert_eq!(stats.waits, 1);
+    /// }
+    /// ```
+    pub fn wait_stats(&self) -> WaitStats {
+        self.waiters.stats()
+    }
+
+    /// Returns the p50, p95, p99, and maximum durations of the latest loads and waits.
+    ///
+    /// Loads include failed ones and reloads; waits are the `get()` calls that had to wait. Each set covers the
+    /// latest [`LATENCY_WINDOW`](crate::LATENCY_WINDOW) durations. With the `metrics` feature, the same durations
+    /// are recorded in the `preloader_load_duration_seconds` and `preloader_wait_duration_seconds` histograms,
+    /// labeled with the name of the preloader.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::spawn(async { "data" });
+    ///     preloader.get().await.unwrap();
+    ///
+    ///     let stats = preloader.stats();
+    ///     let loads = stats.loads.unwrap();
+    ///     assert_eq!(loads.count, 1);
+    ///     assert!(loads.p50 <= loads.p99);
+    /// }
+    /// ```
+    pub fn stats(&self) -> PreloaderStats {
+        self.history.latency().stats()
+    }
+
+    /// Returns the latest finished loads, oldest first.
+    ///
+    /// Each entry records when the load started, how long it took, how many attempts it made, what triggered it,
+    /// and the error that ended it, if any. The number of loads kept is set with
+    /// [`PreloaderBuilder::history`] and defaults to [`DEFAULT_HISTORY_LEN`](crate::DEFAULT_HISTORY_LEN).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{LoadTrigger, Preloader};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::spawn(async { "data" });
+    ///     preloader.get().await.unwrap();
+    ///
+    ///     let history = preloader.history();
+    ///     assert_eq!(history.len(), 1);
+    ///     assert_eq!(history[0].trigger, LoadTrigger::Initial);
+    ///     assert!(history[0].is_success());
+    /// }
+    /// ```
+    pub fn history(&self) -> Vec<LoadAttempt> {
+        self.history.entries()
+    }
+
+    /// Returns the history of finished loads.
+    pub(crate) fn history_log(&self) -> &History {
+        &self.history
+    }
+
+    /// Resets the attempt count at the start of a load that is not run by a spawned task.
+    pub(crate) fn reset_attempts(&self) {
+        self.attempts.store(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of attempts made by the latest load.
+    pub(crate) fn attempt_count(&self) -> u32 {
+        self.attempts.load(Ordering::Relaxed)
+    }
+
+    /// Returns the time at which the value finished loading.
+    ///
+    /// Returns `None` until a value is available.
+    pub fn loaded_at(&self) -> Option<std::time::Instant> {
+        self.loaded_at.get().copied().map(Instant::into_std)
+    }
+
+    /// Returns how long loading took, from starting the load to the value being available.
+    ///
+    /// Includes retries and backoff. Returns `None` until a value is available, or if it was not produced by a load.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::spawn(async {
+    ///         tokio::time::sleep(Duration::from_millis(20)).await;
+    ///         42
+    ///     });
+    ///     preloader.get().await.unwrap();
+    ///     assert!(preloader.load_duration().unwrap() >= Duration::from_millis(20));
+    /// }
+    /// ```
+    pub fn load_duration(&self) -> Option<Duration> {
+        let loaded_at = *self.loaded_at.get()?;
+        let started_at = (*self.started_at.lock().unwrap())?;
+        Some(loaded_at.saturating_duration_since(started_at))
+    }
+
+    /// Returns how long ago the value finished loading.
+    ///
+    /// Returns `None` until a value is available.
+    pub fn age(&self) -> Option<Duration> {
+        self.loaded_at.get().map(Instant::elapsed)
+    }
+
+    /// Returns the loaded value together with its age, even if it has expired.
+    ///
+    /// Serving slightly old data is often better than failing: unlike `try_get()`, this ignores the TTL.
+    ///
+    /// # Returns
+    ///
+    /// The value and the time since it finished loading, or `None` if no value has been loaded
+    pub fn try_get_stale(&self) -> Option<(&T, Duration)> {
+        match self.cell.load(Ordering::Acquire) {
+            PreloaderState::Loaded => Some((self.get_value(), self.age().unwrap_or_default())),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the loaded value together with its age, waiting for loading if no value is available yet.
+    ///
+    /// Behaves like `get()`, except that an expired value is returned instead of `PreloaderError::Expired`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((&T, Duration))`: The value and the time since it finished loading
+    /// - `Err(PreloaderError)`: If the data is not loaded or an error occurred during loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// #[tokio::main(flavor = "current_thread", start_paused = true)]
+    /// async fn main() {
+    ///     let rates: Preloader<f64> = Preloader::builder().ttl(Duration::from_secs(60)).build();
+    ///     rates.load(async { 1.08 }).await;
+    ///     tokio::time::sleep(Duration::from_secs(90)).await;
+    ///
+    ///     assert!(rates.get().await.is_err());
+    ///     let (rate, age) = rates.get_stale().await.unwrap();
+    ///     assert_eq!(*rate, 1.08);
+    ///     assert!(age >= Duration::from_secs(90));
+    /// }
+    /// ```
+    pub async fn get_stale(&self) -> Result<(&T, Duration)> {
+        if let Some(stale) = self.try_get_stale() {
+            return Ok(stale);
+        }
+        let result = self.get().await.map(drop);
+        self.try_get_stale().ok_or_else(|| result.unwrap_err())
+    }
+
+    /// Retrieves the loaded data without checking the state.
+    ///
+    /// This method is unsafe and should only be used when you are sure that the data is loaded.
+    ///
+    /// # Returns
+    ///
+    /// Reference to the stored value
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that loading has completed and the value has been stored.
+    pub unsafe fn get_unchecked(&self) -> &T {
+        match self.cell.load(Ordering::Relaxed) {
+            PreloaderState::Idle | PreloaderState::Start => {
+                panic!("Preloader is not loaded");
+            }
+            PreloaderState::Loading | PreloaderState::Loaded => self.get_value(),
+            PreloaderState::Failed | PreloaderState::Poisoned => {
+                panic!("Preloader failed to load");
+            }
+        }
+    }
+
+    /// Attempts to retrieve the loaded data immediately.
+    ///
+    /// Unlike `get()`, this method does not block. If the data is not yet loaded or is still loading, returns an error immediately.
+    /// A preloader whose `load()` call has not finished registering the task is reported as loading.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the data was successfully loaded
+    /// - `Err(String)`: If the data is not loaded or is still loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
+    ///     // Try before loading
+    ///     match preloader.try_get() {
+    ///         Ok(data) => println!("Data: {}", data),
+    ///         Err(e) => println!("Not loaded yet: {}", e),
+    ///     }
+    ///     // Start loading
+    ///     preloader.load(async { "data".to_string() }).await;
+    ///     // Try while loading
+    ///     match preloader.try_get() {
+    ///         Ok(data) => println!("Data: {}", data),
+    ///         Err(e) => println!("Still loading: {}", e),
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn try_get(&self) -> Result<&T> {
+        match self.loaded() {
+            Some(value) => Ok(value),
+            None => self.poll_result(),
+        }
+    }
+
+    /// Polls for the loaded data, for use in hand-written `Future` and `Stream` implementations.
+    ///
+    /// Behaves like [`get`](Self::get) without allocating a future: it returns `Poll::Pending` and arranges for
+    /// `cx`'s waker to be woken while loading is in progress, and `Poll::Ready` with the result otherwise. Unlike
+    /// `get()`, it does not restart a failed load under an [`ErrorPolicy`] and is not bounded by a deadline scope.
+    ///
+    /// # Returns
+    ///
+    /// - `Poll::Ready(Ok(&T))`: If the data is loaded
+    /// - `Poll::Ready(Err(PreloaderError::NotLoaded))`: If loading has not been started
+    /// - `Poll::Ready(Err(PreloaderError))`: If loading failed
+    /// - `Poll::Pending`: If loading is in progress
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Preloader, PreloaderError};
+    /// use std::{future::Future, pin::Pin, task::{Context, Poll}};
+    /// use tokio;
+    ///
+    /// /// Resolves to the length of the loaded text
+    /// struct TextLength<'a>(&'a Preloader<String>);
+    ///
+    /// impl Future for TextLength<'_> {
+    ///     type Output = Result<usize, PreloaderError>;
+    ///
+    ///     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    ///         self.0.poll_get(cx).map(|text| text.map(String::len))
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let text = Preloader::spawn(async { "hello".to_string() });
+    ///     assert_eq!(TextLength(&text).await.unwrap(), 5);
+    /// }
+    /// ```
+    pub fn poll_get(&self, cx: &mut Context<'_>) -> Poll<Result<&T>> {
+        if let Some(value) = self.loaded() {
+            return Poll::Ready(Ok(value));
+        }
+
+        // Registered before the state is checked, so a change right after the check still wakes the caller
+        self.pollers.register(cx.waker());
+
+        match self.cell.load(Ordering::Acquire) {
+            // Woken once the load started here leaves `Start`
+            PreloaderState::Idle if self.reload_unloaded() => Poll::Pending,
+            PreloaderState::Idle => Poll::Ready(Err(PreloaderError::NotLoaded)),
+            PreloaderState::Start => Poll::Pending,
+            PreloaderState::Loading => {
+                // A caller in `get()` holding the handle stores the result and wakes the pollers
+                let Ok(mut handle) = self.handle.try_lock() else {
+                    return Poll::Pending;
+                };
+                if let Some(receiver) = handle.as_mut() {
+                    // Woken through every registered poller, so dropping this caller does not strand the others
+                    let waker = Waker::from(Arc::clone(&self.pollers));
+                    let completion = match Pin::new(receiver).poll(&mut Context::from_waker(&waker))
+                    {
+                        Poll::Ready(completion) => completion.unwrap_or_else(|_| cancelled()),
+                        Poll::Pending => return Poll::Pending,
+                    };
+                    *handle = None;
+                    match completion {
+                        (Ok(value), finished_at) => self.set_value_at(value, finished_at),
+                        (Err(error), _) => return Poll::Ready(Err(self.set_error(error))),
+                    }
+                }
+                Poll::Ready(self.settled())
+            }
+            PreloaderState::Loaded | PreloaderState::Failed | PreloaderState::Poisoned => {
+                Poll::Ready(self.settled())
+            }
+        }
+    }
+
+    /// Returns the result of loading if it is available, storing it when the loading task has just finished.
+    fn poll_result(&self) -> Result<&T> {
+        match self.cell.load(Ordering::Acquire) {
+            PreloaderState::Idle => Err(PreloaderError::NotLoaded),
+            PreloaderState::Start => Err(PreloaderError::Loading),
+            PreloaderState::Loading => {
+                let mut handle = self
+                    .handle
+                    .try_lock()
+                    .map_err(|_| PreloaderError::Loading)?;
+
+                if let Some(receiver) = handle.as_mut() {
+                    let completion = match receiver.try_recv() {
+                        Ok(completion) => completion,
+                        Err(TryRecvError::Empty) => return Err(PreloaderError::Loading),
+                        Err(TryRecvError::Closed) => cancelled(),
+                    };
+                    *handle = None;
+                    match completion {
+                        (Ok(value), finished_at) => self.set_value_at(value, finished_at),
+                        (Err(error), _) => return Err(self.set_error(error)),
+                    }
+                }
+                self.settled()
+            }
+            PreloaderState::Loaded | PreloaderState::Failed | PreloaderState::Poisoned => {
+                self.settled()
+            }
+        }
+    }
+
+    /// Retrieves the loaded data without checking the state.
+    ///
+    /// This method is unsafe and should only be used when you are sure that the data is loaded.
+    ///
+    /// # Returns
+    ///
+    /// Reference to the stored value
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the preloader is in the `Loaded` state.
+    pub unsafe fn try_get_unchecked(&self) -> &T {
+        match self.cell.load(Ordering::Relaxed) {
+            PreloaderState::Idle | PreloaderState::Start => {
+                panic!("Preloader is not loaded");
+            }
+            PreloaderState::Loading => {
+                panic!("Preloader is loading");
+            }
+            PreloaderState::Loaded => self.get_value(),
+            PreloaderState::Failed | PreloaderState::Poisoned => {
+                panic!("Preloader failed to load");
+            }
+        }
+    }
+
+    /// Moves the preloader from `Idle` to `Start`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the caller is now responsible for loading, `false` if loading was already started
+    fn claim(&self) -> bool {
+        self.cell
+            .compare_exchange(
+                PreloaderState::Idle,
+                PreloaderState::Start,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
+    /// Changes the state from `Failed` to `Start` and clears the recorded error.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the preloader was in the `Failed` state
+    fn claim_failed(&self) -> bool {
+        let claimed = self
+            .cell
+            .compare_exchange(
+                PreloaderState::Failed,
+                PreloaderState::Start,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_ok();
+        if claimed {
+            *self.error.lock().unwrap() = None;
+        }
+        claimed
+    }
+
+    /// Claims an `Idle` preloader for a load driven outside of Tokio, moving it to `Start`.
+    ///
+    /// Callers waiting with `get()` wait until [`finish`](Self::finish) is called.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the preloader was `Idle`
+    pub(crate) fn claim_external(&self) -> bool {
+        let claimed = self.claim();
+        if claimed {
+            self.mark_started();
+        }
+        claimed
+    }
+
+    /// Stores the result of a load claimed with [`claim_external`](Self::claim_external) and wakes waiting callers.
+    ///
+    /// # Parameters
+    ///
+    /// - `result`: The result of loading
+    pub(crate) fn finish(&self, result: Result<T>) {
+        let duration = self
+            .started_at
+            .lock()
+            .unwrap()
+            .map_or(Duration::ZERO, |started_at| started_at.elapsed());
+        self.events.emit_outcome(&result, duration);
+        self.history.record(LoadAttempt {
+            started_at: SystemTime::now() - duration,
+            duration,
+            attempts: self.attempts.load(Ordering::Relaxed),
+            trigger: self.history.next_trigger(),
+            error: result.as_ref().err().cloned(),
+        });
+        match result {
+            Ok(value) => self.set_value(value),
+            Err(error) => {
+                self.set_error(error);
+            }
+        }
+        self.notify_state();
+    }
+
+    /// Spawns the loading task and moves an `Idle` preloader directly to `Loading`.
+    ///
+    /// # Parameters
+    ///
+    /// - `future`: The asynchronous task to execute
+    pub(crate) fn begin(&mut self, future: impl Future<Output = T> + Send + 'static) {
+        let handle = self.spawn_task(future);
+        *self.handle.get_mut() = Some(handle);
+        self.cell.store(PreloaderState::Loading, Ordering::Release);
+    }
+
+    /// Spawns a loading task producing a result and moves an `Idle` preloader directly to `Loading`.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: The asynchronous task producing the load result
+    pub(crate) fn start_loader(
+        &mut self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+    ) {
+        let handle = self.spawn_loader(loader);
+        *self.handle.get_mut() = Some(handle);
+        self.cell.store(PreloaderState::Loading, Ordering::Release);
+    }
+
+    /// Starts a loading task producing a result, following the load policy if loading was already started.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: The asynchronous task producing the load result
+    pub(crate) async fn load_result(
+        &self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> LoadHandle {
+        if !self.claim_for_load() {
+            return self.overlapping_load();
+        }
+
+        self.set_handle(self.spawn_loader(loader)).await;
+        self.load_handle()
+    }
+
+    /// Starts a loading task producing a result, bounded by the configured timeout, like [`load_result`](Self::load_result).
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: The asynchronous task producing the load result
+    pub(crate) async fn load_result_timed(
+        &self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> LoadHandle {
+        let timeout = self.attempt_timeout();
+        self.load_result(async move { attempt(loader, timeout).await? })
+            .await
+    }
+
+    /// Creates a load passing a [`LoadContext`] to `attempt` for every attempt, with the configured timeout,
+    /// retries, and hedging.
+    ///
+    /// An attempt returning an error fails and is retried like one that panics.
+    ///
+    /// # Parameters
+    ///
+    /// - `token`: The cancellation token shared by the attempts
+    /// - `attempt`: Function creating the asynchronous task for each attempt from its context
+    pub(crate) fn context_load<F, Fut>(
+        &self,
+        token: CancellationToken,
+        mut attempt: F,
+    ) -> impl Future<Output = Result<T>> + Send + 'static
+    where
+        F: FnMut(LoadContext) -> Fut + Send + 'static,
+        Fut: Future<Output = std::result::Result<T, String>> + Send + 'static,
+    {
+        let context = LoadContext::new(
+            self.options.name.clone(),
+            self.attempt_timeout(),
+            token,
+            self.events.clone(),
+            self.options.time_slice.as_ref(),
+        );
+        let attempts = Arc::clone(&self.attempts);
+        let load = self.retrying(
+            move || attempt(context.for_attempt(attempts.load(Ordering::Relaxed))),
+            |outcome| {
+                outcome
+                    .as_ref()
+                    .map(Some)
+                    .map_err(|error| PreloaderError::Failed(error.clone()))
+            },
+        );
+        async move { load.await?.map_err(PreloaderError::Failed) }
+    }
+
+    /// Spawns a load passing a [`LoadContext`] to `attempt` for every attempt and moves an `Idle` preloader
+    /// directly to `Loading`.
+    ///
+    /// # Parameters
+    ///
+    /// - `attempt`: Function creating the asynchronous task for each attempt from its context
+    pub(crate) fn start_with_context<F, Fut>(&mut self, attempt: F)
+    where
+        F: FnMut(LoadContext) -> Fut + Send + 'static,
+        Fut: Future<Output = std::result::Result<T, String>> + Send + 'static,
+    {
+        let token = CancellationToken::new();
+        let handle = self.spawn_validated(self.context_load(token.clone(), attempt));
+        *self.handle.get_mut() = Some(handle);
+        *self.cancellation.get_mut().unwrap() = Some(token);
+        self.cell.store(PreloaderState::Loading, Ordering::Release);
+    }
+
+    /// Starts a load passing a [`LoadContext`] to `attempt` for every attempt, following the load policy if loading
+    /// was already started.
+    ///
+    /// # Parameters
+    ///
+    /// - `attempt`: Function creating the asynchronous task for each attempt from its context
+    pub(crate) async fn load_result_with_context<F, Fut>(&self, attempt: F) -> LoadHandle
+    where
+        F: FnMut(LoadContext) -> Fut + Send + 'static,
+        Fut: Future<Output = std::result::Result<T, String>> + Send + 'static,
+    {
+        if !self.claim_for_load() {
+            return self.overlapping_load();
+        }
+
+        let token = CancellationToken::new();
+        let handle = self.spawn_validated(self.context_load(token.clone(), attempt));
+        *self.cancellation.lock().unwrap() = Some(token);
+        self.set_handle(handle).await;
+        self.load_handle()
+    }
+
+    /// Starts a loading task producing a result if the preloader is `Idle` or `Failed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: The asynchronous task producing the load result
+    #[cfg_attr(not(any(feature = "config", feature = "watch")), allow(dead_code))]
+    pub(crate) async fn reload_result(
+        &self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+    ) {
+        if !self.claim() && !self.claim_failed() {
+            return;
+        }
+
+        self.set_handle(self.spawn_loader(loader)).await;
+    }
+
+    /// Checks if loading tasks can be spawned, on the configured runtime or the one of the caller.
+    pub(crate) fn can_spawn(&self) -> bool {
+        self.options.runtime.is_some() || tokio::runtime::Handle::try_current().is_ok()
+    }
+
+    /// Starts an asynchronous task to load data without awaiting, if the preloader is still `Idle`.
+    ///
+    /// # Parameters
+    ///
+    /// - `future`: The asynchronous task to execute
+    pub(crate) fn load_now(&self, future: impl Future<Output = T> + Send + 'static) {
+        if !self.claim() {
+            return;
+        }
+
+        let handle = self.spawn_task(future);
+        // The handle is only locked in the `Loading` state, which has not been entered yet.
+        *self
+            .handle
+            .try_lock()
+            .expect("handle is not locked before loading starts") = Some(handle);
+        self.cell.store(PreloaderState::Loading, Ordering::Release);
+        self.notify_state();
+    }
+
+    /// Starts loading a value dropped by `unload()` again with the stored loader factory.
+    ///
+    /// # Returns
+    ///
+    /// Whether loading was started
+    fn reload_unloaded(&self) -> bool {
+        if !self.unloaded.load(Ordering::Acquire) || !self.claim() {
+            return false;
+        }
+        self.unloaded.store(false, Ordering::Release);
+
+        let handle = self.spawn_stored();
+        // The handle is only locked in the `Loading` state, which has not been entered yet.
+        *self
+            .handle
+            .try_lock()
+            .expect("handle is not locked before loading starts") = Some(handle);
+        self.cell.store(PreloaderState::Loading, Ordering::Release);
+        self.notify_state();
+        true
+    }
+
+    /// Spawns a single load attempt and returns the receiver for its result.
+    ///
+    /// # Parameters
+    ///
+    /// - `future`: The asynchronous task to execute
+    fn spawn_task(
+        &self,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> Receiver<Completion<T>> {
+        let timeout = self.attempt_timeout();
+        self.spawn_loader(attempt(future, timeout))
+    }
+
+    /// Spawns a loading task that retries and hedges attempts, and returns the receiver for its result.
+    ///
+    /// # Parameters
+    ///
+    /// - `factory`: Function creating the asynchronous task for each attempt
+    fn spawn_retrying<F, Fut>(&self, factory: F) -> Receiver<Completion<T>>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        self.spawn_validated(self.retrying(factory, |value| Ok(Some(value))))
+    }
+
+    /// Creates a load that retries and hedges attempts according to the configured policies.
+    ///
+    /// # Parameters
+    ///
+    /// - `factory`: Function creating the asynchronous task for each attempt
+    /// - `value`: Function extracting the value to validate from the output of an attempt, or the error failing it
+    fn retrying<U, F, Fut>(
+        &self,
+        mut factory: F,
+        value: fn(&U) -> Result<Option<&T>>,
+    ) -> impl Future<Output = Result<U>> + Send + 'static
+    where
+        U: Send + 'static,
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = U> + Send + 'static,
+    {
+        let timeout = self.attempt_timeout();
+        let retry = self.options.retry;
+        let hedge = self.options.hedge;
+        let circuit = self.options.circuit.clone();
+        let attempts = Arc::clone(&self.attempts);
+        let validators = self.validators.clone();
+        async move {
+            let mut retries = 0;
+            loop {
+                attempts.store(retries + 1, Ordering::Relaxed);
+                let permit = circuit.as_ref().map(CircuitBreaker::acquire).transpose()?;
+                let mut outcome = hedged_attempt(&mut factory, timeout, hedge).await;
+                // An invalid value fails the attempt, so it is retried like any other failure
+                let check = match &outcome {
+                    Ok(Ok(produced)) => value(produced)
+                        .map(|produced| produced.map(|produced| validation(&validators, produced))),
+                    _ => Ok(None),
+                };
+                let check = match check {
+                    Ok(Some(check)) => check.await,
+                    Ok(None) => Ok(()),
+                    Err(error) => Err(error),
+                };
+                if let Err(error) = check {
+                    outcome = Ok(Err(error));
+                }
+                if let Some(permit) = permit {
+                    permit.finish(matches!(outcome, Ok(Ok(_))));
+                }
+                match outcome {
+                    Ok(Ok(value)) => return Ok(value),
+                    _ if retries < retry.max_retries() => {
+                        tokio::time::sleep(retry.backoff(retries)).await;
+                        retries += 1;
+                    }
+                    Ok(Err(error)) => return Err(error),
+                    Err(payload) => return Err(panicked(payload)),
+                }
+            }
+        }
+    }
+
+    /// Spawns a loading task using the stored loader factory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no loader factory is stored.
+    fn spawn_stored(&self) -> Receiver<Completion<T>> {
+        self.spawn_validated(self.stored_load().expect("loader factory is stored"))
+    }
+
+    /// Checks if a loader factory is stored.
+    pub(crate) fn has_loader(&self) -> bool {
+        self.loader.is_some()
+    }
+
+    /// Creates a load using the stored loader factory, with the configured timeout, retries, and hedging.
+    ///
+    /// # Returns
+    ///
+    /// The load, or `None` if no loader factory is stored
+    pub(crate) fn stored_load(&self) -> Option<impl Future<Output = Result<T>> + Send + 'static> {
+        let loader = Arc::clone(self.loader.as_ref()?);
+        Some(self.retrying(move || (loader.lock().unwrap())(), |value| Ok(Some(value))))
+    }
+
+    /// Spawns the loading task on the configured runtime and returns the receiver for its result.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: The asynchronous task producing the load result
+    fn spawn_loader(
+        &self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> Receiver<Completion<T>> {
+        self.spawn_validated(self.validating(loader))
+    }
+
+    /// Spawns a loading task whose value was already checked by the validation callbacks.
+    ///
+    /// Without a dedicated runtime, the task is polled once first: a loader that is ready right away, such as
+    /// `async { value }`, delivers its result without the cost of spawning a task.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: The asynchronous task producing the validated load result
+    fn spawn_validated(
+        &self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> Receiver<Completion<T>> {
+        self.spawn_loader_with(loader, |mut task| {
+            if self.options.runtime.is_none() && poll_once(task.as_mut()) {
+                return None;
+            }
+            Some(self.spawn_named(task).abort_handle())
+        })
+    }
+
+    /// Wraps `loader` so its value fails the load unless every validation callback accepts it.
+    fn validating(
+        &self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> impl Future<Output = Result<T>> + Send + 'static {
+        let validators = self.validators.clone();
+        async move {
+            let value = loader.await?;
+            let check = validation(&validators, &value);
+            check.await?;
+            Ok(value)
+        }
+    }
+
+    /// Spawns the loading task with `spawn` and returns the receiver for its result.
+    ///
+    /// # Parameters
+    ///
+    /// - `loader`: The asynchronous task producing the load result
+    /// - `spawn`: Function spawning the task and returning its abort handle, or `None` if the task already finished
+    fn spawn_loader_with(
+        &self,
+        loader: impl Future<Output = Result<T>> + Send + 'static,
+        spawn: impl FnOnce(Pin<Box<dyn Future<Output = ()> + Send>>) -> Option<AbortHandle>,
+    ) -> Receiver<Completion<T>> {
+        let loader = self.supervised(loader);
+        let (tx, rx) = oneshot::channel();
+        let (outcome_tx, outcome_rx) = watch::channel(None);
+
+        let events = self.events.clone();
+        #[cfg(feature = "tracing")]
+        let instrument = self.options.instrument;
+        let started_at = Instant::now();
+        self.attempts.store(1, Ordering::Relaxed);
+        *self.cancellation.lock().unwrap() = None;
+        let history = self.history.clone();
+        let trigger = history.next_trigger();
+        let started_wall = SystemTime::now();
+        let attempts = Arc::clone(&self.attempts);
+        let task = async move {
+            let result = AssertUnwindSafe(loader)
+                .catch_unwind()
+                .await
+                .unwrap_or_else(|payload| Err(panicked(payload)));
+            let finished_at = Instant::now();
+            #[cfg(feature = "tracing")]
+            match &result {
+                _ if !instrument => {}
+                Ok(_) => {
+                    tracing::Span::current().record("otel.status_code", "OK");
+                    tracing::debug!(elapsed = ?(finished_at - started_at), "preloader loaded");
+                }
+                Err(error) => {
+                    let span = tracing::Span::current();
+                    span.record("otel.status_code", "ERROR");
+                    span.record("otel.status_message", tracing::field::display(error));
+                    tracing::warn!(%error, "preloader failed to load");
+                }
+            }
+            events.emit_outcome(&result, finished_at - started_at);
+            history.record(LoadAttempt {
+                started_at: started_wall,
+                duration: finished_at - started_at,
+                attempts: attempts.load(Ordering::Relaxed),
+                trigger,
+                error: result.as_ref().err().cloned(),
+            });
+            let outcome = result.as_ref().map(|_| ()).map_err(Clone::clone);
+            _ = tx.send((result, finished_at));
+            outcome_tx.send_replace(Some(outcome));
+        };
+        #[cfg(feature = "tracing")]
+        let task = tracing::Instrument::instrument(
+            task,
+            match self.options.instrument {
+                // `otel.*` fields are picked up by `tracing-opentelemetry` as the kind and status of the span
+                true => tracing::info_span!(
+                    "preload",
+                    name = self.name(),
+                    value_type = std::any::type_name::<T>(),
+                    otel.kind = "internal",
+                    otel.status_code = tracing::field::Empty,
+                    otel.status_message = tracing::field::Empty,
+                ),
+                false => tracing::Span::none(),
+            },
+        );
+        *self.started_at.lock().unwrap() = Some(started_at);
+        // Sent before spawning, so subscribers never see the outcome first
+        self.events.emit(PreloaderEvent::Started);
+        *self.task.lock().unwrap() = Some(LoadHandle::new(spawn(Box::pin(task)), outcome_rx));
+
+        rx
+    }
+
+    /// Cancels the running load.
+    ///
+    /// A load started with [`load_cancellable`](Self::load_cancellable) is cancelled cooperatively: its
+    /// [`CancellationToken`] is triggered and the loader decides when to stop. Any other load is aborted at its
+    /// next await point. Either way, waiting callers receive `PreloaderError::Cancelled` unless the loader
+    /// still produces a value.
+    ///
+    /// # Returns
+    ///
+    /// Whether a running load was cancelled
+    pub fn cancel(&self) -> bool {
+        if !self.is_loading() {
+            return false;
+        }
+        let token = self.cancellation.lock().unwrap().take();
+        match token {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => self.abort(),
+        }
+    }
+
+    /// Aborts the running loading task, so waiting callers receive `PreloaderError::Cancelled`.
+    ///
+    /// # Returns
+    ///
+    /// Whether a loading task was aborted
+    pub(crate) fn abort(&self) -> bool {
+        let task = self.task.lock().unwrap().take();
+        match task {
+            Some(task) if self.is_loading() => {
+                task.abort();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Records the start of a load that is not run by a spawned task and notifies event subscribers.
+    fn mark_started(&self) {
+        *self.started_at.lock().unwrap() = Some(Instant::now());
+        self.attempts.store(1, Ordering::Relaxed);
+        self.events.emit(PreloaderEvent::Started);
+    }
+
+    /// Returns the channel of lifecycle events.
+    pub(crate) fn event_feed(&self) -> &Events {
+        &self.events
+    }
+
+    /// Spawns `task` on the configured runtime.
+    ///
+    /// With the `tokio-console` feature and `--cfg tokio_unstable`, the task is spawned through
+    /// `tokio::task::Builder` under the preloader's name, so it can be identified in tokio-console.
+    ///
+    /// # Parameters
+    ///
+    /// - `task`: The task to spawn
+    pub(crate) fn spawn_named(
+        &self,
+        task: impl Future<Output = ()> + Send + 'static,
+    ) -> JoinHandle<()> {
+        let task = self.track(task);
+
+        #[cfg(all(tokio_unstable, feature = "tokio-console"))]
+        if let Some(name) = self
+            .options
+            .name
+            .as_ref()
+            .filter(|_| self.options.instrument)
+        {
+            let builder = tokio::task::Builder::new().name(name);
+            let spawned = match &self.options.runtime {
+                Some(runtime) => builder.spawn_on(task, runtime),
+                None => builder.spawn(task),
+            };
+            return spawned.expect("failed to spawn the loading task");
+        }
+
+        match &self.options.runtime {
+            Some(runtime) => runtime.spawn(task),
+            None => tokio::spawn(task),
+        }
+    }
+
+    /// Registers `task` with the configured task tracker, if any.
+    fn track<F: Future>(&self, task: F) -> Either<TrackedFuture<F>, F> {
+        match &self.options.tracker {
+            Some(tracker) => Either::Left(tracker.track_future(task)),
+            None => Either::Right(task),
+        }
+    }
+
+    /// Sets the handle for the asynchronous task and changes the state to `Loading`.
+    ///
+    /// # Parameters
+    ///
+    /// - `handle`: Receiver for the asynchronous task
+    #[inline]
+    async fn set_handle(&self, handle: Receiver<Completion<T>>) {
+        *self.handle.lock().await = Some(handle);
+        self.cell.store(PreloaderState::Loading, Ordering::Release);
+        self.notify_state();
+    }
+
+    /// Wakes the callers waiting for the state to leave `Start` or `Idle`.
+    fn notify_state(&self) {
+        self.started.notify_waiters();
+        self.wake_pollers();
+    }
+
+    /// Wakes the `poll_get()` callers waiting for the state to change.
+    fn wake_pollers(&self) {
+        self.pollers.wake_all();
+    }
+
+    /// Waits until the state leaves `Idle` or `Start`.
+    ///
+    /// The notification is registered before the state is re-checked,
+    /// so a `set_handle()` racing with this call cannot be missed.
+    ///
+    /// # Parameters
+    ///
+    /// - `state`: The state to wait on
+    async fn wait_while(&self, state: PreloaderState) {
+        let notified = self.started.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        if self.cell.load(Ordering::Acquire) == state {
+            notified.await;
+        }
+    }
+
+    /// Safely retrieves the stored value.
+    ///
+    /// # Returns
+    ///
+    /// Reference to the stored value
+    ///
+    /// # Safety
+    ///
+    /// This method should only be called in the `Loaded` state, and the value is guaranteed to exist.
+    #[inline]
+    fn get_value(&self) -> &T {
+        unsafe { self.cell.get() }
+    }
+
+    /// Returns the loaded value if it can be read without further checks.
+    ///
+    /// This is the read fast path: a single `Acquire` load of the state, taken only for preloaders without a TTL,
+    /// so a loaded value costs as much to read as a `OnceCell`.
+    #[inline]
+    fn loaded(&self) -> Option<&T> {
+        (self.options.ttl.is_none() && self.cell.load(Ordering::Acquire) == PreloaderState::Loaded)
+            .then(|| self.get_value())
+    }
+
+    /// Returns the result of a finished load.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&T)`: If the value is loaded and has not expired
+    /// - `Err(PreloaderError)`: The recorded error, or the reason no value is available yet
+    #[inline]
+    fn settled(&self) -> Result<&T> {
+        match self.cell.load(Ordering::Acquire) {
+            PreloaderState::Loaded if self.is_expired() => Err(PreloaderError::Expired),
+            PreloaderState::Loaded => Ok(self.get_value()),
+            PreloaderState::Failed => Err(self.get_error()),
+            PreloaderState::Poisoned => Err(PreloaderError::Poisoned),
+            PreloaderState::Idle => Err(PreloaderError::NotLoaded),
+            PreloaderState::Start | PreloaderState::Loading => Err(PreloaderError::Loading),
+        }
+    }
+
+    /// Stores the value and changes the state to `Loaded`.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: The value to store
+    #[inline]
+    fn set_value(&self, value: T) {
+        self.set_value_at(value, Instant::now());
+    }
+
+    /// Stores a value that finished loading at the given time and changes the state to `Loaded`.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: The value to store
+    /// - `finished_at`: Time at which loading finished
+    #[inline]
+    fn set_value_at(&self, value: T, finished_at: Instant) {
+        _ = self.loaded_at.set(finished_at);
+        unsafe { self.cell.publish(value) };
+        self.wake_pollers();
+    }
+
+    /// Records the error and changes the state according to the failure mode.
+    ///
+    /// # Parameters
+    ///
+    /// - `error`: The error that ended loading
+    ///
+    /// # Returns
+    ///
+    /// The recorded error
+    fn set_error(&self, error: PreloaderError) -> PreloaderError {
+        *self.error.lock().unwrap() = Some((error.clone(), Instant::now()));
+        let state = match self.options.failure_mode {
+            FailureMode::Failed => PreloaderState::Failed,
+            FailureMode::Idle => PreloaderState::Idle,
+            FailureMode::Poisoned => PreloaderState::Poisoned,
+        };
+        self.cell.store(state, Ordering::Release);
+        self.wake_pollers();
+        error
+    }
+
+    /// Returns the recorded error, read after observing the `Failed` state.
+    ///
+    /// A retry may claim the preloader and clear the error between reading the state and the error, in which
+    /// case it is loading again and `PreloaderError::Loading` is returned.
+    fn get_error(&self) -> PreloaderError {
+        self.error
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(PreloaderError::Loading, |(error, _)| error.clone())
+    }
+
+    /// Checks if a recorded error should be discarded in favor of a fresh attempt, according to the error policy.
+    ///
+    /// Always `false` without a stored loader factory, since there is nothing to retry with.
+    fn should_retry_error(&self) -> bool {
+        if self.loader.is_none() {
+            return false;
+        }
+        let error = self.error.lock().unwrap();
+        let policy = match (&self.options.error_classifier, error.as_ref()) {
+            (Some(classifier), Some((error, _))) => classifier.classify(error),
+            _ => self.options.error_policy,
+        };
+        match policy {
+            ErrorPolicy::Cache => false,
+            ErrorPolicy::Retry => true,
+            ErrorPolicy::CacheFor(ttl) => match error.as_ref() {
+                Some((_, failed_at)) => failed_at.elapsed() >= ttl,
+                None => false,
+            },
+        }
+    }
+
+    /// Takes the stored value, leaving None in its place.
+    ///
+    /// # Returns
+    ///
+    /// Result containing the stored value or an error if the value is None
+    ///
+    /// # Safety
+    ///
+    /// This method should only be called when the value is guaranteed to exist.
+    #[inline]
+    fn take_value(mut self) -> Result<T> {
+        self.cell.take().ok_or(PreloaderError::NotLoaded)
+    }
+
+    /// Checks if the preloader has completed loading and data is available.
+    ///
+    /// This method returns true if the preloader is in the `Loaded` state,
+    /// indicating that data is ready to be accessed without blocking.
+    ///
+    /// # Returns
+    ///
+    /// - `true`: If the data is loaded and ready for immediate access
+    /// - `false`: If the data is not loaded yet or is still loading
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
     ///     
     ///     // Check before loading
     ///     assert!(!preloader.is_loaded());
@@ -442,4 +3031,313 @@ impl<T: Send + 'static> Preloader<T> {
     pub fn is_loaded(&self) -> bool {
         self.try_get().is_ok()
     }
+
+    /// Returns the current state of the preloader.
+    ///
+    /// A load that has finished is reported as `Loaded` or `Failed` even if no caller has retrieved its result yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Preloader, PreloaderState};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::new();
+    ///     assert_eq!(preloader.state(), PreloaderState::Idle);
+    ///
+    ///     preloader.load(async { 42 }).await;
+    ///     preloader.get().await.unwrap();
+    ///     assert_eq!(preloader.state(), PreloaderState::Loaded);
+    /// }
+    /// ```
+    pub fn state(&self) -> PreloaderState {
+        if self.cell.load(Ordering::Acquire) == PreloaderState::Loading {
+            _ = self.try_get();
+        }
+        self.cell.load(Ordering::Acquire)
+    }
+
+    /// Checks if loading has not been started yet.
+    pub fn is_idle(&self) -> bool {
+        self.state() == PreloaderState::Idle
+    }
+
+    /// Checks if loading has been started and has not finished yet.
+    pub fn is_loading(&self) -> bool {
+        matches!(
+            self.state(),
+            PreloaderState::Start | PreloaderState::Loading
+        )
+    }
+
+    /// Checks if loading finished with an error, including a poisoned preloader.
+    pub fn is_failed(&self) -> bool {
+        matches!(
+            self.state(),
+            PreloaderState::Failed | PreloaderState::Poisoned
+        )
+    }
+}
+
+/// Awaiting a reference to a preloader is equivalent to calling [`Preloader::get`].
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::Preloader;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let preloader = Preloader::new();
+///     preloader.load(async { "data".to_string() }).await;
+///
+///     let data = (&preloader).await.unwrap();
+///     assert_eq!(data, "data");
+/// }
+/// ```
+impl<'a, T: Send + Sync + 'static> IntoFuture for &'a Preloader<T> {
+    type Output = Result<&'a T>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Result<&'a T>> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.get())
+    }
+}
+
+/// Shows the state, timing, and recorded error without requiring `T: Debug`.
+///
+/// The loaded value itself is never printed; use `try_get()` to inspect it.
+impl<T: Send + 'static> fmt::Debug for Preloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.cell.load(Ordering::Acquire);
+        let mut debug = f.debug_struct("Preloader");
+        if let Some(name) = self.name() {
+            debug.field("name", &name);
+        }
+        debug
+            .field("state", &state)
+            .field("has_value", &(state == PreloaderState::Loaded))
+            .field("load_duration", &self.load_duration())
+            .field("age", &self.age());
+        if let Some((error, _)) = self.error.lock().unwrap().as_ref() {
+            debug.field("error", error);
+        }
+        debug.finish_non_exhaustive()
+    }
+}
+
+impl<T: Send + 'static> Drop for Preloader<T> {
+    fn drop(&mut self) {
+        if self.options.abort_on_drop {
+            if let Some(task) = self.task.get_mut().unwrap() {
+                task.abort();
+            }
+        }
+    }
+}
+
+/// Runs the validation callbacks on `value` one after the other, stopping at the first error.
+///
+/// The callbacks are called right away, so the returned future does not borrow `value`.
+fn validation<T>(
+    validators: &[Validator<T>],
+    value: &T,
+) -> impl Future<Output = Result<()>> + Send + 'static {
+    let checks: Vec<_> = validators.iter().map(|validate| validate(value)).collect();
+    async move {
+        for check in checks {
+            check.await.map_err(PreloaderError::Failed)?;
+        }
+        Ok(())
+    }
+}
+
+/// Polls `task` once without a task context to wake, returning whether it finished.
+///
+/// A pending task is polled again once spawned, which registers the waker of the spawned task instead.
+fn poll_once(task: Pin<&mut (dyn Future<Output = ()> + Send)>) -> bool {
+    let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+    task.poll(&mut cx).is_ready()
+}
+
+/// Runs a single load attempt, bounded by the timeout if one is configured.
+///
+/// # Parameters
+///
+/// - `future`: The asynchronous task to execute
+/// - `timeout`: Maximum duration of the attempt
+async fn attempt<T>(future: impl Future<Output = T>, timeout: Option<Duration>) -> Result<T> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| PreloaderError::Timeout),
+        None => Ok(future.await),
+    }
+}
+
+/// Wakers of the `poll_get()` callers of a preloader
+///
+/// As a [`Wake`] implementation it wakes every registered caller, so the receiver of the loading task can be
+/// polled on behalf of all of them.
+#[derive(Default)]
+struct Pollers(std::sync::Mutex<Vec<Waker>>);
+
+impl Pollers {
+    /// Adds `waker` unless an equivalent one is already registered.
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.0.lock().unwrap();
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+
+    /// Wakes and removes every registered waker.
+    fn wake_all(&self) {
+        let wakers = std::mem::take(&mut *self.0.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl Wake for Pollers {
+    fn wake(self: Arc<Self>) {
+        self.wake_all();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wake_all();
+    }
+}
+
+/// Guard waking the `poll_get()` callers when dropped
+#[cfg(not(feature = "std-sync"))]
+struct WakeOnDrop<'a>(&'a Pollers);
+
+#[cfg(not(feature = "std-sync"))]
+impl Drop for WakeOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.wake_all();
+    }
+}
+
+/// Guard moving a preloader claimed by `get_or_try_load` back to `Idle` unless initialization succeeds
+struct Release<'a, T: Send + 'static>(&'a Preloader<T>);
+
+impl<T: Send + 'static> Drop for Release<'_, T> {
+    fn drop(&mut self) {
+        self.0.cell.store(PreloaderState::Idle, Ordering::Release);
+        self.0.notify_state();
+    }
+}
+
+/// Outcome of a load attempt, with panics captured
+type Outcome<T> = std::thread::Result<Result<T>>;
+
+/// Completion reported when the loading task was dropped before sending its result.
+fn cancelled<T>() -> Completion<T> {
+    (Err(PreloaderError::Cancelled), Instant::now())
+}
+
+/// Converts a captured panic payload into `PreloaderError::Panicked` carrying the panic message.
+///
+/// # Parameters
+///
+/// - `payload`: The payload passed to `panic!`
+pub(crate) fn panicked(payload: Box<dyn std::any::Any + Send>) -> PreloaderError {
+    let message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Box<dyn Any>".to_string(),
+        },
+    };
+    PreloaderError::Panicked(message)
+}
+
+/// Creates a load attempt from the factory, capturing panics raised by the factory or the attempt.
+///
+/// # Parameters
+///
+/// - `factory`: Function creating the asynchronous task
+/// - `timeout`: Maximum duration of the attempt
+fn guarded_attempt<T, F, Fut>(
+    factory: &mut F,
+    timeout: Option<Duration>,
+) -> impl Future<Output = Outcome<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    let future = std::panic::catch_unwind(AssertUnwindSafe(factory));
+    async move {
+        match future {
+            Ok(future) => {
+                AssertUnwindSafe(attempt(future, timeout))
+                    .catch_unwind()
+                    .await
+            }
+            Err(payload) => Err(payload),
+        }
+    }
+}
+
+/// Runs a load attempt, starting a second one if the first has not finished within the hedging delay.
+///
+/// Whichever attempt succeeds first provides the value. If the first attempt to finish fails,
+/// the outcome of the other one is used.
+///
+/// # Parameters
+///
+/// - `factory`: Function creating the asynchronous task
+/// - `timeout`: Maximum duration of each attempt
+/// - `hedge`: Delay after which the second attempt is started
+async fn hedged_attempt<T, F, Fut>(
+    factory: &mut F,
+    timeout: Option<Duration>,
+    hedge: Option<Duration>,
+) -> Outcome<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    let primary = guarded_attempt(factory, timeout);
+    let Some(delay) = hedge else {
+        return primary.await;
+    };
+
+    tokio::pin!(primary);
+    tokio::select! {
+        outcome = &mut primary => return outcome,
+        _ = tokio::time::sleep(delay) => {}
+    }
+
+    let secondary = guarded_attempt(factory, timeout);
+    tokio::pin!(secondary);
+    tokio::select! {
+        outcome = &mut primary => match outcome {
+            Ok(Ok(value)) => Ok(Ok(value)),
+            _ => secondary.await,
+        },
+        outcome = &mut secondary => match outcome {
+            Ok(Ok(value)) => Ok(Ok(value)),
+            _ => primary.await,
+        },
+    }
+}
+
+impl<T: Send + 'static> From<T> for Preloader<T> {
+    /// Equivalent to [`Preloader::ready`].
+    fn from(value: T) -> Self {
+        Self::ready(value)
+    }
+}
+
+impl<T: Send + 'static> Default for Preloader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -0,0 +1,60 @@
+//! Prometheus exporter module
+//!
+//! This module installs a Prometheus recorder for the metrics recorded with the `metrics` feature:
+//! the `preloader_load_duration_seconds` and `preloader_wait_duration_seconds` histograms, the
+//! `preloader_failures_total` counter, and the `preloader_waiters` gauge, all labeled `preloader` with the name
+//! set through the builder.
+
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+
+/// Upper bounds in seconds of the buckets of the exported duration histograms
+pub const PROMETHEUS_BUCKETS: [f64; 12] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0, 10.0, 60.0,
+];
+
+/// Returns a `PrometheusBuilder` exporting the preloader duration metrics as histograms with
+/// [`PROMETHEUS_BUCKETS`] instead of summaries.
+///
+/// Use it to add an HTTP listener, global labels, or buckets for other metrics before installing the recorder.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{prometheus_builder, Preloader};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let recorder = prometheus_builder().build_recorder();
+///     let handle = recorder.handle();
+///     let _guard = metrics::set_default_local_recorder(&recorder);
+///
+///     let config = Preloader::builder().name("config").build();
+///     config.load(async { "debug=true" }).await;
+///     config.get().await.unwrap();
+///
+///     assert!(handle
+///         .render()
+///         .contains(r#"preloader_load_duration_seconds_count{preloader="config"} 1"#));
+/// }
+/// ```
+pub fn prometheus_builder() -> PrometheusBuilder {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Prefix("preloader_".to_string()),
+            &PROMETHEUS_BUCKETS,
+        )
+        .expect("buckets are not empty")
+}
+
+/// Installs a Prometheus recorder built with [`prometheus_builder`] as the global `metrics` recorder.
+///
+/// The returned handle renders the current metrics in the Prometheus text format, to be served from a
+/// `/metrics` route.
+///
+/// # Errors
+///
+/// Returns an error if a global recorder is already installed.
+pub fn install_prometheus() -> Result<PrometheusHandle, BuildError> {
+    prometheus_builder().install_recorder()
+}
@@ -0,0 +1,104 @@
+//! Rate limiter module
+//!
+//! This module provides the `RateLimiter` struct, a token bucket limiting how many loads may start per second.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::{sleep, Instant};
+
+/// Token bucket state
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens; negative when starts have been reserved ahead of time
+    tokens: f64,
+    /// Time of the last refill
+    refilled_at: Instant,
+}
+
+/// Token-bucket rate limiter for load starts
+///
+/// The bucket holds up to `burst` tokens and refills at `rate` tokens per second.
+/// Every start takes one token; when none is available, [`acquire`](Self::acquire) waits for its turn.
+/// Waiting callers are served in the order they called `acquire()`.
+///
+/// The limiter can be attached to a [`PreloaderPool`](crate::PreloaderPool) or used directly inside a loader.
+/// Cloning a limiter is cheap; clones share the same bucket.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{Preloader, RateLimiter};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let limiter = RateLimiter::per_second(100);
+///     let preloader = Preloader::new();
+///
+///     let limit = limiter.clone();
+///     preloader.load(async move {
+///         limit.acquire().await;
+///         "response".to_string()
+///     }).await;
+///     assert_eq!(preloader.get().await.unwrap(), "response");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `rate` starts per second, with a burst of one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero.
+    pub fn per_second(rate: u32) -> Self {
+        assert!(rate > 0, "rate must be positive");
+        Self {
+            rate: rate as f64,
+            burst: 1.0,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: 1.0,
+                refilled_at: Instant::now(),
+            })),
+        }
+    }
+
+    /// Sets how many starts may happen back to back before the rate applies.
+    ///
+    /// The bucket starts full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        assert!(burst > 0, "burst must be positive");
+        self.burst = burst as f64;
+        self.bucket.lock().unwrap().tokens = self.burst;
+        self
+    }
+
+    /// Waits until a start is allowed.
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap();
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+            bucket.refilled_at = now;
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        };
+        sleep(wait).await;
+    }
+}
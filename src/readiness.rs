@@ -0,0 +1,124 @@
+//! Readiness module
+//!
+//! This module provides the `Readiness` trait, a type-erased view of whether a preloader or a whole group
+//! has been loaded. Server integrations use it to gate requests and to answer readiness probes, and
+//! [`wait_all`] uses it to wait on a mixed set of differently-typed preloaders.
+
+use futures::future::{self, BoxFuture};
+
+use crate::{ArcPreloader, Preloader, PreloaderError, PreloaderGroup};
+
+/// Something that becomes ready once its values have been loaded
+///
+/// Implemented for [`Preloader`], [`ArcPreloader`], and [`PreloaderGroup`].
+pub trait Readiness: Send + Sync + 'static {
+    /// Checks if the values are loaded.
+    fn is_ready(&self) -> bool;
+
+    /// Waits until loading has been started and has finished.
+    ///
+    /// # Returns
+    ///
+    /// - `true`: If the values were loaded
+    /// - `false`: If loading failed
+    fn wait_ready(&self) -> BoxFuture<'_, bool>;
+
+    /// Waits until loading has been started and has finished, returning the error if it failed.
+    ///
+    /// The default implementation reports a failure of [`wait_ready`](Self::wait_ready) as `PreloaderError::Failed`.
+    fn wait_settled(&self) -> BoxFuture<'_, Result<(), PreloaderError>> {
+        Box::pin(async move {
+            match self.wait_ready().await {
+                true => Ok(()),
+                false => Err(PreloaderError::Failed("not ready".to_string())),
+            }
+        })
+    }
+}
+
+/// Waits until every preloader in `preloaders` has finished loading, whatever their value types.
+///
+/// All preloaders are awaited concurrently, and like [`Readiness::wait_ready`], one that is still `Idle` is waited
+/// on until its loading is started.
+///
+/// # Returns
+///
+/// - `Ok(())`: If every preloader was loaded
+/// - `Err(Vec<(usize, PreloaderError)>)`: The index in `preloaders` and the error of each one that failed
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{wait_all, ArcPreloader, Preloader, PreloaderError};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let users = Preloader::spawn(async { vec!["alice", "bob"] });
+///     let motd = ArcPreloader::spawn(async { "hello".to_string() });
+///     let prices: Preloader<f64> = Preloader::spawn(async { panic!("price feed is down") });
+///
+///     let failed = wait_all(&[&users, &motd, &prices]).await.unwrap_err();
+///     assert_eq!(failed.len(), 1);
+///     assert!(matches!(failed[0], (2, PreloaderError::Panicked(_))));
+/// }
+/// ```
+pub async fn wait_all(preloaders: &[&dyn Readiness]) -> Result<(), Vec<(usize, PreloaderError)>> {
+    let failed: Vec<_> =
+        future::join_all(preloaders.iter().map(|preloader| preloader.wait_settled()))
+            .await
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, result)| Some((index, result.err()?)))
+            .collect();
+    match failed.is_empty() {
+        true => Ok(()),
+        false => Err(failed),
+    }
+}
+
+impl<T: Send + Sync + 'static> Readiness for Preloader<T> {
+    fn is_ready(&self) -> bool {
+        self.try_get().is_ok()
+    }
+
+    fn wait_ready(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async move { self.resolve(true).await.is_ok() })
+    }
+
+    fn wait_settled(&self) -> BoxFuture<'_, Result<(), PreloaderError>> {
+        Box::pin(async move { self.resolve(true).await.map(drop) })
+    }
+}
+
+impl<T: Send + Sync + 'static> Readiness for ArcPreloader<T> {
+    fn is_ready(&self) -> bool {
+        self.try_get().is_ok()
+    }
+
+    fn wait_ready(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async move { self.resolve(true).await.is_ok() })
+    }
+
+    fn wait_settled(&self) -> BoxFuture<'_, Result<(), PreloaderError>> {
+        Box::pin(async move { self.resolve(true).await.map(drop) })
+    }
+}
+
+impl Readiness for PreloaderGroup {
+    fn is_ready(&self) -> bool {
+        PreloaderGroup::is_ready(self)
+    }
+
+    fn wait_ready(&self) -> BoxFuture<'_, bool> {
+        Box::pin(self.ready())
+    }
+
+    fn wait_settled(&self) -> BoxFuture<'_, Result<(), PreloaderError>> {
+        Box::pin(async move {
+            self.wait_all()
+                .await
+                .map_err(|error| PreloaderError::Failed(error.to_string()))
+        })
+    }
+}
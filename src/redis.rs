@@ -0,0 +1,319 @@
+//! Redis tier module
+//!
+//! This module provides the `RedisTier` struct, a shared second tier behind the local memory of a
+//! `PreloaderCache`: a missing key is looked up in Redis before the loader runs, and loaded values are written
+//! back to Redis, so the instances of a service share their loads. With singleflight enabled, a Redis lock lets a
+//! single instance load a missing key while the others wait for its result.
+
+use std::{
+    fmt,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
+
+use ::redis::{aio::MultiplexedConnection, AsyncCommands, Client, RedisResult, Script};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::OnceCell;
+
+use crate::PreloaderCache;
+
+/// Interval at which an instance waiting for another instance's load checks for its result
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Deletes a lock only if it is still held with the given token, so an expired lease never releases another
+/// instance's lock
+const RELEASE: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+/// Redis tier of a [`PreloaderCache`]
+///
+/// Values are stored as JSON under the cache key prefixed with the tier's [prefix](Self::with_prefix). Like the
+/// disk cache, the tier is best effort: if Redis cannot be reached, times out, or holds an unreadable value, the
+/// loader runs and the value is still used. The connection is opened on first use and shared by every key.
+///
+/// Cloning a tier is cheap; clones share the connection.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use preloader::{PreloaderCache, RedisTier};
+/// use std::time::Duration;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let redis = redis::Client::open("redis://127.0.0.1/").unwrap();
+///     let tier = RedisTier::new(redis)
+///         .with_prefix("users:")
+///         .with_ttl(Duration::from_secs(600))
+///         // A single instance loads a missing user at a time
+///         .with_singleflight(Duration::from_secs(30));
+///
+///     // Local memory, then Redis, then the loader
+///     let users = PreloaderCache::new(|id: &u64| {
+///         let id = *id;
+///         async move { format!("user-{}", id) }
+///     })
+///     .with_redis(tier);
+///     users.get(&7).await.unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RedisTier {
+    client: Client,
+    /// Connection shared by every lookup, opened on first use
+    connection: Arc<OnceCell<MultiplexedConnection>>,
+    prefix: Arc<str>,
+    ttl: Option<Duration>,
+    timeout: Option<Duration>,
+    /// Lease of the lock taken to load a missing key, if singleflight is enabled
+    lease: Option<Duration>,
+}
+
+impl RedisTier {
+    /// Creates a tier storing values through `client`, without a key prefix or expiration.
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            connection: Arc::default(),
+            prefix: Arc::from(""),
+            ttl: None,
+            timeout: None,
+            lease: None,
+        }
+    }
+
+    /// Prefixes every Redis key, so several caches can share a Redis database.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Arc::from(prefix.into());
+        self
+    }
+
+    /// Lets Redis expire written values after `ttl`, rounded down to whole seconds and at least one second.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Gives up on a Redis command after `timeout`, falling back to the loader for reads.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Lets a single instance load a missing key at a time, across every instance sharing the Redis database.
+    ///
+    /// Before loading a key missing from Redis, an instance takes a lock on it for `lease`. The other instances
+    /// wait for the value it writes instead of running the loader too, preventing a stampede on the source when
+    /// a popular key expires. A waiting instance loads the key itself if the lock is released or expires without
+    /// a value, for example because its owner crashed or its load failed. Choose a lease longer than a load.
+    ///
+    /// # Parameters
+    ///
+    /// - `lease`: How long the lock is held at most, rounded up to whole milliseconds
+    pub fn with_singleflight(mut self, lease: Duration) -> Self {
+        self.lease = Some(lease);
+        self
+    }
+
+    /// Returns the Redis key storing the value of `key`.
+    pub fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// Reads the value of `key` from Redis, or awaits the future created by `loader` and writes its value back.
+    pub(crate) async fn fetch<T, F, Fut>(self, key: String, loader: F) -> T
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let key = self.key(&key);
+        if let Some(value) = self.read(&key).await {
+            return value;
+        }
+
+        let Some(lease) = self.lease else {
+            let value = loader().await;
+            self.write(&key, &value).await;
+            return value;
+        };
+
+        let lock = format!("{}:lock", key);
+        let token = token();
+        match self.lock(&lock, &token, lease).await {
+            Some(true) => {
+                let value = loader().await;
+                self.write(&key, &value).await;
+                self.unlock(&lock, &token).await;
+                value
+            }
+            Some(false) => match self.wait(&key, &lock, lease).await {
+                Some(value) => value,
+                None => {
+                    let value = loader().await;
+                    self.write(&key, &value).await;
+                    value
+                }
+            },
+            // Redis failed, so load without coordination
+            None => loader().await,
+        }
+    }
+
+    /// Takes the lock `lock` with `token` for `lease`, returning whether it was free, or `None` if Redis failed.
+    async fn lock(&self, lock: &str, token: &str, lease: Duration) -> Option<bool> {
+        let millis = lease.as_nanos().div_ceil(1_000_000).max(1) as u64;
+        let reply: Option<String> = self
+            .command(|mut connection| async move {
+                ::redis::cmd("SET")
+                    .arg(lock)
+                    .arg(token)
+                    .arg("NX")
+                    .arg("PX")
+                    .arg(millis)
+                    .query_async(&mut connection)
+                    .await
+            })
+            .await?;
+        Some(reply.is_some())
+    }
+
+    /// Releases the lock `lock` if it is still held with `token`.
+    async fn unlock(&self, lock: &str, token: &str) {
+        let _: Option<i64> = self
+            .command(|mut connection| async move {
+                Script::new(RELEASE)
+                    .key(lock)
+                    .arg(token)
+                    .invoke_async(&mut connection)
+                    .await
+            })
+            .await;
+    }
+
+    /// Waits up to `lease` for the owner of `lock` to write the value of `key`.
+    ///
+    /// Returns `None` once the lock is gone without a value, when `lease` has elapsed, or if Redis failed.
+    async fn wait<T: DeserializeOwned>(&self, key: &str, lock: &str, lease: Duration) -> Option<T> {
+        let deadline = tokio::time::Instant::now() + lease;
+        while tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if let Some(value) = self.read(key).await {
+                return Some(value);
+            }
+            let locked: bool = self
+                .command(|mut connection| async move { connection.exists(lock).await })
+                .await?;
+            if !locked {
+                // The lock may have been released right after the value was written
+                return self.read(key).await;
+            }
+        }
+        None
+    }
+
+    /// Reads and deserializes a stored value, returning `None` if it is missing, unreadable, or Redis failed.
+    async fn read<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let bytes: Option<Vec<u8>> = self
+            .command(|mut connection| async move { connection.get(key).await })
+            .await?;
+        serde_json::from_slice(&bytes?).ok()
+    }
+
+    /// Serializes and stores a value, ignoring failures.
+    async fn write<T: Serialize>(&self, key: &str, value: &T) {
+        let Ok(bytes) = serde_json::to_vec(value) else {
+            return;
+        };
+        let ttl = self.ttl.map(|ttl| ttl.as_secs().max(1));
+        let _: Option<()> = self
+            .command(|mut connection| async move {
+                match ttl {
+                    Some(seconds) => connection.set_ex(key, bytes, seconds).await,
+                    None => connection.set(key, bytes).await,
+                }
+            })
+            .await;
+    }
+
+    /// Runs a command on the shared connection, returning `None` if it failed or timed out.
+    async fn command<T, F, Fut>(&self, command: F) -> Option<T>
+    where
+        F: FnOnce(MultiplexedConnection) -> Fut,
+        Fut: Future<Output = RedisResult<T>>,
+    {
+        let run = async {
+            // A failed connection attempt is retried by the next command
+            let connection = self
+                .connection
+                .get_or_try_init(|| self.client.get_multiplexed_async_connection())
+                .await
+                .ok()?;
+            command(connection.clone()).await.ok()
+        };
+        match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, run).await.ok()?,
+            None => run.await,
+        }
+    }
+}
+
+impl fmt::Debug for RedisTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisTier")
+            .field("prefix", &self.prefix)
+            .field("ttl", &self.ttl)
+            .field("timeout", &self.timeout)
+            .field("lease", &self.lease)
+            .field("connected", &self.connection.initialized())
+            .finish()
+    }
+}
+
+impl<K, V> PreloaderCache<K, V>
+where
+    K: Eq + std::hash::Hash + Clone + ToString + Send + Sync + 'static,
+    V: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    /// Backs this cache with a shared Redis tier, using `key.to_string()` as the Redis key.
+    ///
+    /// A key missing from local memory is looked up in Redis before the loader runs, and a loaded value is
+    /// written to Redis as well as kept locally. Requires the `redis` feature.
+    ///
+    /// # Parameters
+    ///
+    /// - `tier`: The Redis tier storing the values
+    pub fn with_redis(mut self, tier: RedisTier) -> Self {
+        let loader = self.loader;
+        self.loader = Arc::new(move |key: &K| {
+            let loader = Arc::clone(&loader);
+            let owned = key.clone();
+            Box::pin(tier.clone().fetch(key.to_string(), move || loader(&owned)))
+        });
+        self
+    }
+}
+
+/// Returns a token identifying one lock holder among every instance and load.
+fn token() -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    format!(
+        "{}-{}-{}",
+        std::process::id(),
+        nanos,
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    )
+}
@@ -0,0 +1,192 @@
+//! Registry module
+//!
+//! This module provides an opt-in, process-wide registry of named preloaders. [`snapshot`] reports the state,
+//! timing, and last error of every registered preloader, which answers "what is the application still waiting
+//! on?" during a slow startup.
+//!
+//! The registry only holds weak references, so registering a preloader does not keep it alive;
+//! dropped preloaders disappear from later snapshots.
+//!
+//! # Example
+//!
+//! ```rust
+//! use preloader::{registry, Preloader, PreloaderState};
+//! use std::sync::Arc;
+//! use tokio;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let users = Arc::new(Preloader::new());
+//!     let catalog: Arc<Preloader<Vec<u32>>> = Arc::new(Preloader::new());
+//!     registry::register("users", &users);
+//!     registry::register("catalog", &catalog);
+//!
+//!     users.load(async { vec!["alice"] }).await;
+//!     users.get().await.unwrap();
+//!
+//!     assert_eq!(registry::pending(), ["catalog"]);
+//!     for report in registry::snapshot() {
+//!         println!("{}: {:?} after {:?}", report.name, report.state, report.elapsed);
+//!     }
+//! }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use crate::{ArcPreloader, Preloader, PreloaderError, PreloaderState, WaitStats};
+
+/// Registered preloaders by name
+static REGISTRY: Mutex<BTreeMap<String, Weak<dyn Inspect>>> = Mutex::new(BTreeMap::new());
+
+/// State of a registered preloader at the time of a [`snapshot`]
+#[derive(Debug, Clone)]
+pub struct PreloaderReport {
+    /// Name the preloader was registered under
+    pub name: String,
+    /// Current state
+    pub state: PreloaderState,
+    /// Time the latest load was started
+    pub started_at: Option<Instant>,
+    /// Time spent loading so far, or the total load duration once loaded
+    pub elapsed: Option<Duration>,
+    /// Error of the latest failed load
+    pub last_error: Option<PreloaderError>,
+    /// Callers blocked in `get()` and the distribution of their wait times
+    pub waits: WaitStats,
+    /// Time at which the next scheduled refresh starts, including jitter
+    pub next_refresh: Option<Instant>,
+}
+
+impl PreloaderReport {
+    /// Checks if the preloader has not finished loading successfully.
+    pub fn is_pending(&self) -> bool {
+        self.state != PreloaderState::Loaded
+    }
+}
+
+/// Preloader that can be reported on by the registry
+///
+/// Implemented for [`Preloader`] and [`ArcPreloader`].
+pub trait Inspect: Send + Sync + 'static {
+    /// Reports the current state under `name`.
+    fn report(&self, name: String) -> PreloaderReport;
+
+    /// Returns the name set through the builder, used by [`register_named`].
+    fn name(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl<T: Send + Sync + 'static> Inspect for Preloader<T> {
+    fn report(&self, name: String) -> PreloaderReport {
+        let started_at = self.started_at();
+        PreloaderReport {
+            name,
+            state: self.state(),
+            started_at,
+            elapsed: self
+                .load_duration()
+                .or_else(|| started_at.map(|started_at| started_at.elapsed())),
+            last_error: self.last_error(),
+            waits: self.wait_stats(),
+            next_refresh: None,
+        }
+    }
+
+    fn name(&self) -> Option<&str> {
+        Preloader::name(self)
+    }
+}
+
+impl<T: Send + Sync + 'static> Inspect for ArcPreloader<T> {
+    fn report(&self, name: String) -> PreloaderReport {
+        let started_at = self.started_at();
+        PreloaderReport {
+            name,
+            state: self.state(),
+            started_at,
+            elapsed: self
+                .load_duration()
+                .or_else(|| started_at.map(|started_at| started_at.elapsed())),
+            last_error: self.last_error(),
+            waits: self.wait_stats(),
+            next_refresh: self.next_refresh(),
+        }
+    }
+
+    fn name(&self) -> Option<&str> {
+        ArcPreloader::name(self)
+    }
+}
+
+/// Registers `preloader` under `name`, replacing any preloader registered under the same name.
+///
+/// # Parameters
+///
+/// - `name`: Name shown in snapshots
+/// - `preloader`: The preloader to report on; only a weak reference is kept
+pub fn register<P: Inspect>(name: impl Into<String>, preloader: &Arc<P>) {
+    let preloader: Arc<dyn Inspect> = Arc::clone(preloader) as Arc<dyn Inspect>;
+    REGISTRY
+        .lock()
+        .unwrap()
+        .insert(name.into(), Arc::downgrade(&preloader));
+}
+
+/// Registers `preloader` under the name it was given through [`PreloaderBuilder::name`](crate::PreloaderBuilder::name).
+///
+/// # Returns
+///
+/// `true` if the preloader was registered, `false` if it has no name
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{registry, Preloader};
+/// use std::sync::Arc;
+///
+/// let catalog = Arc::new(Preloader::<Vec<u32>>::builder().name("catalog").build());
+/// assert!(registry::register_named(&catalog));
+/// assert!(registry::pending().contains(&"catalog".to_string()));
+/// ```
+pub fn register_named<P: Inspect>(preloader: &Arc<P>) -> bool {
+    match preloader.name() {
+        Some(name) => {
+            register(name.to_string(), preloader);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Removes the preloader registered under `name`.
+///
+/// # Returns
+///
+/// `true` if a preloader was registered under the name
+pub fn unregister(name: &str) -> bool {
+    REGISTRY.lock().unwrap().remove(name).is_some()
+}
+
+/// Reports on every registered preloader that is still alive, ordered by name.
+pub fn snapshot() -> Vec<PreloaderReport> {
+    let mut registry = REGISTRY.lock().unwrap();
+    registry.retain(|_, preloader| preloader.strong_count() > 0);
+    registry
+        .iter()
+        .filter_map(|(name, preloader)| Some(preloader.upgrade()?.report(name.clone())))
+        .collect()
+}
+
+/// Returns the names of registered preloaders that have not finished loading successfully, ordered by name.
+pub fn pending() -> Vec<String> {
+    snapshot()
+        .into_iter()
+        .filter(PreloaderReport::is_pending)
+        .map(|report| report.name)
+        .collect()
+}
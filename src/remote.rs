@@ -0,0 +1,119 @@
+//! Object store module
+//!
+//! This module writes snapshots of a warm `PreloaderCache` to an object store such as S3, GCS, or Azure Blob
+//! Storage through the `object_store` crate, and restores them, so new instances in an autoscaling group start
+//! with the keys their peers already loaded instead of loading everything cold.
+
+use std::hash::Hash;
+
+use object_store::{path::Path, ObjectStore, PutPayload};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::PreloaderCache;
+
+/// Name reported in the errors raised while encoding or decoding a snapshot
+const STORE: &str = "preloader";
+
+impl<K, V> PreloaderCache<K, V>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    /// Writes every loaded key with its value to `location` in `store`, replacing any previous snapshot.
+    ///
+    /// The snapshot is the JSON form of [`persist`](Self::persist). Requires the `object-store` feature; enable
+    /// the backend you need (such as `aws` or `gcp`) on your own `object_store` dependency.
+    ///
+    /// # Parameters
+    ///
+    /// - `store`: The object store holding the snapshot
+    /// - `location`: The path of the snapshot in the store
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the snapshot was written
+    /// - `Err(object_store::Error)`: If serialization or the upload failed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use object_store::{memory::InMemory, path::Path};
+    /// use preloader::PreloaderCache;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let store = InMemory::new();
+    ///     let location = Path::from("caches/users.json");
+    ///     let users = PreloaderCache::new(|id: &u32| {
+    ///         let id = *id;
+    ///         async move { format!("user-{}", id) }
+    ///     });
+    ///     users.get(&7).await.unwrap();
+    ///     users.persist_to_store(&store, &location).await.unwrap();
+    ///
+    ///     // On a new instance
+    ///     let users = PreloaderCache::new(|id: &u32| {
+    ///         let id = *id;
+    ///         async move { format!("user-{}", id) }
+    ///     });
+    ///     assert_eq!(users.restore_from_store(&store, &location).await.unwrap(), 1);
+    ///     assert_eq!(*users.try_get(&7).unwrap(), "user-7");
+    /// }
+    /// ```
+    pub async fn persist_to_store(
+        &self,
+        store: &dyn ObjectStore,
+        location: &Path,
+    ) -> Result<(), object_store::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        let mut snapshot = Vec::new();
+        self.persist(&mut serde_json::Serializer::new(&mut snapshot))
+            .map_err(generic)?;
+        store.put(location, PutPayload::from(snapshot)).await?;
+        Ok(())
+    }
+
+    /// Reads the snapshot at `location` in `store` and stores its entries, skipping the loader.
+    ///
+    /// Entries are restored like with [`restore`](Self::restore). A missing snapshot, as met by the first instance
+    /// of a deployment, restores nothing and is not an error.
+    ///
+    /// # Parameters
+    ///
+    /// - `store`: The object store holding the snapshot
+    /// - `location`: The path of the snapshot in the store
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(usize)`: The number of restored keys
+    /// - `Err(object_store::Error)`: If the download or deserialization failed, in which case nothing is restored
+    pub async fn restore_from_store(
+        &self,
+        store: &dyn ObjectStore,
+        location: &Path,
+    ) -> Result<usize, object_store::Error>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let snapshot = match store.get(location).await {
+            Ok(snapshot) => snapshot.bytes().await?,
+            Err(object_store::Error::NotFound { .. }) => return Ok(0),
+            Err(error) => return Err(error),
+        };
+        self.restore(&mut serde_json::Deserializer::from_slice(&snapshot))
+            .map_err(generic)
+    }
+}
+
+/// Wraps a serialization error into an object store error.
+fn generic(error: serde_json::Error) -> object_store::Error {
+    object_store::Error::Generic {
+        store: STORE,
+        source: Box::new(error),
+    }
+}
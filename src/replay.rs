@@ -0,0 +1,211 @@
+//! Record/replay module
+//!
+//! This module provides `LoadRecorder`, which saves the values produced by loaders to JSON files during a
+//! recording run and returns them on later runs without executing the loaders. Integration tests of startup logic
+//! then run quickly and deterministically against the recorded values instead of the real databases and services.
+
+use std::{
+    env, fmt,
+    future::Future,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{ArcPreloader, LoadHandle, Preloader, PreloaderError};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Environment variable read by [`LoadRecorder::from_env`]
+pub const RECORD_MODE_VAR: &str = "PRELOADER_RECORD";
+
+/// Whether a [`LoadRecorder`] runs the loaders or replays their recorded values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordMode {
+    /// Runs every loader and overwrites its recording
+    Record,
+    /// Returns the recorded values and fails loads without a recording, never running the loaders
+    Replay,
+    /// Replays the loads with a recording and records the others
+    #[default]
+    Auto,
+}
+
+impl RecordMode {
+    /// Parses `record`, `replay`, or `auto`, ignoring case.
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode.to_ascii_lowercase().as_str() {
+            "record" => Some(Self::Record),
+            "replay" => Some(Self::Replay),
+            "auto" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+}
+
+/// Records loader outputs to a directory and replays them on later runs
+///
+/// Each load is recorded in `<dir>/<key>.json`, where the key is the name of the preloader, or the name of its
+/// value type if the preloader is unnamed. Characters that are not valid in file names are replaced with `_`.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{LoadRecorder, Preloader, RecordMode};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let dir = std::env::temp_dir().join("preloader-doc-replay");
+///     let recorder = LoadRecorder::new(&dir, RecordMode::Record);
+///     let users = Preloader::builder().name("users").build();
+///     users.load_recorded(&recorder, async { vec!["alice".to_string()] }).await;
+///     users.get().await.unwrap();
+///
+///     // Later runs return the recording without running the loader
+///     let recorder = LoadRecorder::new(&dir, RecordMode::Replay);
+///     let users: Preloader<Vec<String>> = Preloader::builder().name("users").build();
+///     users.load_recorded(&recorder, async { unreachable!() }).await;
+///     assert_eq!(*users.get().await.unwrap(), ["alice"]);
+/// #   std::fs::remove_dir_all(&dir).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadRecorder {
+    dir: PathBuf,
+    mode: RecordMode,
+}
+
+impl LoadRecorder {
+    /// Creates a recorder storing its recordings in `dir`.
+    ///
+    /// # Parameters
+    ///
+    /// - `dir`: The directory of the recordings, created on the first recording
+    /// - `mode`: Whether to run the loaders or replay their recordings
+    pub fn new(dir: impl Into<PathBuf>, mode: RecordMode) -> Self {
+        Self {
+            dir: dir.into(),
+            mode,
+        }
+    }
+
+    /// Creates a recorder whose mode is read from the `PRELOADER_RECORD` environment variable.
+    ///
+    /// The mode is [`RecordMode::Auto`] if the variable is unset or not one of `record`, `replay`, and `auto`, so
+    /// `PRELOADER_RECORD=record cargo test` refreshes the recordings and a plain `cargo test` replays them.
+    pub fn from_env(dir: impl Into<PathBuf>) -> Self {
+        let mode = env::var(RECORD_MODE_VAR)
+            .ok()
+            .and_then(|mode| RecordMode::parse(&mode))
+            .unwrap_or_default();
+        Self::new(dir, mode)
+    }
+
+    /// Returns the mode of the recorder.
+    pub fn mode(&self) -> RecordMode {
+        self.mode
+    }
+
+    /// Returns the directory of the recordings.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path of the recording for `key`.
+    pub fn path(&self, key: &str) -> PathBuf {
+        let file: String = key
+            .chars()
+            .map(
+                |c| match c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    true => c,
+                    false => '_',
+                },
+            )
+            .collect();
+        self.dir.join(format!("{}.json", file))
+    }
+
+    /// Replays the recording for `key`, or runs `loader` and records its value, depending on the mode.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(T)`: The replayed or loaded value
+    /// - `Err(PreloaderError::Failed)`: If the recording is missing in replay mode, or could not be read or written
+    pub(crate) fn run<T: Serialize + DeserializeOwned>(
+        &self,
+        key: &str,
+        loader: impl Future<Output = T>,
+    ) -> impl Future<Output = Result<T>> {
+        let path = self.path(key);
+        let mode = self.mode;
+        async move {
+            let replay = match mode {
+                RecordMode::Record => false,
+                RecordMode::Replay => true,
+                RecordMode::Auto => tokio::fs::try_exists(&path).await.unwrap_or(false),
+            };
+            if replay {
+                let bytes = tokio::fs::read(&path).await.map_err(|error| {
+                    failed(format!("cannot read recording {}", path.display()), error)
+                })?;
+                return serde_json::from_slice(&bytes).map_err(|error| {
+                    failed(format!("invalid recording {}", path.display()), error)
+                });
+            }
+
+            let value = loader.await;
+            let bytes = serde_json::to_vec_pretty(&value)
+                .map_err(|error| failed("cannot serialize the loaded value".to_string(), error))?;
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir)
+                    .await
+                    .map_err(|error| failed(format!("cannot create {}", dir.display()), error))?;
+            }
+            tokio::fs::write(&path, bytes).await.map_err(|error| {
+                failed(format!("cannot write recording {}", path.display()), error)
+            })?;
+            Ok(value)
+        }
+    }
+}
+
+fn failed(context: String, error: impl fmt::Display) -> PreloaderError {
+    PreloaderError::Failed(format!("{}: {}", context, error))
+}
+
+impl<T: Serialize + DeserializeOwned + Send + 'static> Preloader<T> {
+    /// Starts loading through `recorder`, replaying the recorded value instead of running `loader` when the
+    /// recorder's mode says so.
+    ///
+    /// The recording is keyed by the name of the preloader, or the name of `T` if the preloader is unnamed.
+    /// Like [`load`](Self::load), this returns a [`LoadHandle`] and follows the configured
+    /// [`LoadPolicy`](crate::LoadPolicy) if loading was already started.
+    ///
+    /// # Parameters
+    ///
+    /// - `recorder`: The recorder replaying or recording the load
+    /// - `loader`: The asynchronous task producing the data, run unless the load is replayed
+    pub async fn load_recorded(
+        &self,
+        recorder: &LoadRecorder,
+        loader: impl Future<Output = T> + Send + 'static,
+    ) -> LoadHandle {
+        let key = self.name().unwrap_or(std::any::type_name::<T>());
+        self.load_result(recorder.run(key, loader)).await
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Send + Sync + 'static> ArcPreloader<T> {
+    /// Starts loading through `recorder`.
+    ///
+    /// See [`Preloader::load_recorded`].
+    pub async fn load_recorded(
+        &self,
+        recorder: &LoadRecorder,
+        loader: impl Future<Output = T> + Send + 'static,
+    ) -> LoadHandle {
+        let key = self.name().unwrap_or(std::any::type_name::<T>());
+        self.load_result(recorder.run(key, loader)).await
+    }
+}
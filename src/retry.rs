@@ -0,0 +1,232 @@
+//! Retry policy module
+//!
+//! This module provides the `RetryPolicy` struct describing how often and how fast a failed load attempt is retried,
+//! the `ErrorPolicy` and `FailureMode` enums describing what happens after a load fails, and the `LoadPolicy` enum
+//! describing what happens when `load()` is called again.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use crate::PreloaderError;
+
+/// Policy describing how failed load attempts are retried
+///
+/// A load attempt fails when it panics or exceeds the configured timeout.
+/// Retrying requires a way to create a fresh future, so the policy only applies to loads started with
+/// [`Preloader::load_with`](crate::Preloader::load_with) or a stored loader factory.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::exponential(3, Duration::from_millis(100));
+/// assert_eq!(policy.backoff(0), Duration::from_millis(100));
+/// assert_eq!(policy.backoff(2), Duration::from_millis(400));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    max_retries: u32,
+    /// Delay before the first retry
+    initial_backoff: Duration,
+    /// Upper bound for the delay between retries
+    max_backoff: Duration,
+    /// Factor applied to the delay after each retry
+    multiplier: u32,
+}
+
+impl RetryPolicy {
+    /// Creates a policy that never retries.
+    pub const fn none() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            multiplier: 1,
+        }
+    }
+
+    /// Creates a policy that retries up to `max_retries` times, waiting `delay` between attempts.
+    ///
+    /// # Parameters
+    ///
+    /// - `max_retries`: Maximum number of retries after the first attempt
+    /// - `delay`: Delay between attempts
+    pub const fn fixed(max_retries: u32, delay: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: delay,
+            max_backoff: delay,
+            multiplier: 1,
+        }
+    }
+
+    /// Creates a policy that retries up to `max_retries` times, doubling the delay after each retry.
+    ///
+    /// The delay is capped at 30 seconds unless changed with [`with_max_backoff`](Self::with_max_backoff).
+    ///
+    /// # Parameters
+    ///
+    /// - `max_retries`: Maximum number of retries after the first attempt
+    /// - `initial_backoff`: Delay before the first retry
+    pub const fn exponential(max_retries: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2,
+        }
+    }
+
+    /// Sets the upper bound for the delay between retries.
+    pub const fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Returns the maximum number of retries after the first attempt.
+    pub const fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns the delay to wait before the given retry.
+    ///
+    /// # Parameters
+    ///
+    /// - `retry`: Zero-based index of the retry
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(retry);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Policy describing whether a failed load is remembered
+///
+/// With a policy other than `Cache`, a `get()` call on a failed preloader discards the error and starts
+/// a fresh load with the stored loader factory (see [`Preloader::with_loader`](crate::Preloader::with_loader)).
+/// Preloaders without a stored loader factory always remember the error.
+/// `try_get()` never starts a load and returns the recorded error.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{ErrorPolicy, Preloader};
+/// use std::time::Duration;
+///
+/// let preloader: Preloader<String> = Preloader::builder()
+///     .error_policy(ErrorPolicy::CacheFor(Duration::from_secs(5)))
+///     .build_with_loader(|| async { "data".to_string() });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// The error is returned by every later `get()` call
+    #[default]
+    Cache,
+    /// The next `get()` call starts a fresh load
+    Retry,
+    /// The error is returned for the given duration, after which the next `get()` call starts a fresh load
+    CacheFor(Duration),
+}
+
+/// Function choosing the [`ErrorPolicy`] of a failed load from its error
+///
+/// Set with [`PreloaderBuilder::error_policy_with`](crate::PreloaderBuilder::error_policy_with).
+#[derive(Clone)]
+pub(crate) struct ErrorClassifier(Arc<dyn Fn(&PreloaderError) -> ErrorPolicy + Send + Sync>);
+
+impl ErrorClassifier {
+    pub(crate) fn new(
+        classify: impl Fn(&PreloaderError) -> ErrorPolicy + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(classify))
+    }
+
+    /// Returns the policy for `error`.
+    pub(crate) fn classify(&self, error: &PreloaderError) -> ErrorPolicy {
+        (self.0)(error)
+    }
+}
+
+impl fmt::Debug for ErrorClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ErrorClassifier")
+    }
+}
+
+/// State a preloader ends up in after a load fails, panics, or is cancelled
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{FailureMode, Preloader, PreloaderError};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let preloader: Preloader<u32> = Preloader::builder().on_failure(FailureMode::Idle).build();
+///     preloader.load(async { panic!("unavailable") }).await;
+///     assert!(matches!(preloader.get().await, Err(PreloaderError::Panicked(_))));
+///
+///     // The preloader is reloadable again
+///     preloader.load(async { 42 }).await;
+///     assert_eq!(*preloader.get().await.unwrap(), 42);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailureMode {
+    /// The preloader moves to the `Failed` state and returns the error to every caller
+    ///
+    /// `load()` is ignored afterwards; a stored loader factory can start again through
+    /// [`restart`](crate::Preloader::restart) or the [`ErrorPolicy`].
+    #[default]
+    Failed,
+    /// The preloader moves back to the `Idle` state and accepts a new `load()` call
+    ///
+    /// The caller that observes the failure receives the error; later callers receive `PreloaderError::NotLoaded`.
+    Idle,
+    /// The preloader moves to the `Poisoned` state and can never be loaded again
+    ///
+    /// The caller that observes the failure receives the error; every later access returns `PreloaderError::Poisoned`.
+    Poisoned,
+}
+
+/// Behavior of `load()` when the preloader is not `Idle`
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{LoadPolicy, Preloader, PreloaderError};
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let preloader = Preloader::builder().load_policy(LoadPolicy::Error).build();
+///     preloader.load(async { 1 }).await;
+///     let second = preloader.load(async { 2 }).await;
+///     assert!(matches!(second.await, Err(PreloaderError::OverlappingLoad)));
+///     assert_eq!(*preloader.get().await.unwrap(), 1);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadPolicy {
+    /// The call is ignored and returns the handle to the latest load
+    #[default]
+    Ignore,
+    /// The new load runs and replaces the value once it completes, keeping the current value until then
+    ///
+    /// Only an [`ArcPreloader`](crate::ArcPreloader) can replace a loaded value, since a
+    /// [`Preloader`](crate::Preloader) hands out borrows of it. A `Preloader` starts the new load only after a failed
+    /// load and otherwise behaves like `Error`.
+    ReplaceWhenReady,
+    /// The call returns a handle that fails with `PreloaderError::OverlappingLoad`
+    Error,
+}
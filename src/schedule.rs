@@ -0,0 +1,59 @@
+//! Cron schedule module
+//!
+//! This module lets an `ArcPreloader` refresh its value on a cron schedule, such as reloading a pricing table
+//! at 02:00 every day, instead of at a fixed interval.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use cron::Schedule;
+
+use crate::ArcPreloader;
+
+impl<T: Send + Sync + 'static> ArcPreloader<T> {
+    /// Re-runs the stored loader factory at every time matched by `schedule`, evaluated in UTC.
+    ///
+    /// Cron expressions have a seconds field, so `"0 0 2 * * *"` fires every day at 02:00.
+    /// Refreshes behave like those of [`refresh_every`](Self::refresh_every): readers keep getting the previous
+    /// value until a refresh succeeds. Calling this again replaces the running schedule, and
+    /// [`next_refresh`](Self::next_refresh) returns the time of the next refresh.
+    ///
+    /// # Parameters
+    ///
+    /// - `schedule`: The cron schedule
+    ///
+    /// # Returns
+    ///
+    /// `true` if refreshing was scheduled, `false` if no loader factory is stored
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{ArcPreloader, Schedule};
+    /// use std::{collections::HashMap, sync::Arc};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let prices = Arc::new(ArcPreloader::with_loader(|| async {
+    ///         HashMap::from([("apple".to_string(), 1.25)])
+    ///     }));
+    ///     prices.start().await;
+    ///
+    ///     let nightly: Schedule = "0 0 2 * * *".parse().unwrap();
+    ///     prices.refresh_cron(nightly);
+    ///     println!("next refresh at {:?}", prices.next_refresh());
+    /// }
+    /// ```
+    pub fn refresh_cron(self: &Arc<Self>, schedule: Schedule) -> bool {
+        self.schedule_refresh(move || {
+            let next = schedule.upcoming(Utc).next()?;
+            let delay = (next - Utc::now()).to_std().unwrap_or_default();
+            Some(tokio::time::Instant::now() + delay)
+        })
+    }
+}
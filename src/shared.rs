@@ -0,0 +1,148 @@
+//! Shared preloader module
+//!
+//! This module provides `SharedPreloader`, a cheaply cloneable handle to a single `Preloader`.
+//! Components can each hold a clone instead of wrapping the preloader in an `Arc` at every call site.
+
+use std::{
+    fmt,
+    future::{Future, IntoFuture},
+    ops::Deref,
+    pin::Pin,
+    sync::Arc,
+};
+
+use crate::{Preloader, PreloaderError, PreloaderWeak};
+
+type Result<T> = std::result::Result<T, PreloaderError>;
+
+/// Cloneable handle to a shared [`Preloader`]
+///
+/// Every clone refers to the same preloader, so loading happens once and all clones see the same value.
+/// `SharedPreloader` derefs to [`Preloader`], so all of its `&self` methods are available, and references
+/// returned by `get()` stay valid for as long as the handle they were obtained from.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::SharedPreloader;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let prices = SharedPreloader::spawn(async { vec![1.25, 0.8] });
+///
+///     let handle = prices.clone();
+///     let count = tokio::spawn(async move { handle.get().await.unwrap().len() })
+///         .await
+///         .unwrap();
+///     assert_eq!(count, 2);
+///     assert!(prices.is_loaded());
+/// }
+/// ```
+pub struct SharedPreloader<T: Send + 'static>(Arc<Preloader<T>>);
+
+impl<T: Send + 'static> SharedPreloader<T> {
+    /// Creates a new `SharedPreloader` in the initial `Idle` state.
+    pub fn new() -> Self {
+        Self::from(Preloader::new())
+    }
+
+    /// Creates a `SharedPreloader` that already holds the given value.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: The already available data
+    pub fn ready(value: T) -> Self {
+        Self::from(Preloader::ready(value))
+    }
+
+    /// Creates a new `SharedPreloader` and immediately starts loading data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn spawn(future: impl Future<Output = T> + Send + 'static) -> Self {
+        Self::from(Preloader::spawn(future))
+    }
+
+    /// Creates a new `SharedPreloader` in the `Idle` state that stores a loader factory.
+    ///
+    /// See [`Preloader::with_loader`].
+    pub fn with_loader<F, Fut>(factory: F) -> Self
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self::from(Preloader::with_loader(factory))
+    }
+
+    /// Creates a [`PreloaderWeak`] referring to the shared preloader.
+    pub fn downgrade(&self) -> PreloaderWeak<T> {
+        Preloader::downgrade(&self.0)
+    }
+
+    /// Checks if both handles refer to the same preloader.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Returns the `Arc` holding the preloader, for APIs that take an `Arc<Preloader<T>>`.
+    pub fn as_arc(&self) -> &Arc<Preloader<T>> {
+        &self.0
+    }
+
+    /// Converts the handle into the `Arc` holding the preloader.
+    pub fn into_arc(self) -> Arc<Preloader<T>> {
+        self.0
+    }
+}
+
+impl<T: Send + 'static> Deref for SharedPreloader<T> {
+    type Target = Preloader<T>;
+
+    fn deref(&self) -> &Preloader<T> {
+        &self.0
+    }
+}
+
+impl<T: Send + 'static> Clone for SharedPreloader<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+/// Awaiting a reference to a `SharedPreloader` is equivalent to calling [`Preloader::get`].
+impl<'a, T: Send + Sync + 'static> IntoFuture for &'a SharedPreloader<T> {
+    type Output = Result<&'a T>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Result<&'a T>> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.0.get())
+    }
+}
+
+/// Shows the state and timing without requiring `T: Debug`; see [`Preloader`]'s `Debug` implementation.
+impl<T: Send + 'static> fmt::Debug for SharedPreloader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedPreloader").field(&self.0).finish()
+    }
+}
+
+impl<T: Send + 'static> From<Preloader<T>> for SharedPreloader<T> {
+    /// Shares a preloader, for example one created with [`Preloader::builder`].
+    fn from(preloader: Preloader<T>) -> Self {
+        Self(Arc::new(preloader))
+    }
+}
+
+impl<T: Send + 'static> From<Arc<Preloader<T>>> for SharedPreloader<T> {
+    fn from(preloader: Arc<Preloader<T>>) -> Self {
+        Self(preloader)
+    }
+}
+
+impl<T: Send + 'static> Default for SharedPreloader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
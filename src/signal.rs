@@ -0,0 +1,95 @@
+//! Signal refresh module
+//!
+//! This module wires Unix signals to the refresh of preloaders, for the classic "reload the configuration on
+//! SIGHUP" pattern.
+
+use std::sync::{Arc, Weak};
+
+use futures::future::{join_all, BoxFuture};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    task::JoinHandle,
+};
+
+use crate::{ArcPreloader, PreloaderError};
+
+/// Preloader whose value can be reloaded with its stored loader factory
+///
+/// Implemented for [`ArcPreloader`].
+pub trait Refresh: Send + Sync + 'static {
+    /// Reloads the value with the stored loader factory, keeping the current value if that fails.
+    fn refresh(&self) -> BoxFuture<'_, Result<(), PreloaderError>>;
+}
+
+impl<T: Send + Sync + 'static> Refresh for ArcPreloader<T> {
+    fn refresh(&self) -> BoxFuture<'_, Result<(), PreloaderError>> {
+        Box::pin(ArcPreloader::refresh(self))
+    }
+}
+
+/// Refreshes every preloader in `preloaders` each time the process receives SIGHUP.
+///
+/// See [`refresh_on_signal`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use preloader::{refresh_on_sighup, ArcPreloader, Refresh};
+/// use std::sync::Arc;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = Arc::new(ArcPreloader::with_loader(|| async {
+///         std::fs::read_to_string("app.toml").unwrap_or_default()
+///     }));
+///     config.start().await;
+///
+///     refresh_on_sighup([Arc::clone(&config) as Arc<dyn Refresh>]).unwrap();
+/// }
+/// ```
+pub fn refresh_on_sighup(
+    preloaders: impl IntoIterator<Item = Arc<dyn Refresh>>,
+) -> std::io::Result<JoinHandle<()>> {
+    refresh_on_signal(SignalKind::hangup(), preloaders)
+}
+
+/// Refreshes every preloader in `preloaders` each time the process receives the signal `kind`.
+///
+/// The preloaders are refreshed concurrently, and a failed refresh keeps the current value of that preloader.
+/// Only weak references are kept: the task stops once every preloader has been dropped, or when aborted through
+/// the returned handle.
+///
+/// # Parameters
+///
+/// - `kind`: The signal to listen for
+/// - `preloaders`: The preloaders to refresh
+///
+/// # Returns
+///
+/// - `Ok(JoinHandle<()>)`: Handle to the task listening for the signal
+/// - `Err(std::io::Error)`: If the signal handler could not be registered
+///
+/// # Panics
+///
+/// Panics if called outside of a Tokio runtime.
+pub fn refresh_on_signal(
+    kind: SignalKind,
+    preloaders: impl IntoIterator<Item = Arc<dyn Refresh>>,
+) -> std::io::Result<JoinHandle<()>> {
+    let mut signals = signal(kind)?;
+    let preloaders: Vec<Weak<dyn Refresh>> = preloaders
+        .into_iter()
+        .map(|preloader| Arc::downgrade(&preloader))
+        .collect();
+
+    Ok(tokio::spawn(async move {
+        while signals.recv().await.is_some() {
+            let alive: Vec<_> = preloaders.iter().filter_map(Weak::upgrade).collect();
+            if alive.is_empty() {
+                return;
+            }
+            join_all(alive.iter().map(|preloader| preloader.refresh())).await;
+        }
+    }))
+}
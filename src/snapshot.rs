@@ -0,0 +1,355 @@
+//! Snapshot module
+//!
+//! This module provides `snapshot()` and `restore()` for serializing a loaded value with serde
+//! and putting a preloader directly into the `Loaded` state from a previous snapshot, and
+//! `PreloaderCache::persist()` / `restore()` doing the same for every loaded key of a cache.
+//! `Preloader` and `ArcPreloader` also implement `Serialize` and `Deserialize` themselves, so they can be
+//! embedded in larger structs that are snapshotted as a whole.
+
+use std::{
+    fmt,
+    hash::Hash,
+    marker::PhantomData,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{
+    de::{self, EnumAccess, VariantAccess, Visitor},
+    ser, Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{ArcPreloader, Preloader, PreloaderCache};
+
+impl<T: Serialize + Send + 'static> Preloader<T> {
+    /// Serializes the loaded value with the given serializer.
+    ///
+    /// # Parameters
+    ///
+    /// - `serializer`: The serializer of the target format
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(S::Ok)`: The serializer's output
+    /// - `Err(S::Error)`: If the value is not loaded or serialization failed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::Preloader;
+    ///
+    /// let preloader = Preloader::ready(vec![1, 2, 3]);
+    /// let mut bytes = Vec::new();
+    /// preloader.snapshot(&mut serde_json::Serializer::new(&mut bytes)).unwrap();
+    ///
+    /// let restored: Preloader<Vec<i32>> = Preloader::new();
+    /// assert!(restored.restore(&mut serde_json::Deserializer::from_slice(&bytes)).unwrap());
+    /// assert_eq!(*restored.try_get().unwrap(), [1, 2, 3]);
+    /// ```
+    pub fn snapshot<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.try_get() {
+            Ok(value) => value.serialize(serializer),
+            Err(error) => Err(ser::Error::custom(error)),
+        }
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Deserializes a value with the given deserializer and stores it, skipping the loader.
+    ///
+    /// Like [`set`](Self::set), the value is only stored if the preloader is in the `Idle` state.
+    ///
+    /// # Parameters
+    ///
+    /// - `deserializer`: The deserializer of the snapshot format
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)`: If the value was restored and the preloader is now `Loaded`
+    /// - `Ok(false)`: If loading had already been started, in which case the snapshot is discarded
+    /// - `Err(D::Error)`: If deserialization failed; the preloader stays `Idle` and can still be loaded
+    pub fn restore<'de, D>(&self, deserializer: D) -> Result<bool, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let value = T::deserialize(deserializer)?;
+        Ok(self.set(value).is_ok())
+    }
+}
+
+impl<T: Serialize + Send + Sync + 'static> ArcPreloader<T> {
+    /// Serializes the loaded value with the given serializer.
+    ///
+    /// See [`Preloader::snapshot`].
+    pub fn snapshot<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.try_get() {
+            Ok(value) => T::serialize(&value, serializer),
+            Err(error) => Err(ser::Error::custom(error)),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> ArcPreloader<T> {
+    /// Deserializes a value with the given deserializer and stores it, skipping the loader.
+    ///
+    /// See [`Preloader::restore`].
+    pub fn restore<'de, D>(&self, deserializer: D) -> Result<bool, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let value = T::deserialize(deserializer)?;
+        Ok(self.set(value).is_ok())
+    }
+}
+
+/// Serializes the loaded value as the `Loaded` variant of a `Preloader` enum, or the unit `Idle` variant if the
+/// preloader is not loaded, so serializing a struct holding the preloader does not fail while it is loading.
+///
+/// The tag keeps a preloader that is not loaded apart from one holding `None` or `()`, which most formats would
+/// otherwise write the same way.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::Preloader;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct State {
+///     users: Preloader<Vec<String>>,
+///     prices: Preloader<Vec<f64>>,
+/// }
+///
+/// let state = State {
+///     users: Preloader::ready(vec!["alice".to_string()]),
+///     prices: Preloader::new(),
+/// };
+/// let json = serde_json::to_string(&state).unwrap();
+/// assert_eq!(json, r#"{"users":{"Loaded":["alice"]},"prices":"Idle"}"#);
+///
+/// let restored: State = serde_json::from_str(&json).unwrap();
+/// assert_eq!(*restored.users.try_get().unwrap(), ["alice"]);
+/// assert!(restored.prices.is_idle());
+/// ```
+impl<T: Serialize + Send + 'static> Serialize for Preloader<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_state(self.try_get().ok(), serializer)
+    }
+}
+
+/// Deserializes a `Loaded` preloader from the `Loaded` variant, or an `Idle` one from the `Idle` variant.
+impl<'de, T: Deserialize<'de> + Send + 'static> Deserialize<'de> for Preloader<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match deserialize_state(deserializer)? {
+            Some(value) => Self::ready(value),
+            None => Self::new(),
+        })
+    }
+}
+
+/// Serializes the current value as the `Loaded` variant, or the `Idle` variant if the preloader is not loaded.
+///
+/// See the `Serialize` implementation of [`Preloader`].
+impl<T: Serialize + Send + Sync + 'static> Serialize for ArcPreloader<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_state(self.try_get().ok().as_deref(), serializer)
+    }
+}
+
+/// Deserializes a `Loaded` preloader from the `Loaded` variant, or an `Idle` one from the `Idle` variant.
+impl<'de, T: Deserialize<'de> + Send + Sync + 'static> Deserialize<'de> for ArcPreloader<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match deserialize_state(deserializer)? {
+            Some(value) => Self::ready(value),
+            None => Self::new(),
+        })
+    }
+}
+
+/// Variant names of the serialized form of a preloader
+const VARIANTS: &[&str] = &["Idle", "Loaded"];
+
+/// Serializes `value` as the `Loaded` variant, or the `Idle` variant if there is none.
+fn serialize_state<T: Serialize, S: Serializer>(
+    value: Option<&T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.serialize_newtype_variant("Preloader", 1, VARIANTS[1], value),
+        None => serializer.serialize_unit_variant("Preloader", 0, VARIANTS[0]),
+    }
+}
+
+/// Deserializes the value of the `Loaded` variant, or `None` from the `Idle` variant.
+fn deserialize_state<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_enum("Preloader", VARIANTS, StateVisitor(PhantomData))
+}
+
+/// Visitor of the serialized form of a preloader
+struct StateVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for StateVisitor<T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an `Idle` or `Loaded` preloader")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let (loaded, variant) = data.variant::<Variant>()?;
+        match loaded.0 {
+            false => variant.unit_variant().map(|()| None),
+            true => variant.newtype_variant().map(Some),
+        }
+    }
+}
+
+/// Variant of the serialized form of a preloader, `true` for `Loaded`
+struct Variant(bool);
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(VariantVisitor)
+    }
+}
+
+/// Visitor of a variant name or index
+struct VariantVisitor;
+
+impl Visitor<'_> for VariantVisitor {
+    type Value = Variant;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("`Idle` or `Loaded`")
+    }
+
+    fn visit_u64<E: de::Error>(self, index: u64) -> Result<Variant, E> {
+        match index {
+            0 => Ok(Variant(false)),
+            1 => Ok(Variant(true)),
+            _ => Err(E::invalid_value(de::Unexpected::Unsigned(index), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<Variant, E> {
+        match name {
+            "Idle" => Ok(Variant(false)),
+            "Loaded" => Ok(Variant(true)),
+            _ => Err(E::unknown_variant(name, VARIANTS)),
+        }
+    }
+}
+
+impl<K, V> PreloaderCache<K, V>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    /// Serializes every loaded key with its value, so a warm cache can be restored after a restart.
+    ///
+    /// Keys still loading or whose load failed are left out. Each entry records when its value was loaded and
+    /// last accessed, so [`restore`](Self::restore) can skip the ones that expired in the meantime.
+    ///
+    /// # Parameters
+    ///
+    /// - `serializer`: The serializer of the target format
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(S::Ok)`: The serializer's output
+    /// - `Err(S::Error)`: If serialization failed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::PreloaderCache;
+    /// use std::{fs::File, io::BufReader};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let path = std::env::temp_dir().join("preloader-doc-persist.json");
+    ///     let users = PreloaderCache::new(|id: &u32| {
+    ///         let id = *id;
+    ///         async move { format!("user-{}", id) }
+    ///     });
+    ///     users.get(&7).await.unwrap();
+    ///     users
+    ///         .persist(&mut serde_json::Serializer::new(File::create(&path).unwrap()))
+    ///         .unwrap();
+    ///
+    ///     // After a restart
+    ///     let users = PreloaderCache::new(|id: &u32| {
+    ///         let id = *id;
+    ///         async move { format!("user-{}", id) }
+    ///     });
+    ///     let file = BufReader::new(File::open(&path).unwrap());
+    ///     let restored = users
+    ///         .restore(&mut serde_json::Deserializer::from_reader(file))
+    ///         .unwrap();
+    ///     assert_eq!(restored, 1);
+    ///     assert_eq!(*users.get(&7).await.unwrap(), "user-7");
+    /// #   std::fs::remove_file(&path).unwrap();
+    /// }
+    /// ```
+    pub fn persist<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        let now = SystemTime::now();
+        let entries = self.loaded_entries();
+        serializer.collect_seq(entries.iter().map(|entry| {
+            (
+                &entry.key,
+                &*entry.value,
+                unix_millis(now, entry.age),
+                unix_millis(now, entry.idle),
+            )
+        }))
+    }
+
+    /// Deserializes entries written by [`persist`](Self::persist) and stores them, skipping the loader.
+    ///
+    /// Entries that expired since they were persisted, because of the cache's time-to-idle or negative TTL,
+    /// are skipped, as are keys that are already loading or loaded. Restored values count their age from
+    /// the restore.
+    ///
+    /// # Parameters
+    ///
+    /// - `deserializer`: The deserializer of the snapshot format
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(usize)`: The number of restored keys
+    /// - `Err(D::Error)`: If deserialization failed, in which case nothing is restored
+    pub fn restore<'de, D>(&self, deserializer: D) -> Result<usize, D::Error>
+    where
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<(K, V, u64, u64)>::deserialize(deserializer)?;
+        let now = unix_millis(SystemTime::now(), Duration::ZERO);
+        let since = |millis: u64| Duration::from_millis(now.saturating_sub(millis));
+        Ok(entries
+            .into_iter()
+            .map(|(key, value, loaded_at, used_at)| {
+                self.insert_loaded(&key, value, since(loaded_at), since(used_at))
+            })
+            .filter(|restored| *restored)
+            .count())
+    }
+}
+
+/// Returns the time `before` prior to `now`, in milliseconds since the Unix epoch.
+fn unix_millis(now: SystemTime, before: Duration) -> u64 {
+    now.checked_sub(before)
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |time| time.as_millis() as u64)
+}
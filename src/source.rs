@@ -0,0 +1,230 @@
+//! Load source module
+//!
+//! This module provides the `LoadSource` trait, a reusable description of where a value comes from. Sources for
+//! HTTP endpoints, files, databases, or combinations of them implement it once and can be shared across projects
+//! or published as companion crates, while [`Preloader::from_source`] runs them with the configured timeout,
+//! retries, and hedging.
+
+use std::{fmt, future::Future, sync::Arc};
+
+use crate::{ArcPreloader, LoadContext, LoadHandle, Preloader};
+
+/// Pluggable data source a preloader loads its value from
+///
+/// Only [`load`](Self::load) is required. [`refresh`](Self::refresh) loads a value replacing an already loaded
+/// one, for example with a conditional request, and defaults to `load`. [`validate`](Self::validate) checks
+/// every loaded value before it is stored, and accepts everything by default.
+///
+/// An error returned by `load` or `refresh`, or a value rejected by `validate`, fails the attempt, which is retried
+/// according to the configured [`RetryPolicy`](crate::RetryPolicy). Sources shared through an [`Arc`] are
+/// sources too.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{LoadContext, LoadSource, Preloader};
+/// use tokio;
+///
+/// struct Greeting {
+///     name: &'static str,
+/// }
+///
+/// impl LoadSource<String> for Greeting {
+///     type Error = std::io::Error;
+///
+///     async fn load(&self, ctx: &LoadContext) -> Result<String, Self::Error> {
+///         ctx.report_progress(1.0);
+///         Ok(format!("hello, {}", self.name))
+///     }
+///
+///     fn validate(&self, value: &String) -> Result<(), String> {
+///         if value.is_empty() {
+///             return Err("empty greeting".to_string());
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let greeting = Preloader::from_source(Greeting { name: "alice" });
+///     assert_eq!(greeting.get().await.unwrap(), "hello, alice");
+/// }
+/// ```
+pub trait LoadSource<T>: Send + Sync + 'static {
+    /// Error returned when loading fails
+    type Error: fmt::Display + Send;
+
+    /// Loads the value.
+    ///
+    /// # Parameters
+    ///
+    /// - `ctx`: The context of the attempt, with its number, deadline, and cancellation token
+    fn load(&self, ctx: &LoadContext) -> impl Future<Output = Result<T, Self::Error>> + Send;
+
+    /// Loads a value replacing `current`.
+    ///
+    /// Defaults to [`load`](Self::load).
+    ///
+    /// # Parameters
+    ///
+    /// - `ctx`: The context of the attempt
+    /// - `current`: The value loaded before
+    fn refresh(
+        &self,
+        ctx: &LoadContext,
+        current: &T,
+    ) -> impl Future<Output = Result<T, Self::Error>> + Send {
+        let _ = current;
+        self.load(ctx)
+    }
+
+    /// Checks a loaded value before it is stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: If the value can be stored
+    /// - `Err(String)`: The reason the value is rejected
+    fn validate(&self, value: &T) -> Result<(), String> {
+        let _ = value;
+        Ok(())
+    }
+}
+
+impl<T, S: LoadSource<T>> LoadSource<T> for Arc<S> {
+    type Error = S::Error;
+
+    fn load(&self, ctx: &LoadContext) -> impl Future<Output = Result<T, Self::Error>> + Send {
+        S::load(self, ctx)
+    }
+
+    fn refresh(
+        &self,
+        ctx: &LoadContext,
+        current: &T,
+    ) -> impl Future<Output = Result<T, Self::Error>> + Send {
+        S::refresh(self, ctx, current)
+    }
+
+    fn validate(&self, value: &T) -> Result<(), String> {
+        S::validate(self, value)
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Creates a new `Preloader` and immediately starts loading its value from `source`.
+    ///
+    /// A [`LoadContext`] is passed to every attempt. The preloader has the default options, so an error fails the
+    /// load; use [`load_source`](Self::load_source) on a preloader created by the builder to add a timeout and
+    /// retries.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: The source of the value
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn from_source<S: LoadSource<T>>(source: S) -> Self {
+        let source = Arc::new(source);
+        let mut preloader = Self::new();
+        preloader.start_with_context(move |ctx| fetch(Arc::clone(&source), ctx));
+        preloader
+    }
+
+    /// Starts an asynchronous task to load the value from `source`.
+    ///
+    /// Behaves like [`load_with_context`](Self::load_with_context): the configured timeout, retries, and hedging
+    /// apply, and an error returned by the source or a value rejected by its validation hook fails the attempt.
+    /// A call made while loading was already started follows the configured [`LoadPolicy`](crate::LoadPolicy).
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: The source of the value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{LoadContext, LoadSource, Preloader, RetryPolicy};
+    /// use std::time::Duration;
+    /// use tokio;
+    ///
+    /// struct Flaky;
+    ///
+    /// impl LoadSource<u32> for Flaky {
+    ///     type Error = String;
+    ///
+    ///     async fn load(&self, ctx: &LoadContext) -> Result<u32, String> {
+    ///         match ctx.attempt() {
+    ///             1 => Err("connection reset".to_string()),
+    ///             attempt => Ok(attempt),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let preloader = Preloader::builder()
+    ///         .retry(RetryPolicy::fixed(3, Duration::from_millis(10)))
+    ///         .build();
+    ///     preloader.load_source(Flaky).await;
+    ///     assert_eq!(*preloader.get().await.unwrap(), 2);
+    /// }
+    /// ```
+    pub async fn load_source<S: LoadSource<T>>(&self, source: S) -> LoadHandle {
+        let source = Arc::new(source);
+        self.load_result_with_context(move |ctx| fetch(Arc::clone(&source), ctx))
+            .await
+    }
+}
+
+impl<T: Send + Sync + 'static> ArcPreloader<T> {
+    /// Creates a new `ArcPreloader` and immediately starts loading its value from `source`.
+    ///
+    /// See [`Preloader::from_source`]. Keep the source in an [`Arc`] to pass it to
+    /// [`refresh_source`](Self::refresh_source) later.
+    pub fn from_source<S: LoadSource<T>>(source: S) -> Self {
+        let source = Arc::new(source);
+        let mut preloader = Preloader::new();
+        preloader.start_with_context(move |ctx| {
+            let load = fetch(Arc::clone(&source), ctx);
+            async move { load.await.map(Arc::new) }
+        });
+        Self::from_inner(preloader)
+    }
+}
+
+/// Loads a value from `source` and checks it with the source's validation hook.
+///
+/// # Parameters
+///
+/// - `source`: The source of the value
+/// - `ctx`: The context of the attempt
+pub(crate) async fn fetch<T, S: LoadSource<T>>(
+    source: Arc<S>,
+    ctx: LoadContext,
+) -> Result<T, String> {
+    let value = source.load(&ctx).await.map_err(|error| error.to_string())?;
+    source.validate(&value)?;
+    Ok(value)
+}
+
+/// Loads a value replacing `current` from `source` and checks it with the source's validation hook.
+///
+/// # Parameters
+///
+/// - `source`: The source of the value
+/// - `ctx`: The context of the attempt
+/// - `current`: The value loaded before
+pub(crate) async fn refetch<T, S>(source: S, ctx: LoadContext, current: Arc<T>) -> Result<T, String>
+where
+    T: Send + Sync,
+    S: LoadSource<T>,
+{
+    let value = source
+        .refresh(&ctx, &current)
+        .await
+        .map_err(|error| error.to_string())?;
+    source.validate(&value)?;
+    Ok(value)
+}
@@ -0,0 +1,187 @@
+//! Spill module
+//!
+//! This module provides `SpilledFile`, a value written to a temporary file instead of being kept in memory, for
+//! preloaded artifacts that do not fit in RAM. [`Preloader::load_spilled`] streams the output of a loader to disk,
+//! and the file is removed once the value is dropped.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    pin::pin,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use futures::{Stream, StreamExt};
+use tokio::{
+    fs::{File, OpenOptions},
+    io::{AsyncRead, AsyncWriteExt, BufWriter},
+};
+
+use crate::{LoadHandle, Preloader, PreloaderError};
+
+/// Data written to a temporary file, removed when the `SpilledFile` is dropped
+///
+/// The file is created in [`std::env::temp_dir`], which honors the `TMPDIR` environment variable on Unix, so
+/// point it at a disk with enough space. Readers open the file with [`open`](Self::open) or read it from
+/// [`path`](Self::path); only the handle lives in memory.
+///
+/// # Example
+///
+/// ```rust
+/// use futures::stream;
+/// use preloader::{Preloader, SpilledFile};
+/// use tokio::io::AsyncReadExt;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let archive: Preloader<SpilledFile> = Preloader::new();
+///     let chunks = stream::iter([Ok::<_, std::io::Error>(b"large ".to_vec()), Ok(b"artifact".to_vec())]);
+///     archive.load_spilled(chunks).await;
+///
+///     let file = archive.get().await.unwrap();
+///     assert_eq!(file.len(), 14);
+///     let mut contents = String::new();
+///     file.open().await.unwrap().read_to_string(&mut contents).await.unwrap();
+///     assert_eq!(contents, "large artifact");
+/// }
+/// ```
+pub struct SpilledFile {
+    path: PathBuf,
+    len: u64,
+}
+
+impl SpilledFile {
+    /// Writes every chunk of `stream` to a new temporary file.
+    ///
+    /// If the stream yields an error or writing fails, the partial file is removed.
+    ///
+    /// # Parameters
+    ///
+    /// - `stream`: The stream producing the chunks of data
+    pub async fn from_stream<S, B>(stream: S) -> io::Result<Self>
+    where
+        S: Stream<Item = io::Result<B>>,
+        B: AsRef<[u8]>,
+    {
+        let (mut spilled, file) = Self::create().await?;
+        let mut writer = BufWriter::new(file);
+        let mut stream = pin!(stream);
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(chunk.as_ref()).await?;
+            spilled.len += chunk.as_ref().len() as u64;
+        }
+        writer.shutdown().await?;
+        Ok(spilled)
+    }
+
+    /// Copies everything `reader` produces to a new temporary file.
+    ///
+    /// If reading or writing fails, the partial file is removed.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: The source of the data, such as a response body or a decompressor
+    pub async fn from_reader(reader: impl AsyncRead + Unpin) -> io::Result<Self> {
+        let (mut spilled, file) = Self::create().await?;
+        let mut reader = reader;
+        let mut writer = BufWriter::new(file);
+        spilled.len = tokio::io::copy(&mut reader, &mut writer).await?;
+        writer.shutdown().await?;
+        Ok(spilled)
+    }
+
+    /// Creates an empty temporary file, owned by the returned `SpilledFile` so it is removed on failure.
+    async fn create() -> io::Result<(Self, File)> {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "preloader-spill-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await?;
+        Ok((Self { path, len: 0 }, file))
+    }
+
+    /// Returns the path of the temporary file.
+    ///
+    /// The file is removed when this `SpilledFile` is dropped, so it must not be used afterwards.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the number of bytes written to the file.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Checks if no data was written to the file.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Opens the file for reading, as an asynchronous reader positioned at the start.
+    pub async fn open(&self) -> io::Result<File> {
+        File::open(&self.path).await
+    }
+}
+
+impl Drop for SpilledFile {
+    fn drop(&mut self) {
+        _ = std::fs::remove_file(&self.path);
+    }
+}
+
+impl fmt::Debug for SpilledFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpilledFile")
+            .field("path", &self.path)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl Preloader<SpilledFile> {
+    /// Starts writing every chunk of `stream` to a temporary file, which becomes the loaded value.
+    ///
+    /// Like [`load`](Self::load), an overlapping call follows the configured [`LoadPolicy`](crate::LoadPolicy). An error yielded
+    /// by the stream or raised while writing makes the load fail with `PreloaderError::Failed` and removes the
+    /// partial file. See [`SpilledFile`].
+    ///
+    /// # Parameters
+    ///
+    /// - `stream`: The stream producing the chunks of data
+    pub async fn load_spilled<S, B>(&self, stream: S) -> LoadHandle
+    where
+        S: Stream<Item = io::Result<B>> + Send + 'static,
+        B: AsRef<[u8]> + Send,
+    {
+        self.load_result(async move { SpilledFile::from_stream(stream).await.map_err(failed) })
+            .await
+    }
+
+    /// Starts copying everything `reader` produces to a temporary file, which becomes the loaded value.
+    ///
+    /// See [`load_spilled`](Self::load_spilled).
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: The source of the data
+    pub async fn load_spilled_reader(
+        &self,
+        reader: impl AsyncRead + Send + Unpin + 'static,
+    ) -> LoadHandle {
+        self.load_result(async move { SpilledFile::from_reader(reader).await.map_err(failed) })
+            .await
+    }
+}
+
+/// Converts an I/O error into a load failure.
+fn failed(error: io::Error) -> PreloaderError {
+    PreloaderError::Failed(error.to_string())
+}
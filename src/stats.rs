@@ -0,0 +1,131 @@
+//! Latency statistics module
+//!
+//! This module keeps the durations of the most recent loads and waits of a preloader and reports their
+//! percentiles through `stats()`, so p50/p95/p99 preload latency can be watched over time rather than only the
+//! duration of the last load. With the `metrics` feature, every duration is also recorded in a histogram of the
+//! installed `metrics` recorder, along with a counter of failed loads and a gauge of blocked callers.
+
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
+
+/// Number of recent durations the percentiles are computed from, per kind
+pub const LATENCY_WINDOW: usize = 1024;
+
+/// Percentiles of a set of durations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyPercentiles {
+    /// Number of durations the percentiles are computed from
+    pub count: usize,
+    /// Median duration
+    pub p50: Duration,
+    /// 95th percentile
+    pub p95: Duration,
+    /// 99th percentile
+    pub p99: Duration,
+    /// Longest duration
+    pub max: Duration,
+}
+
+/// Latency statistics of a preloader, as reported by `Preloader::stats`
+///
+/// Both sets cover the latest [`LATENCY_WINDOW`] durations, so they follow changes in latency over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PreloaderStats {
+    /// Durations of finished loads, including failed ones and reloads, or `None` if no load has finished
+    pub loads: Option<LatencyPercentiles>,
+    /// Durations of the `get()` calls that had to wait, or `None` if no call has waited
+    pub waits: Option<LatencyPercentiles>,
+}
+
+/// Recent load and wait durations of a preloader
+#[derive(Debug)]
+pub(crate) struct Latency {
+    loads: Window,
+    waits: Window,
+    /// Name of the preloader, used as the `preloader` label of the exported metrics
+    #[cfg(feature = "metrics")]
+    label: String,
+}
+
+impl Latency {
+    /// Creates empty statistics for the preloader named `name`.
+    pub(crate) fn new(name: Option<&str>) -> Self {
+        #[cfg(not(feature = "metrics"))]
+        let _ = name;
+        Self {
+            loads: Window::default(),
+            waits: Window::default(),
+            #[cfg(feature = "metrics")]
+            label: name.unwrap_or("unnamed").to_string(),
+        }
+    }
+
+    /// Records the duration of a finished load.
+    pub(crate) fn record_load(&self, duration: Duration) {
+        self.loads.record(duration);
+        #[cfg(feature = "metrics")]
+        metrics::histogram!("preloader_load_duration_seconds", "preloader" => self.label.clone())
+            .record(duration.as_secs_f64());
+    }
+
+    /// Records a failed load.
+    pub(crate) fn record_failure(&self) {
+        #[cfg(feature = "metrics")]
+        metrics::counter!("preloader_failures_total", "preloader" => self.label.clone())
+            .increment(1);
+    }
+
+    /// Records the number of callers currently blocked waiting for the value.
+    pub(crate) fn record_waiting(&self, waiting: usize) {
+        #[cfg(feature = "metrics")]
+        metrics::gauge!("preloader_waiters", "preloader" => self.label.clone()).set(waiting as f64);
+        #[cfg(not(feature = "metrics"))]
+        let _ = waiting;
+    }
+
+    /// Records the duration of a finished wait.
+    pub(crate) fn record_wait(&self, duration: Duration) {
+        self.waits.record(duration);
+        #[cfg(feature = "metrics")]
+        metrics::histogram!("preloader_wait_duration_seconds", "preloader" => self.label.clone())
+            .record(duration.as_secs_f64());
+    }
+
+    /// Returns the current percentiles.
+    pub(crate) fn stats(&self) -> PreloaderStats {
+        PreloaderStats {
+            loads: self.loads.percentiles(),
+            waits: self.waits.percentiles(),
+        }
+    }
+}
+
+/// The latest [`LATENCY_WINDOW`] durations of one kind, oldest first
+#[derive(Debug, Default)]
+struct Window(Mutex<VecDeque<Duration>>);
+
+impl Window {
+    fn record(&self, duration: Duration) {
+        let mut durations = self.0.lock().unwrap();
+        if durations.len() == LATENCY_WINDOW {
+            durations.pop_front();
+        }
+        durations.push_back(duration);
+    }
+
+    fn percentiles(&self) -> Option<LatencyPercentiles> {
+        let mut sorted: Vec<Duration> = self.0.lock().unwrap().iter().copied().collect();
+        sorted.sort_unstable();
+        let max = *sorted.last()?;
+        let percentile = |p: f64| {
+            let rank = (p * sorted.len() as f64).ceil() as usize;
+            sorted[rank.clamp(1, sorted.len()) - 1]
+        };
+        Some(LatencyPercentiles {
+            count: sorted.len(),
+            p50: percentile(0.50),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+            max,
+        })
+    }
+}
@@ -0,0 +1,132 @@
+//! Stream loading module
+//!
+//! This module provides `from_stream()` and `load_stream()`, which drive a `futures::Stream` to its end and
+//! collect its items into the loaded value, for loads that arrive in parts such as paginated APIs or
+//! server-streaming RPCs. Every received item is reported as a [`PreloaderEvent::ItemsLoaded`] event.
+
+use std::pin::pin;
+
+use futures::{Stream, StreamExt};
+
+use crate::{events::Events, LoadHandle, Preloader, PreloaderEvent};
+
+impl<T: Send + 'static> Preloader<Vec<T>> {
+    /// Creates a preloader and immediately starts collecting the items of `stream` into a `Vec`.
+    ///
+    /// The value is available once the stream ends. See [`from_stream_fold`](Preloader::from_stream_fold)
+    /// for the events reported while the items arrive.
+    ///
+    /// # Parameters
+    ///
+    /// - `stream`: The stream producing the items
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures::{stream, StreamExt};
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // For example one item per page of a paginated API
+    ///     let pages = stream::iter(1..=3).then(|page| async move { format!("page-{}", page) });
+    ///     let preloader = Preloader::from_stream(pages);
+    ///     assert_eq!(preloader.get().await.unwrap().len(), 3);
+    /// }
+    /// ```
+    pub fn from_stream(stream: impl Stream<Item = T> + Send + 'static) -> Self {
+        Self::from_stream_fold(stream, Vec::new(), push)
+    }
+
+    /// Starts collecting the items of `stream` into a `Vec`.
+    ///
+    /// Like [`load`](Self::load), an overlapping call follows the configured [`LoadPolicy`](crate::LoadPolicy). Unlike
+    /// [`from_stream`](Self::from_stream), this lets the caller subscribe to [`events`](Self::events) first.
+    ///
+    /// # Parameters
+    ///
+    /// - `stream`: The stream producing the items
+    pub async fn load_stream(&self, stream: impl Stream<Item = T> + Send + 'static) -> LoadHandle {
+        let events = self.event_feed().clone();
+        self.load(fold_stream(stream, Vec::new(), push, events))
+            .await
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Creates a preloader and immediately starts folding the items of `stream` into `init` with `fold`.
+    ///
+    /// The value is the result of the last `fold` once the stream ends. After each item, a
+    /// [`PreloaderEvent::ItemsLoaded`] event reports the number of items received so far; if the stream's
+    /// [`size_hint`](Stream::size_hint) has an upper bound, [`PreloaderEvent::ProgressUpdated`] events also report
+    /// the fraction received.
+    ///
+    /// # Parameters
+    ///
+    /// - `stream`: The stream producing the items
+    /// - `init`: The initial value
+    /// - `fold`: Function combining the value so far with the next item
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures::stream;
+    /// use preloader::Preloader;
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let total = Preloader::from_stream_fold(stream::iter([3u64, 4, 5]), 0, |sum, n| sum + n);
+    ///     assert_eq!(total.get().await.unwrap(), &12);
+    /// }
+    /// ```
+    pub fn from_stream_fold<S, F>(stream: S, init: T, fold: F) -> Self
+    where
+        S: Stream + Send + 'static,
+        F: FnMut(T, S::Item) -> T + Send + 'static,
+    {
+        let mut preloader = Self::new();
+        let events = preloader.event_feed().clone();
+        preloader.begin(fold_stream(stream, init, fold, events));
+        preloader
+    }
+}
+
+/// Appends `item` to `items`.
+fn push<T>(mut items: Vec<T>, item: T) -> Vec<T> {
+    items.push(item);
+    items
+}
+
+/// Folds the items of `stream` into `init`, reporting the number of items and, if the stream's size is bounded,
+/// the fraction received.
+async fn fold_stream<S, T, F>(stream: S, init: T, mut fold: F, events: Events) -> T
+where
+    S: Stream,
+    F: FnMut(T, S::Item) -> T,
+{
+    let mut stream = pin!(stream);
+    let total = stream.size_hint().1.filter(|&total| total > 0);
+    let mut value = init;
+    let mut count = 0u64;
+    while let Some(item) = stream.next().await {
+        value = fold(value, item);
+        count += 1;
+        events.emit(PreloaderEvent::ItemsLoaded { count });
+        if let Some(total) = total {
+            events.emit(PreloaderEvent::ProgressUpdated {
+                progress: (count as f64 / total as f64).min(1.0) as f32,
+            });
+        }
+    }
+    value
+}
@@ -0,0 +1,170 @@
+//! Synchronization primitives module
+//!
+//! The core of `Preloader` hands the result of a loading task to its readers through a oneshot channel kept behind
+//! a mutex. By default these are the `tokio::sync` primitives. With the `std-sync` feature they are replaced by the
+//! minimal ones below, built on `std::sync::Mutex` and a stored `Waker`, for builds that only use the caching and
+//! state machine parts and want to keep the synchronization code they depend on small. The feature swaps the
+//! implementation only and removes no dependency: Tokio itself is still required to run the loading tasks, and
+//! its `sync` primitives are still used outside of the core.
+
+#[cfg(not(feature = "std-sync"))]
+pub(crate) use tokio::sync::{oneshot, Mutex};
+
+#[cfg(feature = "std-sync")]
+pub(crate) use self::std_sync::{oneshot, Mutex};
+
+#[cfg(feature = "std-sync")]
+mod std_sync {
+    use std::sync::{MutexGuard, PoisonError, TryLockError};
+
+    /// Mutex with the API of `tokio::sync::Mutex` used by the core
+    ///
+    /// The core never holds the guard across an await point under this feature, so a blocking lock is enough.
+    #[derive(Debug, Default)]
+    pub(crate) struct Mutex<T>(std::sync::Mutex<T>);
+
+    /// Error returned by [`Mutex::try_lock`] when the mutex is held
+    #[derive(Debug)]
+    pub(crate) struct WouldBlock;
+
+    impl<T> Mutex<T> {
+        pub(crate) fn new(value: T) -> Self {
+            Self(std::sync::Mutex::new(value))
+        }
+
+        /// Locks the mutex, blocking the thread until it is free.
+        pub(crate) async fn lock(&self) -> MutexGuard<'_, T> {
+            self.0.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        /// Locks the mutex if it is free.
+        pub(crate) fn try_lock(&self) -> Result<MutexGuard<'_, T>, WouldBlock> {
+            match self.0.try_lock() {
+                Ok(guard) => Ok(guard),
+                Err(TryLockError::Poisoned(poisoned)) => Ok(poisoned.into_inner()),
+                Err(TryLockError::WouldBlock) => Err(WouldBlock),
+            }
+        }
+
+        pub(crate) fn get_mut(&mut self) -> &mut T {
+            self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    /// Oneshot channel with the API of `tokio::sync::oneshot` used by the core
+    pub(crate) mod oneshot {
+        use std::{
+            future::Future,
+            pin::Pin,
+            sync::{Arc, Mutex, PoisonError},
+            task::{Context, Poll, Waker},
+        };
+
+        pub(crate) mod error {
+            /// Error returned by awaiting a [`Receiver`](super::Receiver) whose sender was dropped without sending
+            #[derive(Debug)]
+            pub(crate) struct RecvError;
+
+            /// Error returned by [`Receiver::try_recv`](super::Receiver::try_recv)
+            #[derive(Debug)]
+            pub(crate) enum TryRecvError {
+                /// No value has been sent yet
+                Empty,
+                /// The sender was dropped without sending a value
+                Closed,
+            }
+        }
+
+        use error::{RecvError, TryRecvError};
+
+        /// State shared by both ends of the channel
+        struct Slot<T> {
+            /// Value sent and not yet received
+            value: Option<T>,
+            /// Whether either end has been dropped
+            closed: bool,
+            /// Waker of the task awaiting the receiver
+            waker: Option<Waker>,
+        }
+
+        type Shared<T> = Arc<Mutex<Slot<T>>>;
+
+        fn lock<T>(shared: &Shared<T>) -> std::sync::MutexGuard<'_, Slot<T>> {
+            shared.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        /// Sending end of the channel
+        pub(crate) struct Sender<T>(Shared<T>);
+
+        /// Receiving end of the channel
+        pub(crate) struct Receiver<T>(Shared<T>);
+
+        /// Creates a channel carrying a single value.
+        pub(crate) fn channel<T>() -> (Sender<T>, Receiver<T>) {
+            let shared = Arc::new(Mutex::new(Slot {
+                value: None,
+                closed: false,
+                waker: None,
+            }));
+            (Sender(Arc::clone(&shared)), Receiver(shared))
+        }
+
+        impl<T> Sender<T> {
+            /// Sends `value`, handing it back if the receiver has been dropped.
+            pub(crate) fn send(self, value: T) -> Result<(), T> {
+                let mut slot = lock(&self.0);
+                if slot.closed {
+                    return Err(value);
+                }
+                slot.value = Some(value);
+                Ok(())
+            }
+        }
+
+        impl<T> Drop for Sender<T> {
+            fn drop(&mut self) {
+                let mut slot = lock(&self.0);
+                slot.closed = true;
+                let waker = slot.waker.take();
+                drop(slot);
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+        }
+
+        impl<T> Receiver<T> {
+            /// Takes the value if it has been sent.
+            pub(crate) fn try_recv(&mut self) -> Result<T, TryRecvError> {
+                let mut slot = lock(&self.0);
+                match slot.value.take() {
+                    Some(value) => Ok(value),
+                    None if slot.closed => Err(TryRecvError::Closed),
+                    None => Err(TryRecvError::Empty),
+                }
+            }
+        }
+
+        impl<T> Future for Receiver<T> {
+            type Output = Result<T, RecvError>;
+
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let mut slot = lock(&self.0);
+                match slot.value.take() {
+                    Some(value) => Poll::Ready(Ok(value)),
+                    None if slot.closed => Poll::Ready(Err(RecvError)),
+                    None => {
+                        slot.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            }
+        }
+
+        impl<T> Drop for Receiver<T> {
+            fn drop(&mut self) {
+                lock(&self.0).closed = true;
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Test utilities module
+//!
+//! This module provides [`Preloader::pending`], which creates a preloader whose load is completed by hand
+//! through a [`TestHandle`]. Components consuming preloaders can then be tested in the `Loading` state,
+//! after a success, or after a failure, without sleeping to simulate slow loads.
+
+use std::fmt;
+
+use tokio::sync::oneshot;
+
+use crate::{Preloader, PreloaderError};
+
+/// Handle completing the load of a preloader created by [`Preloader::pending`]
+///
+/// Dropping the handle without completing the load cancels it, so the preloader fails with
+/// `PreloaderError::Cancelled`.
+pub struct TestHandle<T> {
+    sender: oneshot::Sender<Result<T, PreloaderError>>,
+}
+
+impl<T> TestHandle<T> {
+    /// Completes loading with `value`.
+    pub fn complete(self, value: T) {
+        _ = self.sender.send(Ok(value));
+    }
+
+    /// Fails loading with `error`.
+    pub fn fail(self, error: PreloaderError) {
+        _ = self.sender.send(Err(error));
+    }
+
+    /// Keeps the preloader in the `Loading` state forever.
+    pub fn never(self) {
+        std::mem::forget(self.sender);
+    }
+}
+
+impl<T> fmt::Debug for TestHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestHandle")
+            .field("closed", &self.sender.is_closed())
+            .finish()
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Creates a preloader in the `Loading` state whose load is completed through the returned handle.
+    ///
+    /// # Returns
+    ///
+    /// The preloader and the [`TestHandle`] that completes, fails, or never finishes its load
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Preloader, PreloaderError};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (preloader, handle) = Preloader::<u32>::pending();
+    ///     assert!(matches!(preloader.try_get(), Err(PreloaderError::Loading)));
+    ///
+    ///     handle.complete(42);
+    ///     assert_eq!(*preloader.get().await.unwrap(), 42);
+    /// }
+    /// ```
+    pub fn pending() -> (Self, TestHandle<T>) {
+        let (sender, receiver) = oneshot::channel();
+        let mut preloader = Self::new();
+        preloader
+            .start_loader(async move { receiver.await.unwrap_or(Err(PreloaderError::Cancelled)) });
+        (preloader, TestHandle { sender })
+    }
+}
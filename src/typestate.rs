@@ -0,0 +1,184 @@
+//! Typestate module
+//!
+//! This module provides [`TypedPreloader`], a flavour of [`Preloader`] that tracks its loading state in the type:
+//! `TypedPreloader<T, Idle>` → `TypedPreloader<T, Loading>` → `TypedPreloader<T, Loaded>`.
+//! Only the `Loaded` type offers `get()`, which is infallible and not async, so hot paths can prove statically
+//! that loading already happened.
+//!
+//! # Example
+//!
+//! ```rust
+//! use preloader::typestate::{Loaded, TypedPreloader};
+//! use tokio;
+//!
+//! fn lookup(table: &TypedPreloader<Vec<u32>, Loaded>, index: usize) -> u32 {
+//!     table.get()[index]
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let loading = TypedPreloader::new().load(async { vec![10, 20, 30] });
+//!     // ... other startup work ...
+//!     let table = loading.wait().await.unwrap();
+//!     assert_eq!(lookup(&table, 1), 20);
+//! }
+//! ```
+
+use std::{fmt, future::Future, marker::PhantomData, ops::Deref};
+
+use crate::{Preloader, PreloaderError};
+
+/// Loading has not been started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Idle;
+
+/// Loading has been started and may still be running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Loading;
+
+/// The value has been loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Loaded;
+
+/// Preloader whose loading state `S` is part of its type
+///
+/// Transitions consume the preloader and return it in the next state. A `TypedPreloader` never expires and
+/// cannot be unloaded, so a `TypedPreloader<T, Loaded>` holds its value for as long as it exists.
+///
+/// # Generic Types
+///
+/// - `T`: The type of data to load. Must satisfy `Send + 'static`.
+/// - `S`: The loading state, one of [`Idle`], [`Loading`], or [`Loaded`]
+pub struct TypedPreloader<T: Send + 'static, S = Idle> {
+    inner: Preloader<T>,
+    state: PhantomData<S>,
+}
+
+impl<T: Send + 'static, S> TypedPreloader<T, S> {
+    /// Moves the preloader into state `N`.
+    fn into_state<N>(self) -> TypedPreloader<T, N> {
+        TypedPreloader {
+            inner: self.inner,
+            state: PhantomData,
+        }
+    }
+
+    /// Returns the underlying preloader, for example to inspect its timing.
+    pub fn as_preloader(&self) -> &Preloader<T> {
+        &self.inner
+    }
+}
+
+impl<T: Send + 'static> TypedPreloader<T, Idle> {
+    /// Creates a new preloader in the `Idle` state.
+    pub fn new() -> Self {
+        Self {
+            inner: Preloader::new(),
+            state: PhantomData,
+        }
+    }
+
+    /// Starts loading `future` in a spawned task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn load(
+        self,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> TypedPreloader<T, Loading> {
+        self.inner.load_now(future);
+        self.into_state()
+    }
+}
+
+impl<T: Send + 'static> Default for TypedPreloader<T, Idle> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Send + 'static> TypedPreloader<T, Loading> {
+    /// Creates a preloader and immediately starts loading `future`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn spawn(future: impl Future<Output = T> + Send + 'static) -> Self {
+        TypedPreloader::new().load(future)
+    }
+
+    /// Waits until loading has finished.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(TypedPreloader<T, Loaded>)`: If the value was loaded
+    /// - `Err(PreloaderError)`: If loading failed
+    pub async fn wait(self) -> Result<TypedPreloader<T, Loaded>, PreloaderError> {
+        self.inner.get().await?;
+        Ok(self.into_state())
+    }
+
+    /// Checks if loading has finished without waiting.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(TypedPreloader<T, Loaded>)`: If the value is loaded
+    /// - `Err(Self)`: If loading is still running or failed; the error is available through `wait()`
+    #[allow(clippy::result_large_err)]
+    pub fn try_loaded(self) -> Result<TypedPreloader<T, Loaded>, Self> {
+        if self.inner.is_loaded() {
+            Ok(self.into_state())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<T: Send + 'static> TypedPreloader<T, Loaded> {
+    /// Creates a preloader that is already loaded with `value`.
+    pub fn ready(value: T) -> Self {
+        Self {
+            inner: Preloader::ready(value),
+            state: PhantomData,
+        }
+    }
+
+    /// Returns the loaded value, without waiting and without failing.
+    #[inline]
+    pub fn get(&self) -> &T {
+        // SAFETY: this state is only reached after the value was loaded, and a preloader without a TTL
+        // keeps its value until it is dropped
+        unsafe { self.inner.get_unchecked() }
+    }
+
+    /// Extracts the loaded value, consuming the preloader.
+    pub fn into_inner(self) -> T {
+        self.inner
+            .into_inner()
+            .expect("a loaded typed preloader holds its value")
+    }
+}
+
+impl<T: Send + 'static> Deref for TypedPreloader<T, Loaded> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T: Send + 'static, S> fmt::Debug for TypedPreloader<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedPreloader")
+            .field("state", &format_args!("{}", short_type_name::<S>()))
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+/// Returns the name of `S` without its module path.
+fn short_type_name<S>() -> &'static str {
+    let name = std::any::type_name::<S>();
+    name.rsplit("::").next().unwrap_or(name)
+}
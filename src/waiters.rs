@@ -0,0 +1,137 @@
+//! Waiters module
+//!
+//! This module tracks the callers blocked in `get()` of a preloader: how many are waiting right now and how long
+//! their waits took. Preloaders with many or long waits are the ones on the critical path.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+use crate::stats::Latency;
+
+/// Upper bounds of the wait time histogram buckets; a final bucket counts longer waits
+pub const WAIT_BUCKETS: [Duration; 5] = [
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+    Duration::from_secs(10),
+];
+
+/// Waiter counts and wait time distribution of a preloader, as reported by `Preloader::wait_stats`
+///
+/// Only calls that had to wait are counted; reading a value that is already loaded is not a wait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WaitStats {
+    /// Callers currently blocked waiting for the value
+    pub waiting: usize,
+    /// Number of finished waits, including cancelled ones
+    pub waits: u64,
+    /// Total duration of the finished waits
+    pub total_wait: Duration,
+    /// Longest finished wait
+    pub max_wait: Duration,
+    /// Number of finished waits per bucket of [`WAIT_BUCKETS`], with the last entry counting longer waits
+    pub histogram: [u64; WAIT_BUCKETS.len() + 1],
+}
+
+impl WaitStats {
+    /// Returns the average duration of the finished waits.
+    pub fn average_wait(&self) -> Option<Duration> {
+        let waits = u32::try_from(self.waits).ok().filter(|&waits| waits > 0)?;
+        Some(self.total_wait / waits)
+    }
+
+    /// Returns the histogram as pairs of bucket upper bound and count; the last bound is `None`.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        WAIT_BUCKETS
+            .iter()
+            .copied()
+            .map(Some)
+            .chain([None])
+            .zip(self.histogram.iter().copied())
+    }
+}
+
+/// Atomic counters behind [`WaitStats`]
+#[derive(Debug)]
+pub(crate) struct Waiters {
+    waiting: AtomicUsize,
+    waits: AtomicU64,
+    /// Total wait time in nanoseconds
+    total_nanos: AtomicU64,
+    /// Longest wait in nanoseconds
+    max_nanos: AtomicU64,
+    histogram: [AtomicU64; WAIT_BUCKETS.len() + 1],
+    /// Statistics the duration of every finished wait is added to
+    latency: Arc<Latency>,
+}
+
+impl Waiters {
+    /// Creates counters without any recorded wait.
+    pub(crate) fn new(latency: Arc<Latency>) -> Self {
+        Self {
+            waiting: AtomicUsize::new(0),
+            waits: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            max_nanos: AtomicU64::new(0),
+            histogram: Default::default(),
+            latency,
+        }
+    }
+
+    /// Registers a blocked caller until the returned guard is dropped.
+    pub(crate) fn enter(&self) -> WaitGuard<'_> {
+        let waiting = self.waiting.fetch_add(1, Ordering::Relaxed) + 1;
+        self.latency.record_waiting(waiting);
+        WaitGuard {
+            waiters: self,
+            started: Instant::now(),
+        }
+    }
+
+    /// Returns the current counts.
+    pub(crate) fn stats(&self) -> WaitStats {
+        WaitStats {
+            waiting: self.waiting.load(Ordering::Relaxed),
+            waits: self.waits.load(Ordering::Relaxed),
+            total_wait: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            max_wait: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+            histogram: std::array::from_fn(|bucket| self.histogram[bucket].load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Records a finished wait.
+    fn record(&self, waited: Duration) {
+        let nanos = u64::try_from(waited.as_nanos()).unwrap_or(u64::MAX);
+        let bucket = WAIT_BUCKETS
+            .iter()
+            .position(|&bound| waited <= bound)
+            .unwrap_or(WAIT_BUCKETS.len());
+        self.waits.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        self.histogram[bucket].fetch_add(1, Ordering::Relaxed);
+        let waiting = self.waiting.fetch_sub(1, Ordering::Relaxed) - 1;
+        self.latency.record_waiting(waiting);
+        self.latency.record_wait(waited);
+    }
+}
+
+/// A caller blocked in `get()`, recorded when it stops waiting or is cancelled
+pub(crate) struct WaitGuard<'a> {
+    waiters: &'a Waiters,
+    started: Instant,
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        self.waiters.record(self.started.elapsed());
+    }
+}
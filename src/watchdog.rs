@@ -0,0 +1,181 @@
+//! Watchdog module
+//!
+//! This module provides `Watchdog`, which watches running loads against an expected duration. A load exceeding its
+//! budget is reported through a callback, a tracing warning, and a `PreloaderEvent::Stalled` event, and can be
+//! aborted and restarted with the stored loader factory. Hangs in detached loading tasks then show up as soon as
+//! they happen instead of when someone notices that `get()` never returns.
+
+use std::{fmt, future::Future, pin::pin, sync::Arc, time::Duration};
+
+use futures::future::{self, BoxFuture, Either};
+
+use crate::{events::Events, PreloaderEvent};
+
+/// Function called when a load exceeds the budget of a [`Watchdog`]
+type OnStalled = Arc<dyn Fn(&StalledLoad) + Send + Sync>;
+
+/// Report of a load that exceeded the budget of its [`Watchdog`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StalledLoad {
+    /// Name of the preloader or group entry, if it has one
+    pub name: Option<String>,
+    /// The exceeded budget
+    pub budget: Duration,
+    /// Number of times the load was already restarted
+    pub restarts: u32,
+    /// Whether the load is aborted and restarted, rather than left running
+    pub restarting: bool,
+}
+
+/// Expected-duration budget of loads, reporting the loads that exceed it
+///
+/// By default a stalled load is only reported and keeps running. With [`restart`](Self::restart), it is aborted
+/// and started again with the stored loader factory, which requires the preloader to have one.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::{Preloader, Watchdog};
+/// use std::sync::{
+///     atomic::{AtomicU32, Ordering},
+///     Arc,
+/// };
+/// use std::time::Duration;
+/// use tokio;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let stalls = Arc::new(AtomicU32::new(0));
+///     let counter = Arc::clone(&stalls);
+///     let mut attempts = 0;
+///     let preloader = Preloader::builder()
+///         .name("catalog")
+///         .watchdog(
+///             Watchdog::new(Duration::from_millis(50))
+///                 .restart(1)
+///                 .on_stalled(move |stalled| {
+///                     counter.fetch_add(1, Ordering::SeqCst);
+///                     eprintln!("{:?} exceeded {:?}", stalled.name, stalled.budget);
+///                 }),
+///         )
+///         .build_with_loader(move || {
+///             attempts += 1;
+///             let hang = attempts == 1;
+///             async move {
+///                 if hang {
+///                     std::future::pending::<()>().await;
+///                 }
+///                 "catalog"
+///             }
+///         });
+///
+///     preloader.start().await;
+///     assert_eq!(*preloader.get().await.unwrap(), "catalog");
+///     assert_eq!(stalls.load(Ordering::SeqCst), 1);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Watchdog {
+    budget: Duration,
+    max_restarts: u32,
+    on_stalled: Option<OnStalled>,
+}
+
+impl Watchdog {
+    /// Creates a watchdog reporting the loads that run longer than `budget`.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            max_restarts: 0,
+            on_stalled: None,
+        }
+    }
+
+    /// Aborts and restarts a stalled load with the stored loader factory, up to `max_restarts` times per load.
+    ///
+    /// Once the restarts are used up, or if no loader factory is stored, a stalled load keeps running.
+    pub fn restart(mut self, max_restarts: u32) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Sets a function called every time a load exceeds the budget.
+    pub fn on_stalled(mut self, f: impl Fn(&StalledLoad) + Send + Sync + 'static) -> Self {
+        self.on_stalled = Some(Arc::new(f));
+        self
+    }
+
+    /// Returns the expected duration of a load.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Returns the maximum number of restarts of a stalled load.
+    pub fn max_restarts(&self) -> u32 {
+        self.max_restarts
+    }
+
+    /// Runs `load`, reporting it every time it exceeds the budget and replacing it with the next of `restarts`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the preloader or group entry, for the report
+    /// - `events`: Feed receiving the `Stalled` events
+    /// - `load`: The load to watch
+    /// - `restarts`: Fresh loads started in turn when the running one stalls
+    pub(crate) async fn watch<O>(
+        self,
+        name: Option<String>,
+        events: Events,
+        load: impl Future<Output = O> + Send + 'static,
+        restarts: Vec<BoxFuture<'static, O>>,
+    ) -> O {
+        let mut load: BoxFuture<'static, O> = Box::pin(load);
+        let mut restarts = restarts.into_iter();
+        let mut restarted = 0;
+        loop {
+            let sleep = pin!(tokio::time::sleep(self.budget));
+            if let Either::Left((output, _)) = future::select(&mut load, sleep).await {
+                return output;
+            }
+            let restart = restarts.next();
+            let report = StalledLoad {
+                name: name.clone(),
+                budget: self.budget,
+                restarts: restarted,
+                restarting: restart.is_some(),
+            };
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                name = report.name.as_deref(),
+                budget = ?report.budget,
+                restarting = report.restarting,
+                "preloader load exceeded its watchdog budget"
+            );
+            events.emit(PreloaderEvent::Stalled {
+                budget: self.budget,
+                restarting: report.restarting,
+            });
+            if let Some(on_stalled) = &self.on_stalled {
+                on_stalled(&report);
+            }
+            match restart {
+                Some(restart) => {
+                    load = restart;
+                    restarted += 1;
+                }
+                // Keeps waiting for the stalled load, without reporting it again
+                None => return load.await,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Watchdog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Watchdog")
+            .field("budget", &self.budget)
+            .field("max_restarts", &self.max_restarts)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,91 @@
+//! Weak handle module
+//!
+//! This module provides `PreloaderWeak`, a non-owning reference to a shared preloader. Background refreshers
+//! and observers can hold one without keeping a large loaded value alive after its owners have dropped it.
+
+use std::{
+    fmt,
+    sync::{Arc, Weak},
+};
+
+use crate::Preloader;
+
+/// Non-owning reference to a preloader shared through an `Arc`
+///
+/// Created with [`Preloader::downgrade`]. Once every `Arc` to the preloader is dropped, the preloader
+/// and its loaded value are dropped as well, and [`upgrade`](Self::upgrade) returns `None`.
+///
+/// # Example
+///
+/// ```rust
+/// use preloader::Preloader;
+/// use std::sync::Arc;
+///
+/// let catalog = Arc::new(Preloader::ready(vec![0u8; 1024]));
+/// let observer = Preloader::downgrade(&catalog);
+/// assert!(observer.upgrade().unwrap().is_loaded());
+///
+/// drop(catalog);
+/// assert!(observer.upgrade().is_none());
+/// ```
+pub struct PreloaderWeak<T: Send + 'static>(Weak<Preloader<T>>);
+
+impl<T: Send + 'static> PreloaderWeak<T> {
+    /// Creates a handle that does not refer to any preloader; `upgrade()` always returns `None`.
+    pub fn new() -> Self {
+        Self(Weak::new())
+    }
+
+    /// Returns the preloader if it is still alive.
+    pub fn upgrade(&self) -> Option<Arc<Preloader<T>>> {
+        self.0.upgrade()
+    }
+
+    /// Returns the number of `Arc`s keeping the preloader alive.
+    pub fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+
+    /// Checks if the preloader has been dropped.
+    pub fn is_dropped(&self) -> bool {
+        self.0.strong_count() == 0
+    }
+
+    /// Checks if both handles refer to the same preloader.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+}
+
+impl<T: Send + 'static> Preloader<T> {
+    /// Creates a [`PreloaderWeak`] referring to a shared preloader.
+    ///
+    /// # Parameters
+    ///
+    /// - `this`: The shared preloader
+    pub fn downgrade(this: &Arc<Self>) -> PreloaderWeak<T> {
+        PreloaderWeak(Arc::downgrade(this))
+    }
+}
+
+impl<T: Send + 'static> Clone for PreloaderWeak<T> {
+    fn clone(&self) -> Self {
+        Self(Weak::clone(&self.0))
+    }
+}
+
+impl<T: Send + 'static> Default for PreloaderWeak<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shows the state of the preloader if it is still alive, without requiring `T: Debug`.
+impl<T: Send + 'static> fmt::Debug for PreloaderWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.upgrade() {
+            Some(preloader) => f.debug_tuple("PreloaderWeak").field(&preloader).finish(),
+            None => f.write_str("PreloaderWeak(Dropped)"),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Zero-copy bytes module
+//!
+//! This module provides loader presets for `Preloader<Bytes>` that hold large binary data without copying it:
+//! memory-mapped files and owned buffers such as downloaded bodies.
+//! `Bytes` handles are reference counted, so [`Preloader::get_cloned`] hands out cheap views of the same memory.
+
+use std::{fs::File, future::Future, path::PathBuf};
+
+use ::bytes::Bytes;
+use memmap2::Mmap;
+use tokio::task;
+
+use crate::{LoadHandle, Preloader, PreloaderError};
+
+impl Preloader<Bytes> {
+    /// Starts loading by memory-mapping the file at `path`.
+    ///
+    /// The file is mapped on a blocking thread and its pages are read lazily by the operating system,
+    /// so loading returns quickly even for multi-gigabyte files and the data is never copied onto the heap.
+    /// The mapping stays alive as long as the preloader or any `Bytes` handle cloned from it.
+    ///
+    /// Like [`load`](Self::load), a call made while loading was already started follows the configured
+    /// [`LoadPolicy`](crate::LoadPolicy).
+    /// If the file cannot be opened or mapped, loading fails with `PreloaderError::Failed`.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The file to map
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped, including by other processes.
+    /// Doing so is undefined behavior, as the loaded bytes would change or become inaccessible.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use preloader::{Bytes, Preloader};
+    /// use tokio;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let path = std::env::temp_dir().join("preloader-mmap-example.bin");
+    ///     std::fs::write(&path, b"model weights").unwrap();
+    ///
+    ///     let weights: Preloader<Bytes> = Preloader::new();
+    ///     // SAFETY: the file is not modified while it is mapped
+    ///     unsafe { weights.load_mmap(&path).await };
+    ///
+    ///     let view = weights.get_cloned().await.unwrap();
+    ///     assert_eq!(&view[..5], b"model");
+    ///     # drop((view, weights));
+    ///     # std::fs::remove_file(&path).unwrap();
+    /// }
+    /// ```
+    pub async unsafe fn load_mmap(&self, path: impl Into<PathBuf>) -> LoadHandle {
+        let path = path.into();
+        self.load_result(async move {
+            let mapped = task::spawn_blocking(move || {
+                let file = File::open(&path)?;
+                // SAFETY: the caller guarantees that the file is not modified while it is mapped
+                unsafe { Mmap::map(&file) }
+            })
+            .await
+            .map_err(|error| PreloaderError::Failed(error.to_string()))?
+            .map_err(|error| PreloaderError::Failed(error.to_string()))?;
+            Ok(Bytes::from_owner(mapped))
+        })
+        .await
+    }
+
+    /// Starts loading with a future producing an owned buffer, converting it to `Bytes` without copying.
+    ///
+    /// Buffers such as `Vec<u8>`, `Box<[u8]>`, and `String` are taken over as they are, so a downloaded body
+    /// is stored exactly once.
+    ///
+    /// # Parameters
+    ///
+    /// - `future`: The asynchronous task producing the buffer
+    pub async fn load_bytes<B>(
+        &self,
+        future: impl Future<Output = B> + Send + 'static,
+    ) -> LoadHandle
+    where
+        B: Into<Bytes>,
+    {
+        self.load(async move { future.await.into() }).await
+    }
+}